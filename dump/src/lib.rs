@@ -101,6 +101,7 @@ pub enum KindDump {
         documents_ids: Vec<String>,
     },
     DocumentClear,
+    DocumentExpiry,
     DocumentDeletionByFilter {
         filter: serde_json::Value,
     },
@@ -181,6 +182,7 @@ impl From<KindWithContent> for KindDump {
                 KindDump::DocumentEdition { filter: filter_expr, context, function }
             }
             KindWithContent::DocumentClear { .. } => KindDump::DocumentClear,
+            KindWithContent::DocumentExpiry { .. } => KindDump::DocumentExpiry,
             KindWithContent::SettingsUpdate {
                 new_settings,
                 is_deletion,
@@ -268,6 +270,8 @@ pub(crate) mod test {
             searchable_attributes: Setting::Set(vec![S("name"), S("race")]).into(),
             filterable_attributes: Setting::Set(btreeset! { S("race"), S("age") }),
             sortable_attributes: Setting::Set(btreeset! { S("age") }),
+            datetime_attributes: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
@@ -285,6 +289,7 @@ pub(crate) mod test {
             }),
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
+            rerankers: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
             localized_attributes: Setting::NotSet,
             _kind: std::marker::PhantomData,
@@ -309,6 +314,7 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 12,
                         indexed_documents: Some(10),
+                        version_conflicts: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -332,6 +338,7 @@ pub(crate) mod test {
                     details: Some(Details::DocumentAdditionOrUpdate {
                         received_documents: 2,
                         indexed_documents: None,
+                        version_conflicts: Vec::new(),
                     }),
                     error: None,
                     enqueued_at: datetime!(2022-11-11 0:00 UTC),
@@ -343,6 +350,33 @@ pub(crate) mod test {
                     json!({ "id": 5, "race": "patou" }).as_object().unwrap().clone(),
                 ]),
             ),
+            (
+                TaskDump {
+                    uid: 2,
+                    index_uid: Some(S("doggo")),
+                    status: Status::Enqueued,
+                    kind: KindDump::DocumentImport {
+                        method: milli::update::IndexDocumentsMethod::DeepMergeDocuments,
+                        allow_index_creation: true,
+                        primary_key: None,
+                        documents_count: 1,
+                    },
+                    canceled_by: None,
+                    details: Some(Details::DocumentAdditionOrUpdate {
+                        received_documents: 1,
+                        indexed_documents: None,
+                        version_conflicts: Vec::new(),
+                    }),
+                    error: None,
+                    enqueued_at: datetime!(2022-11-12 0:00 UTC),
+                    started_at: None,
+                    finished_at: None,
+                },
+                Some(vec![json!({ "id": 4, "owner": { "name": "tamo" } })
+                    .as_object()
+                    .unwrap()
+                    .clone()]),
+            ),
             (
                 TaskDump {
                     uid: 5,
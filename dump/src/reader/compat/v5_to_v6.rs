@@ -132,6 +132,7 @@ impl CompatV5ToV6 {
                             v6::Details::DocumentAdditionOrUpdate {
                                 received_documents: received_documents as u64,
                                 indexed_documents,
+                                version_conflicts: Vec::new(),
                             }
                         }
                         v5::Details::Settings { settings } => {
@@ -319,6 +320,8 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
             searchable_attributes: v6::Setting::from(settings.searchable_attributes).into(),
             filterable_attributes: settings.filterable_attributes.into(),
             sortable_attributes: settings.sortable_attributes.into(),
+            datetime_attributes: v6::Setting::NotSet,
+            expiry_attribute: v6::Setting::NotSet,
             ranking_rules: {
                 match settings.ranking_rules {
                     v5::settings::Setting::Set(ranking_rules) => {
@@ -379,6 +382,7 @@ impl<T> From<v5::Settings<T>> for v6::Settings<v6::Unchecked> {
                 v5::Setting::NotSet => v6::Setting::NotSet,
             },
             embedders: v6::Setting::NotSet,
+            rerankers: v6::Setting::NotSet,
             localized_attributes: v6::Setting::NotSet,
             search_cutoff_ms: v6::Setting::NotSet,
             _kind: std::marker::PhantomData,
@@ -290,7 +290,8 @@ pub(crate) mod test {
         │    │    └---- settings.json
         ├---- tasks/
         │    ├---- update_files/
-        │    │    └---- 1.jsonl
+        │    │    ├---- 1.jsonl
+        │    │    └---- 2.jsonl
         │    └---- queue.jsonl
         ├---- experimental-features.json
         ├---- instance_uid.uuid
@@ -1,8 +1,8 @@
 //! BNF grammar:
 //!
 //! ```text
-//! condition      = value ("==" | ">" ...) value
-//! to             = value value TO value
+//! condition      = value ("==" | ">" ...) (datetime | value)
+//! to             = value (datetime | value) TO (datetime | value)
 //! ```
 
 use nom::branch::alt;
@@ -12,6 +12,7 @@ use nom::combinator::cut;
 use nom::sequence::{terminated, tuple};
 use Condition::*;
 
+use crate::value::parse_datetime_or_value;
 use crate::{parse_value, FilterCondition, IResult, Span, Token};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -27,12 +28,15 @@ pub enum Condition<'a> {
     LowerThanOrEqual(Token<'a>),
     Between { from: Token<'a>, to: Token<'a> },
     Contains { keyword: Token<'a>, word: Token<'a> },
+    StartsWith { keyword: Token<'a>, word: Token<'a> },
+    EndsWith { keyword: Token<'a>, word: Token<'a> },
 }
 
-/// condition      = value ("==" | ">" ...) value
+/// condition      = value ("==" | ">" ...) (datetime | value)
 pub fn parse_condition(input: Span) -> IResult<FilterCondition> {
     let operator = alt((tag("<="), tag(">="), tag("!="), tag("<"), tag(">"), tag("=")));
-    let (input, (fid, op, value)) = tuple((parse_value, operator, cut(parse_value)))(input)?;
+    let (input, (fid, op, value)) =
+        tuple((parse_value, operator, cut(parse_datetime_or_value)))(input)?;
 
     let condition = match *op.fragment() {
         "<=" => FilterCondition::Condition { fid, op: LowerThanOrEqual(value) },
@@ -121,10 +125,78 @@ pub fn parse_not_contains(input: Span) -> IResult<FilterCondition> {
     ))
 }
 
-/// to             = value value "TO" WS+ value
+/// starts_with    = value "STARTS" WS+ "WITH" value
+///
+/// `STARTS` and `ENDS` are not reserved keywords, they are only recognized right after the field
+/// name, so they can still be used as unquoted field names and values.
+pub fn parse_starts_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("STARTS"), multispace1, tag("WITH")));
+    let (input, (fid, (starts, _spaces, _with), value)) =
+        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: StartsWith { keyword: Token { span: starts, value: None }, word: value },
+        },
+    ))
+}
+
+/// not_starts_with = value "NOT" WS+ "STARTS" WS+ "WITH" value
+pub fn parse_not_starts_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("STARTS"), multispace1, tag("WITH")));
+    let (input, (fid, (_not, _, starts, _, _with), value)) =
+        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: StartsWith { keyword: Token { span: starts, value: None }, word: value },
+        })),
+    ))
+}
+
+/// ends_with      = value "ENDS" WS+ "WITH" value
+pub fn parse_ends_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("ENDS"), multispace1, tag("WITH")));
+    let (input, (fid, (ends, _spaces, _with), value)) =
+        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Condition {
+            fid,
+            op: EndsWith { keyword: Token { span: ends, value: None }, word: value },
+        },
+    ))
+}
+
+/// not_ends_with  = value "NOT" WS+ "ENDS" WS+ "WITH" value
+pub fn parse_not_ends_with(input: Span) -> IResult<FilterCondition> {
+    let keyword = tuple((tag("NOT"), multispace1, tag("ENDS"), multispace1, tag("WITH")));
+    let (input, (fid, (_not, _, ends, _, _with), value)) =
+        tuple((parse_value, keyword, cut(parse_value)))(input)?;
+
+    Ok((
+        input,
+        FilterCondition::Not(Box::new(FilterCondition::Condition {
+            fid,
+            op: EndsWith { keyword: Token { span: ends, value: None }, word: value },
+        })),
+    ))
+}
+
+/// to             = value (datetime | value) "TO" WS+ (datetime | value)
 pub fn parse_to(input: Span) -> IResult<FilterCondition> {
-    let (input, (key, from, _, _, to)) =
-        tuple((parse_value, parse_value, tag("TO"), multispace1, cut(parse_value)))(input)?;
+    let (input, (key, from, _, _, to)) = tuple((
+        parse_value,
+        parse_datetime_or_value,
+        tag("TO"),
+        multispace1,
+        cut(parse_datetime_or_value),
+    ))(input)?;
 
     Ok((input, FilterCondition::Condition { fid: key, op: Between { from, to } }))
 }
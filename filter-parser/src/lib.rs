@@ -6,19 +6,23 @@
 //! or             = and ("OR" WS+ and)*
 //! and            = not ("AND" WS+ not)*
 //! not            = ("NOT" WS+ not) | primary
-//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to
+//! primary        = (WS* "(" WS* expression WS* ")" WS*) | geoRadius | in | condition | exists | not_exists | to | starts_with | ends_with
 //! in             = value "IN" WS* "[" value_list "]"
-//! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") value
+//! condition      = value ("=" | "!=" | ">" | ">=" | "<" | "<=") (datetime | value)
 //! exists         = value "EXISTS"
 //! not_exists     = value "NOT" WS+ "EXISTS"
-//! to             = value value "TO" WS+ value
+//! starts_with    = value ("NOT" WS+)? "STARTS" WS+ "WITH" value
+//! ends_with      = value ("NOT" WS+)? "ENDS" WS+ "WITH" value
+//! to             = value (datetime | value) "TO" WS+ (datetime | value)
 //! value          = WS* ( word | singleQuoted | doubleQuoted) WS+
 //! value_list     = (value ("," value)* ","?)?
 //! singleQuoted   = "'" .* all but quotes "'"
 //! doubleQuoted   = "\"" .* all but double quotes "\""
 //! word           = (alphanumeric | _ | - | .)+
+//! datetime       = WS* (rfc3339 | "NOW" (("+" | "-") digit+ ("s" | "m" | "h" | "d" | "w"))?) WS*
 //! geoRadius      = "_geoRadius(" WS* float WS* "," WS* float WS* "," float WS* ")"
 //! geoBoundingBox = "_geoBoundingBox([" WS * float WS* "," WS* float WS* "], [" WS* float WS* "," WS* float WS* "]")
+//! geoPolygon     = "_geoPolygon([" WS * float WS* "," WS* float WS* "]" ("," WS* "[" WS* float WS* "," WS* float WS* "]")+ ")"
 //! ```
 //!
 //! Other BNF grammar used to handle some specific errors:
@@ -48,8 +52,9 @@ use std::fmt::Debug;
 
 pub use condition::{parse_condition, parse_to, Condition};
 use condition::{
-    parse_contains, parse_exists, parse_is_empty, parse_is_not_empty, parse_is_not_null,
-    parse_is_null, parse_not_contains, parse_not_exists,
+    parse_contains, parse_ends_with, parse_exists, parse_is_empty, parse_is_not_empty,
+    parse_is_not_null, parse_is_null, parse_not_contains, parse_not_ends_with, parse_not_exists,
+    parse_not_starts_with, parse_starts_with,
 };
 use error::{cut_with_err, ExpectedValueKind, NomErrorExt};
 pub use error::{Error, ErrorKind};
@@ -62,6 +67,8 @@ use nom::number::complete::recognize_float;
 use nom::sequence::{delimited, preceded, terminated, tuple};
 use nom::Finish;
 use nom_locate::LocatedSpan;
+use time::format_description::well_known::Rfc3339;
+use time::{Duration, OffsetDateTime};
 pub(crate) use value::parse_value;
 use value::word_exact;
 
@@ -121,6 +128,53 @@ impl<'a> Token<'a> {
             Err(Error::new_from_kind(self.span, ErrorKind::NonFiniteFloat))
         }
     }
+
+    /// Parses the token as a datetime and returns it as a number of seconds since the Unix epoch.
+    ///
+    /// Accepts RFC 3339 datetimes, Unix timestamps, and datetimes relative to `now`
+    /// such as `NOW`, `NOW-7d` or `NOW+12h`.
+    pub fn parse_datetime(&self, now: OffsetDateTime) -> Result<f64, Error> {
+        if let Ok(timestamp) = self.parse_finite_float() {
+            return Ok(timestamp);
+        }
+
+        let value = self.value();
+        let datetime = match value.strip_prefix("NOW") {
+            Some(offset) => {
+                parse_datetime_offset(offset).and_then(|offset| now.checked_add(offset))
+            }
+            None => OffsetDateTime::parse(value, &Rfc3339).ok(),
+        };
+        match datetime {
+            Some(datetime) => Ok(datetime.unix_timestamp_nanos() as f64 / 1_000_000_000.0),
+            None => Err(Error::new_from_kind(self.span, ErrorKind::InvalidDatetime)),
+        }
+    }
+}
+
+/// Parses the offset following `NOW` in a relative datetime, e.g. the `-7d` of `NOW-7d`.
+fn parse_datetime_offset(offset: &str) -> Option<Duration> {
+    if offset.is_empty() {
+        return Some(Duration::ZERO);
+    }
+
+    let (sign, offset) = match offset.strip_prefix('-') {
+        Some(offset) => (-1, offset),
+        None => (1, offset.strip_prefix('+')?),
+    };
+    let unit_start = offset.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = offset.split_at(unit_start);
+    let amount: i64 = amount.parse().ok()?;
+    let unit_in_seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+
+    amount.checked_mul(unit_in_seconds).map(|seconds| Duration::seconds(sign * seconds))
 }
 
 impl<'a> From<Span<'a>> for Token<'a> {
@@ -145,6 +199,7 @@ pub enum FilterCondition<'a> {
     And(Vec<Self>),
     GeoLowerThan { point: [Token<'a>; 2], radius: Token<'a> },
     GeoBoundingBox { top_right_point: [Token<'a>; 2], bottom_left_point: [Token<'a>; 2] },
+    GeoPolygon { points: Vec<[Token<'a>; 2]> },
 }
 
 pub enum TraversedElement<'a> {
@@ -165,7 +220,9 @@ impl<'a> FilterCondition<'a> {
                 | Condition::Exists
                 | Condition::LowerThan(_)
                 | Condition::LowerThanOrEqual(_)
-                | Condition::Between { .. } => None,
+                | Condition::Between { .. }
+                | Condition::StartsWith { .. }
+                | Condition::EndsWith { .. } => None,
                 Condition::Contains { keyword, word: _ } => Some(keyword),
             },
             FilterCondition::Not(this) => this.use_contains_operator(),
@@ -174,6 +231,7 @@ impl<'a> FilterCondition<'a> {
             }
             FilterCondition::GeoLowerThan { .. }
             | FilterCondition::GeoBoundingBox { .. }
+            | FilterCondition::GeoPolygon { .. }
             | FilterCondition::In { .. } => None,
         }
     }
@@ -401,6 +459,37 @@ fn parse_geo_bounding_box(input: Span) -> IResult<FilterCondition> {
     Ok((input, res))
 }
 
+/// geoPolygon      = WS* "_geoPolygon([float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], [float WS* "," WS* float WS* "], ...)"
+/// If we parse `_geoPolygon` we MUST parse the rest of the expression.
+fn parse_geo_polygon(input: Span) -> IResult<FilterCondition> {
+    // we want to allow space BEFORE the _geoPolygon but not after
+    let parsed = preceded(
+        tuple((multispace0, word_exact("_geoPolygon"))),
+        // if we were able to parse `_geoPolygon` and can't parse the rest of the input we return a failure
+        cut(delimited(
+            char('('),
+            separated_list1(
+                tag(","),
+                ws(delimited(char('['), separated_list1(tag(","), ws(recognize_float)), char(']'))),
+            ),
+            char(')'),
+        )),
+    )(input)
+    .map_err(|e| e.map(|_| Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+
+    let (input, args) = parsed?;
+
+    // a polygon needs at least three vertices, the validity of the shape itself is checked when evaluating the filter
+    if args.len() < 3 || args.iter().any(|point| point.len() != 2) {
+        return Err(nom::Err::Failure(Error::new_from_kind(input, ErrorKind::GeoPolygon)));
+    }
+
+    let res = FilterCondition::GeoPolygon {
+        points: args.into_iter().map(|point| [point[0].into(), point[1].into()]).collect(),
+    };
+    Ok((input, res))
+}
+
 /// geoPoint      = WS* "_geoPoint(float WS* "," WS* float WS* "," WS* float)
 fn parse_geo_point(input: Span) -> IResult<FilterCondition> {
     // we want to forbid space BEFORE the _geoPoint but not after
@@ -472,6 +561,7 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         ),
         parse_geo_radius,
         parse_geo_bounding_box,
+        parse_geo_polygon,
         parse_in,
         parse_not_in,
         parse_condition,
@@ -484,6 +574,8 @@ fn parse_primary(input: Span, depth: usize) -> IResult<FilterCondition> {
         parse_to,
         parse_contains,
         parse_not_contains,
+        // nom can't handle more than 21 parsers in a single `alt`
+        alt((parse_starts_with, parse_not_starts_with, parse_ends_with, parse_not_ends_with)),
         // the next lines are only for error handling and are written at the end to have the less possible performance impact
         parse_geo,
         parse_geo_distance,
@@ -550,6 +642,16 @@ impl<'a> std::fmt::Display for FilterCondition<'a> {
                     bottom_right_point[1]
                 )
             }
+            FilterCondition::GeoPolygon { points } => {
+                write!(f, "_geoPolygon(")?;
+                for (i, [lat, lng]) in points.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "[{lat}, {lng}]")?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -567,6 +669,8 @@ impl<'a> std::fmt::Display for Condition<'a> {
             Condition::LowerThanOrEqual(token) => write!(f, "<= {token}"),
             Condition::Between { from, to } => write!(f, "{from} TO {to}"),
             Condition::Contains { word, keyword: _ } => write!(f, "CONTAINS {word}"),
+            Condition::StartsWith { word, keyword: _ } => write!(f, "STARTS WITH {word}"),
+            Condition::EndsWith { word, keyword: _ } => write!(f, "ENDS WITH {word}"),
         }
     }
 }
@@ -619,6 +723,14 @@ pub mod tests {
         insta::assert_snapshot!(p("'dog race' = 'Bernese Mountain'"), @"{dog race} = {Bernese Mountain}");
         insta::assert_snapshot!(p("\"dog race\" = 'Bernese Mountain'"), @"{dog race} = {Bernese Mountain}");
 
+        // Test datetimes
+        insta::assert_snapshot!(p("createdAt > 2024-01-31T08:00:00Z"), @"{createdAt} > {2024-01-31T08:00:00Z}");
+        insta::assert_snapshot!(p("createdAt >= 2024-01-31T08:00:00+02:00"), @"{createdAt} >= {2024-01-31T08:00:00+02:00}");
+        insta::assert_snapshot!(p("createdAt > NOW-7d"), @"{createdAt} > {NOW-7d}");
+        insta::assert_snapshot!(p("createdAt NOW-1w TO NOW+12h"), @"{createdAt} {NOW-1w} TO {NOW+12h}");
+        insta::assert_snapshot!(p("createdAt = '2024-01-31T08:00:00Z'"), @"{createdAt} = {2024-01-31T08:00:00Z}");
+        insta::assert_snapshot!(p("'created:at' = 'a+b'"), @"{created:at} = {a+b}");
+
         // Test IN
         insta::assert_snapshot!(p("colour IN[]"), @"{colour} IN[]");
         insta::assert_snapshot!(p("colour IN[green]"), @"{colour} IN[{green}, ]");
@@ -679,6 +791,23 @@ pub mod tests {
         insta::assert_snapshot!(p("subscribers NOT CONTAINS hello"), @"NOT ({subscribers} CONTAINS {hello})");
         insta::assert_snapshot!(p("NOT subscribers NOT CONTAINS 'hello'"), @"{subscribers} CONTAINS {hello}");
         insta::assert_snapshot!(p("subscribers NOT   CONTAINS 'hello'"), @"NOT ({subscribers} CONTAINS {hello})");
+        // Test STARTS WITH + NOT STARTS WITH
+        insta::assert_snapshot!(p("sku STARTS WITH 'AB-'"), @"{sku} STARTS WITH {AB-}");
+        insta::assert_snapshot!(p("NOT sku STARTS WITH AB-"), @"NOT ({sku} STARTS WITH {AB-})");
+        insta::assert_snapshot!(p("sku NOT STARTS WITH 'AB-'"), @"NOT ({sku} STARTS WITH {AB-})");
+        insta::assert_snapshot!(p("NOT sku NOT STARTS WITH 'AB-'"), @"{sku} STARTS WITH {AB-}");
+        insta::assert_snapshot!(p("sku NOT   STARTS   WITH 'AB-'"), @"NOT ({sku} STARTS WITH {AB-})");
+
+        // Test ENDS WITH + NOT ENDS WITH
+        insta::assert_snapshot!(p("email ENDS WITH '@meilisearch.com'"), @"{email} ENDS WITH {@meilisearch.com}");
+        insta::assert_snapshot!(p("NOT email ENDS WITH '.com'"), @"NOT ({email} ENDS WITH {.com})");
+        insta::assert_snapshot!(p("email NOT ENDS WITH .com"), @"NOT ({email} ENDS WITH {.com})");
+        insta::assert_snapshot!(p("NOT email NOT ENDS WITH '.com'"), @"{email} ENDS WITH {.com}");
+        insta::assert_snapshot!(p("email   ENDS   WITH '.com'"), @"{email} ENDS WITH {.com}");
+        insta::assert_snapshot!(p("position = STARTS"), @"{position} = {STARTS}");
+        insta::assert_snapshot!(p("ENDS IN [STARTS, ENDS]"), @"{ENDS} IN[{STARTS}, {ENDS}, ]");
+        insta::assert_snapshot!(p("STARTS STARTS WITH ENDS"), @"{STARTS} STARTS WITH {ENDS}");
+        insta::assert_snapshot!(p("ENDS NOT ENDS WITH STARTS"), @"NOT ({ENDS} ENDS WITH {STARTS})");
 
         // Test nested NOT
         insta::assert_snapshot!(p("NOT NOT NOT NOT x = 5"), @"{x} = {5}");
@@ -694,6 +823,11 @@ pub mod tests {
         insta::assert_snapshot!(p("NOT _geoBoundingBox([12, 13], [14, 15])"), @"NOT (_geoBoundingBox([{12}, {13}], [{14}, {15}]))");
         insta::assert_snapshot!(p("_geoBoundingBox([12,13],[14,15])"), @"_geoBoundingBox([{12}, {13}], [{14}, {15}])");
 
+        // Test geo polygon
+        insta::assert_snapshot!(p("_geoPolygon([12, 13], [14, 15], [16, 17])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}])");
+        insta::assert_snapshot!(p("NOT _geoPolygon([12, 13], [14, 15], [16, 17])"), @"NOT (_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}]))");
+        insta::assert_snapshot!(p("_geoPolygon([12,13],[14,15],[16,17],[-18.5,19])"), @"_geoPolygon([{12}, {13}], [{14}, {15}], [{16}, {17}], [{-18.5}, {19}])");
+
         // Test OR + AND
         insta::assert_snapshot!(p("channel = ponce AND 'dog race' != 'bernese mountain'"), @"AND[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
         insta::assert_snapshot!(p("channel = ponce OR 'dog race' != 'bernese mountain'"), @"OR[{channel} = {ponce}, {dog race} != {bernese mountain}, ]");
@@ -750,8 +884,23 @@ pub mod tests {
         11:12 channel = 🐻 AND followers < 100
         "###);
 
+        insta::assert_snapshot!(p("created:at = 2024-01-31T08:00:00Z"), @r###"
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `created:at = 2024-01-31T08:00:00Z`.
+        1:34 created:at = 2024-01-31T08:00:00Z
+        "###);
+
+        insta::assert_snapshot!(p("channel = a+b"), @r###"
+        Found unexpected characters at the end of the filter: `+b`. You probably forgot an `OR` or an `AND` rule.
+        12:14 channel = a+b
+        "###);
+
+        insta::assert_snapshot!(p("createdAt = 2024-01-31T08:00"), @r###"
+        Found unexpected characters at the end of the filter: `:00`. You probably forgot an `OR` or an `AND` rule.
+        26:29 createdAt = 2024-01-31T08:00
+        "###);
+
         insta::assert_snapshot!(p("'OR'"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `\'OR\'`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `\'OR\'`.
         1:5 'OR'
         "###);
 
@@ -761,12 +910,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("channel Ponce"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `channel Ponce`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `channel Ponce`.
         1:14 channel Ponce
         "###);
 
         insta::assert_snapshot!(p("channel = Ponce OR"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` but instead got nothing.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` but instead got nothing.
         19:19 channel = Ponce OR
         "###);
 
@@ -795,33 +944,53 @@ pub mod tests {
         1:26 _geoBoundingBox(1.0, 1.0)
         "###);
 
+        insta::assert_snapshot!(p("_geoPolygon"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`.
+        1:12 _geoPolygon
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon(1.0, 1.0, 2.0)"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`.
+        1:27 _geoPolygon(1.0, 1.0, 2.0)
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`.
+        28:28 _geoPolygon([1, 2], [3, 4])
+        "###);
+
+        insta::assert_snapshot!(p("_geoPolygon([1, 2], [3, 4], [5, 6, 7])"), @r###"
+        The `_geoPolygon` filter expects at least three pairs of arguments: `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)`.
+        39:39 _geoPolygon([1, 2], [3, 4], [5, 6, 7])
+        "###);
+
         insta::assert_snapshot!(p("_geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:22 _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoPoint(12, 13, 14)"), @r###"
-        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:34 position <= _geoPoint(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:25 _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geoDistance(12, 13, 14)"), @r###"
-        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:37 position <= _geoDistance(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("_geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         1:17 _geo(12, 13, 14)
         "###);
 
         insta::assert_snapshot!(p("position <= _geo(12, 13, 14)"), @r###"
-        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.
+        `_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.
         13:29 position <= _geo(12, 13, 14)
         "###);
 
@@ -830,6 +999,11 @@ pub mod tests {
         13:35 position <= _geoRadius(12, 13, 14)
         "###);
 
+        insta::assert_snapshot!(p("position <= _geoPolygon([1, 2], [3, 4], [5, 6])"), @r###"
+        The `_geoPolygon` filter is an operation and can't be used as a value.
+        13:48 position <= _geoPolygon([1, 2], [3, 4], [5, 6])
+        "###);
+
         insta::assert_snapshot!(p("channel = 'ponce"), @r###"
         Expression `\'ponce` is missing the following closing delimiter: `'`.
         11:17 channel = 'ponce
@@ -851,12 +1025,12 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p("colour NOT EXIST"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `colour NOT EXIST`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `colour NOT EXIST`.
         1:17 colour NOT EXIST
         "###);
 
         insta::assert_snapshot!(p("subscribers 100 TO1000"), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `subscribers 100 TO1000`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `subscribers 100 TO1000`.
         1:23 subscribers 100 TO1000
         "###);
 
@@ -919,35 +1093,35 @@ pub mod tests {
         "###);
 
         insta::assert_snapshot!(p(r#"value NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value NULL`.
         1:11 value NULL
         "###);
         insta::assert_snapshot!(p(r#"value NOT NULL"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value NOT NULL`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value NOT NULL`.
         1:15 value NOT NULL
         "###);
         insta::assert_snapshot!(p(r#"value EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value EMPTY`.
         1:12 value EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value NOT EMPTY"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value NOT EMPTY`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value NOT EMPTY`.
         1:16 value NOT EMPTY
         "###);
         insta::assert_snapshot!(p(r#"value IS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value IS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value IS`.
         1:9 value IS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value IS NOT`.
         1:13 value IS NOT
         "###);
         insta::assert_snapshot!(p(r#"value IS EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value IS EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value IS EXISTS`.
         1:16 value IS EXISTS
         "###);
         insta::assert_snapshot!(p(r#"value IS NOT EXISTS"#), @r###"
-        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `value IS NOT EXISTS`.
+        Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `value IS NOT EXISTS`.
         1:20 value IS NOT EXISTS
         "###);
     }
@@ -967,6 +1141,30 @@ pub mod tests {
         assert!(filter.token_at_depth(3).is_none());
     }
 
+    #[test]
+    fn parse_datetime() {
+        let now = OffsetDateTime::from_unix_timestamp(1706688000).unwrap();
+        let parse = |s: &str| Token::from(s).parse_datetime(now).map_err(|e| e.to_string());
+
+        assert_eq!(parse("2024-01-31T08:00:00Z"), Ok(1706688000.0));
+        assert_eq!(parse("2024-01-31T10:00:00+02:00"), Ok(1706688000.0));
+        assert_eq!(parse("2024-01-31T08:00:00.5Z"), Ok(1706688000.5));
+        assert_eq!(parse("1706688000"), Ok(1706688000.0));
+        assert_eq!(parse("NOW"), Ok(1706688000.0));
+        assert_eq!(parse("NOW-7d"), Ok(1706688000.0 - 7.0 * 86400.0));
+        assert_eq!(parse("NOW+2h"), Ok(1706688000.0 + 7200.0));
+        assert_eq!(parse("NOW-30m"), Ok(1706688000.0 - 1800.0));
+        assert_eq!(parse("NOW+1w"), Ok(1706688000.0 + 7.0 * 86400.0));
+
+        for invalid in ["2024-01-31", "NOW-", "NOW-d", "NOW-7", "NOW*7d", "inf", "yesterday"] {
+            assert!(parse(invalid).is_err(), "{invalid} should not be a valid datetime");
+        }
+        insta::assert_snapshot!(parse("NOW-7y").unwrap_err(), @r###"
+        Expected a datetime in the RFC 3339 format such as `2024-01-31T08:00:00Z`, a Unix timestamp, or a datetime relative to now such as `NOW-7d`, but instead got `NOW-7y`.
+        1:7 NOW-7y
+        "###);
+    }
+
     #[test]
     fn token_from_str() {
         let s = "test string that should not be parsed";
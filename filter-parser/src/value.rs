@@ -5,10 +5,13 @@ use nom::combinator::cut;
 use nom::sequence::{delimited, terminated};
 use nom::{InputIter, InputLength, InputTake, Slice};
 
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
 use crate::error::{ExpectedValueKind, NomErrorExt};
 use crate::{
-    parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point, parse_geo_radius,
-    Error, ErrorKind, IResult, Span, Token,
+    parse_datetime_offset, parse_geo, parse_geo_bounding_box, parse_geo_distance, parse_geo_point,
+    parse_geo_polygon, parse_geo_radius, Error, ErrorKind, IResult, Span, Token,
 };
 
 /// This function goes through all characters in the [Span] if it finds any escaped character (`\`).
@@ -128,6 +131,24 @@ pub fn parse_value(input: Span) -> IResult<Token> {
         _ => (),
     }
 
+    match parse_geo_polygon(input) {
+        Ok(_) => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        // if we encountered a failure it means the user badly wrote a _geoPolygon filter.
+        // But instead of showing them how to fix his syntax we are going to tell them they should not use this filter as a value.
+        Err(e) if e.is_failure() => {
+            return Err(nom::Err::Failure(Error::new_from_kind(
+                input,
+                ErrorKind::MisusedGeoPolygon,
+            )))
+        }
+        _ => (),
+    }
+
     // this parser is only used when an error is encountered and it parse the
     // largest string possible that do not contain any “language” syntax.
     // If we try to parse `name = 🦀 AND language = rust` we want to return an
@@ -195,6 +216,35 @@ fn is_value_component(c: char) -> bool {
     c.is_alphanumeric() || ['_', '-', '.'].contains(&c)
 }
 
+/// datetime       = WS* (rfc3339 | "NOW" (("+" | "-") digit+ ("s" | "m" | "h" | "d" | "w"))?) WS*
+///
+/// Only tried where a datetime may be expected, i.e. on the right-hand side of a comparison
+/// and in the bounds of a range, and falls back to a plain [value](parse_value) otherwise.
+/// The `:` and `+` of the datetimes are only accepted when the whole word is a valid datetime,
+/// so they are not allowed anywhere else in unquoted values and field names.
+pub fn parse_datetime_or_value(input: Span) -> IResult<Token> {
+    let (rest, _) = take_while(char::is_whitespace)(input)?;
+    let datetime: IResult<Span> = take_while1(is_datetime_component)(rest);
+    match datetime {
+        Ok((rest, datetime)) if is_datetime(&datetime) => {
+            let (rest, _) = multispace0(rest)?;
+            Ok((rest, datetime.into()))
+        }
+        _ => parse_value(input),
+    }
+}
+
+fn is_datetime_component(c: char) -> bool {
+    is_value_component(c) || [':', '+'].contains(&c)
+}
+
+fn is_datetime(s: &str) -> bool {
+    match s.strip_prefix("NOW") {
+        Some(offset) => parse_datetime_offset(offset).is_some(),
+        None => OffsetDateTime::parse(s, &Rfc3339).is_ok(),
+    }
+}
+
 fn is_syntax_component(c: char) -> bool {
     c.is_whitespace() || ['(', ')', '=', '<', '>', '!'].contains(&c)
 }
@@ -214,6 +264,7 @@ fn is_keyword(s: &str) -> bool {
             | "CONTAINS"
             | "_geoRadius"
             | "_geoBoundingBox"
+            | "_geoPolygon"
     )
 }
 
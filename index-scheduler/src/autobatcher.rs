@@ -70,7 +70,8 @@ impl From<KindWithContent> for AutobatchKind {
                 AutobatchKind::DocumentDeletion { by_filter: false }
             }
             KindWithContent::DocumentClear { .. } => AutobatchKind::DocumentClear,
-            KindWithContent::DocumentDeletionByFilter { .. } => {
+            KindWithContent::DocumentDeletionByFilter { .. }
+            | KindWithContent::DocumentExpiry { .. } => {
                 AutobatchKind::DocumentDeletion { by_filter: true }
             }
             KindWithContent::SettingsUpdate { allow_index_creation, is_deletion, .. } => {
@@ -328,6 +329,18 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::DocumentOperation { method, allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: import_method, primary_key: pk, .. },
+            ) if method == import_method => {
+                operation_ids.push(id);
+                Continue(BatchKind::DocumentOperation {
+                    method,
+                    allow_index_creation,
+                    primary_key: pk,
+                    operation_ids,
+                })
+            }
             (
                 BatchKind::DocumentOperation { method, allow_index_creation, primary_key, mut operation_ids },
                 K::DocumentDeletion { by_filter: false },
@@ -515,6 +528,19 @@ impl BatchKind {
                     operation_ids,
                 })
             }
+            (
+                BatchKind::SettingsAndDocumentOperation { settings_ids, method, allow_index_creation, primary_key: _, mut operation_ids },
+                K::DocumentImport { method: import_method, primary_key: pk2, .. },
+            ) if method == import_method => {
+                operation_ids.push(id);
+                Continue(BatchKind::SettingsAndDocumentOperation {
+                    settings_ids,
+                    method,
+                    allow_index_creation,
+                    primary_key: pk2,
+                    operation_ids,
+                })
+            }
             // But we can't batch a settings and a doc op with another doc op
             // this MUST be AFTER the two previous branch
             (
@@ -589,6 +615,9 @@ pub fn autobatch(
 
 #[cfg(test)]
 mod tests {
+    use meilisearch_types::milli::update::IndexDocumentsMethod::{
+        DeepMergeDocuments, UpdateDocumentsWithOperators,
+    };
     use meilisearch_types::tasks::IndexSwap;
     use uuid::Uuid;
 
@@ -695,6 +724,16 @@ mod tests {
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0] }, false))");
         debug_snapshot!(autobatch_from(false,None,  [doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None), doc_imp(UpdateDocuments, false, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: false, primary_key: None, operation_ids: [0, 1, 2] }, false))");
 
+        // we can autobatch one or multiple `DeepMergeDocuments` together, but not with the other methods.
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(DeepMergeDocuments, true, None), doc_imp(DeepMergeDocuments, true, None), doc_imp(DeepMergeDocuments, true, None)]), @"Some((DocumentOperation { method: DeepMergeDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0, 1, 2] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(DeepMergeDocuments, true, None), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: DeepMergeDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), doc_imp(DeepMergeDocuments, true, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+
+        // the updates applying the field operators are not autobatched with the other updates.
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocumentsWithOperators, true, None), doc_imp(UpdateDocumentsWithOperators, true, None)]), @"Some((DocumentOperation { method: UpdateDocumentsWithOperators, allow_index_creation: true, primary_key: None, operation_ids: [0, 1] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocumentsWithOperators, true, None), doc_imp(UpdateDocuments, true, None)]), @"Some((DocumentOperation { method: UpdateDocumentsWithOperators, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+        debug_snapshot!(autobatch_from(true, None, [doc_imp(UpdateDocuments, true, None), doc_imp(UpdateDocumentsWithOperators, true, None)]), @"Some((DocumentOperation { method: UpdateDocuments, allow_index_creation: true, primary_key: None, operation_ids: [0] }, true))");
+
         // we can autobatch one or multiple DocumentDeletion together
         debug_snapshot!(autobatch_from(true, None, [doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0], includes_by_filter: false }, false))");
         debug_snapshot!(autobatch_from(true, None, [doc_del(), doc_del(), doc_del()]), @"Some((DocumentDeletion { deletion_ids: [0, 1, 2], includes_by_filter: false }, false))");
@@ -557,7 +557,14 @@ impl IndexScheduler {
         }
 
         // 5. We make a batch from the unprioritised tasks. Start by taking the next enqueued task.
-        let task_id = if let Some(task_id) = enqueued.min() { task_id } else { return Ok(None) };
+        //    The document expiries come last, they only run once nothing else is enqueued.
+        let to_expire = self.get_kind(rtxn, Kind::DocumentExpiry)? & enqueued;
+        let prioritised = enqueued - &to_expire;
+        let task_id = if let Some(task_id) = prioritised.min().or_else(|| to_expire.min()) {
+            task_id
+        } else {
+            return Ok(None);
+        };
         let task = self.get_task(rtxn, task_id)?.ok_or(Error::CorruptedTaskQueue)?;
 
         // If the task is not associated with any index, verify that it is an index swap and
@@ -579,7 +586,10 @@ impl IndexScheduler {
             primary_key = index.primary_key(&rtxn)?.map(|pk| pk.to_string());
         }
 
-        let index_tasks = self.index_tasks(rtxn, index_name)? & enqueued;
+        let mut index_tasks = self.index_tasks(rtxn, index_name)? & enqueued;
+        if !prioritised.is_empty() {
+            index_tasks -= to_expire;
+        }
 
         // If autobatching is disabled we only take one task at a time.
         // Otherwise, we take only a maximum of tasks to create batches.
@@ -1302,6 +1312,7 @@ impl IndexScheduler {
                                 .map_err(milli::Error::from)?;
                             let (new_builder, user_result) = builder.add_documents(reader)?;
                             builder = new_builder;
+                            let version_conflicts = builder.version_conflicts().to_vec();
 
                             builder = builder.with_embedders(embedders.clone());
 
@@ -1323,6 +1334,7 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(count),
+                                        version_conflicts,
                                     })
                                 }
                                 Err(e) => {
@@ -1330,6 +1342,7 @@ impl IndexScheduler {
                                     task.details = Some(Details::DocumentAdditionOrUpdate {
                                         received_documents,
                                         indexed_documents: Some(0),
+                                        version_conflicts: Vec::new(),
                                     });
                                     task.error = Some(milli::Error::from(e).into());
                                 }
@@ -1505,6 +1518,15 @@ impl IndexScheduler {
                                 unreachable!()
                             }
                         }
+                        KindWithContent::DocumentExpiry { index_uid: _ } => {
+                            let expired = index
+                                .expired_documents_ids(index_wtxn, OffsetDateTime::now_utc())?;
+                            let will_be_removed = (&expired - &to_delete).len();
+                            to_delete |= expired;
+                            task.details = Some(Details::DocumentExpiry {
+                                deleted_documents: Some(will_be_removed),
+                            });
+                        }
                         _ => unreachable!(),
                     }
                 }
@@ -113,6 +113,11 @@ pub struct IndexStats {
     /// Date of the last update of the index.
     #[serde(with = "time::serde::rfc3339")]
     pub updated_at: OffsetDateTime,
+    /// Earliest expiry date of the documents of the index.
+    ///
+    /// Used to know which indexes have expired documents without opening them.
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub next_expiry: Option<OffsetDateTime>,
 }
 
 impl IndexStats {
@@ -129,6 +134,7 @@ impl IndexStats {
             field_distribution: index.field_distribution(rtxn)?,
             created_at: index.created_at(rtxn)?,
             updated_at: index.updated_at(rtxn)?,
+            next_expiry: index.next_expiry(rtxn)?,
         })
     }
 }
@@ -464,6 +470,25 @@ impl IndexMapper {
         }
     }
 
+    /// The names of the indexes whose cached stats report documents expiring at or before `now`.
+    ///
+    /// The indexes are not opened, the indexes without cached stats are skipped.
+    pub fn indexes_with_expired_documents(
+        &self,
+        rtxn: &RoTxn,
+        now: OffsetDateTime,
+    ) -> Result<Vec<String>> {
+        let mut indexes = Vec::new();
+        for res in self.index_mapping.iter(rtxn)? {
+            let (name, uuid) = res?;
+            let next_expiry = self.index_stats.get(rtxn, &uuid)?.and_then(|s| s.next_expiry);
+            if next_expiry.is_some_and(|date| date <= now) {
+                indexes.push(name.to_string());
+            }
+        }
+        Ok(indexes)
+    }
+
     /// Stores the new stats for an index.
     ///
     /// Expected usage is to compute the stats the index using `IndexStats::new`, the pass it to this function.
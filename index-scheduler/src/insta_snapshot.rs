@@ -35,6 +35,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         features: _,
         max_number_of_tasks: _,
         max_number_of_batched_tasks: _,
+        document_expiry_interval: _,
         wake_up: _,
         dumps_path: _,
         snapshots_path: _,
@@ -46,6 +47,7 @@ pub fn snapshot_index_scheduler(scheduler: &IndexScheduler) -> String {
         planned_failures: _,
         run_loop_iteration: _,
         embedders: _,
+        rerankers: _,
     } = scheduler;
 
     let rtxn = env.read_txn().unwrap();
@@ -177,6 +179,7 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentAdditionOrUpdate {
             received_documents,
             indexed_documents,
+            version_conflicts: _,
         } => {
             format!("{{ received_documents: {received_documents}, indexed_documents: {indexed_documents:?} }}")
         }
@@ -204,7 +207,7 @@ fn snapshot_details(d: &Details) -> String {
         Details::DocumentDeletionByFilter { original_filter, deleted_documents } => format!(
            "{{ original_filter: {original_filter}, deleted_documents: {deleted_documents:?} }}"
         ),
-        Details::ClearAll { deleted_documents } => {
+        Details::ClearAll { deleted_documents } | Details::DocumentExpiry { deleted_documents } => {
             format!("{{ deleted_documents: {deleted_documents:?} }}")
         },
         Details::TaskCancelation {
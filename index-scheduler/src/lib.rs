@@ -40,7 +40,7 @@ use std::path::{Path, PathBuf};
 use std::sync::atomic::Ordering::{self, Relaxed};
 use std::sync::atomic::{AtomicBool, AtomicU32};
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use dump::{KindDump, TaskDump, UpdateFile};
 pub use error::Error;
@@ -56,6 +56,7 @@ use meilisearch_types::heed::{self, Database, Env, PutFlags, RoTxn, RwTxn};
 use meilisearch_types::milli::documents::DocumentsBatchBuilder;
 use meilisearch_types::milli::index::IndexEmbeddingConfig;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::rerank::{Reranker, RerankerOptions};
 use meilisearch_types::milli::vector::{Embedder, EmbedderOptions, EmbeddingConfigs};
 use meilisearch_types::milli::{self, CboRoaringBitmapCodec, Index, RoaringBitmapCodec, BEU32};
 use meilisearch_types::task_view::TaskView;
@@ -275,6 +276,9 @@ pub struct IndexSchedulerOptions {
     /// If the autobatcher is allowed to automatically batch tasks
     /// it will only batch this defined number of tasks at once.
     pub max_number_of_batched_tasks: usize,
+    /// The interval between two checks for the expired documents of the indexes,
+    /// `None` to never delete them automatically.
+    pub document_expiry_interval: Option<Duration>,
     /// The experimental features enabled for this instance.
     pub instance_features: InstanceTogglableFeatures,
 }
@@ -339,6 +343,9 @@ pub struct IndexScheduler {
     /// The maximum number of tasks that will be batched together.
     pub(crate) max_number_of_batched_tasks: usize,
 
+    /// The interval between two registrations of the document expiry tasks.
+    pub(crate) document_expiry_interval: Option<Duration>,
+
     /// The webhook url we should send tasks to after processing every batches.
     pub(crate) webhook_url: Option<String>,
     /// The Authorization header to send to the webhook URL.
@@ -358,6 +365,8 @@ pub struct IndexScheduler {
 
     embedders: Arc<RwLock<HashMap<EmbedderOptions, Arc<Embedder>>>>,
 
+    rerankers: Arc<RwLock<HashMap<RerankerOptions, Arc<Reranker>>>>,
+
     // ================= test
     // The next entry is dedicated to the tests.
     /// Provide a way to set a breakpoint in multiple part of the scheduler.
@@ -398,6 +407,7 @@ impl IndexScheduler {
             cleanup_enabled: self.cleanup_enabled,
             max_number_of_tasks: self.max_number_of_tasks,
             max_number_of_batched_tasks: self.max_number_of_batched_tasks,
+            document_expiry_interval: self.document_expiry_interval,
             snapshots_path: self.snapshots_path.clone(),
             dumps_path: self.dumps_path.clone(),
             auth_path: self.auth_path.clone(),
@@ -405,6 +415,7 @@ impl IndexScheduler {
             webhook_url: self.webhook_url.clone(),
             webhook_authorization_header: self.webhook_authorization_header.clone(),
             embedders: self.embedders.clone(),
+            rerankers: self.rerankers.clone(),
             #[cfg(test)]
             test_breakpoint_sdr: self.test_breakpoint_sdr.clone(),
             #[cfg(test)]
@@ -500,6 +511,7 @@ impl IndexScheduler {
             cleanup_enabled: options.cleanup_enabled,
             max_number_of_tasks: options.max_number_of_tasks,
             max_number_of_batched_tasks: options.max_number_of_batched_tasks,
+            document_expiry_interval: options.document_expiry_interval,
             dumps_path: options.dumps_path,
             snapshots_path: options.snapshots_path,
             auth_path: options.auth_path,
@@ -507,6 +519,7 @@ impl IndexScheduler {
             webhook_url: options.webhook_url,
             webhook_authorization_header: options.webhook_authorization_header,
             embedders: Default::default(),
+            rerankers: Default::default(),
 
             #[cfg(test)]
             test_breakpoint_sdr,
@@ -615,11 +628,29 @@ impl IndexScheduler {
 
                 run.wake_up.wait_timeout(std::time::Duration::from_secs(60));
 
+                let mut last_expiry_check = Instant::now();
                 loop {
+                    if let Some(interval) = run.document_expiry_interval {
+                        if last_expiry_check.elapsed() >= interval {
+                            last_expiry_check = Instant::now();
+                            if let Err(e) = run.register_document_expiries() {
+                                tracing::error!("Could not register the document expiries: {e}");
+                            }
+                        }
+                    }
+
                     let ret = catch_unwind(AssertUnwindSafe(|| run.tick()));
                     match ret {
                         Ok(Ok(TickOutcome::TickAgain(_))) => (),
-                        Ok(Ok(TickOutcome::WaitForSignal)) => run.wake_up.wait(),
+                        Ok(Ok(TickOutcome::WaitForSignal)) => match run.document_expiry_interval {
+                            // wake up in time for the next check of the expired documents.
+                            Some(interval) => {
+                                run.wake_up.wait_timeout(
+                                    interval.saturating_sub(last_expiry_check.elapsed()),
+                                );
+                            }
+                            None => run.wake_up.wait(),
+                        },
                         Ok(Err(e)) => {
                             tracing::error!("{e}");
                             // Wait one second when an irrecoverable error occurs.
@@ -1442,6 +1473,32 @@ impl IndexScheduler {
         Ok(())
     }
 
+    /// Registers a document expiry task for each index with expired documents,
+    /// unless one is already enqueued for this index.
+    ///
+    /// The indexes are not opened: the earliest expiry date of their documents
+    /// is cached in their stats after each update.
+    fn register_document_expiries(&self) -> Result<()> {
+        let rtxn = self.env.read_txn().map_err(Error::HeedTransaction)?;
+        let pending = self.get_status(&rtxn, Status::Enqueued)?
+            & self.get_kind(&rtxn, Kind::DocumentExpiry)?;
+
+        let now = OffsetDateTime::now_utc();
+        let mut to_expire = Vec::new();
+        for index_uid in self.index_mapper.indexes_with_expired_documents(&rtxn, now)? {
+            if self.index_tasks(&rtxn, &index_uid)?.is_disjoint(&pending) {
+                to_expire.push(index_uid);
+            }
+        }
+        drop(rtxn);
+
+        for index_uid in to_expire {
+            self.register(KindWithContent::DocumentExpiry { index_uid }, None, false)?;
+        }
+
+        Ok(())
+    }
+
     pub fn index_stats(&self, index_uid: &str) -> Result<IndexStats> {
         let is_indexing = self.is_index_processing(index_uid)?;
         let rtxn = self.read_txn()?;
@@ -1477,7 +1534,7 @@ impl IndexScheduler {
             .map(
                 |IndexEmbeddingConfig {
                      name,
-                     config: milli::vector::EmbeddingConfig { embedder_options, prompt },
+                     config: milli::vector::EmbeddingConfig { embedder_options, prompt, .. },
                      ..
                  }| {
                     let prompt =
@@ -1492,9 +1549,12 @@ impl IndexScheduler {
 
                     // add missing embedder
                     let embedder = Arc::new(
-                        Embedder::new(embedder_options.clone())
-                            .map_err(meilisearch_types::milli::vector::Error::from)
-                            .map_err(meilisearch_types::milli::Error::from)?,
+                        Embedder::new(
+                            embedder_options.clone(),
+                            &self.indexer_config().onnx_runtime,
+                        )
+                        .map_err(meilisearch_types::milli::vector::Error::from)
+                        .map_err(meilisearch_types::milli::Error::from)?,
                     );
                     {
                         let mut embedders = self.embedders.write().unwrap();
@@ -1507,6 +1567,29 @@ impl IndexScheduler {
         res.map(EmbeddingConfigs::new)
     }
 
+    /// Returns the reranker of these options, loading it on first use.
+    pub fn reranker(&self, reranker_options: RerankerOptions) -> Result<Arc<Reranker>> {
+        // optimistically return existing reranker
+        {
+            let rerankers = self.rerankers.read().unwrap();
+            if let Some(reranker) = rerankers.get(&reranker_options) {
+                return Ok(reranker.clone());
+            }
+        }
+
+        // add missing reranker
+        let reranker = Arc::new(
+            Reranker::new(reranker_options.clone())
+                .map_err(meilisearch_types::milli::vector::Error::from)
+                .map_err(meilisearch_types::milli::Error::from)?,
+        );
+        {
+            let mut rerankers = self.rerankers.write().unwrap();
+            rerankers.insert(reranker_options, reranker.clone());
+        }
+        Ok(reranker)
+    }
+
     /// Blocks the thread until the test handle asks to progress to/through this breakpoint.
     ///
     /// Two messages are sent through the channel for each breakpoint.
@@ -1624,6 +1707,9 @@ impl<'a> Dump<'a> {
                 KindDump::DocumentClear => KindWithContent::DocumentClear {
                     index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
                 },
+                KindDump::DocumentExpiry => KindWithContent::DocumentExpiry {
+                    index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
+                },
                 KindDump::Settings { settings, is_deletion, allow_index_creation } => {
                     KindWithContent::SettingsUpdate {
                         index_uid: task.index_uid.ok_or(Error::CorruptedDump)?,
@@ -1777,7 +1863,7 @@ mod tests {
     use meilisearch_types::milli::update::Setting;
     use meilisearch_types::milli::vector::settings::EmbeddingSettings;
     use meilisearch_types::settings::Unchecked;
-    use meilisearch_types::tasks::IndexSwap;
+    use meilisearch_types::tasks::{Details, IndexSwap};
     use meilisearch_types::VERSION_FILE_NAME;
     use tempfile::{NamedTempFile, TempDir};
     use time::Duration;
@@ -1837,6 +1923,7 @@ mod tests {
                 cleanup_enabled: true,
                 max_number_of_tasks: 1_000_000,
                 max_number_of_batched_tasks: usize::MAX,
+                document_expiry_interval: None,
                 instance_features: Default::default(),
             };
             configuration(&mut options);
@@ -5518,7 +5605,11 @@ mod tests {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
+                    distance: None,
+                    quantization: None,
+                    rescore: None,
                 },
                 user_provided: RoaringBitmap<[1, 2]>,
             },
@@ -5736,7 +5827,11 @@ mod tests {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
+                    distance: None,
+                    quantization: None,
+                    rescore: None,
                 },
                 user_provided: RoaringBitmap<[0]>,
             },
@@ -5779,7 +5874,11 @@ mod tests {
                         max_bytes: Some(
                             400,
                         ),
+                        chunking: None,
                     },
+                    distance: None,
+                    quantization: None,
+                    rescore: None,
                 },
                 user_provided: RoaringBitmap<[]>,
             },
@@ -5953,4 +6052,118 @@ mod tests {
             snapshot!(json_string!(serde_json::to_string(&documents).unwrap(), { "[]._vectors.doggo_embedder.embeddings" => "[vector]" }),  @r###""[{\"id\":0,\"doggo\":\"kefir\",\"_vectors\":{\"manual\":{\"embeddings\":[[0.0,0.0,0.0]],\"regenerate\":false},\"my_doggo_embedder\":{\"embeddings\":[[1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0,1.0]],\"regenerate\":false}}},{\"id\":1,\"doggo\":\"intel\",\"_vectors\":{\"manual\":{\"embeddings\":[[1.0,1.0,1.0]],\"regenerate\":false}}}]""###);
         }
     }
+
+    #[test]
+    fn document_expiry_is_processed_last() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        index_scheduler
+            .register(
+                KindWithContent::IndexCreation { index_uid: S("doggos"), primary_key: None },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        index_scheduler
+            .register(KindWithContent::DocumentExpiry { index_uid: S("doggos") }, None, false)
+            .unwrap();
+        index_scheduler
+            .register(KindWithContent::DocumentClear { index_uid: S("doggos") }, None, false)
+            .unwrap();
+        index_scheduler.assert_internally_consistent();
+
+        // the document clear was enqueued after the document expiry but is processed first
+        handle.advance_one_successful_batch();
+        {
+            let rtxn = index_scheduler.read_txn().unwrap();
+            let expiry = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+            assert_eq!(expiry.status, Status::Enqueued);
+            let clear = index_scheduler.get_task(&rtxn, 2).unwrap().unwrap();
+            assert_eq!(clear.status, Status::Succeeded);
+        }
+
+        handle.advance_one_successful_batch();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let expiry = index_scheduler.get_task(&rtxn, 1).unwrap().unwrap();
+        assert_eq!(expiry.status, Status::Succeeded);
+        assert_eq!(expiry.details, Some(Details::DocumentExpiry { deleted_documents: Some(0) }));
+    }
+
+    #[test]
+    fn document_expiry_is_registered_from_the_index_stats() {
+        let (index_scheduler, mut handle) = IndexScheduler::test(true, vec![]);
+
+        use meilisearch_types::settings::{Settings, Unchecked};
+        let mut new_settings: Box<Settings<Unchecked>> = Box::default();
+        new_settings.expiry_attribute = Setting::Set(S("expiresAt"));
+        index_scheduler
+            .register(
+                KindWithContent::SettingsUpdate {
+                    index_uid: S("doggos"),
+                    new_settings,
+                    is_deletion: false,
+                    allow_index_creation: true,
+                },
+                None,
+                false,
+            )
+            .unwrap();
+        handle.advance_one_successful_batch();
+
+        let documents = [
+            ("doggos", r#"{ "id": 1, "expiresAt": "2000-01-01T00:00:00Z" }"#),
+            ("doggos", r#"{ "id": 2, "expiresAt": "4000-01-01T00:00:00Z" }"#),
+            ("catto", r#"{ "id": 1, "expiresAt": "2000-01-01T00:00:00Z" }"#),
+        ];
+        for (id, (index_uid, content)) in documents.into_iter().enumerate() {
+            let (uuid, mut file) =
+                index_scheduler.create_update_file_with_uuid(id as u128).unwrap();
+            let documents_count = read_json(content.as_bytes(), &mut file).unwrap();
+            file.persist().unwrap();
+            index_scheduler
+                .register(
+                    KindWithContent::DocumentAdditionOrUpdate {
+                        index_uid: S(index_uid),
+                        primary_key: Some(S("id")),
+                        method: ReplaceDocuments,
+                        content_file: uuid,
+                        documents_count,
+                        allow_index_creation: true,
+                    },
+                    None,
+                    false,
+                )
+                .unwrap();
+            handle.advance_one_successful_batch();
+        }
+
+        // only the index with an expiry attribute gets a document expiry task, and only once
+        index_scheduler.register_document_expiries().unwrap();
+        index_scheduler.register_document_expiries().unwrap();
+        {
+            let rtxn = index_scheduler.read_txn().unwrap();
+            let expiries = index_scheduler.get_kind(&rtxn, Kind::DocumentExpiry).unwrap();
+            assert_eq!(expiries.iter().collect::<Vec<_>>(), vec![4]);
+            let expiry = index_scheduler.get_task(&rtxn, 4).unwrap().unwrap();
+            assert_eq!(expiry.index_uid(), Some("doggos"));
+        }
+
+        handle.advance_one_successful_batch();
+        {
+            let rtxn = index_scheduler.read_txn().unwrap();
+            let expiry = index_scheduler.get_task(&rtxn, 4).unwrap().unwrap();
+            assert_eq!(
+                expiry.details,
+                Some(Details::DocumentExpiry { deleted_documents: Some(1) })
+            );
+        }
+
+        // the stats were updated after the deletion, the next expiry date is in the future
+        index_scheduler.register_document_expiries().unwrap();
+        let rtxn = index_scheduler.read_txn().unwrap();
+        let expiries = index_scheduler.get_kind(&rtxn, Kind::DocumentExpiry).unwrap();
+        assert_eq!(expiries.len(), 1);
+    }
 }
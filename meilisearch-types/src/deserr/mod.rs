@@ -196,4 +196,8 @@ merge_with_error_impl_take_error_message!(InvalidMultiSearchWeight);
 merge_with_error_impl_take_error_message!(InvalidSearchSemanticRatio);
 merge_with_error_impl_take_error_message!(InvalidSearchRankingScoreThreshold);
 merge_with_error_impl_take_error_message!(InvalidSimilarRankingScoreThreshold);
+merge_with_error_impl_take_error_message!(InvalidSearchRerank);
+merge_with_error_impl_take_error_message!(InvalidSearchHitsPerGroup);
 merge_with_error_impl_take_error_message!(InvalidSimilarId);
+merge_with_error_impl_take_error_message!(milli::InvalidSearchCursor);
+merge_with_error_impl_take_error_message!(milli::CriterionError);
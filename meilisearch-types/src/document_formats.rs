@@ -1,24 +1,42 @@
 use std::fmt::{self, Debug, Display};
 use std::fs::File;
-use std::io::{self, BufWriter, Write};
+use std::io::{self, BufWriter, Cursor, Write};
 use std::marker::PhantomData;
+use std::ops::Range;
 
+use arrow::array::{Array, ArrowPrimitiveType, AsArray, StructArray};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    DataType, Date32Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+    TimeUnit, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+};
+use arrow::error::ArrowError;
+use arrow::ipc::reader::{FileReader, StreamReader};
+use arrow::record_batch::RecordBatch;
 use memmap2::MmapOptions;
 use milli::documents::{DocumentsBatchBuilder, Error};
 use milli::Object;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::basic::Compression;
+use parquet::errors::ParquetError;
 use serde::de::{SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer};
 use serde_json::error::Category;
+use serde_json::Value;
+use time::format_description::well_known::Rfc3339;
+use time::{Date, OffsetDateTime};
 
 use crate::error::{Code, ErrorCode};
 
 type Result<T> = std::result::Result<T, DocumentFormatError>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum PayloadType {
     Ndjson,
     Json,
     Csv { delimiter: u8 },
+    Parquet,
+    Arrow,
 }
 
 impl fmt::Display for PayloadType {
@@ -27,6 +45,8 @@ impl fmt::Display for PayloadType {
             PayloadType::Ndjson => f.write_str("ndjson"),
             PayloadType::Json => f.write_str("json"),
             PayloadType::Csv { .. } => f.write_str("csv"),
+            PayloadType::Parquet => f.write_str("parquet"),
+            PayloadType::Arrow => f.write_str("arrow"),
         }
     }
 }
@@ -35,6 +55,10 @@ impl fmt::Display for PayloadType {
 pub enum DocumentFormatError {
     Io(io::Error),
     MalformedPayload(Error, PayloadType),
+    /// A Parquet or Arrow payload that can't be read or converted to documents.
+    MalformedColumnarPayload(Box<dyn std::error::Error + Send + Sync>, PayloadType),
+    /// A Parquet payload compressed with a codec that isn't compiled in.
+    UnsupportedParquetCompression(Compression),
 }
 
 impl Display for DocumentFormatError {
@@ -73,6 +97,15 @@ impl Display for DocumentFormatError {
                 }
                 _ => write!(f, "The `{}` payload provided is malformed: `{}`.", b, me),
             },
+            Self::MalformedColumnarPayload(e, b) => {
+                write!(f, "The `{}` payload provided is malformed: `{}`.", b, e)
+            }
+            Self::UnsupportedParquetCompression(compression) => write!(
+                f,
+                "The `{}` payload provided is compressed with `{}`, which is not supported. Accepted compressions are: `UNCOMPRESSED`, `SNAPPY`, `GZIP`, `BROTLI`, `ZSTD`.",
+                PayloadType::Parquet,
+                compression
+            ),
         }
     }
 }
@@ -98,7 +131,9 @@ impl ErrorCode for DocumentFormatError {
     fn error_code(&self) -> Code {
         match self {
             DocumentFormatError::Io(e) => e.error_code(),
-            DocumentFormatError::MalformedPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::MalformedPayload(_, _)
+            | DocumentFormatError::MalformedColumnarPayload(_, _) => Code::MalformedPayload,
+            DocumentFormatError::UnsupportedParquetCompression(_) => Code::UnsupportedMediaType,
         }
     }
 }
@@ -166,6 +201,267 @@ pub fn read_ndjson(file: &File, writer: impl Write) -> Result<u64> {
     Ok(count as u64)
 }
 
+/// Reads Parquet from input and write an obkv batch to writer.
+pub fn read_parquet(file: &File, writer: impl Write) -> Result<u64> {
+    let malformed = |e: ParquetError| {
+        DocumentFormatError::MalformedColumnarPayload(e.into(), PayloadType::Parquet)
+    };
+    let builder = ParquetRecordBatchReaderBuilder::try_new(file.try_clone()?).map_err(malformed)?;
+
+    // The LZ4 and LZO codecs are not compiled in, we reject the files using them
+    // before reading any row rather than failing in the middle of a column.
+    let unsupported = builder
+        .metadata()
+        .row_groups()
+        .iter()
+        .flat_map(|row_group| row_group.columns())
+        .map(|column| column.compression())
+        .find(|compression| {
+            !matches!(
+                compression,
+                Compression::UNCOMPRESSED
+                    | Compression::SNAPPY
+                    | Compression::GZIP(_)
+                    | Compression::BROTLI(_)
+                    | Compression::ZSTD(_)
+            )
+        });
+    if let Some(compression) = unsupported {
+        return Err(DocumentFormatError::UnsupportedParquetCompression(compression));
+    }
+
+    let reader = builder.build().map_err(malformed)?;
+    read_record_batches(reader, writer, PayloadType::Parquet)
+}
+
+/// Reads Arrow IPC, either in the file or the streaming format, from input
+/// and write an obkv batch to writer.
+pub fn read_arrow(file: &File, writer: impl Write) -> Result<u64> {
+    let mmap = unsafe { MmapOptions::new().map(file)? };
+    let malformed =
+        |e: ArrowError| DocumentFormatError::MalformedColumnarPayload(e.into(), PayloadType::Arrow);
+
+    // Only the file format starts with the magic bytes, the streaming format directly
+    // starts with the schema message.
+    if mmap.starts_with(b"ARROW1") {
+        let reader = FileReader::try_new(Cursor::new(mmap.as_ref()), None).map_err(malformed)?;
+        read_record_batches(reader, writer, PayloadType::Arrow)
+    } else {
+        let reader = StreamReader::try_new(mmap.as_ref(), None).map_err(malformed)?;
+        read_record_batches(reader, writer, PayloadType::Arrow)
+    }
+}
+
+fn read_record_batches(
+    batches: impl Iterator<Item = std::result::Result<RecordBatch, ArrowError>>,
+    writer: impl Write,
+    payload_type: PayloadType,
+) -> Result<u64> {
+    let mut builder = DocumentsBatchBuilder::new(BufWriter::new(writer));
+
+    for batch in batches {
+        let documents = batch
+            .map_err(Into::into)
+            .and_then(|batch| arrow_values(&StructArray::from(batch)))
+            .map_err(|e| DocumentFormatError::MalformedColumnarPayload(e, payload_type))?;
+        for document in documents {
+            // the rows of a record batch are never null.
+            if let Value::Object(object) = document {
+                builder.append_json_object(&object).map_err(DocumentFormatError::Io)?;
+            }
+        }
+    }
+
+    let count = builder.documents_count();
+    let _ = builder.into_inner().map_err(DocumentFormatError::Io)?;
+
+    Ok(count as u64)
+}
+
+type ArrowConversionError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Converts an Arrow array to the JSON values of its rows.
+///
+/// The structs and maps are converted to objects, the lists to arrays, the timestamps to
+/// RFC 3339 strings in UTC and the dates to `YYYY-MM-DD` strings. The other temporal types
+/// and the binaries are converted to strings, the decimals to floats.
+fn arrow_values(array: &dyn Array) -> std::result::Result<Vec<Value>, ArrowConversionError> {
+    let values = match array.data_type() {
+        DataType::Null => vec![Value::Null; array.len()],
+        DataType::Boolean => {
+            array.as_boolean().iter().map(|value| value.map_or(Value::Null, Value::Bool)).collect()
+        }
+        DataType::Int8 => primitive_values::<Int8Type>(array),
+        DataType::Int16 => primitive_values::<Int16Type>(array),
+        DataType::Int32 => primitive_values::<Int32Type>(array),
+        DataType::Int64 => primitive_values::<Int64Type>(array),
+        DataType::UInt8 => primitive_values::<UInt8Type>(array),
+        DataType::UInt16 => primitive_values::<UInt16Type>(array),
+        DataType::UInt32 => primitive_values::<UInt32Type>(array),
+        DataType::UInt64 => primitive_values::<UInt64Type>(array),
+        DataType::Float32 => primitive_values::<Float32Type>(array),
+        DataType::Float64 => primitive_values::<Float64Type>(array),
+        DataType::Float16 | DataType::Decimal128(_, _) | DataType::Decimal256(_, _) => {
+            arrow_values(&cast(array, &DataType::Float64)?)?
+        }
+        DataType::Utf8 => string_values(array.as_string::<i32>().iter()),
+        DataType::LargeUtf8 => string_values(array.as_string::<i64>().iter()),
+        DataType::Binary
+        | DataType::LargeBinary
+        | DataType::Time32(_)
+        | DataType::Time64(_)
+        | DataType::Duration(_)
+        | DataType::Interval(_) => arrow_values(&cast(array, &DataType::Utf8)?)?,
+        DataType::Timestamp(unit, _) => {
+            let nanoseconds_per_unit = match unit {
+                TimeUnit::Second => 1_000_000_000,
+                TimeUnit::Millisecond => 1_000_000,
+                TimeUnit::Microsecond => 1_000,
+                TimeUnit::Nanosecond => 1,
+            };
+            // the timestamps are always relative to the UTC epoch, the timezone is only
+            // a hint about how to display them.
+            let timestamps = cast(array, &DataType::Int64)?;
+            let mut values = Vec::with_capacity(timestamps.len());
+            for timestamp in timestamps.as_primitive::<Int64Type>() {
+                let value = match timestamp {
+                    Some(timestamp) => {
+                        let nanoseconds = timestamp as i128 * nanoseconds_per_unit;
+                        let datetime = OffsetDateTime::from_unix_timestamp_nanos(nanoseconds)?;
+                        Value::String(datetime.format(&Rfc3339)?)
+                    }
+                    None => Value::Null,
+                };
+                values.push(value);
+            }
+            values
+        }
+        DataType::Date32 | DataType::Date64 => {
+            let dates = cast(array, &DataType::Date32)?;
+            let mut values = Vec::with_capacity(dates.len());
+            for days in dates.as_primitive::<Date32Type>() {
+                let value = match days {
+                    Some(days) => {
+                        let date = Date::from_julian_day(UNIX_EPOCH_JULIAN_DAY + days)?;
+                        Value::String(date.to_string())
+                    }
+                    None => Value::Null,
+                };
+                values.push(value);
+            }
+            values
+        }
+        DataType::List(_) => {
+            let list = array.as_list::<i32>();
+            let ranges = list.value_offsets().windows(2).map(|w| w[0] as usize..w[1] as usize);
+            list_values(list, arrow_values(list.values())?, ranges)
+        }
+        DataType::LargeList(_) => {
+            let list = array.as_list::<i64>();
+            let ranges = list.value_offsets().windows(2).map(|w| w[0] as usize..w[1] as usize);
+            list_values(list, arrow_values(list.values())?, ranges)
+        }
+        DataType::FixedSizeList(_, _) => {
+            let list = array.as_fixed_size_list();
+            let ranges = (0..list.len()).map(|i| {
+                let start = list.value_offset(i) as usize;
+                start..start + list.value_length() as usize
+            });
+            list_values(list, arrow_values(list.values())?, ranges)
+        }
+        DataType::Struct(fields) => {
+            let array = array.as_struct();
+            let mut columns = array
+                .columns()
+                .iter()
+                .map(|column| arrow_values(column).map(Vec::into_iter))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            (0..array.len())
+                .map(|row| {
+                    let object = fields
+                        .iter()
+                        .zip(&mut columns)
+                        .map(|(field, column)| {
+                            (field.name().clone(), column.next().unwrap_or_default())
+                        })
+                        .collect();
+                    if array.is_null(row) {
+                        Value::Null
+                    } else {
+                        Value::Object(object)
+                    }
+                })
+                .collect()
+        }
+        DataType::Map(_, _) => {
+            let map = array.as_map();
+            let keys = arrow_values(map.keys())?;
+            let values = arrow_values(map.values())?;
+            map.value_offsets()
+                .windows(2)
+                .enumerate()
+                .map(|(row, w)| {
+                    if map.is_null(row) {
+                        return Value::Null;
+                    }
+                    let range = w[0] as usize..w[1] as usize;
+                    let entries = keys[range.clone()].iter().zip(&values[range]);
+                    let object = entries
+                        .map(|(key, value)| {
+                            let key = match key {
+                                Value::String(key) => key.clone(),
+                                key => key.to_string(),
+                            };
+                            (key, value.clone())
+                        })
+                        .collect();
+                    Value::Object(object)
+                })
+                .collect()
+        }
+        DataType::Dictionary(_, value_type) => arrow_values(&cast(array, value_type)?)?,
+        data_type => return Err(format!("the `{data_type}` type is not supported").into()),
+    };
+
+    Ok(values)
+}
+
+/// The Julian day of the 1970-01-01, the epoch of the Arrow dates.
+const UNIX_EPOCH_JULIAN_DAY: i32 = 2_440_588;
+
+fn primitive_values<T>(array: &dyn Array) -> Vec<Value>
+where
+    T: ArrowPrimitiveType,
+    T::Native: Into<Value>,
+{
+    array.as_primitive::<T>().iter().map(|value| value.map_or(Value::Null, Into::into)).collect()
+}
+
+fn string_values<'a>(strings: impl Iterator<Item = Option<&'a str>>) -> Vec<Value> {
+    strings
+        .map(|value| value.map_or(Value::Null, |value| Value::String(value.to_string())))
+        .collect()
+}
+
+fn list_values(
+    list: &dyn Array,
+    values: Vec<Value>,
+    ranges: impl Iterator<Item = Range<usize>>,
+) -> Vec<Value> {
+    ranges
+        .enumerate()
+        .map(
+            |(row, range)| {
+                if list.is_null(row) {
+                    Value::Null
+                } else {
+                    Value::Array(values[range].to_vec())
+                }
+            },
+        )
+        .collect()
+}
+
 /// The actual handling of the deserialization process in serde
 /// avoids storing the deserialized object in memory.
 ///
@@ -211,3 +507,156 @@ where
     let visitor = SeqVisitor(f, PhantomData);
     deserializer.deserialize_seq(visitor)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{
+        ArrayRef, Date32Array, ListArray, StringArray, TimestampMillisecondArray, UInt32Array,
+    };
+    use arrow::datatypes::Field;
+    use arrow::ipc::writer::{FileWriter, StreamWriter};
+    use meili_snap::{json_string, snapshot};
+    use milli::documents::{obkv_to_object, DocumentsBatchReader};
+    use parquet::arrow::ArrowWriter;
+
+    use super::*;
+
+    fn record_batch() -> RecordBatch {
+        let owner = StructArray::from(vec![(
+            Arc::new(Field::new("name", DataType::Utf8, false)),
+            Arc::new(StringArray::from(vec!["tamo", "loic"])) as ArrayRef,
+        )]);
+        RecordBatch::try_from_iter(vec![
+            ("id", Arc::new(UInt32Array::from(vec![1, 2])) as ArrayRef),
+            ("title", Arc::new(StringArray::from(vec![Some("kefir"), None]))),
+            (
+                "tags",
+                Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                    Some(vec![Some(1), Some(2)]),
+                    None,
+                ])),
+            ),
+            ("owner", Arc::new(owner)),
+            (
+                "birth",
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![0, 1_700_000_000_000])
+                        .with_timezone("+02:00"),
+                ),
+            ),
+            ("day", Arc::new(Date32Array::from(vec![0, 19_000]))),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn arrow_to_json_values() {
+        let documents = arrow_values(&StructArray::from(record_batch())).unwrap();
+        snapshot!(json_string!(documents), @r###"
+        [
+          {
+            "id": 1,
+            "title": "kefir",
+            "tags": [
+              1,
+              2
+            ],
+            "owner": {
+              "name": "tamo"
+            },
+            "birth": "1970-01-01T00:00:00Z",
+            "day": "1970-01-01"
+          },
+          {
+            "id": 2,
+            "title": null,
+            "tags": null,
+            "owner": {
+              "name": "loic"
+            },
+            "birth": "2023-11-14T22:13:20Z",
+            "day": "2022-01-08"
+          }
+        ]
+        "###);
+    }
+
+    #[test]
+    fn read_arrow_file_and_stream_formats() {
+        let batch = record_batch();
+
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = FileWriter::try_new(&file, &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(read_arrow(&file, Vec::new()).unwrap(), 2);
+
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = StreamWriter::try_new(&file, &batch.schema()).unwrap();
+        writer.write(&batch).unwrap();
+        writer.finish().unwrap();
+        assert_eq!(read_arrow(&file, Vec::new()).unwrap(), 2);
+
+        let file = tempfile::tempfile().unwrap();
+        let mut garbage = &file;
+        garbage.write_all(b"\x02\x00\x00\x00{}").unwrap();
+        let error = read_arrow(&file, Vec::new()).unwrap_err();
+        assert!(matches!(error, DocumentFormatError::MalformedColumnarPayload(_, _)));
+    }
+    #[test]
+    fn parquet_round_trip() {
+        let batch = record_batch();
+
+        let file = tempfile::tempfile().unwrap();
+        let mut writer = ArrowWriter::try_new(&file, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let mut documents = Vec::new();
+        assert_eq!(read_parquet(&file, &mut documents).unwrap(), 2);
+
+        let (mut cursor, fields_index) = DocumentsBatchReader::from_reader(Cursor::new(documents))
+            .unwrap()
+            .into_cursor_and_fields_index();
+        let mut objects = Vec::new();
+        while let Some(document) = cursor.next_document().unwrap() {
+            objects.push(obkv_to_object(&document, &fields_index).unwrap());
+        }
+        // the nested struct, the list and the timestamp are read back as written
+        snapshot!(json_string!(objects), @r###"
+        [
+          {
+            "id": 1,
+            "title": "kefir",
+            "tags": [
+              1,
+              2
+            ],
+            "owner": {
+              "name": "tamo"
+            },
+            "birth": "1970-01-01T00:00:00Z",
+            "day": "1970-01-01"
+          },
+          {
+            "id": 2,
+            "title": null,
+            "tags": null,
+            "owner": {
+              "name": "loic"
+            },
+            "birth": "2023-11-14T22:13:20Z",
+            "day": "2022-01-08"
+          }
+        ]
+        "###);
+
+        let file = tempfile::tempfile().unwrap();
+        let mut garbage = &file;
+        garbage.write_all(b"PAR1{}").unwrap();
+        let error = read_parquet(&file, Vec::new()).unwrap_err();
+        assert!(matches!(error, DocumentFormatError::MalformedColumnarPayload(_, _)));
+    }
+}
@@ -221,8 +221,13 @@ InvalidApiKeyOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidApiKeyUid                      , InvalidRequest       , BAD_REQUEST ;
 InvalidContentType                    , InvalidRequest       , UNSUPPORTED_MEDIA_TYPE ;
 InvalidDocumentCsvDelimiter           , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentExpectedVersion        , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFields                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentFieldOperator          , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentMergeStrategy          , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentOperators              , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentRetrieveVectors        , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentRetrieveVersion        , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
 MissingDocumentEditionFunction        , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentFilter                 , InvalidRequest       , BAD_REQUEST ;
@@ -232,6 +237,7 @@ InvalidVectorsType                    , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentId                     , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentLimit                  , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentOffset                 , InvalidRequest       , BAD_REQUEST ;
+InvalidDocumentSearchAfter            , InvalidRequest       , BAD_REQUEST ;
 InvalidEmbedder                       , InvalidRequest       , BAD_REQUEST ;
 InvalidHybridQuery                    , InvalidRequest       , BAD_REQUEST ;
 InvalidIndexLimit                     , InvalidRequest       , BAD_REQUEST ;
@@ -255,10 +261,15 @@ InvalidSearchRetrieveVectors          , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropLength               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchCropMarker               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFacets                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFacetRanges              , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSemanticRatio            , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFusion                   , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchFusionK                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchLocales                  , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchFacetName           , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarId                      , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarText                    , InvalidRequest       , BAD_REQUEST ;
+InvalidSimilarVector                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchFilter                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarFilter                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchHighlightPostTag         , InvalidRequest       , BAD_REQUEST ;
@@ -271,6 +282,8 @@ InvalidSimilarOffset                  , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchOffset                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchPage                     , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchQ                        , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRankingRules             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchSearchAfter              , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchQuery               , InvalidRequest       , BAD_REQUEST ;
 InvalidFacetSearchName                , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchVector                   , InvalidRequest       , BAD_REQUEST ;
@@ -279,8 +292,23 @@ InvalidSearchShowRankingScore         , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScore        , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchShowRankingScoreDetails  , InvalidRequest       , BAD_REQUEST ;
 InvalidSimilarShowRankingScoreDetails , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendPositive              , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendNegative              , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendNegativeWeight        , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendOffset                , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendLimit                 , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendFilter                , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendAttributesToRetrieve  , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendRetrieveVectors       , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendShowRankingScore      , InvalidRequest       , BAD_REQUEST ;
+InvalidRecommendShowRankingScoreDetails , InvalidRequest     , BAD_REQUEST ;
+InvalidRecommendRankingScoreThreshold , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchSort                     , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchDidYouMean               , InvalidRequest       , BAD_REQUEST ;
 InvalidSearchDistinct                 , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchGroupBy                  , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchHitsPerGroup             , InvalidRequest       , BAD_REQUEST ;
+InvalidSearchRerank                   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDisplayedAttributes    , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsDistinctAttribute      , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsProximityPrecision     , InvalidRequest       , BAD_REQUEST ;
@@ -289,9 +317,12 @@ InvalidSettingsFilterableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsPagination             , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchCutoffMs         , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsEmbedders              , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsRerankers              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsRankingRules           , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSearchableAttributes   , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSortableAttributes     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsDatetimeAttributes     , InvalidRequest       , BAD_REQUEST ;
+InvalidSettingsExpiryAttribute        , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsStopWords              , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsNonSeparatorTokens     , InvalidRequest       , BAD_REQUEST ;
 InvalidSettingsSeparatorTokens        , InvalidRequest       , BAD_REQUEST ;
@@ -345,6 +376,7 @@ UnsupportedMediaType                  , InvalidRequest       , UNSUPPORTED_MEDIA
 // Experimental features
 VectorEmbeddingError                  , InvalidRequest       , BAD_REQUEST ;
 NotFoundSimilarId                     , InvalidRequest       , BAD_REQUEST ;
+NotFoundRecommendId                   , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentEditionContext         , InvalidRequest       , BAD_REQUEST ;
 InvalidDocumentEditionFunctionFilter  , InvalidRequest       , BAD_REQUEST ;
 EditDocumentsByFunctionError          , InvalidRequest       , BAD_REQUEST
@@ -384,23 +416,38 @@ impl ErrorCode for milli::Error {
                     UserError::InvalidFieldForSource { .. }
                     | UserError::MissingFieldForSource { .. }
                     | UserError::InvalidOpenAiModel { .. }
+                    | UserError::InvalidOnnxModelPath { .. }
                     | UserError::InvalidOpenAiModelDimensions { .. }
                     | UserError::InvalidOpenAiModelDimensionsMax { .. }
                     | UserError::InvalidSettingsDimensions { .. }
                     | UserError::InvalidUrl { .. }
                     | UserError::InvalidSettingsDocumentTemplateMaxBytes { .. }
+                    | UserError::InvalidChangeQuantization { .. }
+                    | UserError::InvalidBinaryQuantizationDistance { .. }
+                    | UserError::InvalidSettingsChunkingSize { .. }
+                    | UserError::InvalidSettingsChunkingOverlap { .. }
                     | UserError::InvalidPrompt(_) => Code::InvalidSettingsEmbedders,
                     UserError::TooManyEmbedders(_) => Code::InvalidSettingsEmbedders,
                     UserError::InvalidPromptForEmbeddings(..) => Code::InvalidSettingsEmbedders,
+                    UserError::InvalidFieldForRerankerSource { .. }
+                    | UserError::MissingFieldForRerankerSource { .. }
+                    | UserError::InvalidPromptForReranker(..)
+                    | UserError::InvalidSettingsRerankerDocumentTemplateMaxBytes { .. }
+                    | UserError::InvalidRerankerUrl { .. } => Code::InvalidSettingsRerankers,
                     UserError::NoPrimaryKeyCandidateFound => Code::IndexPrimaryKeyNoCandidateFound,
                     UserError::MultiplePrimaryKeyCandidatesFound { .. } => {
                         Code::IndexPrimaryKeyMultipleCandidatesFound
                     }
                     UserError::PrimaryKeyCannotBeChanged(_) => Code::IndexPrimaryKeyAlreadyExists,
                     UserError::InvalidDistinctAttribute { .. } => Code::InvalidSearchDistinct,
+                    UserError::InvalidGroupByAttribute { .. } => Code::InvalidSearchGroupBy,
                     UserError::SortRankingRuleMissing => Code::InvalidSearchSort,
                     UserError::InvalidFacetsDistribution { .. } => Code::InvalidSearchFacets,
+                    UserError::InvalidFacetRanges { .. } => Code::InvalidSearchFacetRanges,
                     UserError::InvalidSortableAttribute { .. } => Code::InvalidSearchSort,
+                    UserError::InvalidRankingRuleSortableAttribute { .. } => {
+                        Code::InvalidSearchRankingRules
+                    }
                     UserError::InvalidSearchableAttribute { .. } => {
                         Code::InvalidSearchAttributesToSearchOn
                     }
@@ -409,6 +456,12 @@ impl ErrorCode for milli::Error {
                     }
                     UserError::CriterionError(_) => Code::InvalidSettingsRankingRules,
                     UserError::InvalidGeoField { .. } => Code::InvalidDocumentGeoField,
+                    UserError::InvalidDocumentFieldOperator { .. } => {
+                        Code::InvalidDocumentFieldOperator
+                    }
+                    UserError::InvalidDocumentExpectedVersion { .. } => {
+                        Code::InvalidDocumentExpectedVersion
+                    }
                     UserError::InvalidVectorDimensions { .. } => Code::InvalidVectorDimensions,
                     UserError::InvalidVectorsMapType { .. }
                     | UserError::InvalidVectorsEmbedderConf { .. } => Code::InvalidVectorsType,
@@ -418,6 +471,7 @@ impl ErrorCode for milli::Error {
                         Code::InvalidSettingsTypoTolerance
                     }
                     UserError::InvalidEmbedder(_) => Code::InvalidEmbedder,
+                    UserError::InvalidReranker(_) => Code::InvalidSearchRerank,
                     UserError::VectorEmbeddingError(_) | UserError::DocumentEmbeddingError(_) => {
                         Code::VectorEmbeddingError
                     }
@@ -548,6 +602,21 @@ impl fmt::Display for deserr_codes::InvalidSearchRankingScoreThreshold {
     }
 }
 
+impl fmt::Display for deserr_codes::InvalidSearchHitsPerGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the value of `hitsPerGroup` is invalid, expected an integer between `1` and `1000`."
+        )
+    }
+}
+
+impl fmt::Display for deserr_codes::InvalidSearchRerank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the value of `topK` is invalid, expected an integer between `1` and `1000`.")
+    }
+}
+
 impl fmt::Display for deserr_codes::InvalidSimilarRankingScoreThreshold {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         deserr_codes::InvalidSearchRankingScoreThreshold.fmt(f)
@@ -3,10 +3,10 @@ use std::convert::Infallible;
 use std::fmt;
 use std::marker::PhantomData;
 use std::num::NonZeroUsize;
-use std::ops::{ControlFlow, Deref};
+use std::ops::Deref;
 use std::str::FromStr;
 
-use deserr::{DeserializeError, Deserr, ErrorKind, MergeWithError, ValuePointerRef};
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use fst::IntoStreamer;
 use milli::index::IndexEmbeddingConfig;
 use milli::proximity::ProximityPrecision;
@@ -120,20 +120,6 @@ pub struct PaginationSettings {
     pub max_total_hits: Setting<usize>,
 }
 
-impl MergeWithError<milli::CriterionError> for DeserrJsonError<InvalidSettingsRankingRules> {
-    fn merge(
-        _self_: Option<Self>,
-        other: milli::CriterionError,
-        merge_location: ValuePointerRef,
-    ) -> ControlFlow<Self, Self> {
-        Self::error::<Infallible>(
-            None,
-            ErrorKind::Unexpected { msg: other.to_string() },
-            merge_location,
-        )
-    }
-}
-
 /// Holds all the settings for an index. `T` can either be `Checked` if they represents settings
 /// whose validity is guaranteed, or `Unchecked` if they need to be validated. In the later case, a
 /// call to `check` will return a `Settings<Checked>` from a `Settings<Unchecked>`.
@@ -160,6 +146,12 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSortableAttributes>)]
     pub sortable_attributes: Setting<BTreeSet<String>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsDatetimeAttributes>)]
+    pub datetime_attributes: Setting<BTreeSet<String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsExpiryAttribute>)]
+    pub expiry_attribute: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsRankingRules>)]
     pub ranking_rules: Setting<Vec<RankingRuleView>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -197,6 +189,9 @@ pub struct Settings<T> {
     #[deserr(default, error = DeserrJsonError<InvalidSettingsEmbedders>)]
     pub embedders: Setting<BTreeMap<String, Setting<milli::vector::settings::EmbeddingSettings>>>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default, error = DeserrJsonError<InvalidSettingsRerankers>)]
+    pub rerankers: Setting<BTreeMap<String, Setting<milli::vector::settings::RerankerSettings>>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default, error = DeserrJsonError<InvalidSettingsSearchCutoffMs>)]
     pub search_cutoff_ms: Setting<u64>,
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
@@ -210,20 +205,32 @@ pub struct Settings<T> {
 
 impl<T> Settings<T> {
     pub fn hide_secrets(&mut self) {
-        let Setting::Set(embedders) = &mut self.embedders else {
-            return;
-        };
+        if let Setting::Set(embedders) = &mut self.embedders {
+            for mut embedder in embedders.values_mut() {
+                let Setting::Set(embedder) = &mut embedder else {
+                    continue;
+                };
+
+                let Setting::Set(api_key) = &mut embedder.api_key else {
+                    continue;
+                };
+
+                Self::hide_secret(api_key);
+            }
+        }
 
-        for mut embedder in embedders.values_mut() {
-            let Setting::Set(embedder) = &mut embedder else {
-                continue;
-            };
+        if let Setting::Set(rerankers) = &mut self.rerankers {
+            for reranker in rerankers.values_mut() {
+                let Setting::Set(reranker) = reranker else {
+                    continue;
+                };
 
-            let Setting::Set(api_key) = &mut embedder.api_key else {
-                continue;
-            };
+                let Setting::Set(api_key) = &mut reranker.api_key else {
+                    continue;
+                };
 
-            Self::hide_secret(api_key);
+                Self::hide_secret(api_key);
+            }
         }
     }
 
@@ -252,6 +259,8 @@ impl Settings<Checked> {
             searchable_attributes: Setting::Reset.into(),
             filterable_attributes: Setting::Reset,
             sortable_attributes: Setting::Reset,
+            datetime_attributes: Setting::Reset,
+            expiry_attribute: Setting::Reset,
             ranking_rules: Setting::Reset,
             stop_words: Setting::Reset,
             synonyms: Setting::Reset,
@@ -264,6 +273,7 @@ impl Settings<Checked> {
             faceting: Setting::Reset,
             pagination: Setting::Reset,
             embedders: Setting::Reset,
+            rerankers: Setting::Reset,
             search_cutoff_ms: Setting::Reset,
             localized_attributes: Setting::Reset,
             _kind: PhantomData,
@@ -276,6 +286,8 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            datetime_attributes,
+            expiry_attribute,
             ranking_rules,
             stop_words,
             non_separator_tokens,
@@ -288,6 +300,7 @@ impl Settings<Checked> {
             faceting,
             pagination,
             embedders,
+            rerankers,
             search_cutoff_ms,
             localized_attributes: localized_attributes_rules,
             _kind,
@@ -298,6 +311,8 @@ impl Settings<Checked> {
             searchable_attributes,
             filterable_attributes,
             sortable_attributes,
+            datetime_attributes,
+            expiry_attribute,
             ranking_rules,
             stop_words,
             non_separator_tokens,
@@ -310,6 +325,7 @@ impl Settings<Checked> {
             faceting,
             pagination,
             embedders,
+            rerankers,
             search_cutoff_ms,
             localized_attributes: localized_attributes_rules,
             _kind: PhantomData,
@@ -346,6 +362,8 @@ impl Settings<Unchecked> {
             searchable_attributes: searchable_attributes.into(),
             filterable_attributes: self.filterable_attributes,
             sortable_attributes: self.sortable_attributes,
+            datetime_attributes: self.datetime_attributes,
+            expiry_attribute: self.expiry_attribute,
             ranking_rules: self.ranking_rules,
             stop_words: self.stop_words,
             synonyms: self.synonyms,
@@ -358,6 +376,7 @@ impl Settings<Unchecked> {
             faceting: self.faceting,
             pagination: self.pagination,
             embedders: self.embedders,
+            rerankers: self.rerankers,
             search_cutoff_ms: self.search_cutoff_ms,
             localized_attributes: self.localized_attributes,
             _kind: PhantomData,
@@ -365,7 +384,7 @@ impl Settings<Unchecked> {
     }
 
     pub fn validate(self) -> Result<Self, milli::Error> {
-        self.validate_embedding_settings()
+        self.validate_embedding_settings()?.validate_reranker_settings()
     }
 
     fn validate_embedding_settings(mut self) -> Result<Self, milli::Error> {
@@ -378,6 +397,17 @@ impl Settings<Unchecked> {
         self.embedders = Setting::Set(configs);
         Ok(self)
     }
+
+    fn validate_reranker_settings(mut self) -> Result<Self, milli::Error> {
+        let Setting::Set(mut configs) = self.rerankers else { return Ok(self) };
+        for (name, config) in configs.iter_mut() {
+            let config_to_check = std::mem::take(config);
+            let checked_config = milli::update::validate_reranker_settings(config_to_check, name)?;
+            *config = checked_config
+        }
+        self.rerankers = Setting::Set(configs);
+        Ok(self)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -397,6 +427,8 @@ pub fn apply_settings_to_builder(
         searchable_attributes,
         filterable_attributes,
         sortable_attributes,
+        datetime_attributes,
+        expiry_attribute,
         ranking_rules,
         stop_words,
         non_separator_tokens,
@@ -409,6 +441,7 @@ pub fn apply_settings_to_builder(
         faceting,
         pagination,
         embedders,
+        rerankers,
         search_cutoff_ms,
         localized_attributes: localized_attributes_rules,
         _kind,
@@ -440,6 +473,18 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match datetime_attributes {
+        Setting::Set(ref fields) => builder.set_datetime_fields(fields.iter().cloned().collect()),
+        Setting::Reset => builder.reset_datetime_fields(),
+        Setting::NotSet => (),
+    }
+
+    match expiry_attribute {
+        Setting::Set(ref attr) => builder.set_expiry_field(attr.clone()),
+        Setting::Reset => builder.reset_expiry_field(),
+        Setting::NotSet => (),
+    }
+
     match ranking_rules {
         Setting::Set(ref criteria) => {
             builder.set_criteria(criteria.iter().map(|c| c.clone().into()).collect())
@@ -594,6 +639,12 @@ pub fn apply_settings_to_builder(
         Setting::NotSet => (),
     }
 
+    match rerankers {
+        Setting::Set(value) => builder.set_reranker_settings(value.clone()),
+        Setting::Reset => builder.reset_reranker_settings(),
+        Setting::NotSet => (),
+    }
+
     match search_cutoff_ms {
         Setting::Set(cutoff) => builder.set_search_cutoff(*cutoff),
         Setting::Reset => builder.reset_search_cutoff(),
@@ -622,6 +673,10 @@ pub fn settings(
 
     let sortable_attributes = index.sortable_fields(rtxn)?.into_iter().collect();
 
+    let datetime_attributes = index.datetime_fields(rtxn)?.into_iter().collect();
+
+    let expiry_field = index.expiry_field(rtxn)?.map(String::from);
+
     let criteria = index.criteria(rtxn)?;
 
     let stop_words = index
@@ -693,6 +748,13 @@ pub fn settings(
         .collect();
     let embedders = if embedders.is_empty() { Setting::NotSet } else { Setting::Set(embedders) };
 
+    let rerankers: BTreeMap<_, _> = index
+        .reranker_configs(rtxn)?
+        .into_iter()
+        .map(|(name, config)| (name, Setting::Set(config.into())))
+        .collect();
+    let rerankers = if rerankers.is_empty() { Setting::NotSet } else { Setting::Set(rerankers) };
+
     let search_cutoff_ms = index.search_cutoff(rtxn)?;
 
     let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
@@ -710,6 +772,11 @@ pub fn settings(
         .into(),
         filterable_attributes: Setting::Set(filterable_attributes),
         sortable_attributes: Setting::Set(sortable_attributes),
+        datetime_attributes: Setting::Set(datetime_attributes),
+        expiry_attribute: match expiry_field {
+            Some(field) => Setting::Set(field),
+            None => Setting::Reset,
+        },
         ranking_rules: Setting::Set(criteria.iter().map(|c| c.clone().into()).collect()),
         stop_words: Setting::Set(stop_words),
         non_separator_tokens: Setting::Set(non_separator_tokens),
@@ -725,6 +792,7 @@ pub fn settings(
         faceting: Setting::Set(faceting),
         pagination: Setting::Set(pagination),
         embedders,
+        rerankers,
         search_cutoff_ms: match search_cutoff_ms {
             Some(cutoff) => Setting::Set(cutoff),
             None => Setting::Reset,
@@ -765,6 +833,10 @@ pub enum RankingRuleView {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing closeness of the value of the field specified to an origin.
+    Decay(milli::Decay),
+    /// Sorted by the decreasing value of the field specified, scaled by a modifier.
+    Boost(milli::Boost),
 }
 impl Serialize for RankingRuleView {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -821,6 +893,8 @@ impl From<Criterion> for RankingRuleView {
             Criterion::Exactness => RankingRuleView::Exactness,
             Criterion::Asc(x) => RankingRuleView::Asc(x),
             Criterion::Desc(x) => RankingRuleView::Desc(x),
+            Criterion::Decay(x) => RankingRuleView::Decay(x),
+            Criterion::Boost(x) => RankingRuleView::Boost(x),
         }
     }
 }
@@ -835,6 +909,8 @@ impl From<RankingRuleView> for Criterion {
             RankingRuleView::Exactness => Criterion::Exactness,
             RankingRuleView::Asc(x) => Criterion::Asc(x),
             RankingRuleView::Desc(x) => Criterion::Desc(x),
+            RankingRuleView::Decay(x) => Criterion::Decay(x),
+            RankingRuleView::Boost(x) => Criterion::Boost(x),
         }
     }
 }
@@ -912,6 +988,8 @@ pub(crate) mod test {
             searchable_attributes: Setting::Set(vec![String::from("hello")]).into(),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            datetime_attributes: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
@@ -924,6 +1002,7 @@ pub(crate) mod test {
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
+            rerankers: Setting::NotSet,
             localized_attributes: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
@@ -941,6 +1020,8 @@ pub(crate) mod test {
                 .into(),
             filterable_attributes: Setting::NotSet,
             sortable_attributes: Setting::NotSet,
+            datetime_attributes: Setting::NotSet,
+            expiry_attribute: Setting::NotSet,
             ranking_rules: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
@@ -953,6 +1034,7 @@ pub(crate) mod test {
             faceting: Setting::NotSet,
             pagination: Setting::NotSet,
             embedders: Setting::NotSet,
+            rerankers: Setting::NotSet,
             localized_attributes: Setting::NotSet,
             search_cutoff_ms: Setting::NotSet,
             _kind: PhantomData::<Unchecked>,
@@ -55,6 +55,8 @@ pub struct DetailsView {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexed_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_conflicts: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub edited_documents: Option<Option<u64>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub primary_key: Option<Option<String>>,
@@ -86,13 +88,16 @@ pub struct DetailsView {
 impl From<Details> for DetailsView {
     fn from(details: Details) -> Self {
         match details {
-            Details::DocumentAdditionOrUpdate { received_documents, indexed_documents } => {
-                DetailsView {
-                    received_documents: Some(received_documents),
-                    indexed_documents: Some(indexed_documents),
-                    ..DetailsView::default()
-                }
-            }
+            Details::DocumentAdditionOrUpdate {
+                received_documents,
+                indexed_documents,
+                version_conflicts,
+            } => DetailsView {
+                received_documents: Some(received_documents),
+                indexed_documents: Some(indexed_documents),
+                version_conflicts: (!version_conflicts.is_empty()).then_some(version_conflicts),
+                ..DetailsView::default()
+            },
             Details::DocumentEdition {
                 deleted_documents,
                 edited_documents,
@@ -131,7 +136,8 @@ impl From<Details> for DetailsView {
                     ..DetailsView::default()
                 }
             }
-            Details::ClearAll { deleted_documents } => {
+            Details::ClearAll { deleted_documents }
+            | Details::DocumentExpiry { deleted_documents } => {
                 DetailsView { deleted_documents: Some(deleted_documents), ..DetailsView::default() }
             }
             Details::TaskCancelation { matched_tasks, canceled_tasks, original_filter } => {
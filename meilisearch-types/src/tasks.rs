@@ -53,6 +53,7 @@ impl Task {
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
+            | DocumentExpiry { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
@@ -73,6 +74,7 @@ impl Task {
             | KindWithContent::DocumentDeletion { .. }
             | KindWithContent::DocumentDeletionByFilter { .. }
             | KindWithContent::DocumentClear { .. }
+            | KindWithContent::DocumentExpiry { .. }
             | KindWithContent::SettingsUpdate { .. }
             | KindWithContent::IndexDeletion { .. }
             | KindWithContent::IndexCreation { .. }
@@ -114,6 +116,10 @@ pub enum KindWithContent {
     DocumentClear {
         index_uid: String,
     },
+    /// Deletes the documents whose expiry date is past, registered by the scheduler itself.
+    DocumentExpiry {
+        index_uid: String,
+    },
     SettingsUpdate {
         index_uid: String,
         new_settings: Box<Settings<Unchecked>>,
@@ -163,6 +169,7 @@ impl KindWithContent {
             KindWithContent::DocumentDeletion { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentDeletionByFilter { .. } => Kind::DocumentDeletion,
             KindWithContent::DocumentClear { .. } => Kind::DocumentDeletion,
+            KindWithContent::DocumentExpiry { .. } => Kind::DocumentExpiry,
             KindWithContent::SettingsUpdate { .. } => Kind::SettingsUpdate,
             KindWithContent::IndexCreation { .. } => Kind::IndexCreation,
             KindWithContent::IndexDeletion { .. } => Kind::IndexDeletion,
@@ -188,6 +195,7 @@ impl KindWithContent {
             | DocumentDeletion { index_uid, .. }
             | DocumentDeletionByFilter { index_uid, .. }
             | DocumentClear { index_uid }
+            | DocumentExpiry { index_uid }
             | SettingsUpdate { index_uid, .. }
             | IndexCreation { index_uid, .. }
             | IndexUpdate { index_uid, .. }
@@ -211,6 +219,7 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    version_conflicts: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
@@ -237,6 +246,9 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } | KindWithContent::IndexDeletion { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
+            KindWithContent::DocumentExpiry { .. } => {
+                Some(Details::DocumentExpiry { deleted_documents: None })
+            }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
@@ -268,6 +280,7 @@ impl KindWithContent {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: Some(0),
+                    version_conflicts: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { index_uid: _, filter_expr, context, function } => {
@@ -294,6 +307,9 @@ impl KindWithContent {
             KindWithContent::DocumentClear { .. } => {
                 Some(Details::ClearAll { deleted_documents: None })
             }
+            KindWithContent::DocumentExpiry { .. } => {
+                Some(Details::DocumentExpiry { deleted_documents: Some(0) })
+            }
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
@@ -328,12 +344,14 @@ impl From<&KindWithContent> for Option<Details> {
                 Some(Details::DocumentAdditionOrUpdate {
                     received_documents: *documents_count,
                     indexed_documents: None,
+                    version_conflicts: Vec::new(),
                 })
             }
             KindWithContent::DocumentEdition { .. } => None,
             KindWithContent::DocumentDeletion { .. } => None,
             KindWithContent::DocumentDeletionByFilter { .. } => None,
             KindWithContent::DocumentClear { .. } => None,
+            KindWithContent::DocumentExpiry { .. } => None,
             KindWithContent::SettingsUpdate { new_settings, .. } => {
                 Some(Details::SettingsUpdate { settings: new_settings.clone() })
             }
@@ -435,6 +453,8 @@ pub enum Kind {
     TaskDeletion,
     DumpCreation,
     SnapshotCreation,
+    // the kinds are stored by their position in the scheduler database, new ones go last.
+    DocumentExpiry,
 }
 
 impl Kind {
@@ -443,6 +463,7 @@ impl Kind {
             Kind::DocumentAdditionOrUpdate
             | Kind::DocumentEdition
             | Kind::DocumentDeletion
+            | Kind::DocumentExpiry
             | Kind::SettingsUpdate
             | Kind::IndexCreation
             | Kind::IndexDeletion
@@ -461,6 +482,7 @@ impl Display for Kind {
             Kind::DocumentAdditionOrUpdate => write!(f, "documentAdditionOrUpdate"),
             Kind::DocumentEdition => write!(f, "documentEdition"),
             Kind::DocumentDeletion => write!(f, "documentDeletion"),
+            Kind::DocumentExpiry => write!(f, "documentExpiry"),
             Kind::SettingsUpdate => write!(f, "settingsUpdate"),
             Kind::IndexCreation => write!(f, "indexCreation"),
             Kind::IndexDeletion => write!(f, "indexDeletion"),
@@ -491,6 +513,8 @@ impl FromStr for Kind {
             Ok(Kind::DocumentEdition)
         } else if kind.eq_ignore_ascii_case("documentDeletion") {
             Ok(Kind::DocumentDeletion)
+        } else if kind.eq_ignore_ascii_case("documentExpiry") {
+            Ok(Kind::DocumentExpiry)
         } else if kind.eq_ignore_ascii_case("settingsUpdate") {
             Ok(Kind::SettingsUpdate)
         } else if kind.eq_ignore_ascii_case("taskCancelation") {
@@ -533,6 +557,8 @@ pub enum Details {
     DocumentAdditionOrUpdate {
         received_documents: u64,
         indexed_documents: Option<u64>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        version_conflicts: Vec<String>,
     },
     SettingsUpdate {
         settings: Box<Settings<Unchecked>>,
@@ -558,6 +584,9 @@ pub enum Details {
     ClearAll {
         deleted_documents: Option<u64>,
     },
+    DocumentExpiry {
+        deleted_documents: Option<u64>,
+    },
     TaskCancelation {
         matched_tasks: u64,
         canceled_tasks: Option<u64>,
@@ -589,6 +618,7 @@ impl Details {
                 *deleted_documents = Some(0)
             }
             Self::ClearAll { deleted_documents } => *deleted_documents = Some(0),
+            Self::DocumentExpiry { deleted_documents } => *deleted_documents = Some(0),
             Self::TaskCancelation { canceled_tasks, .. } => *canceled_tasks = Some(0),
             Self::TaskDeletion { deleted_tasks, .. } => *deleted_tasks = Some(0),
             Self::SettingsUpdate { .. }
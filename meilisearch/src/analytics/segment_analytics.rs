@@ -31,14 +31,16 @@ use crate::analytics::Analytics;
 use crate::option::{
     default_http_addr, IndexerOpts, LogMode, MaxMemory, MaxThreads, ScheduleSnapshot,
 };
-use crate::routes::indexes::documents::{DocumentEditionByFunction, UpdateDocumentsQuery};
+use crate::routes::indexes::documents::{
+    DocumentEditionByFunction, MergeStrategy, UpdateDocumentsQuery,
+};
 use crate::routes::indexes::facet_search::FacetSearchQuery;
 use crate::routes::{create_all_stats, Stats};
 use crate::search::{
-    FacetSearchResult, FederatedSearch, MatchingStrategy, SearchQuery, SearchQueryWithIndex,
-    SearchResult, SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
-    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT,
-    DEFAULT_SEMANTIC_RATIO,
+    FacetSearchResult, FederatedSearch, FusionMethod, MatchingStrategy, SearchQuery,
+    SearchQueryWithIndex, SearchResult, SimilarQuery, SimilarResult, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_SEARCH_LIMIT, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::Opt;
 
@@ -270,7 +272,10 @@ struct Infos {
     experimental_enable_logs_route: bool,
     experimental_reduce_indexing_memory_usage: bool,
     experimental_max_number_of_batched_tasks: usize,
+    experimental_document_expiry_interval: u64,
     gpu_enabled: bool,
+    onnx_models_dir: bool,
+    onnx_runtime_path: bool,
     db_path: bool,
     import_dump: bool,
     dump_dir: bool,
@@ -313,6 +318,9 @@ impl From<Opt> for Infos {
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             experimental_max_number_of_batched_tasks,
+            experimental_document_expiry_interval,
+            onnx_models_dir,
+            onnx_runtime_path,
             http_addr,
             master_key: _,
             env,
@@ -364,6 +372,8 @@ impl From<Opt> for Infos {
             experimental_enable_logs_route,
             experimental_reduce_indexing_memory_usage,
             gpu_enabled: meilisearch_types::milli::vector::is_cuda_enabled(),
+            onnx_models_dir: onnx_models_dir.is_some(),
+            onnx_runtime_path: onnx_runtime_path.is_some(),
             db_path: db_path != PathBuf::from("./data.ms"),
             import_dump: import_dump.is_some(),
             dump_dir: dump_dir != PathBuf::from("dumps/"),
@@ -377,6 +387,7 @@ impl From<Opt> for Infos {
             http_addr: http_addr != default_http_addr(),
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_document_expiry_interval,
             task_queue_webhook: task_webhook_url.is_some(),
             task_webhook_authorization_header: task_webhook_authorization_header.is_some(),
             log_level: log_level.to_string(),
@@ -624,9 +635,16 @@ pub struct SearchAggregator {
     // distinct
     distinct: bool,
 
+    // group by
+    group_by: bool,
+
+    // did you mean
+    did_you_mean: bool,
+
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -649,6 +667,8 @@ pub struct SearchAggregator {
     // Whether a non-default embedder was specified
     embedder: bool,
     hybrid: bool,
+    // Whether the results of a hybrid search were merged by reciprocal rank
+    fusion: bool,
     retrieve_vectors: bool,
 
     // every time a search is done, we increment the counter linked to the used settings
@@ -661,6 +681,7 @@ pub struct SearchAggregator {
     max_limit: usize,
     max_offset: usize,
     finite_pagination: usize,
+    search_after: usize,
 
     // formatting
     max_attributes_to_retrieve: usize,
@@ -675,11 +696,14 @@ pub struct SearchAggregator {
     // facets
     facets_sum_of_terms: usize,
     facets_total_number_of_facets: usize,
+    facet_ranges: bool,
 
     // scoring
     show_ranking_score: bool,
     show_ranking_score_details: bool,
     ranking_score_threshold: bool,
+    ranking_rules: bool,
+    rerank: bool,
 }
 
 impl SearchAggregator {
@@ -692,6 +716,7 @@ impl SearchAggregator {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve: _,
             retrieve_vectors,
             attributes_to_crop: _,
@@ -702,8 +727,14 @@ impl SearchAggregator {
             show_ranking_score_details,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            group_by,
+            hits_per_group: _,
+            did_you_mean,
+            rerank,
             facets: _,
+            facet_ranges,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -728,6 +759,12 @@ impl SearchAggregator {
 
         ret.distinct = distinct.is_some();
 
+        ret.group_by = group_by.is_some();
+
+        ret.did_you_mean = *did_you_mean;
+
+        ret.facet_ranges = facet_ranges.is_some();
+
         if let Some(ref filter) = filter {
             static RE: Lazy<Regex> = Lazy::new(|| Regex::new("AND | OR").unwrap());
             ret.filter_total_number_of_criteria = 1;
@@ -749,6 +786,7 @@ impl SearchAggregator {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -776,6 +814,7 @@ impl SearchAggregator {
             ret.max_offset = *offset;
             ret.finite_pagination = 0;
         }
+        ret.search_after = search_after.is_some() as usize;
 
         ret.matching_strategy.insert(format!("{:?}", matching_strategy), 1);
 
@@ -792,11 +831,14 @@ impl SearchAggregator {
         ret.show_ranking_score = *show_ranking_score;
         ret.show_ranking_score_details = *show_ranking_score_details;
         ret.ranking_score_threshold = ranking_score_threshold.is_some();
+        ret.ranking_rules = ranking_rules.is_some();
+        ret.rerank = rerank.is_some();
 
         if let Some(hybrid) = hybrid {
             ret.semantic_ratio = hybrid.semantic_ratio != DEFAULT_SEMANTIC_RATIO();
             ret.embedder = hybrid.embedder.is_some();
             ret.hybrid = true;
+            ret.fusion = hybrid.fusion == FusionMethod::Rrf;
         }
 
         ret
@@ -811,6 +853,10 @@ impl SearchAggregator {
             semantic_hit_count: _,
             facet_distribution: _,
             facet_stats: _,
+            facet_ranges: _,
+            next_search_after: _,
+            corrected_query: _,
+            groups: _,
             degraded,
             used_negative_operator,
         } = result;
@@ -837,8 +883,11 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            group_by,
+            did_you_mean,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -850,6 +899,7 @@ impl SearchAggregator {
             max_limit,
             max_offset,
             finite_pagination,
+            search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -860,14 +910,18 @@ impl SearchAggregator {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
             embedder,
             hybrid,
+            fusion,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            ranking_rules,
+            rerank,
             ref mut locales,
         } = other;
 
@@ -898,9 +952,16 @@ impl SearchAggregator {
         // distinct
         self.distinct |= distinct;
 
+        // group by
+        self.group_by |= group_by;
+
+        // did you mean
+        self.did_you_mean |= did_you_mean;
+
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -923,12 +984,14 @@ impl SearchAggregator {
         self.retrieve_vectors |= retrieve_vectors;
         self.semantic_ratio |= semantic_ratio;
         self.hybrid |= hybrid;
+        self.fusion |= fusion;
         self.embedder |= embedder;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
         self.max_offset = self.max_offset.max(max_offset);
         self.finite_pagination += finite_pagination;
+        self.search_after += search_after;
 
         // formatting
         self.max_attributes_to_retrieve =
@@ -946,6 +1009,7 @@ impl SearchAggregator {
         self.facets_sum_of_terms = self.facets_sum_of_terms.saturating_add(facets_sum_of_terms);
         self.facets_total_number_of_facets =
             self.facets_total_number_of_facets.saturating_add(facets_total_number_of_facets);
+        self.facet_ranges |= facet_ranges;
 
         // matching strategy
         for (key, value) in matching_strategy.into_iter() {
@@ -957,6 +1021,8 @@ impl SearchAggregator {
         self.show_ranking_score |= show_ranking_score;
         self.show_ranking_score_details |= show_ranking_score_details;
         self.ranking_score_threshold |= ranking_score_threshold;
+        self.ranking_rules |= ranking_rules;
+        self.rerank |= rerank;
 
         // locales
         self.locales.append(locales);
@@ -973,8 +1039,11 @@ impl SearchAggregator {
             sort_sum_of_criteria_terms,
             sort_total_number_of_criteria,
             distinct,
+            group_by,
+            did_you_mean,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -986,6 +1055,7 @@ impl SearchAggregator {
             max_limit,
             max_offset,
             finite_pagination,
+            search_after,
             max_attributes_to_retrieve,
             max_attributes_to_highlight,
             highlight_pre_tag,
@@ -996,14 +1066,18 @@ impl SearchAggregator {
             crop_length,
             facets_sum_of_terms,
             facets_total_number_of_facets,
+            facet_ranges,
             show_ranking_score,
             show_ranking_score_details,
             semantic_ratio,
             embedder,
             hybrid,
+            fusion,
             total_degraded,
             total_used_negative_operator,
             ranking_score_threshold,
+            ranking_rules,
+            rerank,
             locales,
         } = self;
 
@@ -1032,9 +1106,12 @@ impl SearchAggregator {
                     "avg_criteria_number": format!("{:.2}", sort_sum_of_criteria_terms as f64 / sort_total_number_of_criteria as f64),
                 },
                 "distinct": distinct,
+                "group_by": group_by,
+                "did_you_mean": did_you_mean,
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
+                   "with_geoPolygon": filter_with_geo_polygon,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
@@ -1052,11 +1129,13 @@ impl SearchAggregator {
                     "enabled": hybrid,
                     "semantic_ratio": semantic_ratio,
                     "embedder": embedder,
+                    "fusion": fusion,
                 },
                 "pagination": {
                    "max_limit": max_limit,
                    "max_offset": max_offset,
                    "most_used_navigation": if finite_pagination > (total_received / 2) { "exhaustive" } else { "estimated" },
+                   "total_search_after": search_after,
                 },
                 "formatting": {
                     "max_attributes_to_retrieve": max_attributes_to_retrieve,
@@ -1070,6 +1149,7 @@ impl SearchAggregator {
                 },
                 "facets": {
                     "avg_facets_number": format!("{:.2}", facets_sum_of_terms as f64 / facets_total_number_of_facets as f64),
+                    "with_facet_ranges": facet_ranges,
                 },
                 "matching_strategy": {
                     "most_used_strategy": matching_strategy.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
@@ -1079,6 +1159,8 @@ impl SearchAggregator {
                     "show_ranking_score": show_ranking_score,
                     "show_ranking_score_details": show_ranking_score_details,
                     "ranking_score_threshold": ranking_score_threshold,
+                    "ranking_rules": ranking_rules,
+                    "rerank": rerank,
                 },
             });
 
@@ -1146,6 +1228,7 @@ impl MultiSearchAggregator {
                     limit: _,
                     page: _,
                     hits_per_page: _,
+                    search_after: _,
                     attributes_to_retrieve: _,
                     retrieve_vectors: _,
                     attributes_to_crop: _,
@@ -1156,8 +1239,14 @@ impl MultiSearchAggregator {
                     show_matches_position: _,
                     filter: _,
                     sort: _,
+                    ranking_rules: _,
                     distinct: _,
+                    group_by: _,
+                    hits_per_group: _,
+                    did_you_mean: _,
+                    rerank: _,
                     facets: _,
+                    facet_ranges: _,
                     highlight_pre_tag: _,
                     highlight_post_tag: _,
                     crop_marker: _,
@@ -1445,6 +1534,8 @@ pub struct DocumentsAggregator {
     content_types: HashSet<String>,
     primary_keys: HashSet<String>,
     index_creation: bool,
+    deep_merge: bool,
+    operators: bool,
 }
 
 impl DocumentsAggregator {
@@ -1453,7 +1544,8 @@ impl DocumentsAggregator {
         index_creation: bool,
         request: &HttpRequest,
     ) -> Self {
-        let UpdateDocumentsQuery { primary_key, csv_delimiter: _ } = documents_query;
+        let UpdateDocumentsQuery { primary_key, csv_delimiter: _, merge_strategy, operators } =
+            documents_query;
 
         let mut primary_keys = HashSet::new();
         if let Some(primary_key) = primary_key.clone() {
@@ -1476,13 +1568,23 @@ impl DocumentsAggregator {
             content_types,
             primary_keys,
             index_creation,
+            deep_merge: *merge_strategy == Some(MergeStrategy::Deep),
+            operators: operators.0,
         }
     }
 
     /// Aggregate one [DocumentsAggregator] into another.
     pub fn aggregate(&mut self, other: Self) {
-        let Self { timestamp, user_agents, primary_keys, content_types, index_creation, updated } =
-            other;
+        let Self {
+            timestamp,
+            user_agents,
+            primary_keys,
+            content_types,
+            index_creation,
+            updated,
+            deep_merge,
+            operators,
+        } = other;
 
         if self.timestamp.is_none() {
             self.timestamp = timestamp;
@@ -1500,11 +1602,21 @@ impl DocumentsAggregator {
             self.content_types.insert(content_type);
         }
         self.index_creation |= index_creation;
+        self.deep_merge |= deep_merge;
+        self.operators |= operators;
     }
 
     pub fn into_event(self, user: &User, event_name: &str) -> Option<Track> {
-        let Self { timestamp, user_agents, primary_keys, content_types, index_creation, updated } =
-            self;
+        let Self {
+            timestamp,
+            user_agents,
+            primary_keys,
+            content_types,
+            index_creation,
+            updated,
+            deep_merge,
+            operators,
+        } = self;
 
         if !updated {
             None
@@ -1514,6 +1626,8 @@ impl DocumentsAggregator {
                 "payload_type": content_types,
                 "primary_key": primary_keys,
                 "index_creation": index_creation,
+                "deep_merge": deep_merge,
+                "operators": operators,
             });
 
             Some(Track {
@@ -1775,6 +1889,7 @@ pub struct SimilarAggregator {
     // filter
     filter_with_geo_radius: bool,
     filter_with_geo_bounding_box: bool,
+    filter_with_geo_polygon: bool,
     // every time a request has a filter, this field must be incremented by the number of terms it contains
     filter_sum_of_criteria_terms: usize,
     // every time a request has a filter, this field must be incremented by one
@@ -1784,6 +1899,9 @@ pub struct SimilarAggregator {
     // Whether a non-default embedder was specified
     embedder: bool,
     retrieve_vectors: bool,
+    // Whether the documents are similar to a text or a vector rather than a document
+    with_text: bool,
+    with_vector: bool,
 
     // pagination
     max_limit: usize,
@@ -1803,6 +1921,8 @@ impl SimilarAggregator {
     pub fn from_query(query: &SimilarQuery, request: &HttpRequest) -> Self {
         let SimilarQuery {
             id: _,
+            text,
+            vector,
             embedder,
             offset,
             limit,
@@ -1841,6 +1961,7 @@ impl SimilarAggregator {
             let stringified_filters = filter.to_string();
             ret.filter_with_geo_radius = stringified_filters.contains("_geoRadius(");
             ret.filter_with_geo_bounding_box = stringified_filters.contains("_geoBoundingBox(");
+            ret.filter_with_geo_polygon = stringified_filters.contains("_geoPolygon(");
             ret.filter_sum_of_criteria_terms = RE.split(&stringified_filters).count();
         }
 
@@ -1853,6 +1974,8 @@ impl SimilarAggregator {
 
         ret.embedder = embedder.is_some();
         ret.retrieve_vectors = *retrieve_vectors;
+        ret.with_text = text.is_some();
+        ret.with_vector = vector.is_some();
 
         ret
     }
@@ -1875,6 +1998,7 @@ impl SimilarAggregator {
             ref mut time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -1886,6 +2010,8 @@ impl SimilarAggregator {
             embedder,
             ranking_score_threshold,
             retrieve_vectors,
+            with_text,
+            with_vector,
         } = other;
 
         if self.timestamp.is_none() {
@@ -1905,6 +2031,7 @@ impl SimilarAggregator {
         // filter
         self.filter_with_geo_radius |= filter_with_geo_radius;
         self.filter_with_geo_bounding_box |= filter_with_geo_bounding_box;
+        self.filter_with_geo_polygon |= filter_with_geo_polygon;
         self.filter_sum_of_criteria_terms =
             self.filter_sum_of_criteria_terms.saturating_add(filter_sum_of_criteria_terms);
         self.filter_total_number_of_criteria =
@@ -1916,6 +2043,8 @@ impl SimilarAggregator {
 
         self.embedder |= embedder;
         self.retrieve_vectors |= retrieve_vectors;
+        self.with_text |= with_text;
+        self.with_vector |= with_vector;
 
         // pagination
         self.max_limit = self.max_limit.max(max_limit);
@@ -1940,6 +2069,7 @@ impl SimilarAggregator {
             time_spent,
             filter_with_geo_radius,
             filter_with_geo_bounding_box,
+            filter_with_geo_polygon,
             filter_sum_of_criteria_terms,
             filter_total_number_of_criteria,
             used_syntax,
@@ -1951,6 +2081,8 @@ impl SimilarAggregator {
             embedder,
             ranking_score_threshold,
             retrieve_vectors,
+            with_text,
+            with_vector,
         } = self;
 
         if total_received == 0 {
@@ -1974,11 +2106,14 @@ impl SimilarAggregator {
                 "filter": {
                    "with_geoRadius": filter_with_geo_radius,
                    "with_geoBoundingBox": filter_with_geo_bounding_box,
+                   "with_geoPolygon": filter_with_geo_polygon,
                    "avg_criteria_number": format!("{:.2}", filter_sum_of_criteria_terms as f64 / filter_total_number_of_criteria as f64),
                    "most_used_syntax": used_syntax.iter().max_by_key(|(_, v)| *v).map(|(k, _)| json!(k)).unwrap_or_else(|| json!(null)),
                 },
                 "vector": {
                     "retrieve_vectors": retrieve_vectors,
+                    "with_text": with_text,
+                    "with_vector": with_vector,
                 },
                 "hybrid": {
                     "embedder": embedder,
@@ -31,6 +31,20 @@ pub enum MeilisearchHttpError {
     FederationOptionsInNonFederatedRequest(usize),
     #[error("Inside `.queries[{0}]`: Using pagination options is not allowed in federated queries.\n Hint: remove `{1}` from query #{0} or remove `federation: {{}}` from the request")]
     PaginationInFederatedQuery(usize, &'static str),
+    #[error("Using `searchAfter` is not compatible with `{0}`.\n Hint: remove `{0}` from the request to paginate with `searchAfter`.")]
+    SearchAfterWith(&'static str),
+    #[error("Using `groupBy` is not compatible with `{0}`.\n Hint: remove `{0}` from the request to group the results with `groupBy`.")]
+    GroupByWith(&'static str),
+    #[error("Inside `.queries[{0}]`: Using `groupBy` is not allowed in federated queries.\n Hint: remove `groupBy` from query #{0} or remove `federation: {{}}` from the request")]
+    GroupByInFederatedQuery(usize),
+    #[error("Inside `.queries[{0}]`: Using `rerank` is not allowed in federated queries.\n Hint: remove `rerank` from query #{0} or remove `federation: {{}}` from the request")]
+    RerankInFederatedQuery(usize),
+    #[error("The `mergeStrategy` parameter can only be used when updating documents with the `PUT` method.")]
+    MergeStrategyWithDocumentsReplacement,
+    #[error(
+        "The `operators` parameter can only be used when updating documents with the `PUT` method."
+    )]
+    OperatorsWithDocumentsReplacement,
     #[error("A {0} payload is missing.")]
     MissingPayload(PayloadType),
     #[error("Too many search requests running at the same time: {0}. Retry after 10s.")]
@@ -96,6 +110,16 @@ impl ErrorCode for MeilisearchHttpError {
             MeilisearchHttpError::PaginationInFederatedQuery(_, _) => {
                 Code::InvalidMultiSearchQueryPagination
             }
+            MeilisearchHttpError::SearchAfterWith(_) => Code::InvalidSearchSearchAfter,
+            MeilisearchHttpError::GroupByWith(_)
+            | MeilisearchHttpError::GroupByInFederatedQuery(_) => Code::InvalidSearchGroupBy,
+            MeilisearchHttpError::RerankInFederatedQuery(_) => Code::InvalidSearchRerank,
+            MeilisearchHttpError::MergeStrategyWithDocumentsReplacement => {
+                Code::InvalidDocumentMergeStrategy
+            }
+            MeilisearchHttpError::OperatorsWithDocumentsReplacement => {
+                Code::InvalidDocumentOperators
+            }
         }
     }
 }
@@ -305,11 +305,13 @@ fn open_or_create_database_unchecked(
             task_db_size: opt.max_task_db_size.as_u64() as usize,
             index_base_map_size: opt.max_index_size.as_u64() as usize,
             enable_mdb_writemap: opt.experimental_reduce_indexing_memory_usage,
-            indexer_config: (&opt.indexer_options).try_into()?,
+            indexer_config: opt.indexer_config()?,
             autobatching_enabled: true,
             cleanup_enabled: !opt.experimental_replication_parameters,
             max_number_of_tasks: 1_000_000,
             max_number_of_batched_tasks: opt.experimental_max_number_of_batched_tasks,
+            document_expiry_interval: (opt.experimental_document_expiry_interval != 0)
+                .then(|| Duration::from_secs(opt.experimental_document_expiry_interval)),
             index_growth_amount: byte_unit::Byte::from_str("10GiB").unwrap().as_u64() as usize,
             index_count: DEFAULT_INDEX_COUNT,
             instance_features,
@@ -13,6 +13,7 @@ use byte_unit::{Byte, ParseError, UnitType};
 use clap::Parser;
 use meilisearch_types::features::InstanceTogglableFeatures;
 use meilisearch_types::milli::update::IndexerConfig;
+use meilisearch_types::milli::vector::onnx;
 use meilisearch_types::milli::ThreadPoolNoAbortBuilder;
 use rustls::server::{ServerSessionMemoryCache, WebPkiClientVerifier};
 use rustls::RootCertStore;
@@ -59,6 +60,10 @@ const MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE: &str =
     "MEILI_EXPERIMENTAL_REDUCE_INDEXING_MEMORY_USAGE";
 const MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS: &str =
     "MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS";
+const MEILI_EXPERIMENTAL_DOCUMENT_EXPIRY_INTERVAL: &str =
+    "MEILI_EXPERIMENTAL_DOCUMENT_EXPIRY_INTERVAL";
+const MEILI_ONNX_MODELS_DIR: &str = "MEILI_ONNX_MODELS_DIR";
+const ORT_DYLIB_PATH: &str = "ORT_DYLIB_PATH";
 
 const DEFAULT_CONFIG_FILE_PATH: &str = "./config.toml";
 const DEFAULT_DB_PATH: &str = "./data.ms";
@@ -395,6 +400,26 @@ pub struct Opt {
     #[serde(default = "default_limit_batched_tasks")]
     pub experimental_max_number_of_batched_tasks: usize,
 
+    /// Experimental interval, in seconds, between two checks for the expired documents of the indexes
+    /// with an expiry attribute. A document expiry task is enqueued for the indexes with expired documents,
+    /// `0` disables these tasks.
+    #[clap(long, env = MEILI_EXPERIMENTAL_DOCUMENT_EXPIRY_INTERVAL, default_value_t = default_document_expiry_interval())]
+    #[serde(default = "default_document_expiry_interval")]
+    pub experimental_document_expiry_interval: u64,
+
+    /// Sets the directory of the models and tokenizers of the `onnx` embedders.
+    ///
+    /// The paths of the models in the embedder settings are relative to this directory, the `onnx`
+    /// embedders can't be used when it is not defined.
+    #[clap(long, env = MEILI_ONNX_MODELS_DIR)]
+    pub onnx_models_dir: Option<PathBuf>,
+
+    /// Sets the path to the ONNX Runtime library used by the `onnx` embedders.
+    ///
+    /// By default, the library is searched in the library search path of the system.
+    #[clap(long, env = ORT_DYLIB_PATH)]
+    pub onnx_runtime_path: Option<PathBuf>,
+
     #[serde(flatten)]
     #[clap(flatten)]
     pub indexer_options: IndexerOpts,
@@ -468,6 +493,9 @@ impl Opt {
             max_task_db_size: _,
             http_payload_size_limit,
             experimental_max_number_of_batched_tasks,
+            experimental_document_expiry_interval,
+            onnx_models_dir,
+            onnx_runtime_path,
             ssl_cert_path,
             ssl_key_path,
             ssl_auth_path,
@@ -525,6 +553,16 @@ impl Opt {
             MEILI_EXPERIMENTAL_MAX_NUMBER_OF_BATCHED_TASKS,
             experimental_max_number_of_batched_tasks.to_string(),
         );
+        export_to_env_if_not_present(
+            MEILI_EXPERIMENTAL_DOCUMENT_EXPIRY_INTERVAL,
+            experimental_document_expiry_interval.to_string(),
+        );
+        if let Some(onnx_models_dir) = onnx_models_dir {
+            export_to_env_if_not_present(MEILI_ONNX_MODELS_DIR, onnx_models_dir);
+        }
+        if let Some(onnx_runtime_path) = onnx_runtime_path {
+            export_to_env_if_not_present(ORT_DYLIB_PATH, onnx_runtime_path);
+        }
         if let Some(ssl_cert_path) = ssl_cert_path {
             export_to_env_if_not_present(MEILI_SSL_CERT_PATH, ssl_cert_path);
         }
@@ -622,6 +660,17 @@ impl Opt {
         }
     }
 
+    /// Builds the configuration of the indexer from the indexer options and the paths
+    /// of the ONNX models and runtime.
+    pub fn indexer_config(&self) -> anyhow::Result<IndexerConfig> {
+        let mut config = IndexerConfig::try_from(&self.indexer_options)?;
+        config.onnx_runtime = onnx::RuntimeOptions {
+            models_dir: self.onnx_models_dir.clone(),
+            runtime_path: self.onnx_runtime_path.clone(),
+        };
+        Ok(config)
+    }
+
     pub(crate) fn to_instance_features(&self) -> InstanceTogglableFeatures {
         InstanceTogglableFeatures {
             metrics: self.experimental_enable_metrics,
@@ -878,6 +927,10 @@ fn default_limit_batched_tasks() -> usize {
     usize::MAX
 }
 
+fn default_document_expiry_interval() -> u64 {
+    60
+}
+
 fn default_snapshot_dir() -> PathBuf {
     PathBuf::from(DEFAULT_SNAPSHOT_DIR)
 }
@@ -10,20 +10,23 @@ use futures::StreamExt;
 use index_scheduler::{IndexScheduler, RoFeatures, TaskId};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
-use meilisearch_types::document_formats::{read_csv, read_json, read_ndjson, PayloadType};
+use meilisearch_types::document_formats::{
+    read_arrow, read_csv, read_json, read_ndjson, read_parquet, PayloadType,
+};
 use meilisearch_types::error::deserr_codes::*;
 use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::index::RESERVED_VERSION_FIELD_NAME;
 use meilisearch_types::milli::update::IndexDocumentsMethod;
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
-use meilisearch_types::milli::DocumentId;
+use meilisearch_types::milli::{DocumentId, SearchCursor};
 use meilisearch_types::star_or::OptionStarOrList;
 use meilisearch_types::tasks::KindWithContent;
 use meilisearch_types::{milli, Document, Index};
 use mime::Mime;
 use once_cell::sync::Lazy;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use tempfile::tempfile;
 use tokio::fs::File;
@@ -40,11 +43,18 @@ use crate::extractors::sequential_extractor::SeqHandler;
 use crate::routes::{
     get_task_id, is_dry_run, PaginationView, SummarizedTaskView, PAGINATION_DEFAULT_LIMIT,
 };
-use crate::search::{parse_filter, RetrieveVectors};
+use crate::search::{parse_filter, RetrieveVectors, SearchAfter};
 use crate::Opt;
 
 static ACCEPTED_CONTENT_TYPE: Lazy<Vec<String>> = Lazy::new(|| {
-    vec!["application/json".to_string(), "application/x-ndjson".to_string(), "text/csv".to_string()]
+    vec![
+        "application/json".to_string(),
+        "application/x-ndjson".to_string(),
+        "text/csv".to_string(),
+        "application/vnd.apache.parquet".to_string(),
+        "application/vnd.apache.arrow.file".to_string(),
+        "application/vnd.apache.arrow.stream".to_string(),
+    ]
 });
 
 /// Extracts the mime type from the content type and return
@@ -98,6 +108,8 @@ pub struct GetDocument {
     fields: OptionStarOrList<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRetrieveVectors>)]
     retrieve_vectors: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentRetrieveVersion>)]
+    retrieve_version: Param<bool>,
 }
 
 pub async fn get_document(
@@ -111,7 +123,8 @@ pub async fn get_document(
     debug!(parameters = ?params, "Get document");
     let index_uid = IndexUid::try_from(index_uid)?;
 
-    let GetDocument { fields, retrieve_vectors: param_retrieve_vectors } = params.into_inner();
+    let GetDocument { fields, retrieve_vectors: param_retrieve_vectors, retrieve_version } =
+        params.into_inner();
     let attributes_to_retrieve = fields.merge_star_and_none();
 
     let features = index_scheduler.features();
@@ -123,8 +136,13 @@ pub async fn get_document(
     );
 
     let index = index_scheduler.index(&index_uid)?;
-    let document =
-        retrieve_document(&index, &document_id, attributes_to_retrieve, retrieve_vectors)?;
+    let document = retrieve_document(
+        &index,
+        &document_id,
+        attributes_to_retrieve,
+        retrieve_vectors,
+        retrieve_version.0,
+    )?;
     debug!(returns = ?document, "Get document");
     Ok(HttpResponse::Ok().json(document))
 }
@@ -168,6 +186,8 @@ pub struct BrowseQueryGet {
     retrieve_vectors: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidDocumentFilter>)]
     filter: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentSearchAfter>)]
+    search_after: Option<SearchAfter>,
 }
 
 #[derive(Debug, Deserr)]
@@ -183,6 +203,18 @@ pub struct BrowseQuery {
     retrieve_vectors: bool,
     #[deserr(default, error = DeserrJsonError<InvalidDocumentFilter>)]
     filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidDocumentSearchAfter>)]
+    search_after: Option<SearchAfter>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DocumentsPageView {
+    #[serde(flatten)]
+    page: PaginationView<Document>,
+    /// Only returned when the request used `searchAfter` and more documents may follow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_search_after: Option<String>,
 }
 
 pub async fn documents_by_query_post(
@@ -217,7 +249,8 @@ pub async fn get_documents(
 ) -> Result<HttpResponse, ResponseError> {
     debug!(parameters = ?params, "Get documents GET");
 
-    let BrowseQueryGet { limit, offset, fields, retrieve_vectors, filter } = params.into_inner();
+    let BrowseQueryGet { limit, offset, fields, retrieve_vectors, filter, search_after } =
+        params.into_inner();
 
     let filter = match filter {
         Some(f) => match serde_json::from_str(&f) {
@@ -233,6 +266,7 @@ pub async fn get_documents(
         fields: fields.merge_star_and_none(),
         retrieve_vectors: retrieve_vectors.0,
         filter,
+        search_after,
     };
 
     analytics.get_fetch_documents(
@@ -254,35 +288,62 @@ fn documents_by_query(
     query: BrowseQuery,
 ) -> Result<HttpResponse, ResponseError> {
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
-    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter } = query;
+    let BrowseQuery { offset, limit, fields, retrieve_vectors, filter, search_after } = query;
 
     let features = index_scheduler.features();
     let retrieve_vectors = RetrieveVectors::new(retrieve_vectors, features)?;
 
     let index = index_scheduler.index(&index_uid)?;
-    let (total, documents) = retrieve_documents(
+    let (total, documents, last_docid) = retrieve_documents(
         &index,
         offset,
         limit,
         filter,
+        search_after.as_ref().and_then(|SearchAfter(cursor)| cursor.as_ref()),
         fields,
         retrieve_vectors,
         index_scheduler.features(),
     )?;
 
-    let ret = PaginationView::new(offset, limit, total as usize, documents);
+    // A page that is not full is the last one, there is no need to send a cursor for the next one.
+    let next_search_after = match (search_after, last_docid) {
+        (Some(_), Some(docid)) if documents.len() == limit => {
+            Some(SearchCursor::from_docid(docid).to_string())
+        }
+        _ => None,
+    };
+
+    let ret = DocumentsPageView {
+        page: PaginationView::new(offset, limit, total as usize, documents),
+        next_search_after,
+    };
 
     debug!(returns = ?ret, "Get documents");
     Ok(HttpResponse::Ok().json(ret))
 }
 
-#[derive(Deserialize, Debug, Deserr)]
+#[derive(Debug, Deserr)]
 #[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
 pub struct UpdateDocumentsQuery {
     #[deserr(default, error = DeserrQueryParamError<InvalidIndexPrimaryKey>)]
     pub primary_key: Option<String>,
     #[deserr(default, try_from(char) = from_char_csv_delimiter -> DeserrQueryParamError<InvalidDocumentCsvDelimiter>, error = DeserrQueryParamError<InvalidDocumentCsvDelimiter>)]
     pub csv_delimiter: Option<u8>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentMergeStrategy>)]
+    pub merge_strategy: Option<MergeStrategy>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidDocumentOperators>)]
+    pub operators: Param<bool>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum MergeStrategy {
+    /// Only the top-level attributes of the documents are merged
+    #[default]
+    Shallow,
+    /// The documents are applied as JSON merge patches, merging the nested objects
+    Deep,
 }
 
 fn from_char_csv_delimiter(
@@ -312,6 +373,13 @@ pub async fn replace_documents(
     debug!(parameters = ?params, "Replace documents");
     let params = params.into_inner();
 
+    if params.merge_strategy.is_some() {
+        return Err(MeilisearchHttpError::MergeStrategyWithDocumentsReplacement.into());
+    }
+    if params.operators.0 {
+        return Err(MeilisearchHttpError::OperatorsWithDocumentsReplacement.into());
+    }
+
     analytics.add_documents(
         &params,
         index_scheduler.index_exists(&index_uid).map_or(true, |x| !x),
@@ -369,7 +437,12 @@ pub async fn update_documents(
         params.primary_key,
         params.csv_delimiter,
         body,
-        IndexDocumentsMethod::UpdateDocuments,
+        match (params.merge_strategy.unwrap_or_default(), params.operators.0) {
+            (MergeStrategy::Shallow, false) => IndexDocumentsMethod::UpdateDocuments,
+            (MergeStrategy::Shallow, true) => IndexDocumentsMethod::UpdateDocumentsWithOperators,
+            (MergeStrategy::Deep, false) => IndexDocumentsMethod::DeepMergeDocuments,
+            (MergeStrategy::Deep, true) => IndexDocumentsMethod::DeepMergeDocumentsWithOperators,
+        },
         uid,
         dry_run,
         allow_index_creation,
@@ -401,6 +474,10 @@ async fn document_addition(
         (Some(("application", "x-ndjson")), None) => PayloadType::Ndjson,
         (Some(("text", "csv")), None) => PayloadType::Csv { delimiter: b',' },
         (Some(("text", "csv")), Some(delimiter)) => PayloadType::Csv { delimiter },
+        (Some(("application", "vnd.apache.parquet")), None) => PayloadType::Parquet,
+        (Some(("application", "vnd.apache.arrow.file" | "vnd.apache.arrow.stream")), None) => {
+            PayloadType::Arrow
+        }
 
         (Some(("application", "json")), Some(_)) => {
             return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(String::from(
@@ -412,6 +489,16 @@ async fn document_addition(
                 "application/x-ndjson",
             )))
         }
+        (Some(("application", subtype)), Some(_))
+            if matches!(
+                subtype,
+                "vnd.apache.parquet" | "vnd.apache.arrow.file" | "vnd.apache.arrow.stream"
+            ) =>
+        {
+            return Err(MeilisearchHttpError::CsvDelimiterWithWrongContentType(format!(
+                "application/{subtype}"
+            )))
+        }
         (Some((type_, subtype)), _) => {
             return Err(MeilisearchHttpError::InvalidContentType(
                 format!("{}/{}", type_, subtype),
@@ -465,6 +552,8 @@ async fn document_addition(
             PayloadType::Json => read_json(&read_file, &mut update_file)?,
             PayloadType::Csv { delimiter } => read_csv(&read_file, &mut update_file, delimiter)?,
             PayloadType::Ndjson => read_ndjson(&read_file, &mut update_file)?,
+            PayloadType::Parquet => read_parquet(&read_file, &mut update_file)?,
+            PayloadType::Arrow => read_arrow(&read_file, &mut update_file)?,
         };
         // we NEED to persist the file here because we moved the `udpate_file` in another task.
         update_file.persist()?;
@@ -735,15 +824,17 @@ fn some_documents<'a, 't: 'a>(
     }))
 }
 
+#[allow(clippy::too_many_arguments)]
 fn retrieve_documents<S: AsRef<str>>(
     index: &Index,
     offset: usize,
     limit: usize,
     filter: Option<Value>,
+    search_after: Option<&SearchCursor>,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
-) -> Result<(u64, Vec<Document>), ResponseError> {
+) -> Result<(u64, Vec<Document>, Option<DocumentId>), ResponseError> {
     let rtxn = index.read_txn()?;
     let filter = &filter;
     let filter = if let Some(filter) = filter {
@@ -752,7 +843,7 @@ fn retrieve_documents<S: AsRef<str>>(
         None
     };
 
-    let candidates = if let Some(filter) = filter {
+    let mut candidates = if let Some(filter) = filter {
         filter.evaluate(&rtxn, index).map_err(|err| match err {
             milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
                 ResponseError::from_msg(err.to_string(), Code::InvalidDocumentFilter)
@@ -763,18 +854,16 @@ fn retrieve_documents<S: AsRef<str>>(
         index.documents_ids(&rtxn)?
     };
 
-    let (it, number_of_documents) = {
-        let number_of_documents = candidates.len();
-        (
-            some_documents(
-                index,
-                &rtxn,
-                candidates.into_iter().skip(offset).take(limit),
-                retrieve_vectors,
-            )?,
-            number_of_documents,
-        )
-    };
+    let number_of_documents = candidates.len();
+
+    // documents are browsed in docid order, the cursor is simply the last returned docid
+    if let Some(cursor) = search_after {
+        candidates.remove_range(..=cursor.docid);
+    }
+    let documents_ids: Vec<_> = candidates.into_iter().skip(offset).take(limit).collect();
+    let last_docid = documents_ids.last().copied();
+
+    let it = some_documents(index, &rtxn, documents_ids, retrieve_vectors)?;
 
     let documents: Vec<_> = it
         .map(|document| {
@@ -790,7 +879,7 @@ fn retrieve_documents<S: AsRef<str>>(
         })
         .collect::<Result<_, ResponseError>>()?;
 
-    Ok((number_of_documents, documents))
+    Ok((number_of_documents, documents, last_docid))
 }
 
 fn retrieve_document<S: AsRef<str>>(
@@ -798,6 +887,7 @@ fn retrieve_document<S: AsRef<str>>(
     doc_id: &str,
     attributes_to_retrieve: Option<Vec<S>>,
     retrieve_vectors: RetrieveVectors,
+    retrieve_version: bool,
 ) -> Result<Document, ResponseError> {
     let txn = index.read_txn()?;
 
@@ -810,7 +900,7 @@ fn retrieve_document<S: AsRef<str>>(
         .next()
         .ok_or_else(|| MeilisearchHttpError::DocumentNotFound(doc_id.to_string()))??;
 
-    let document = match &attributes_to_retrieve {
+    let mut document = match &attributes_to_retrieve {
         Some(attributes_to_retrieve) => permissive_json_pointer::select_values(
             &document,
             attributes_to_retrieve
@@ -821,5 +911,10 @@ fn retrieve_document<S: AsRef<str>>(
         None => document,
     };
 
+    if retrieve_version {
+        let version = index.document_version(&txn, internal_id)?;
+        document.insert(RESERVED_VERSION_FIELD_NAME.to_string(), version.into());
+    }
+
     Ok(document)
 }
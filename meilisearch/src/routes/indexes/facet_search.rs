@@ -17,7 +17,7 @@ use crate::routes::indexes::search::search_kind;
 use crate::search::{
     add_search_rules, perform_facet_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
     SearchQuery, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG,
-    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+    DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_HITS_PER_GROUP, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
 };
 use crate::search_queue::SearchQueue;
 
@@ -129,6 +129,7 @@ impl From<FacetSearchQuery> for SearchQuery {
             limit: DEFAULT_SEARCH_LIMIT(),
             page: None,
             hits_per_page: None,
+            search_after: None,
             attributes_to_retrieve: None,
             retrieve_vectors: false,
             attributes_to_crop: None,
@@ -139,8 +140,14 @@ impl From<FacetSearchQuery> for SearchQuery {
             show_ranking_score_details: false,
             filter,
             sort: None,
+            ranking_rules: None,
             distinct: None,
+            group_by: None,
+            hits_per_group: DEFAULT_HITS_PER_GROUP(),
+            did_you_mean: false,
+            rerank: None,
             facets: None,
+            facet_ranges: None,
             highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
             highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
             crop_marker: DEFAULT_CROP_MARKER(),
@@ -27,9 +27,11 @@ use crate::Opt;
 
 pub mod documents;
 pub mod facet_search;
+pub mod recommend;
 pub mod search;
 pub mod settings;
 pub mod similar;
+pub mod suggest;
 
 pub fn configure(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -50,6 +52,8 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
             .service(web::scope("/search").configure(search::configure))
             .service(web::scope("/facet-search").configure(facet_search::configure))
             .service(web::scope("/similar").configure(similar::configure))
+            .service(web::scope("/recommend").configure(recommend::configure))
+            .service(web::scope("/suggest").configure(suggest::configure))
             .service(web::scope("/settings").configure(settings::configure)),
     );
 }
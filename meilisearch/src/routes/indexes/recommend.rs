@@ -0,0 +1,79 @@
+use actix_web::web::{self, Data};
+use actix_web::{HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::keys::actions;
+use serde_json::json;
+use tracing::debug;
+
+use super::ActionPolicy;
+use crate::analytics::Analytics;
+use crate::extractors::authentication::GuardedData;
+use crate::extractors::sequential_extractor::SeqHandler;
+use crate::search::{
+    add_search_rules, perform_recommend, RecommendQuery, RetrieveVectors, SearchKind,
+};
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(SeqHandler(recommend))));
+}
+
+pub async fn recommend(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    index_uid: web::Path<String>,
+    params: AwebJson<RecommendQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let mut query = params.into_inner();
+    debug!(parameters = ?query, "Recommend post");
+
+    let features = index_scheduler.features();
+    features.check_vector("Using the recommend API")?;
+
+    analytics.publish(
+        "Recommendations POST".to_string(),
+        json!({
+            "positive": query.positive.len(),
+            "positive_vectors": query.positive_vectors.len(),
+            "negative": query.negative.len(),
+            "negative_vectors": query.negative_vectors.len(),
+            "filter": query.filter.is_some(),
+            "embedder": query.embedder.is_some(),
+            "ranking_score_threshold": query.ranking_score_threshold.is_some(),
+        }),
+        Some(&req),
+    );
+
+    let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut query.filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+
+    let (embedder_name, embedder) =
+        SearchKind::embedder(&index_scheduler, &index, query.embedder.as_deref(), None)?;
+
+    let recommendations = tokio::task::spawn_blocking(move || {
+        perform_recommend(
+            &index,
+            query,
+            embedder_name,
+            embedder,
+            retrieve_vectors,
+            index_scheduler.features(),
+        )
+    })
+    .await??;
+
+    debug!(returns = ?recommendations, "Recommend post");
+    Ok(HttpResponse::Ok().json(recommendations))
+}
@@ -1,15 +1,20 @@
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
 use actix_web::web::Data;
 use actix_web::{web, HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::{DeserializeError, ErrorKind, ValuePointerRef};
 use index_scheduler::{IndexScheduler, RoFeatures};
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
 use meilisearch_types::error::deserr_codes::*;
-use meilisearch_types::error::ResponseError;
+use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
 use meilisearch_types::milli;
 use meilisearch_types::serde_cs::vec::CS;
+use meilisearch_types::settings::RankingRuleView;
 use serde_json::Value;
 use tracing::debug;
 
@@ -20,10 +25,11 @@ use crate::extractors::authentication::GuardedData;
 use crate::extractors::sequential_extractor::SeqHandler;
 use crate::metrics::MEILISEARCH_DEGRADED_SEARCH_REQUESTS;
 use crate::search::{
-    add_search_rules, perform_search, HybridQuery, MatchingStrategy, RankingScoreThreshold,
-    RetrieveVectors, SearchKind, SearchQuery, SemanticRatio, DEFAULT_CROP_LENGTH,
-    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
-    DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
+    add_search_rules, perform_search, FacetRangesQuery, FusionMethod, HitsPerGroup, HybridQuery,
+    MatchingStrategy, RankingScoreThreshold, RerankKind, RerankQuery, RerankTopK, RetrieveVectors,
+    SearchAfter, SearchKind, SearchQuery, SemanticRatio, DEFAULT_CROP_LENGTH, DEFAULT_CROP_MARKER,
+    DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG, DEFAULT_HITS_PER_GROUP,
+    DEFAULT_RERANK_TOP_K, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET, DEFAULT_SEMANTIC_RATIO,
 };
 use crate::search_queue::SearchQueue;
 
@@ -36,7 +42,7 @@ pub fn configure(cfg: &mut web::ServiceConfig) {
 }
 
 #[derive(Debug, deserr::Deserr)]
-#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields, validate = validate_search_query_get -> DeserrQueryParamError<InvalidSearchFusionK>)]
 pub struct SearchQueryGet {
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchQ>)]
     q: Option<String>,
@@ -50,6 +56,8 @@ pub struct SearchQueryGet {
     page: Option<Param<usize>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchHitsPerPage>)]
     hits_per_page: Option<Param<usize>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchSearchAfter>)]
+    search_after: Option<SearchAfter>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchAttributesToRetrieve>)]
     attributes_to_retrieve: Option<CS<String>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRetrieveVectors>)]
@@ -64,8 +72,20 @@ pub struct SearchQueryGet {
     filter: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSort>)]
     sort: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingRules>)]
+    ranking_rules: Option<CS<RankingRuleView>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchDistinct>)]
     distinct: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchGroupBy>)]
+    group_by: Option<String>,
+    #[deserr(default = HitsPerGroupGet(DEFAULT_HITS_PER_GROUP()), error = DeserrQueryParamError<InvalidSearchHitsPerGroup>)]
+    hits_per_group: HitsPerGroupGet,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchDidYouMean>)]
+    did_you_mean: Param<bool>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerank>)]
+    pub rerank_reranker: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchRerank>)]
+    pub rerank_top_k: Option<RerankTopKGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowMatchesPosition>)]
     show_matches_position: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchShowRankingScore>)]
@@ -74,6 +94,8 @@ pub struct SearchQueryGet {
     show_ranking_score_details: Param<bool>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacets>)]
     facets: Option<CS<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFacetRanges>)]
+    facet_ranges: Option<String>,
     #[deserr( default = DEFAULT_HIGHLIGHT_PRE_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPreTag>)]
     highlight_pre_tag: String,
     #[deserr( default = DEFAULT_HIGHLIGHT_POST_TAG(), error = DeserrQueryParamError<InvalidSearchHighlightPostTag>)]
@@ -88,12 +110,41 @@ pub struct SearchQueryGet {
     pub hybrid_embedder: Option<String>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchSemanticRatio>)]
     pub hybrid_semantic_ratio: Option<SemanticRatioGet>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFusion>)]
+    pub hybrid_fusion: Option<FusionMethod>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSearchFusionK>)]
+    pub hybrid_k: Option<Param<u32>>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchRankingScoreThreshold>)]
     pub ranking_score_threshold: Option<RankingScoreThresholdGet>,
     #[deserr(default, error = DeserrQueryParamError<InvalidSearchLocales>)]
     pub locales: Option<CS<Locale>>,
 }
 
+fn validate_search_query_get<E: DeserializeError>(
+    query: SearchQueryGet,
+    location: ValuePointerRef,
+) -> Result<SearchQueryGet, E> {
+    if query.hybrid_k.is_some() && query.hybrid_fusion != Some(FusionMethod::Rrf) {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "`hybridK` can only be used with the `rrf` fusion.".to_string(),
+            },
+            location,
+        )));
+    }
+    if query.rerank_top_k.is_some() && query.rerank_reranker.is_none() {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "`rerankTopK` can only be used with `rerankReranker`.".to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(query)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
 #[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchRankingScoreThreshold)]
 pub struct RankingScoreThresholdGet(RankingScoreThreshold);
@@ -107,6 +158,32 @@ impl std::convert::TryFrom<String> for RankingScoreThresholdGet {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, deserr::Deserr)]
+#[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchHitsPerGroup)]
+pub struct HitsPerGroupGet(HitsPerGroup);
+
+impl std::convert::TryFrom<String> for HitsPerGroupGet {
+    type Error = InvalidSearchHitsPerGroup;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let hits_per_group: usize = s.parse().map_err(|_| InvalidSearchHitsPerGroup)?;
+        Ok(HitsPerGroupGet(HitsPerGroup::try_from(hits_per_group)?))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, deserr::Deserr)]
+#[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchRerank)]
+pub struct RerankTopKGet(RerankTopK);
+
+impl std::convert::TryFrom<String> for RerankTopKGet {
+    type Error = InvalidSearchRerank;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        let top_k: usize = s.parse().map_err(|_| InvalidSearchRerank)?;
+        Ok(RerankTopKGet(RerankTopK::try_from(top_k)?))
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, deserr::Deserr)]
 #[deserr(try_from(String) = TryFrom::try_from -> InvalidSearchSemanticRatio)]
 pub struct SemanticRatioGet(SemanticRatio);
@@ -128,8 +205,10 @@ impl std::ops::Deref for SemanticRatioGet {
     }
 }
 
-impl From<SearchQueryGet> for SearchQuery {
-    fn from(other: SearchQueryGet) -> Self {
+impl TryFrom<SearchQueryGet> for SearchQuery {
+    type Error = ResponseError;
+
+    fn try_from(other: SearchQueryGet) -> Result<Self, Self::Error> {
         let filter = match other.filter {
             Some(f) => match serde_json::from_str(&f) {
                 Ok(v) => Some(v),
@@ -138,27 +217,34 @@ impl From<SearchQueryGet> for SearchQuery {
             None => None,
         };
 
-        let hybrid = match (other.hybrid_embedder, other.hybrid_semantic_ratio) {
-            (None, None) => None,
-            (None, Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder: None })
-            }
-            (Some(embedder), None) => Some(HybridQuery {
-                semantic_ratio: DEFAULT_SEMANTIC_RATIO(),
-                embedder: Some(embedder),
+        let hybrid = match (other.hybrid_embedder, other.hybrid_semantic_ratio, other.hybrid_fusion)
+        {
+            (None, None, None) => None,
+            (embedder, semantic_ratio, fusion) => Some(HybridQuery {
+                semantic_ratio: semantic_ratio
+                    .map(|semantic_ratio| *semantic_ratio)
+                    .unwrap_or_else(DEFAULT_SEMANTIC_RATIO),
+                embedder,
+                fusion: fusion.unwrap_or_default(),
+                k: other.hybrid_k.map(|k| k.0),
             }),
-            (Some(embedder), Some(semantic_ratio)) => {
-                Some(HybridQuery { semantic_ratio: *semantic_ratio, embedder: Some(embedder) })
-            }
         };
 
-        Self {
+        let rerank = other.rerank_reranker.map(|reranker| RerankQuery {
+            reranker,
+            top_k: other.rerank_top_k.map(|top_k| top_k.0).unwrap_or_else(DEFAULT_RERANK_TOP_K),
+        });
+
+        let facet_ranges = other.facet_ranges.as_deref().map(parse_facet_ranges).transpose()?;
+
+        Ok(Self {
             q: other.q,
             vector: other.vector.map(CS::into_inner),
             offset: other.offset.0,
             limit: other.limit.0,
             page: other.page.as_deref().copied(),
             hits_per_page: other.hits_per_page.as_deref().copied(),
+            search_after: other.search_after,
             attributes_to_retrieve: other.attributes_to_retrieve.map(|o| o.into_iter().collect()),
             retrieve_vectors: other.retrieve_vectors.0,
             attributes_to_crop: other.attributes_to_crop.map(|o| o.into_iter().collect()),
@@ -166,11 +252,17 @@ impl From<SearchQueryGet> for SearchQuery {
             attributes_to_highlight: other.attributes_to_highlight.map(|o| o.into_iter().collect()),
             filter,
             sort: other.sort.map(|attr| fix_sort_query_parameters(&attr)),
+            ranking_rules: other.ranking_rules.map(|o| o.into_iter().collect()),
             distinct: other.distinct,
+            group_by: other.group_by,
+            hits_per_group: other.hits_per_group.0,
+            did_you_mean: other.did_you_mean.0,
+            rerank,
             show_matches_position: other.show_matches_position.0,
             show_ranking_score: other.show_ranking_score.0,
             show_ranking_score_details: other.show_ranking_score_details.0,
             facets: other.facets.map(|o| o.into_iter().collect()),
+            facet_ranges,
             highlight_pre_tag: other.highlight_pre_tag,
             highlight_post_tag: other.highlight_post_tag,
             crop_marker: other.crop_marker,
@@ -179,10 +271,24 @@ impl From<SearchQueryGet> for SearchQuery {
             hybrid,
             ranking_score_threshold: other.ranking_score_threshold.map(|o| o.0),
             locales: other.locales.map(|o| o.into_iter().collect()),
-        }
+        })
     }
 }
 
+/// Parses the `facetRanges` query parameter, that holds the same JSON object as in a POST search.
+fn parse_facet_ranges(
+    facet_ranges: &str,
+) -> Result<BTreeMap<String, FacetRangesQuery>, ResponseError> {
+    let value: Value = serde_json::from_str(facet_ranges).map_err(|e| {
+        ResponseError::from_msg(
+            format!("Invalid value in parameter `facetRanges`: expected a JSON object: {e}"),
+            Code::InvalidSearchFacetRanges,
+        )
+    })?;
+    deserr::deserialize::<_, _, DeserrJsonError<InvalidSearchFacetRanges>>(value)
+        .map_err(ResponseError::from)
+}
+
 // TODO: TAMO: split on :asc, and :desc, instead of doing some weird things
 
 /// Transform the sort query parameter into something that matches the post expected format.
@@ -219,7 +325,7 @@ pub async fn search_with_url_query(
     debug!(parameters = ?params, "Search get");
     let index_uid = IndexUid::try_from(index_uid.into_inner())?;
 
-    let mut query: SearchQuery = params.into_inner().into();
+    let mut query = SearchQuery::try_from(params.into_inner())?;
 
     // Tenant token search_rules.
     if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
@@ -232,10 +338,18 @@ pub async fn search_with_url_query(
     let features = index_scheduler.features();
 
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
+    let rerank_kind = rerank_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors, features)?;
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vector, index_scheduler.features())
+        perform_search(
+            &index,
+            query,
+            search_kind,
+            rerank_kind,
+            retrieve_vector,
+            index_scheduler.features(),
+        )
     })
     .await;
     permit.drop().await;
@@ -276,11 +390,19 @@ pub async fn search_with_post(
     let features = index_scheduler.features();
 
     let search_kind = search_kind(&query, index_scheduler.get_ref(), &index, features)?;
+    let rerank_kind = rerank_kind(&query, index_scheduler.get_ref(), &index, features)?;
     let retrieve_vectors = RetrieveVectors::new(query.retrieve_vectors, features)?;
 
     let permit = search_queue.try_get_search_permit().await?;
     let search_result = tokio::task::spawn_blocking(move || {
-        perform_search(&index, query, search_kind, retrieve_vectors, index_scheduler.features())
+        perform_search(
+            &index,
+            query,
+            search_kind,
+            rerank_kind,
+            retrieve_vectors,
+            index_scheduler.features(),
+        )
     })
     .await;
     permit.drop().await;
@@ -322,7 +444,7 @@ pub fn search_kind(
     }
 
     match &query.hybrid {
-        Some(HybridQuery { semantic_ratio, embedder }) if **semantic_ratio == 1.0 => {
+        Some(HybridQuery { semantic_ratio, embedder, .. }) if **semantic_ratio == 1.0 => {
             Ok(SearchKind::semantic(
                 index_scheduler,
                 index,
@@ -330,14 +452,15 @@ pub fn search_kind(
                 query.vector.as_ref().map(Vec::len),
             )?)
         }
-        Some(HybridQuery { semantic_ratio, embedder: _ }) if **semantic_ratio == 0.0 => {
+        Some(HybridQuery { semantic_ratio, .. }) if **semantic_ratio == 0.0 => {
             Ok(SearchKind::KeywordOnly)
         }
-        Some(HybridQuery { semantic_ratio, embedder }) => Ok(SearchKind::hybrid(
+        Some(hybrid) => Ok(SearchKind::hybrid(
             index_scheduler,
             index,
-            embedder.as_deref(),
-            **semantic_ratio,
+            hybrid.embedder.as_deref(),
+            *hybrid.semantic_ratio,
+            hybrid.fusion(),
             query.vector.as_ref().map(Vec::len),
         )?),
         None => match (query.q.as_deref(), query.vector.as_deref()) {
@@ -353,6 +476,17 @@ pub fn search_kind(
     }
 }
 
+pub fn rerank_kind(
+    query: &SearchQuery,
+    index_scheduler: &IndexScheduler,
+    index: &milli::Index,
+    features: RoFeatures,
+) -> Result<Option<RerankKind>, ResponseError> {
+    let Some(rerank) = &query.rerank else { return Ok(None) };
+    features.check_vector("Passing `rerank` as a parameter")?;
+    Ok(Some(RerankKind::new(index_scheduler, index, rerank)?))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
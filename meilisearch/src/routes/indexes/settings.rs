@@ -203,6 +203,55 @@ make_setting_route!(
     }
 );
 
+make_setting_route!(
+    "/datetime-attributes",
+    put,
+    std::collections::BTreeSet<String>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsDatetimeAttributes,
+    >,
+    datetime_attributes,
+    "datetimeAttributes",
+    analytics,
+    |setting: &Option<std::collections::BTreeSet<String>>, req: &HttpRequest| {
+        use serde_json::json;
+
+        analytics.publish(
+            "DatetimeAttributes Updated".to_string(),
+            json!({
+                "datetime_attributes": {
+                    "total": setting.as_ref().map(|datetime| datetime.len()),
+                },
+            }),
+            Some(req),
+        );
+    }
+);
+
+make_setting_route!(
+    "/expiry-attribute",
+    put,
+    String,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsExpiryAttribute,
+    >,
+    expiry_attribute,
+    "expiryAttribute",
+    analytics,
+    |expiry: &Option<String>, req: &HttpRequest| {
+        use serde_json::json;
+        analytics.publish(
+            "ExpiryAttribute Updated".to_string(),
+            json!({
+                "expiry_attribute": {
+                    "set": expiry.is_some(),
+                }
+            }),
+            Some(req),
+        );
+    }
+);
+
 make_setting_route!(
     "/displayed-attributes",
     put,
@@ -626,6 +675,7 @@ fn embedder_analytics(
                 EmbedderSource::UserProvided => sources.insert("userProvided"),
                 EmbedderSource::Ollama => sources.insert("ollama"),
                 EmbedderSource::Rest => sources.insert("rest"),
+                EmbedderSource::Onnx => sources.insert("onnx"),
             };
         }
     };
@@ -643,12 +693,86 @@ fn embedder_analytics(
             .max()
     });
 
+    let chunking_used = setting.as_ref().map(|map| {
+        map.values()
+            .filter_map(|config| config.clone().set())
+            .any(|config| config.chunking.set().is_some())
+    });
+
+    let quantizations: Option<std::collections::BTreeSet<String>> = setting.as_ref().map(|map| {
+        map.values()
+            .filter_map(|config| config.clone().set())
+            .filter_map(|config| config.quantization.set())
+            .map(|quantization| quantization.to_string())
+            .collect()
+    });
+
+    json!(
+        {
+            "total": setting.as_ref().map(|s| s.len()),
+            "sources": sources,
+            "document_template_used": document_template_used,
+            "document_template_max_bytes": document_template_max_bytes,
+            "quantizations": quantizations,
+            "chunking_used": chunking_used
+        }
+    )
+}
+
+make_setting_route!(
+    "/rerankers",
+    patch,
+    std::collections::BTreeMap<String, Setting<meilisearch_types::milli::vector::settings::RerankerSettings>>,
+    meilisearch_types::deserr::DeserrJsonError<
+        meilisearch_types::error::deserr_codes::InvalidSettingsRerankers,
+    >,
+    rerankers,
+    "rerankers",
+    analytics,
+    |setting: &Option<std::collections::BTreeMap<String, Setting<meilisearch_types::milli::vector::settings::RerankerSettings>>>, req: &HttpRequest| {
+        analytics.publish(
+            "Rerankers Updated".to_string(),
+            serde_json::json!({"rerankers": crate::routes::indexes::settings::reranker_analytics(setting.as_ref())}),
+            Some(req),
+        );
+    }
+);
+
+fn reranker_analytics(
+    setting: Option<
+        &std::collections::BTreeMap<
+            String,
+            Setting<meilisearch_types::milli::vector::settings::RerankerSettings>,
+        >,
+    >,
+) -> serde_json::Value {
+    let mut sources = std::collections::HashSet::new();
+
+    if let Some(s) = &setting {
+        for source in s
+            .values()
+            .filter_map(|config| config.clone().set())
+            .filter_map(|config| config.source.set())
+        {
+            use meilisearch_types::milli::vector::settings::RerankerSource;
+            match source {
+                RerankerSource::HuggingFace => sources.insert("huggingFace"),
+                RerankerSource::Rest => sources.insert("rest"),
+            };
+        }
+    };
+
+    let document_template_used = setting.as_ref().map(|map| {
+        map.values()
+            .filter_map(|config| config.clone().set())
+            .any(|config| config.document_template.set().is_some())
+    });
+
     json!(
         {
             "total": setting.as_ref().map(|s| s.len()),
             "sources": sources,
             "document_template_used": document_template_used,
-            "document_template_max_bytes": document_template_max_bytes
         }
     )
 }
@@ -689,6 +813,8 @@ macro_rules! generate_configure {
 generate_configure!(
     filterable_attributes,
     sortable_attributes,
+    datetime_attributes,
+    expiry_attribute,
     displayed_attributes,
     localized_attributes,
     searchable_attributes,
@@ -704,6 +830,7 @@ generate_configure!(
     pagination,
     faceting,
     embedders,
+    rerankers,
     search_cutoff_ms
 );
 
@@ -745,6 +872,12 @@ pub async fn update_all(
                 "total": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.len()),
                 "has_geo": new_settings.sortable_attributes.as_ref().set().map(|sort| sort.iter().any(|s| s == "_geo")),
             },
+           "datetime_attributes": {
+                "total": new_settings.datetime_attributes.as_ref().set().map(|datetime| datetime.len()),
+            },
+            "expiry_attribute": {
+                "set": new_settings.expiry_attribute.as_ref().set().is_some()
+            },
            "filterable_attributes": {
                 "total": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.len()),
                 "has_geo": new_settings.filterable_attributes.as_ref().set().map(|filter| filter.iter().any(|s| s == "_geo")),
@@ -816,6 +949,7 @@ pub async fn update_all(
                 "total": new_settings.synonyms.as_ref().set().map(|synonyms| synonyms.len()),
             },
             "embedders": crate::routes::indexes::settings::embedder_analytics(new_settings.embedders.as_ref().set()),
+            "rerankers": crate::routes::indexes::settings::reranker_analytics(new_settings.rerankers.as_ref().set()),
             "search_cutoff_ms": new_settings.search_cutoff_ms.as_ref().set(),
             "locales": new_settings.localized_attributes.as_ref().set().map(|rules| rules.iter().flat_map(|rule| rule.locales.iter().cloned()).collect::<std::collections::BTreeSet<_>>()),
         }),
@@ -890,5 +1024,8 @@ fn validate_settings(
     if matches!(settings.embedders, Setting::Set(_)) {
         index_scheduler.features().check_vector("Passing `embedders` in settings")?
     }
+    if matches!(settings.rerankers, Setting::Set(_)) {
+        index_scheduler.features().check_vector("Passing `rerankers` in settings")?
+    }
     Ok(settings.validate()?)
 }
@@ -1,6 +1,9 @@
+use std::convert::Infallible;
+
 use actix_web::web::{self, Data};
 use actix_web::{HttpRequest, HttpResponse};
 use deserr::actix_web::{AwebJson, AwebQueryParameter};
+use deserr::{DeserializeError, ErrorKind, ValuePointerRef};
 use index_scheduler::IndexScheduler;
 use meilisearch_types::deserr::query_params::Param;
 use meilisearch_types::deserr::{DeserrJsonError, DeserrQueryParamError};
@@ -119,10 +122,14 @@ async fn similar(
 }
 
 #[derive(Debug, deserr::Deserr)]
-#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields)]
+#[deserr(error = DeserrQueryParamError, rename_all = camelCase, deny_unknown_fields, validate = validate_similar_query_get -> DeserrQueryParamError<InvalidSimilarId>)]
 pub struct SimilarQueryGet {
-    #[deserr(error = DeserrQueryParamError<InvalidSimilarId>)]
-    id: Param<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarId>)]
+    id: Option<Param<String>>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarText>)]
+    text: Option<String>,
+    #[deserr(default, error = DeserrQueryParamError<InvalidSimilarVector>)]
+    vector: Option<CS<f32>>,
     #[deserr(default = Param(DEFAULT_SEARCH_OFFSET()), error = DeserrQueryParamError<InvalidSimilarOffset>)]
     offset: Param<usize>,
     #[deserr(default = Param(DEFAULT_SEARCH_LIMIT()), error = DeserrQueryParamError<InvalidSimilarLimit>)]
@@ -143,6 +150,25 @@ pub struct SimilarQueryGet {
     pub embedder: Option<String>,
 }
 
+fn validate_similar_query_get<E: DeserializeError>(
+    query: SimilarQueryGet,
+    location: ValuePointerRef,
+) -> Result<SimilarQueryGet, E> {
+    let target_count = query.id.is_some() as usize
+        + query.text.is_some() as usize
+        + query.vector.is_some() as usize;
+    if target_count != 1 {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "exactly one of `id`, `text` or `vector` must be specified.".to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(query)
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, deserr::Deserr)]
 #[deserr(try_from(String) = TryFrom::try_from -> InvalidSimilarRankingScoreThreshold)]
 pub struct RankingScoreThresholdGet(RankingScoreThresholdSimilar);
@@ -162,6 +188,8 @@ impl TryFrom<SimilarQueryGet> for SimilarQuery {
     fn try_from(
         SimilarQueryGet {
             id,
+            text,
+            vector,
             offset,
             limit,
             attributes_to_retrieve,
@@ -182,9 +210,11 @@ impl TryFrom<SimilarQueryGet> for SimilarQuery {
         };
 
         Ok(SimilarQuery {
-            id: id.0.try_into().map_err(|code: InvalidSimilarId| {
+            id: id.map(|id| id.0.try_into()).transpose().map_err(|code: InvalidSimilarId| {
                 ResponseError::from_msg(code.to_string(), code.error_code())
             })?,
+            text,
+            vector: vector.map(CS::into_inner),
             offset: offset.0,
             limit: limit.0,
             filter,
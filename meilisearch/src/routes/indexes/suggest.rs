@@ -0,0 +1,124 @@
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse};
+use deserr::actix_web::AwebJson;
+use index_scheduler::IndexScheduler;
+use meilisearch_types::deserr::DeserrJsonError;
+use meilisearch_types::error::deserr_codes::*;
+use meilisearch_types::error::ResponseError;
+use meilisearch_types::index_uid::IndexUid;
+use meilisearch_types::milli::DEFAULT_SUGGESTIONS_LIMIT;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::analytics::Analytics;
+use crate::extractors::authentication::policies::*;
+use crate::extractors::authentication::GuardedData;
+use crate::search::{
+    add_search_rules, perform_suggest, MatchingStrategy, SearchQuery, DEFAULT_CROP_LENGTH,
+    DEFAULT_CROP_MARKER, DEFAULT_HIGHLIGHT_POST_TAG, DEFAULT_HIGHLIGHT_PRE_TAG,
+    DEFAULT_HITS_PER_GROUP, DEFAULT_SEARCH_LIMIT, DEFAULT_SEARCH_OFFSET,
+};
+use crate::search_queue::SearchQueue;
+
+pub fn configure(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::resource("").route(web::post().to(suggest)));
+}
+
+#[derive(Debug, Clone, Default, PartialEq, deserr::Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+pub struct SuggestQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidSearchQ>)]
+    pub q: Option<String>,
+    #[deserr(default = DEFAULT_SUGGESTIONS_LIMIT, error = DeserrJsonError<InvalidSearchLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToSearchOn>)]
+    pub attributes_to_search_on: Option<Vec<String>>,
+}
+
+pub async fn suggest(
+    index_scheduler: GuardedData<ActionPolicy<{ actions::SEARCH }>, Data<IndexScheduler>>,
+    search_queue: Data<SearchQueue>,
+    index_uid: web::Path<String>,
+    params: AwebJson<SuggestQuery, DeserrJsonError>,
+    req: HttpRequest,
+    analytics: web::Data<dyn Analytics>,
+) -> Result<HttpResponse, ResponseError> {
+    let index_uid = IndexUid::try_from(index_uid.into_inner())?;
+
+    let query = params.into_inner();
+    debug!(parameters = ?query, "Suggest");
+
+    analytics.publish(
+        "Suggestions POST".to_string(),
+        json!({
+            "limit": query.limit,
+            "filter": query.filter.is_some(),
+            "attributes_to_search_on": query.attributes_to_search_on.is_some(),
+        }),
+        Some(&req),
+    );
+
+    let limit = query.limit;
+    let mut search_query = SearchQuery::from(query);
+
+    // Tenant token search_rules.
+    if let Some(search_rules) = index_scheduler.filters().get_index_search_rules(&index_uid) {
+        add_search_rules(&mut search_query.filter, search_rules);
+    }
+
+    let index = index_scheduler.index(&index_uid)?;
+    let features = index_scheduler.features();
+    let permit = search_queue.try_get_search_permit().await?;
+    let suggest_result =
+        tokio::task::spawn_blocking(move || perform_suggest(&index, search_query, limit, features))
+            .await;
+    permit.drop().await;
+    let suggest_result = suggest_result??;
+
+    debug!(returns = ?suggest_result, "Suggest");
+    Ok(HttpResponse::Ok().json(suggest_result))
+}
+
+impl From<SuggestQuery> for SearchQuery {
+    fn from(value: SuggestQuery) -> Self {
+        let SuggestQuery { q, limit: _, filter, attributes_to_search_on } = value;
+
+        SearchQuery {
+            q,
+            offset: DEFAULT_SEARCH_OFFSET(),
+            limit: DEFAULT_SEARCH_LIMIT(),
+            page: None,
+            hits_per_page: None,
+            search_after: None,
+            attributes_to_retrieve: None,
+            retrieve_vectors: false,
+            attributes_to_crop: None,
+            crop_length: DEFAULT_CROP_LENGTH(),
+            attributes_to_highlight: None,
+            show_matches_position: false,
+            show_ranking_score: false,
+            show_ranking_score_details: false,
+            filter,
+            sort: None,
+            ranking_rules: None,
+            distinct: None,
+            group_by: None,
+            hits_per_group: DEFAULT_HITS_PER_GROUP(),
+            did_you_mean: false,
+            rerank: None,
+            facets: None,
+            facet_ranges: None,
+            highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
+            highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
+            crop_marker: DEFAULT_CROP_MARKER(),
+            matching_strategy: MatchingStrategy::default(),
+            vector: None,
+            attributes_to_search_on,
+            hybrid: None,
+            ranking_score_threshold: None,
+            locales: None,
+        }
+    }
+}
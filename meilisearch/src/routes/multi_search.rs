@@ -14,7 +14,7 @@ use crate::error::MeilisearchHttpError;
 use crate::extractors::authentication::policies::ActionPolicy;
 use crate::extractors::authentication::{AuthenticationError, GuardedData};
 use crate::extractors::sequential_extractor::SeqHandler;
-use crate::routes::indexes::search::search_kind;
+use crate::routes::indexes::search::{rerank_kind, search_kind};
 use crate::search::{
     add_search_rules, perform_federated_search, perform_search, FederatedSearch, RetrieveVectors,
     SearchQueryWithIndex, SearchResultWithIndex,
@@ -127,11 +127,21 @@ pub async fn multi_search_with_post(
                     let search_kind =
                         search_kind(&query, index_scheduler.get_ref(), &index, features)
                             .with_index(query_index)?;
+                    let rerank_kind =
+                        rerank_kind(&query, index_scheduler.get_ref(), &index, features)
+                            .with_index(query_index)?;
                     let retrieve_vector = RetrieveVectors::new(query.retrieve_vectors, features)
                         .with_index(query_index)?;
 
                     let search_result = tokio::task::spawn_blocking(move || {
-                        perform_search(&index, query, search_kind, retrieve_vector, features)
+                        perform_search(
+                            &index,
+                            query,
+                            search_kind,
+                            rerank_kind,
+                            retrieve_vector,
+                            features,
+                        )
                     })
                     .await
                     .with_index(query_index)?;
@@ -341,6 +341,12 @@ pub fn perform_federated_search(
             )
             .into());
         }
+        if federated_query.group_by.is_some() {
+            return Err(MeilisearchHttpError::GroupByInFederatedQuery(query_index).into());
+        }
+        if federated_query.rerank.is_some() {
+            return Err(MeilisearchHttpError::RerankInFederatedQuery(query_index).into());
+        }
 
         let (index_uid, query, federation_options) = federated_query.into_index_query_federation();
 
@@ -427,8 +433,15 @@ pub fn perform_federated_search(
                     None
                 };
 
+                let criteria = match &query.ranking_rules {
+                    Some(ranking_rules) => {
+                        ranking_rules.iter().cloned().map(milli::Criterion::from).collect()
+                    }
+                    None => criteria.clone(),
+                };
+
                 let ranking_rules = ranking_rules::RankingRules::new(
-                    criteria.clone(),
+                    criteria,
                     sort,
                     query.matching_strategy.into(),
                     canonicalization_kind,
@@ -501,6 +514,9 @@ pub fn perform_federated_search(
                     document_scores,
                     degraded: query_degraded,
                     used_negative_operator: query_used_negative_operator,
+                    cursor: _,
+                    corrected_query: _,
+                    groups: _,
                 } = result;
 
                 candidates |= query_candidates;
@@ -1,11 +1,12 @@
 use core::fmt;
 use std::cmp::min;
 use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::convert::Infallible;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use deserr::Deserr;
+use deserr::{DeserializeError, Deserr, ErrorKind, ValuePointerRef};
 use either::Either;
 use index_scheduler::RoFeatures;
 use indexmap::IndexMap;
@@ -16,11 +17,15 @@ use meilisearch_types::error::{Code, ResponseError};
 use meilisearch_types::heed::RoTxn;
 use meilisearch_types::index_uid::IndexUid;
 use meilisearch_types::locales::Locale;
+use meilisearch_types::milli::prompt::Prompt;
 use meilisearch_types::milli::score_details::{ScoreDetails, ScoringStrategy};
 use meilisearch_types::milli::vector::parsed_vectors::ExplicitVectors;
+use meilisearch_types::milli::vector::rerank::Reranker;
 use meilisearch_types::milli::vector::Embedder;
-use meilisearch_types::milli::{FacetValueHit, OrderBy, SearchForFacetValues, TimeBudget};
-use meilisearch_types::settings::DEFAULT_PAGINATION_MAX_TOTAL_HITS;
+use meilisearch_types::milli::{
+    FacetValueHit, OrderBy, SearchForFacetValues, SearchForSuggestions, Suggestion, TimeBudget,
+};
+use meilisearch_types::settings::{RankingRuleView, DEFAULT_PAGINATION_MAX_TOTAL_HITS};
 use meilisearch_types::{milli, Document};
 use milli::tokenizer::{Language, TokenizerBuilder};
 use milli::{
@@ -40,6 +45,9 @@ mod ranking_rules;
 
 type MatchesPosition = BTreeMap<String, Vec<MatchBounds>>;
 
+/// Searches matching at most this number of documents return a corrected query, when one exists.
+pub const DID_YOU_MEAN_MAX_HITS: u64 = 5;
+
 pub const DEFAULT_SEARCH_OFFSET: fn() -> usize = || 0;
 pub const DEFAULT_SEARCH_LIMIT: fn() -> usize = || 20;
 pub const DEFAULT_CROP_LENGTH: fn() -> usize = || 10;
@@ -47,6 +55,14 @@ pub const DEFAULT_CROP_MARKER: fn() -> String = || "…".to_string();
 pub const DEFAULT_HIGHLIGHT_PRE_TAG: fn() -> String = || "<em>".to_string();
 pub const DEFAULT_HIGHLIGHT_POST_TAG: fn() -> String = || "</em>".to_string();
 pub const DEFAULT_SEMANTIC_RATIO: fn() -> SemanticRatio = || SemanticRatio(0.5);
+pub const DEFAULT_HITS_PER_GROUP: fn() -> HitsPerGroup = || HitsPerGroup(1);
+/// The maximum number of hits per group, whatever the `maxTotalHits` of the index.
+pub const MAX_HITS_PER_GROUP: usize = 1000;
+pub const DEFAULT_RERANK_TOP_K: fn() -> RerankTopK = || RerankTopK(20);
+/// The maximum number of hits reranked by a search, whatever the `maxTotalHits` of the index.
+pub const MAX_RERANK_TOP_K: usize = 1000;
+pub const DEFAULT_RECOMMEND_NEGATIVE_WEIGHT: fn() -> f32 = || 1.0;
+pub const DEFAULT_FUSION_K: u32 = 60;
 
 #[derive(Clone, Default, PartialEq, Deserr)]
 #[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
@@ -65,6 +81,8 @@ pub struct SearchQuery {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<SearchAfter>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -85,10 +103,22 @@ pub struct SearchQuery {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: HitsPerGroup,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDidYouMean>, default)]
+    pub did_you_mean: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>)]
+    pub rerank: Option<RerankQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -141,6 +171,104 @@ impl std::convert::TryFrom<f64> for RankingScoreThresholdSimilar {
     }
 }
 
+/// The number of hits returned for each group, between 1 and [`MAX_HITS_PER_GROUP`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr)]
+#[deserr(try_from(usize) = TryFrom::try_from -> InvalidSearchHitsPerGroup)]
+pub struct HitsPerGroup(pub usize);
+
+impl std::convert::TryFrom<usize> for HitsPerGroup {
+    type Error = InvalidSearchHitsPerGroup;
+
+    fn try_from(hits_per_group: usize) -> Result<Self, Self::Error> {
+        if (1..=MAX_HITS_PER_GROUP).contains(&hits_per_group) {
+            Ok(HitsPerGroup(hits_per_group))
+        } else {
+            Err(InvalidSearchHitsPerGroup)
+        }
+    }
+}
+
+/// The opaque cursor sent back by the user to fetch the hits following a previous page.
+///
+/// An empty string starts the pagination from the first hit.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserr)]
+#[deserr(try_from(&String) = FromStr::from_str -> milli::InvalidSearchCursor)]
+pub struct SearchAfter(pub Option<milli::SearchCursor>);
+
+impl FromStr for SearchAfter {
+    type Err = milli::InvalidSearchCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            Ok(SearchAfter(None))
+        } else {
+            s.parse().map(|cursor| SearchAfter(Some(cursor)))
+        }
+    }
+}
+
+/// The buckets in which the numeric values of a facet are counted,
+/// either consecutive buckets of `interval` width or explicit `ranges`.
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields, validate = validate_facet_ranges -> DeserrJsonError<InvalidSearchFacetRanges>)]
+pub struct FacetRangesQuery {
+    #[deserr(default)]
+    pub interval: Option<f64>,
+    #[deserr(default)]
+    pub ranges: Option<Vec<FacetRangeQuery>>,
+}
+
+/// A range of facet values, `from` is included and `to` is excluded.
+#[derive(Debug, Clone, Copy, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchFacetRanges>, rename_all = camelCase, deny_unknown_fields)]
+pub struct FacetRangeQuery {
+    #[deserr(default)]
+    pub from: Option<f64>,
+    #[deserr(default)]
+    pub to: Option<f64>,
+}
+
+fn validate_facet_ranges<E: DeserializeError>(
+    query: FacetRangesQuery,
+    location: ValuePointerRef,
+) -> Result<FacetRangesQuery, E> {
+    let msg = match &query {
+        FacetRangesQuery { interval: Some(_), ranges: Some(_) } => {
+            "`interval` and `ranges` cannot be used together."
+        }
+        FacetRangesQuery { interval: None, ranges: None } => {
+            "Expected either an `interval` or a list of `ranges`."
+        }
+        FacetRangesQuery { interval: Some(interval), .. } if *interval <= 0.0 => {
+            "The `interval` must be strictly greater than 0."
+        }
+        _ => return Ok(query),
+    };
+    Err(deserr::take_cf_content(E::error::<Infallible>(
+        None,
+        ErrorKind::Unexpected { msg: msg.to_string() },
+        location,
+    )))
+}
+
+impl From<FacetRangesQuery> for milli::FacetRanges {
+    fn from(query: FacetRangesQuery) -> Self {
+        match query {
+            FacetRangesQuery { interval: Some(interval), .. } => {
+                milli::FacetRanges::Histogram { interval }
+            }
+            FacetRangesQuery { ranges, .. } => milli::FacetRanges::Ranges(
+                ranges
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|FacetRangeQuery { from, to }| milli::FacetRange { from, to })
+                    .collect(),
+            ),
+        }
+    }
+}
+
 // Since this structure is logged A LOT we're going to reduce the number of things it logs to the bare minimum.
 // - Only what IS used, we know everything else is set to None so there is no need to print it
 // - Re-order the most important field to debug first
@@ -154,6 +282,7 @@ impl fmt::Debug for SearchQuery {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -164,8 +293,14 @@ impl fmt::Debug for SearchQuery {
             show_ranking_score_details,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            group_by,
+            hits_per_group,
+            did_you_mean,
+            rerank,
             facets,
+            facet_ranges,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -185,6 +320,9 @@ impl fmt::Debug for SearchQuery {
         if let Some(hits_per_page) = hits_per_page {
             debug.field("hits_per_page", &hits_per_page);
         }
+        if let Some(search_after) = search_after {
+            debug.field("search_after", &search_after);
+        }
 
         // Then, everything related to the queries
         if let Some(q) = q {
@@ -215,12 +353,27 @@ impl fmt::Debug for SearchQuery {
         if let Some(sort) = sort {
             debug.field("sort", &sort);
         }
+        if let Some(ranking_rules) = ranking_rules {
+            debug.field("ranking_rules", &ranking_rules);
+        }
         if let Some(distinct) = distinct {
             debug.field("distinct", &distinct);
         }
+        if let Some(group_by) = group_by {
+            debug.field("group_by", &group_by).field("hits_per_group", &hits_per_group);
+        }
+        if *did_you_mean {
+            debug.field("did_you_mean", &did_you_mean);
+        }
+        if let Some(rerank) = rerank {
+            debug.field("rerank", &rerank);
+        }
         if let Some(facets) = facets {
             debug.field("facets", &facets);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
         debug.field("matching_strategy", &matching_strategy);
 
         // Then everything related to the formatting
@@ -263,19 +416,95 @@ impl fmt::Debug for SearchQuery {
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Deserr)]
-#[deserr(error = DeserrJsonError<InvalidHybridQuery>, rename_all = camelCase, deny_unknown_fields)]
+#[deserr(error = DeserrJsonError<InvalidHybridQuery>, rename_all = camelCase, deny_unknown_fields, validate = validate_hybrid_query -> DeserrJsonError<InvalidSearchFusionK>)]
 pub struct HybridQuery {
     #[deserr(default, error = DeserrJsonError<InvalidSearchSemanticRatio>, default)]
     pub semantic_ratio: SemanticRatio,
     #[deserr(default, error = DeserrJsonError<InvalidEmbedder>, default)]
     pub embedder: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFusion>)]
+    pub fusion: FusionMethod,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFusionK>)]
+    pub k: Option<u32>,
+}
+
+impl HybridQuery {
+    pub fn fusion(&self) -> milli::HybridFusion {
+        match self.fusion {
+            FusionMethod::Score => milli::HybridFusion::Score,
+            FusionMethod::Rrf => {
+                milli::HybridFusion::ReciprocalRank { k: self.k.unwrap_or(DEFAULT_FUSION_K) }
+            }
+        }
+    }
+}
+
+fn validate_hybrid_query<E: DeserializeError>(
+    query: HybridQuery,
+    location: ValuePointerRef,
+) -> Result<HybridQuery, E> {
+    if query.k.is_some() && query.fusion != FusionMethod::Rrf {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "`k` can only be used with the `rrf` fusion.".to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(query)
+}
+
+/// How the keyword and semantic results of a hybrid search are merged.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Deserr)]
+#[deserr(rename_all = camelCase)]
+pub enum FusionMethod {
+    /// Merge the results by their ranking scores, weighted by the semantic ratio
+    #[default]
+    Score,
+    /// Merge the results by reciprocal rank, weighted by the semantic ratio
+    Rrf,
+}
+
+/// Reorder the first hits of the search with one of the rerankers of the index.
+#[derive(Debug, Clone, PartialEq, Eq, Deserr)]
+#[deserr(error = DeserrJsonError<InvalidSearchRerank>, rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankQuery {
+    pub reranker: String,
+    #[deserr(default = DEFAULT_RERANK_TOP_K())]
+    pub top_k: RerankTopK,
+}
+
+/// The number of first hits to rerank, between 1 and [`MAX_RERANK_TOP_K`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserr)]
+#[deserr(try_from(usize) = TryFrom::try_from -> InvalidSearchRerank)]
+pub struct RerankTopK(pub usize);
+
+impl std::convert::TryFrom<usize> for RerankTopK {
+    type Error = InvalidSearchRerank;
+
+    fn try_from(top_k: usize) -> Result<Self, Self::Error> {
+        if (1..=MAX_RERANK_TOP_K).contains(&top_k) {
+            Ok(RerankTopK(top_k))
+        } else {
+            Err(InvalidSearchRerank)
+        }
+    }
 }
 
 #[derive(Clone)]
 pub enum SearchKind {
     KeywordOnly,
-    SemanticOnly { embedder_name: String, embedder: Arc<Embedder> },
-    Hybrid { embedder_name: String, embedder: Arc<Embedder>, semantic_ratio: f32 },
+    SemanticOnly {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+    },
+    Hybrid {
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+        semantic_ratio: f32,
+        fusion: milli::HybridFusion,
+    },
 }
 
 impl SearchKind {
@@ -295,11 +524,12 @@ impl SearchKind {
         index: &Index,
         embedder_name: Option<&str>,
         semantic_ratio: f32,
+        fusion: milli::HybridFusion,
         vector_len: Option<usize>,
     ) -> Result<Self, ResponseError> {
         let (embedder_name, embedder) =
             Self::embedder(index_scheduler, index, embedder_name, vector_len)?;
-        Ok(Self::Hybrid { embedder_name, embedder, semantic_ratio })
+        Ok(Self::Hybrid { embedder_name, embedder, semantic_ratio, fusion })
     }
 
     pub(crate) fn embedder(
@@ -336,6 +566,38 @@ impl SearchKind {
     }
 }
 
+/// The reranker of a search, resolved from the `rerank` parameter of the query.
+#[derive(Clone)]
+pub struct RerankKind {
+    reranker_name: String,
+    reranker: Arc<Reranker>,
+    prompt: Prompt,
+    top_k: usize,
+}
+
+impl RerankKind {
+    pub(crate) fn new(
+        index_scheduler: &index_scheduler::IndexScheduler,
+        index: &Index,
+        query: &RerankQuery,
+    ) -> Result<Self, ResponseError> {
+        let mut reranker_configs = index.reranker_configs(&index.read_txn()?)?;
+        let config = reranker_configs
+            .remove(&query.reranker)
+            .ok_or(milli::UserError::InvalidReranker(query.reranker.clone()))
+            .map_err(milli::Error::from)?;
+
+        let reranker = index_scheduler.reranker(config.reranker_options)?;
+        let prompt = Prompt::try_from(config.prompt)
+            .map_err(|error| {
+                milli::UserError::InvalidPromptForReranker(query.reranker.clone(), error)
+            })
+            .map_err(milli::Error::from)?;
+
+        Ok(Self { reranker_name: query.reranker.clone(), reranker, prompt, top_k: query.top_k.0 })
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Deserr)]
 #[deserr(try_from(f32) = TryFrom::try_from -> InvalidSearchSemanticRatio)]
 pub struct SemanticRatio(f32);
@@ -397,6 +659,8 @@ pub struct SearchQueryWithIndex {
     pub page: Option<usize>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHitsPerPage>)]
     pub hits_per_page: Option<usize>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchSearchAfter>)]
+    pub search_after: Option<SearchAfter>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchAttributesToRetrieve>)]
     pub attributes_to_retrieve: Option<BTreeSet<String>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchRetrieveVectors>)]
@@ -417,10 +681,22 @@ pub struct SearchQueryWithIndex {
     pub filter: Option<Value>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchSort>)]
     pub sort: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRankingRules>)]
+    pub ranking_rules: Option<Vec<RankingRuleView>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchDistinct>)]
     pub distinct: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchGroupBy>)]
+    pub group_by: Option<String>,
+    #[deserr(default = DEFAULT_HITS_PER_GROUP(), error = DeserrJsonError<InvalidSearchHitsPerGroup>)]
+    pub hits_per_group: HitsPerGroup,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchDidYouMean>, default)]
+    pub did_you_mean: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchRerank>)]
+    pub rerank: Option<RerankQuery>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchFacets>)]
     pub facets: Option<Vec<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidSearchFacetRanges>)]
+    pub facet_ranges: Option<BTreeMap<String, FacetRangesQuery>>,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPreTag>, default = DEFAULT_HIGHLIGHT_PRE_TAG())]
     pub highlight_pre_tag: String,
     #[deserr(default, error = DeserrJsonError<InvalidSearchHighlightPostTag>, default = DEFAULT_HIGHLIGHT_POST_TAG())]
@@ -453,6 +729,8 @@ impl SearchQueryWithIndex {
             Some("page")
         } else if self.hits_per_page.is_some() {
             Some("hitsPerPage")
+        } else if self.search_after.is_some() {
+            Some("searchAfter")
         } else {
             None
         }
@@ -468,6 +746,7 @@ impl SearchQueryWithIndex {
             limit,
             page,
             hits_per_page,
+            search_after,
             attributes_to_retrieve,
             retrieve_vectors,
             attributes_to_crop,
@@ -478,8 +757,14 @@ impl SearchQueryWithIndex {
             show_matches_position,
             filter,
             sort,
+            ranking_rules,
             distinct,
+            group_by,
+            hits_per_group,
+            did_you_mean,
+            rerank,
             facets,
+            facet_ranges,
             highlight_pre_tag,
             highlight_post_tag,
             crop_marker,
@@ -498,6 +783,7 @@ impl SearchQueryWithIndex {
                 limit: limit.unwrap_or(DEFAULT_SEARCH_LIMIT()),
                 page,
                 hits_per_page,
+                search_after,
                 attributes_to_retrieve,
                 retrieve_vectors,
                 attributes_to_crop,
@@ -508,8 +794,14 @@ impl SearchQueryWithIndex {
                 show_matches_position,
                 filter,
                 sort,
+                ranking_rules,
                 distinct,
+                group_by,
+                hits_per_group,
+                did_you_mean,
+                rerank,
                 facets,
+                facet_ranges,
                 highlight_pre_tag,
                 highlight_post_tag,
                 crop_marker,
@@ -527,10 +819,14 @@ impl SearchQueryWithIndex {
 }
 
 #[derive(Debug, Clone, PartialEq, Deserr)]
-#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields, validate = validate_similar_query -> DeserrJsonError<InvalidSimilarId>)]
 pub struct SimilarQuery {
-    #[deserr(error = DeserrJsonError<InvalidSimilarId>)]
-    pub id: ExternalDocumentId,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarId>)]
+    pub id: Option<ExternalDocumentId>,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarText>)]
+    pub text: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidSimilarVector>)]
+    pub vector: Option<Vec<f32>>,
     #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidSimilarOffset>)]
     pub offset: usize,
     #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidSimilarLimit>)]
@@ -551,6 +847,29 @@ pub struct SimilarQuery {
     pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
 }
 
+impl SimilarQuery {
+    /// The number of targets among `id`, `text` and `vector`, exactly one is expected.
+    pub fn target_count(&self) -> usize {
+        self.id.is_some() as usize + self.text.is_some() as usize + self.vector.is_some() as usize
+    }
+}
+
+fn validate_similar_query<E: DeserializeError>(
+    query: SimilarQuery,
+    location: ValuePointerRef,
+) -> Result<SimilarQuery, E> {
+    if query.target_count() != 1 {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg: "exactly one of `id`, `text` or `vector` must be specified.".to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(query)
+}
+
 #[derive(Debug, Clone, PartialEq, Deserr)]
 #[deserr(try_from(Value) = TryFrom::try_from -> InvalidSimilarId)]
 pub struct ExternalDocumentId(String);
@@ -583,6 +902,57 @@ impl TryFrom<Value> for ExternalDocumentId {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Deserr)]
+#[deserr(error = DeserrJsonError, rename_all = camelCase, deny_unknown_fields, validate = validate_recommend_query -> DeserrJsonError<InvalidRecommendPositive>)]
+pub struct RecommendQuery {
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendPositive>)]
+    pub positive: Vec<ExternalDocumentId>,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendPositive>)]
+    pub positive_vectors: Vec<Vec<f32>>,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendNegative>)]
+    pub negative: Vec<ExternalDocumentId>,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendNegative>)]
+    pub negative_vectors: Vec<Vec<f32>>,
+    #[deserr(default = DEFAULT_RECOMMEND_NEGATIVE_WEIGHT(), error = DeserrJsonError<InvalidRecommendNegativeWeight>)]
+    pub negative_weight: f32,
+    #[deserr(default = DEFAULT_SEARCH_OFFSET(), error = DeserrJsonError<InvalidRecommendOffset>)]
+    pub offset: usize,
+    #[deserr(default = DEFAULT_SEARCH_LIMIT(), error = DeserrJsonError<InvalidRecommendLimit>)]
+    pub limit: usize,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendFilter>)]
+    pub filter: Option<Value>,
+    #[deserr(default, error = DeserrJsonError<InvalidEmbedder>, default)]
+    pub embedder: Option<String>,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendAttributesToRetrieve>)]
+    pub attributes_to_retrieve: Option<BTreeSet<String>>,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendRetrieveVectors>)]
+    pub retrieve_vectors: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendShowRankingScore>, default)]
+    pub show_ranking_score: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendShowRankingScoreDetails>, default)]
+    pub show_ranking_score_details: bool,
+    #[deserr(default, error = DeserrJsonError<InvalidRecommendRankingScoreThreshold>, default)]
+    pub ranking_score_threshold: Option<RankingScoreThresholdSimilar>,
+}
+
+fn validate_recommend_query<E: DeserializeError>(
+    query: RecommendQuery,
+    location: ValuePointerRef,
+) -> Result<RecommendQuery, E> {
+    if query.positive.is_empty() && query.positive_vectors.is_empty() {
+        return Err(deserr::take_cf_content(E::error::<Infallible>(
+            None,
+            ErrorKind::Unexpected {
+                msg:
+                    "at least one positive example is required in `positive` or `positiveVectors`."
+                        .to_string(),
+            },
+            location,
+        )));
+    }
+    Ok(query)
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Deserr)]
 #[deserr(rename_all = camelCase)]
 pub enum MatchingStrategy {
@@ -656,10 +1026,24 @@ pub struct SearchResult {
     pub facet_distribution: Option<BTreeMap<String, IndexMap<String, u64>>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub facet_stats: Option<BTreeMap<String, FacetStats>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facet_ranges: Option<BTreeMap<String, Vec<FacetRangeCount>>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub semantic_hit_count: Option<u32>,
 
+    /// Only returned when the request used `searchAfter` and more hits may follow
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_search_after: Option<String>,
+
+    /// Only returned when few documents matched and a spelling correction of the query was found
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub corrected_query: Option<String>,
+
+    /// Only returned when the request used `groupBy`, the hits are then empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub groups: Option<Vec<SearchHitGroup>>,
+
     // These fields are only used for analytics purposes
     #[serde(skip)]
     pub degraded: bool,
@@ -676,7 +1060,11 @@ impl fmt::Debug for SearchResult {
             hits_info,
             facet_distribution,
             facet_stats,
+            facet_ranges,
             semantic_hit_count,
+            next_search_after,
+            corrected_query,
+            groups,
             degraded,
             used_negative_operator,
         } = self;
@@ -685,6 +1073,9 @@ impl fmt::Debug for SearchResult {
         // The most important thing when looking at a search result is the time it took to process
         debug.field("processing_time_ms", &processing_time_ms);
         debug.field("hits", &format!("[{} hits returned]", hits.len()));
+        if let Some(groups) = groups {
+            debug.field("groups", &format!("[{} groups returned]", groups.len()));
+        }
         debug.field("query", &query);
         debug.field("hits_info", &hits_info);
         if *used_negative_operator {
@@ -699,19 +1090,45 @@ impl fmt::Debug for SearchResult {
         if let Some(facet_stats) = facet_stats {
             debug.field("facet_stats", &facet_stats);
         }
+        if let Some(facet_ranges) = facet_ranges {
+            debug.field("facet_ranges", &facet_ranges);
+        }
         if let Some(semantic_hit_count) = semantic_hit_count {
             debug.field("semantic_hit_count", &semantic_hit_count);
         }
+        if let Some(next_search_after) = next_search_after {
+            debug.field("next_search_after", &next_search_after);
+        }
+        if let Some(corrected_query) = corrected_query {
+            debug.field("corrected_query", &corrected_query);
+        }
 
         debug.finish()
     }
 }
 
+/// The hits sharing a value of the `groupBy` attribute.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct SearchHitGroup {
+    pub group: Value,
+    pub hits: Vec<SearchHit>,
+}
+
 #[derive(Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct SimilarResult {
     pub hits: Vec<SearchHit>,
-    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    pub processing_time_ms: u128,
+    #[serde(flatten)]
+    pub hits_info: HitsInfo,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecommendResult {
+    pub hits: Vec<SearchHit>,
     pub processing_time_ms: u128,
     #[serde(flatten)]
     pub hits_info: HitsInfo,
@@ -736,8 +1153,34 @@ pub enum HitsInfo {
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
 pub struct FacetStats {
-    pub min: f64,
-    pub max: f64,
+    pub min: FacetStatValue,
+    pub max: FacetStatValue,
+}
+
+/// The bound of a facet stat, datetime attributes are returned in the RFC 3339 format.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FacetStatValue {
+    Number(f64),
+    Datetime(String),
+}
+
+impl FacetStatValue {
+    fn new(value: f64, datetime: bool) -> Self {
+        match datetime.then(|| milli::format_datetime(value)).flatten() {
+            Some(datetime) => FacetStatValue::Datetime(datetime),
+            None => FacetStatValue::Number(value),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct FacetRangeCount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<f64>,
+    pub count: u64,
 }
 
 #[derive(Serialize, Debug, Clone, PartialEq)]
@@ -748,6 +1191,14 @@ pub struct FacetSearchResult {
     pub processing_time_ms: u128,
 }
 
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SuggestResult {
+    pub suggestions: Vec<Suggestion>,
+    pub query: Option<String>,
+    pub processing_time_ms: u128,
+}
+
 /// Incorporate search rules in search query
 pub fn add_search_rules(filter: &mut Option<Value>, rules: IndexSearchRules) {
     *filter = match (filter.take(), rules.filter) {
@@ -778,6 +1229,12 @@ fn prepare_search<'t>(
 ) -> Result<(milli::Search<'t>, bool, usize, usize), ResponseError> {
     let mut search = index.search(rtxn);
     search.time_budget(time_budget);
+    let max_total_hits = index
+        .pagination_max_total_hits(rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+
     if let Some(ranking_score_threshold) = query.ranking_score_threshold {
         search.ranking_score_threshold(ranking_score_threshold.0);
     }
@@ -786,6 +1243,26 @@ fn prepare_search<'t>(
         search.distinct(distinct.clone());
     }
 
+    if let Some(group_by) = &query.group_by {
+        if query.distinct.is_some() {
+            return Err(MeilisearchHttpError::GroupByWith("distinct").into());
+        }
+        if query.search_after.is_some() {
+            return Err(MeilisearchHttpError::GroupByWith("searchAfter").into());
+        }
+        if let SearchKind::Hybrid { .. } = search_kind {
+            return Err(MeilisearchHttpError::GroupByWith("hybrid").into());
+        }
+        if query.rerank.is_some() {
+            return Err(MeilisearchHttpError::GroupByWith("rerank").into());
+        }
+        search.group_by(group_by.clone(), query.hits_per_group.0.min(max_total_hits));
+    }
+
+    if let Some(ranking_rules) = &query.ranking_rules {
+        search.ranking_rules(ranking_rules.iter().cloned().map(milli::Criterion::from).collect());
+    }
+
     match search_kind {
         SearchKind::KeywordOnly => {
             if let Some(q) = &query.q {
@@ -808,7 +1285,7 @@ fn prepare_search<'t>(
 
             search.semantic(embedder_name.clone(), embedder.clone(), Some(vector));
         }
-        SearchKind::Hybrid { embedder_name, embedder, semantic_ratio: _ } => {
+        SearchKind::Hybrid { embedder_name, embedder, semantic_ratio: _, fusion: _ } => {
             if let Some(q) = &query.q {
                 search.query(q);
             }
@@ -823,12 +1300,25 @@ fn prepare_search<'t>(
 
     let is_finite_pagination = query.is_finite_pagination();
     search.terms_matching_strategy(query.matching_strategy.into());
+    if query.did_you_mean {
+        search.did_you_mean(DID_YOU_MEAN_MAX_HITS);
+    }
 
-    let max_total_hits = index
-        .pagination_max_total_hits(rtxn)
-        .map_err(milli::Error::from)?
-        .map(|x| x as usize)
-        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+    if let Some(SearchAfter(cursor)) = &query.search_after {
+        if is_finite_pagination {
+            let field = if query.page.is_some() { "page" } else { "hitsPerPage" };
+            return Err(MeilisearchHttpError::SearchAfterWith(field).into());
+        }
+        if let SearchKind::Hybrid { .. } = search_kind {
+            return Err(MeilisearchHttpError::SearchAfterWith("hybrid").into());
+        }
+        if query.rerank.is_some() {
+            return Err(MeilisearchHttpError::SearchAfterWith("rerank").into());
+        }
+        if let Some(cursor) = cursor {
+            search.search_after(cursor.clone());
+        }
+    }
 
     search.exhaustive_number_hits(is_finite_pagination);
     search.scoring_strategy(
@@ -855,6 +1345,7 @@ fn prepare_search<'t>(
 
     // Make sure that a user can't get more documents than the hard limit,
     // we align that on the offset too.
+    // When paginating with `searchAfter` the limit applies to each page, relatively to the cursor.
     let offset = min(offset, max_total_hits);
     let limit = min(limit, max_total_hits.saturating_sub(offset));
 
@@ -889,6 +1380,7 @@ pub fn perform_search(
     index: &Index,
     query: SearchQuery,
     search_kind: SearchKind,
+    rerank_kind: Option<RerankKind>,
     retrieve_vectors: RetrieveVectors,
     features: RoFeatures,
 ) -> Result<SearchResult, ResponseError> {
@@ -899,9 +1391,13 @@ pub fn perform_search(
         None => TimeBudget::default(),
     };
 
-    let (search, is_finite_pagination, max_total_hits, offset) =
+    let (mut search, is_finite_pagination, max_total_hits, offset) =
         prepare_search(index, &rtxn, &query, &search_kind, time_budget, features)?;
 
+    if let Some(RerankKind { reranker_name, reranker, prompt, top_k }) = rerank_kind {
+        search.rerank(reranker_name, reranker, prompt, top_k.min(max_total_hits));
+    }
+
     let (
         milli::SearchResult {
             documents_ids,
@@ -910,6 +1406,9 @@ pub fn perform_search(
             document_scores,
             degraded,
             used_negative_operator,
+            cursor,
+            corrected_query,
+            groups,
         },
         semantic_hit_count,
     ) = search_from_kind(search_kind, search)?;
@@ -919,6 +1418,7 @@ pub fn perform_search(
         limit,
         page,
         hits_per_page,
+        search_after,
         attributes_to_retrieve,
         // use the enum passed as parameter
         retrieve_vectors: _,
@@ -930,6 +1430,7 @@ pub fn perform_search(
         show_ranking_score_details,
         sort,
         facets,
+        facet_ranges,
         highlight_pre_tag,
         highlight_post_tag,
         crop_marker,
@@ -943,6 +1444,11 @@ pub fn perform_search(
         attributes_to_search_on: _,
         filter: _,
         distinct: _,
+        ranking_rules: _,
+        group_by,
+        hits_per_group: _,
+        did_you_mean: _,
+        rerank: _,
     } = query;
 
     let format = AttributesFormat {
@@ -969,6 +1475,22 @@ pub fn perform_search(
         documents_ids.iter().copied().zip(document_scores.iter()),
     )?;
 
+    // when grouping, the candidates and the offset and limit count the groups
+    let (documents, groups) = match group_by {
+        Some(_) => {
+            let mut documents = documents.into_iter();
+            let groups = groups
+                .into_iter()
+                .map(|milli::SearchGroup { value, len }| SearchHitGroup {
+                    group: value,
+                    hits: documents.by_ref().take(len).collect(),
+                })
+                .collect();
+            (Vec::new(), Some(groups))
+        }
+        None => (documents, None),
+    };
+
     let number_of_hits = min(candidates.len() as usize, max_total_hits);
     let hits_info = if is_finite_pagination {
         let hits_per_page = hits_per_page.unwrap_or_else(DEFAULT_SEARCH_LIMIT);
@@ -987,6 +1509,40 @@ pub fn perform_search(
         HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits }
     };
 
+    let facet_ranges = match facet_ranges {
+        Some(facet_ranges) => {
+            let max_values_by_facet = index
+                .max_values_per_facet(&rtxn)
+                .map_err(milli::Error::from)?
+                .map(|x| x as usize)
+                .unwrap_or(DEFAULT_VALUES_PER_FACET);
+            let facet_ranges: BTreeMap<String, milli::FacetRanges> =
+                facet_ranges.into_iter().map(|(name, ranges)| (name, ranges.into())).collect();
+
+            let ranges = index
+                .facets_distribution(&rtxn)
+                .candidates(candidates.clone())
+                .max_values_per_facet(max_values_by_facet)
+                .compute_ranges(&facet_ranges)?;
+            let ranges = ranges
+                .into_iter()
+                .map(|(name, counts)| {
+                    let counts = counts
+                        .into_iter()
+                        .map(|(range, count)| FacetRangeCount {
+                            from: range.from,
+                            to: range.to,
+                            count,
+                        })
+                        .collect();
+                    (name, counts)
+                })
+                .collect();
+            Some(ranges)
+        }
+        None => None,
+    };
+
     let (facet_distribution, facet_stats) = match facets {
         Some(ref fields) => {
             let mut facet_distribution = index.facets_distribution(&rtxn);
@@ -1017,9 +1573,31 @@ pub fn perform_search(
         None => (None, None),
     };
 
-    let facet_stats = facet_stats.map(|stats| {
-        stats.into_iter().map(|(k, (min, max))| (k, FacetStats { min, max })).collect()
-    });
+    let facet_stats = match facet_stats {
+        Some(stats) => {
+            let datetime_fields = index.indexed_datetime_fields(&rtxn)?;
+            Some(
+                stats
+                    .into_iter()
+                    .map(|(k, (min, max))| {
+                        let datetime = datetime_fields.contains(&k);
+                        let stats = FacetStats {
+                            min: FacetStatValue::new(min, datetime),
+                            max: FacetStatValue::new(max, datetime),
+                        };
+                        (k, stats)
+                    })
+                    .collect(),
+            )
+        }
+        None => None,
+    };
+
+    // A page that is not full is the last one, there is no need to send a cursor for the next one.
+    let next_search_after = match (search_after, cursor) {
+        (Some(_), Some(cursor)) if documents.len() == limit => Some(cursor.to_string()),
+        _ => None,
+    };
 
     let result = SearchResult {
         hits: documents,
@@ -1028,9 +1606,13 @@ pub fn perform_search(
         processing_time_ms: before_search.elapsed().as_millis(),
         facet_distribution,
         facet_stats,
+        facet_ranges,
         degraded,
         used_negative_operator,
         semantic_hit_count,
+        next_search_after,
+        corrected_query,
+        groups,
     };
     Ok(result)
 }
@@ -1046,7 +1628,9 @@ pub fn search_from_kind(
             let semantic_hit_count = results.document_scores.len() as u32;
             (results, Some(semantic_hit_count))
         }
-        SearchKind::Hybrid { semantic_ratio, .. } => search.execute_hybrid(*semantic_ratio)?,
+        SearchKind::Hybrid { semantic_ratio, fusion, .. } => {
+            search.execute_hybrid(*semantic_ratio, *fusion)?
+        }
     };
     Ok((milli_result, semantic_hit_count))
 }
@@ -1408,6 +1992,37 @@ pub fn perform_facet_search(
     })
 }
 
+pub fn perform_suggest(
+    index: &Index,
+    search_query: SearchQuery,
+    limit: usize,
+    features: RoFeatures,
+) -> Result<SuggestResult, ResponseError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+    let time_budget = match index.search_cutoff(&rtxn)? {
+        Some(cutoff) => TimeBudget::new(Duration::from_millis(cutoff)),
+        None => TimeBudget::default(),
+    };
+
+    let (search, _, max_total_hits, _) = prepare_search(
+        index,
+        &rtxn,
+        &search_query,
+        &SearchKind::KeywordOnly,
+        time_budget,
+        features,
+    )?;
+    let mut suggest = SearchForSuggestions::new(search);
+    suggest.limit(limit.min(max_total_hits));
+
+    Ok(SuggestResult {
+        suggestions: suggest.execute()?,
+        query: search_query.q.clone(),
+        processing_time_ms: before_search.elapsed().as_millis(),
+    })
+}
+
 pub fn perform_similar(
     index: &Index,
     query: SimilarQuery,
@@ -1421,6 +2036,8 @@ pub fn perform_similar(
 
     let SimilarQuery {
         id,
+        text,
+        vector,
         offset,
         limit,
         filter: _,
@@ -1432,18 +2049,69 @@ pub fn perform_similar(
         ranking_score_threshold,
     } = query;
 
-    // using let-else rather than `?` so that the borrow checker identifies we're always returning here,
-    // preventing a use-after-move
-    let Some(internal_id) = index.external_documents_ids().get(&rtxn, &id)? else {
-        return Err(ResponseError::from_msg(
-            MeilisearchHttpError::DocumentNotFound(id.into_inner()).to_string(),
-            Code::NotFoundSimilarId,
-        ));
+    let mut similar = match (&id, text, vector) {
+        (Some(id), _, _) => {
+            // using let-else rather than `?` so that the borrow checker identifies we're always returning here,
+            // preventing a use-after-move
+            let Some(internal_id) = index.external_documents_ids().get(&rtxn, id)? else {
+                return Err(ResponseError::from_msg(
+                    MeilisearchHttpError::DocumentNotFound(id.as_ref().to_owned()).to_string(),
+                    Code::NotFoundSimilarId,
+                ));
+            };
+            milli::Similar::new(internal_id, offset, limit, index, &rtxn, embedder_name, embedder)
+        }
+        (None, Some(text), _) => {
+            // the text is embedded like the documents rather than like a search query
+            let span = tracing::trace_span!(target: "search::similar", "embed");
+            let embeddings = span
+                .in_scope(|| embedder.embed(vec![text]))
+                .map_err(milli::vector::Error::from)
+                .map_err(milli::Error::from)?;
+            let embeddings = embeddings
+                .iter()
+                .flat_map(|embeddings| embeddings.iter().map(<[f32]>::to_vec))
+                .collect();
+            milli::Similar::from_embeddings(
+                embeddings,
+                offset,
+                limit,
+                index,
+                &rtxn,
+                embedder_name,
+                embedder,
+            )
+        }
+        (None, None, Some(vector)) => {
+            if vector.len() != embedder.dimensions() {
+                return Err(ResponseError::from_msg(
+                    format!(
+                        "Invalid value at `.vector`: expected a vector of {} dimensions, got a vector of {} dimensions.",
+                        embedder.dimensions(),
+                        vector.len()
+                    ),
+                    Code::InvalidSimilarVector,
+                ));
+            }
+            milli::Similar::from_embeddings(
+                vec![vector],
+                offset,
+                limit,
+                index,
+                &rtxn,
+                embedder_name,
+                embedder,
+            )
+        }
+        (None, None, None) => {
+            return Err(ResponseError::from_msg(
+                "Invalid value: exactly one of `id`, `text` or `vector` must be specified."
+                    .to_string(),
+                Code::InvalidSimilarId,
+            ))
+        }
     };
 
-    let mut similar =
-        milli::Similar::new(internal_id, offset, limit, index, &rtxn, embedder_name, embedder);
-
     if let Some(ref filter) = query.filter {
         if let Some(facets) = parse_filter(filter, Code::InvalidSimilarFilter, features)? {
             similar.filter(facets);
@@ -1461,6 +2129,9 @@ pub fn perform_similar(
         document_scores,
         degraded: _,
         used_negative_operator: _,
+        cursor: _,
+        corrected_query: _,
+        groups: _,
     } = similar.execute().map_err(|err| match err {
         milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
             ResponseError::from_msg(err.to_string(), Code::InvalidSimilarFilter)
@@ -1504,7 +2175,148 @@ pub fn perform_similar(
     let result = SimilarResult {
         hits,
         hits_info,
-        id: id.into_inner(),
+        id: id.map(ExternalDocumentId::into_inner),
+        processing_time_ms: before_search.elapsed().as_millis(),
+    };
+    Ok(result)
+}
+
+pub fn perform_recommend(
+    index: &Index,
+    query: RecommendQuery,
+    embedder_name: String,
+    embedder: Arc<Embedder>,
+    retrieve_vectors: RetrieveVectors,
+    features: RoFeatures,
+) -> Result<RecommendResult, ResponseError> {
+    let before_search = Instant::now();
+    let rtxn = index.read_txn()?;
+
+    let RecommendQuery {
+        positive,
+        positive_vectors,
+        negative,
+        negative_vectors,
+        negative_weight,
+        offset,
+        limit,
+        filter: _,
+        embedder: _,
+        attributes_to_retrieve,
+        retrieve_vectors: _,
+        show_ranking_score,
+        show_ranking_score_details,
+        ranking_score_threshold,
+    } = query;
+
+    if !negative_weight.is_finite() || negative_weight < 0.0 {
+        return Err(ResponseError::from_msg(
+            format!(
+                "Invalid value at `.negativeWeight`: expected a positive float, got `{negative_weight}`."
+            ),
+            Code::InvalidRecommendNegativeWeight,
+        ));
+    }
+
+    for (vectors, field, code) in [
+        (&positive_vectors, "positiveVectors", Code::InvalidRecommendPositive),
+        (&negative_vectors, "negativeVectors", Code::InvalidRecommendNegative),
+    ] {
+        if let Some(vector) = vectors.iter().find(|vector| vector.len() != embedder.dimensions()) {
+            return Err(ResponseError::from_msg(
+                format!(
+                    "Invalid value at `.{field}`: expected vectors of {} dimensions, got a vector of {} dimensions.",
+                    embedder.dimensions(),
+                    vector.len()
+                ),
+                code,
+            ));
+        }
+    }
+
+    let internal_ids = |ids: Vec<ExternalDocumentId>| -> Result<Vec<_>, ResponseError> {
+        let external_documents_ids = index.external_documents_ids();
+        ids.into_iter()
+            .map(|id| match external_documents_ids.get(&rtxn, &id)? {
+                Some(internal_id) => Ok(internal_id),
+                None => Err(ResponseError::from_msg(
+                    MeilisearchHttpError::DocumentNotFound(id.into_inner()).to_string(),
+                    Code::NotFoundRecommendId,
+                )),
+            })
+            .collect()
+    };
+    let positive = internal_ids(positive)?;
+    let negative = internal_ids(negative)?;
+
+    let mut recommend = milli::Recommend::new(offset, limit, index, &rtxn, embedder_name, embedder);
+    recommend.positive(positive, positive_vectors);
+    recommend.negative(negative, negative_vectors);
+    recommend.negative_weight(negative_weight);
+
+    if let Some(ref filter) = query.filter {
+        if let Some(facets) = parse_filter(filter, Code::InvalidRecommendFilter, features)? {
+            recommend.filter(facets);
+        }
+    }
+
+    if let Some(ranking_score_threshold) = ranking_score_threshold {
+        recommend.ranking_score_threshold(ranking_score_threshold.0);
+    }
+
+    let milli::SearchResult {
+        documents_ids,
+        matching_words: _,
+        candidates,
+        document_scores,
+        degraded: _,
+        used_negative_operator: _,
+        cursor: _,
+        corrected_query: _,
+        groups: _,
+    } = recommend.execute().map_err(|err| match err {
+        milli::Error::UserError(milli::UserError::InvalidFilter(_)) => {
+            ResponseError::from_msg(err.to_string(), Code::InvalidRecommendFilter)
+        }
+        err => err.into(),
+    })?;
+
+    let format = AttributesFormat {
+        attributes_to_retrieve,
+        retrieve_vectors,
+        attributes_to_highlight: None,
+        attributes_to_crop: None,
+        crop_length: DEFAULT_CROP_LENGTH(),
+        crop_marker: DEFAULT_CROP_MARKER(),
+        highlight_pre_tag: DEFAULT_HIGHLIGHT_PRE_TAG(),
+        highlight_post_tag: DEFAULT_HIGHLIGHT_POST_TAG(),
+        show_matches_position: false,
+        sort: None,
+        show_ranking_score,
+        show_ranking_score_details,
+        locales: None,
+    };
+
+    let hits = make_hits(
+        index,
+        &rtxn,
+        format,
+        Default::default(),
+        documents_ids.iter().copied().zip(document_scores.iter()),
+    )?;
+
+    let max_total_hits = index
+        .pagination_max_total_hits(&rtxn)
+        .map_err(milli::Error::from)?
+        .map(|x| x as usize)
+        .unwrap_or(DEFAULT_PAGINATION_MAX_TOTAL_HITS);
+
+    let number_of_hits = min(candidates.len() as usize, max_total_hits);
+    let hits_info = HitsInfo::OffsetLimit { limit, offset, estimated_total_hits: number_of_hits };
+
+    let result = RecommendResult {
+        hits,
+        hits_info,
         processing_time_ms: before_search.elapsed().as_millis(),
     };
     Ok(result)
@@ -1518,11 +2330,20 @@ fn insert_geo_distance(sorts: &[String], document: &mut Document) {
     if let Some(capture_group) = sorts.iter().find_map(|sort| GEO_REGEX.captures(sort)) {
         // TODO: TAMO: milli encountered an internal error, what do we want to do?
         let base = [capture_group[1].parse().unwrap(), capture_group[2].parse().unwrap()];
-        let geo_point = &document.get("_geo").unwrap_or(&json!(null));
-        if let Some((lat, lng)) =
-            extract_geo_value(&geo_point["lat"]).zip(extract_geo_value(&geo_point["lng"]))
-        {
-            let distance = milli::distance_between_two_points(&base, &[lat, lng]);
+        let geo_points = match document.get("_geo") {
+            Some(Value::Array(points)) => points.iter().collect(),
+            Some(point) => vec![point],
+            None => Vec::new(),
+        };
+        // a document containing several points is as far as its nearest point
+        let distance = geo_points
+            .into_iter()
+            .filter_map(|point| {
+                extract_geo_value(&point["lat"]).zip(extract_geo_value(&point["lng"]))
+            })
+            .map(|(lat, lng)| milli::distance_between_two_points(&base, &[lat, lng]))
+            .min_by(f64::total_cmp);
+        if let Some(distance) = distance {
             document.insert("_geoDistance".to_string(), json!(distance.round() as usize));
         }
     }
@@ -1998,4 +2819,35 @@ mod test {
         insert_geo_distance(sorters, &mut document);
         assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
     }
+
+    #[test]
+    fn test_insert_geo_distance_with_multiple_points() {
+        let value: Document = serde_json::from_str(
+            r#"{
+              "_geo": [
+                { "lat": 48.8566, "lng": 2.3522 },
+                { "lat": 50.629973371633746, "lng": 3.0569447399419567 },
+                { "lat": "45.764", "lng": "4.8357" }
+              ],
+              "id": "1"
+            }"#,
+        )
+        .unwrap();
+
+        // the distance to the nearest point is used
+        let sorters = &["_geoPoint(50.629973371633746,3.0569447399419567):asc".to_string()];
+        let mut document = value.clone();
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+        let sorters = &["_geoPoint(45.764,4.8357):desc".to_string()];
+        let mut document = value;
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), Some(&json!(0)));
+
+        // an empty array doesn't have any distance
+        let mut document: Document = serde_json::from_str(r#"{ "_geo": [] }"#).unwrap();
+        insert_geo_distance(sorters, &mut document);
+        assert_eq!(document.get("_geoDistance"), None);
+    }
 }
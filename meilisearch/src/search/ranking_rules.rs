@@ -247,6 +247,9 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Decay(_) | Criterion::Boost(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
             }
         }
 
@@ -327,6 +330,9 @@ impl RankingRules {
                         canonical_criteria.push(criterion.clone())
                     }
                 },
+                Criterion::Decay(_) | Criterion::Boost(_) => {
+                    canonical_criteria.push(criterion.clone())
+                }
             }
         }
 
@@ -461,6 +467,7 @@ impl RankingRules {
                         canonical_criteria.push(criterion)
                     }
                 },
+                Criterion::Decay(_) | Criterion::Boost(_) => canonical_criteria.push(criterion),
             }
         }
 
@@ -606,6 +613,9 @@ fn canonicalize_sort(
                     canonical_sort.push(asc_desc);
                 }
             },
+            AscDesc::Asc(Member::Random(_)) | AscDesc::Desc(Member::Random(_)) => {
+                canonical_sort.push(asc_desc)
+            }
         }
     }
     canonical_sort
@@ -690,6 +700,8 @@ impl RankingRuleSource {
                         }
                         AscDesc::Asc(Member::Geo(_)) => "_geo(..):asc".to_string(),
                         AscDesc::Desc(Member::Geo(_)) => "_geo(..):desc".to_string(),
+                        AscDesc::Asc(Member::Random(seed)) => format!("_random({seed}):asc"),
+                        AscDesc::Desc(Member::Random(seed)) => format!("_random({seed}):desc"),
                     },
                     None => "unknown".into(),
                 }
@@ -723,7 +735,9 @@ impl RankingRule {
             | Criterion::Typo
             | Criterion::Proximity
             | Criterion::Attribute
-            | Criterion::Exactness => RankingRuleKind::Relevancy,
+            | Criterion::Exactness
+            | Criterion::Decay(_)
+            | Criterion::Boost(_) => RankingRuleKind::Relevancy,
             Criterion::Asc(s) if s == "_geo" => RankingRuleKind::AscendingGeoSort,
 
             Criterion::Asc(_) => RankingRuleKind::AscendingSort,
@@ -747,8 +761,12 @@ impl RankingRule {
 
     fn from_asc_desc(asc_desc: &AscDesc, sort_index: usize, rule_index_in_sort: usize) -> Self {
         let kind = match asc_desc {
-            AscDesc::Asc(Member::Field(_)) => RankingRuleKind::AscendingSort,
-            AscDesc::Desc(Member::Field(_)) => RankingRuleKind::DescendingSort,
+            AscDesc::Asc(Member::Field(_)) | AscDesc::Asc(Member::Random(_)) => {
+                RankingRuleKind::AscendingSort
+            }
+            AscDesc::Desc(Member::Field(_)) | AscDesc::Desc(Member::Random(_)) => {
+                RankingRuleKind::DescendingSort
+            }
             AscDesc::Asc(Member::Geo(_)) => RankingRuleKind::AscendingGeoSort,
             AscDesc::Desc(Member::Geo(_)) => RankingRuleKind::DescendingGeoSort,
         };
@@ -467,11 +467,21 @@ impl<State> Index<'_, State> {
         self.service.get(url).await
     }
 
+    pub async fn recommend_post(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/recommend", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn facet_search(&self, query: Value) -> (Value, StatusCode) {
         let url = format!("/indexes/{}/facet-search", urlencode(self.uid.as_ref()));
         self.service.post_encoded(url, query, self.encoder).await
     }
 
+    pub async fn suggest(&self, query: Value) -> (Value, StatusCode) {
+        let url = format!("/indexes/{}/suggest", urlencode(self.uid.as_ref()));
+        self.service.post_encoded(url, query, self.encoder).await
+    }
+
     pub async fn get_distinct_attribute(&self) -> (Value, StatusCode) {
         let url =
             format!("/indexes/{}/settings/{}", urlencode(self.uid.as_ref()), "distinct-attribute");
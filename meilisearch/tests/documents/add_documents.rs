@@ -1,4 +1,11 @@
+use std::sync::Arc;
+
 use actix_web::test;
+use arrow::array::{
+    ArrayRef, ListArray, StringArray, StructArray, TimestampSecondArray, UInt32Array,
+};
+use arrow::datatypes::{DataType, Field, Int32Type};
+use arrow::record_batch::RecordBatch;
 use meili_snap::{json_string, snapshot};
 use meilisearch::Opt;
 use time::format_description::well_known::Rfc3339;
@@ -332,6 +339,88 @@ async fn add_csv_document() {
     "###);
 }
 
+#[actix_rt::test]
+async fn add_parquet_documents() {
+    let server = Server::new().await;
+    let index = server.index("pets");
+
+    let owner = StructArray::from(vec![(
+        Arc::new(Field::new("name", DataType::Utf8, false)),
+        Arc::new(StringArray::from(vec!["tamo", "loic"])) as ArrayRef,
+    )]);
+    let batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(UInt32Array::from(vec![0, 1])) as ArrayRef),
+        ("name", Arc::new(StringArray::from(vec!["jean", "jorts"]))),
+        ("owner", Arc::new(owner)),
+        (
+            "toys",
+            Arc::new(ListArray::from_iter_primitive::<Int32Type, _, _>(vec![
+                Some(vec![Some(1), Some(2)]),
+                None,
+            ])),
+        ),
+        ("birth", Arc::new(TimestampSecondArray::from(vec![0, 1_700_000_000]))),
+    ])
+    .unwrap();
+    let mut payload = Vec::new();
+    let mut writer =
+        parquet::arrow::ArrowWriter::try_new(&mut payload, batch.schema(), None).unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    let req = test::TestRequest::post()
+        .uri("/indexes/pets/documents")
+        .set_payload(payload)
+        .insert_header(("content-type", "application/vnd.apache.parquet"));
+    let (response, code) = server.service.request(req).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response["taskUid"].as_u64().unwrap()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"], @r###"{"receivedDocuments":2,"indexedDocuments":2}"###);
+
+    let (documents, code) = index.get_all_documents(GetAllDocumentsOptions::default()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(documents), @r###"
+    {
+      "results": [
+        {
+          "id": 0,
+          "name": "jean",
+          "owner": {
+            "name": "tamo"
+          },
+          "toys": [
+            1,
+            2
+          ],
+          "birth": "1970-01-01T00:00:00Z"
+        },
+        {
+          "id": 1,
+          "name": "jorts",
+          "owner": {
+            "name": "loic"
+          },
+          "toys": null,
+          "birth": "2023-11-14T22:13:20Z"
+        }
+      ],
+      "offset": 0,
+      "limit": 20,
+      "total": 2
+    }
+    "###);
+
+    // a payload that isn't a parquet file is rejected before creating a task
+    let req = test::TestRequest::post()
+        .uri("/indexes/pets/documents")
+        .set_payload("id,name\n0,jean")
+        .insert_header(("content-type", "application/vnd.apache.parquet"));
+    let (response, code) = server.service.request(req).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""malformed_payload""###);
+}
+
 #[actix_rt::test]
 async fn add_csv_document_with_types() {
     let server = Server::new().await;
@@ -532,7 +621,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.file`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -553,7 +642,7 @@ async fn error_add_documents_test_bad_content_types() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "The Content-Type `text/plain` is invalid. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.file`, `application/vnd.apache.arrow.stream`",
       "code": "invalid_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#invalid_content_type"
@@ -587,7 +676,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.file`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -607,7 +696,7 @@ async fn error_add_documents_test_no_content_type() {
     snapshot!(json_string!(response),
         @r###"
     {
-      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`",
+      "message": "A Content-Type header is missing. Accepted values for the Content-Type header are: `application/json`, `application/x-ndjson`, `text/csv`, `application/vnd.apache.parquet`, `application/vnd.apache.arrow.file`, `application/vnd.apache.arrow.stream`",
       "code": "missing_content_type",
       "type": "invalid_request",
       "link": "https://docs.meilisearch.com/errors#missing_content_type"
@@ -2188,6 +2277,78 @@ async fn add_documents_invalid_geo_field() {
       "finishedAt": "[date]"
     }
     "###);
+
+    // _geo is an array containing something else than a point
+    let documents = json!([
+        {
+            "id": "14",
+            "_geo": [{ "lat": 12, "lng": 13 }, null]
+        }
+    ]);
+
+    let (response, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response, { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }),
+        @r###"
+    {
+      "uid": 17,
+      "indexUid": "test",
+      "status": "failed",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 1,
+        "indexedDocuments": 0
+      },
+      "error": {
+        "message": "The `_geo` field in the document with the id: `14` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `null`.",
+        "code": "invalid_document_geo_field",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+      },
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
+
+    // one of the points of the array is missing its longitude
+    let documents = json!([
+        {
+            "id": "15",
+            "_geo": [{ "lat": 12, "lng": 13 }, { "lat": 12 }]
+        }
+    ]);
+
+    let (response, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(response.uid()).await;
+    snapshot!(json_string!(response, { ".duration" => "[duration]", ".enqueuedAt" => "[date]", ".startedAt" => "[date]", ".finishedAt" => "[date]" }),
+        @r###"
+    {
+      "uid": 18,
+      "indexUid": "test",
+      "status": "failed",
+      "type": "documentAdditionOrUpdate",
+      "canceledBy": null,
+      "details": {
+        "receivedDocuments": 1,
+        "indexedDocuments": 0
+      },
+      "error": {
+        "message": "Could not find longitude in the document with the id: `15`. Was expecting a `_geo.lng` field.",
+        "code": "invalid_document_geo_field",
+        "type": "invalid_request",
+        "link": "https://docs.meilisearch.com/errors#invalid_document_geo_field"
+      },
+      "duration": "[duration]",
+      "enqueuedAt": "[date]",
+      "startedAt": "[date]",
+      "finishedAt": "[date]"
+    }
+    "###);
 }
 
 // Related to #4333
@@ -2521,3 +2682,97 @@ async fn dry_register_file() {
     "###);
     snapshot!(code, @"404 Not Found");
 }
+
+#[actix_rt::test]
+async fn add_documents_expected_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .add_documents(json!([{ "id": 1, "doggo": "kefir" }, { "id": 2, "doggo": "bob" }]), None)
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, Some(json!({ "retrieveVersion": true }))).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "doggo": "kefir",
+      "_version": 1
+    }
+    "###);
+
+    // the documents that were updated since their expected version are skipped
+    let (task, _code) = index
+        .add_documents(
+            json!([
+                { "id": 1, "doggo": "kefirounet", "_expectedVersion": 1 },
+                { "id": 2, "doggo": "bobby", "_expectedVersion": 2 },
+                { "id": 3, "doggo": "jean", "_expectedVersion": 1 },
+            ]),
+            None,
+        )
+        .await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(json_string!(response["details"]), @r###"
+    {
+      "receivedDocuments": 3,
+      "indexedDocuments": 1,
+      "versionConflicts": [
+        "2",
+        "3"
+      ]
+    }
+    "###);
+
+    let (response, _code) = index.get_document(1, Some(json!({ "retrieveVersion": true }))).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "doggo": "kefirounet",
+      "_version": 2
+    }
+    "###);
+    let (response, _code) = index.get_document(2, Some(json!({ "retrieveVersion": true }))).await;
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 2,
+      "doggo": "bob",
+      "_version": 1
+    }
+    "###);
+    let (_response, code) = index.get_document(3, None).await;
+    snapshot!(code, @"404 Not Found");
+}
+
+#[actix_rt::test]
+async fn error_add_documents_expected_version() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, _code) = index
+        .add_documents(json!([{ "id": 1, "doggo": "kefir", "_expectedVersion": -1 }]), None)
+        .await;
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "The `_expectedVersion` field in the document with id: `1` is invalid. Was expecting a positive integer, but instead got `-1`.",
+      "code": "invalid_document_expected_version",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_expected_version"
+    }
+    "###);
+
+    let (response, code) = index.get_document(1, Some(json!({ "retrieveVersion": "doggo" }))).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `retrieveVersion`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_document_retrieve_version",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_retrieve_version"
+    }
+    "###);
+}
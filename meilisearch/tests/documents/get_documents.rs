@@ -364,6 +364,34 @@ async fn get_documents_displayed_attributes_is_ignored() {
     assert!(response.as_object().unwrap().get("gender").is_some());
 }
 
+#[actix_rt::test]
+async fn get_documents_search_after() {
+    let server = Server::new().await;
+    let index = server.index("doggo");
+    let documents: Vec<_> = (0..7).map(|id| json!({ "id": id })).collect();
+    let (task, _code) = index.add_documents(json!(documents), Some("id")).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let mut ids = Vec::new();
+    let mut search_after = String::new();
+    loop {
+        let (response, code) =
+            index.get_document_by_filter(json!({ "limit": 3, "searchAfter": search_after })).await;
+        assert_eq!(code, 200, "{}", response);
+        assert_eq!(response["total"], 7);
+        ids.extend(response["results"].as_array().unwrap().iter().map(|d| d["id"].clone()));
+        match response.get("nextSearchAfter") {
+            Some(next) => search_after = next.as_str().unwrap().to_string(),
+            None => break,
+        }
+    }
+    assert_eq!(ids, (0..7).map(|id| json!(id)).collect::<Vec<_>>());
+
+    let (response, code) = index.get_document_by_filter(json!({ "searchAfter": "zz" })).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_document_search_after");
+}
+
 #[actix_rt::test]
 async fn get_document_by_filter() {
     let server = Server::new().await;
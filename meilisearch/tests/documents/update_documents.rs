@@ -299,3 +299,262 @@ async fn update_faceted_document() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn update_documents_deep_merge() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) = index
+        .add_documents(
+            json!([
+                { "id": 1, "title": "kevin", "price": { "eur": 10, "usd": 12 }, "tags": { "a": 1 } },
+            ]),
+            None,
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await;
+
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[{ "id": 1, "price": { "eur": 11, "chf": null }, "tags": null, "new": { "a": null, "b": 2 } }]"#,
+            Some("application/json"),
+            "?mergeStrategy=deep",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "title": "kevin",
+      "price": {
+        "eur": 11,
+        "usd": 12
+      },
+      "new": {
+        "b": 2
+      }
+    }
+    "###);
+
+    // the default strategy only merges the top-level attributes
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[{ "id": 1, "price": { "eur": 12 } }]"#,
+            Some("application/json"),
+            "?mergeStrategy=shallow",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await;
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "title": "kevin",
+      "price": {
+        "eur": 12
+      },
+      "new": {
+        "b": 2
+      }
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_update_documents_merge_strategy() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .raw_update_documents(r#"[{ "id": 1 }]"#, Some("application/json"), "?mergeStrategy=doggo")
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Unknown value `doggo` for parameter `mergeStrategy`: expected one of `shallow`, `deep`",
+      "code": "invalid_document_merge_strategy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_merge_strategy"
+    }
+    "###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json")],
+            "?mergeStrategy=deep",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `mergeStrategy` parameter can only be used when updating documents with the `PUT` method.",
+      "code": "invalid_document_merge_strategy",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_merge_strategy"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn update_documents_field_operators() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) = index
+        .add_documents(
+            json!([{ "id": 1, "title": "kevin", "views": 10, "tags": ["a", "b"] }]),
+            None,
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await;
+
+    // two concurrent updates of the same document are both applied
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[{ "id": 1, "views": { "$inc": 1 }, "tags": { "$pull": "a" } }]"#,
+            Some("application/json"),
+            "?operators=true",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[{
+                "id": 1,
+                "views": { "$inc": 2 },
+                "tags": { "$push": ["c", "d"] },
+                "title": { "$set": "lol" },
+                "rating": { "$set": 5 }
+            }]"#,
+            Some("application/json"),
+            "?operators=true",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "title": "kevin",
+      "views": 13,
+      "tags": [
+        "b",
+        "c",
+        "d"
+      ],
+      "rating": 5
+    }
+    "###);
+
+    let (task, code) = index
+        .raw_update_documents(
+            r#"[{ "id": 1, "title": { "$inc": 1 } }]"#,
+            Some("application/json"),
+            "?operators=true",
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Invalid operator in the document with id: `1`. `$inc` can only increment a number, but the field is `\"kevin\"`.",
+      "code": "invalid_document_field_operator",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_field_operator"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn update_documents_field_operators_are_opt_in() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (task, code) =
+        index.add_documents(json!([{ "id": 1, "title": "kevin", "views": 10 }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await;
+
+    // without the `operators` parameter the operator objects are regular values
+    let (task, code) =
+        index.update_documents(json!([{ "id": 1, "views": { "$inc": 1 } }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.get_document(1, None).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response), @r###"
+    {
+      "id": 1,
+      "title": "kevin",
+      "views": {
+        "$inc": 1
+      }
+    }
+    "###);
+
+    // the replaced documents have nothing to update, their operator objects are rejected
+    let (task, code) =
+        index.add_documents(json!([{ "id": 1, "views": { "$inc": 1 } }]), None).await;
+    snapshot!(code, @"202 Accepted");
+    let response = index.wait_task(task.uid()).await;
+    snapshot!(json_string!(response["error"]), @r###"
+    {
+      "message": "Invalid operator in the document with id: `1`. Field operators can only be used when updating documents.",
+      "code": "invalid_document_field_operator",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_field_operator"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn error_update_documents_operators() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let (response, code) = index
+        .raw_update_documents(r#"[{ "id": 1 }]"#, Some("application/json"), "?operators=doggo")
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `operators`: could not parse `doggo` as a boolean, expected either `true` or `false`",
+      "code": "invalid_document_operators",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_operators"
+    }
+    "###);
+
+    let (response, code) = index
+        .raw_add_documents(
+            r#"[{ "id": 1 }]"#,
+            vec![("Content-Type", "application/json")],
+            "?operators=true",
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "The `operators` parameter can only be used when updating documents with the `PUT` method.",
+      "code": "invalid_document_operators",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_document_operators"
+    }
+    "###);
+}
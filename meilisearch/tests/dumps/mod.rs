@@ -46,6 +46,8 @@ async fn import_dump_v1_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -206,6 +208,8 @@ async fn import_dump_v1_movie_with_settings() {
       "sortableAttributes": [
         "genres"
       ],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -356,6 +360,8 @@ async fn import_dump_v1_rubygems_with_settings() {
       "sortableAttributes": [
         "version"
       ],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -493,6 +499,8 @@ async fn import_dump_v2_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -637,6 +645,8 @@ async fn import_dump_v2_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -784,6 +794,8 @@ async fn import_dump_v2_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -921,6 +933,8 @@ async fn import_dump_v3_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -1065,6 +1079,8 @@ async fn import_dump_v3_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -1212,6 +1228,8 @@ async fn import_dump_v3_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -1349,6 +1367,8 @@ async fn import_dump_v4_movie_raw() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -1493,6 +1513,8 @@ async fn import_dump_v4_movie_with_settings() {
         "genres"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -1640,6 +1662,8 @@ async fn import_dump_v4_rubygems_with_settings() {
         "version"
       ],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "typo",
         "words",
@@ -1891,6 +1915,8 @@ async fn import_dump_v6_containing_experimental_features() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -2059,6 +2085,8 @@ async fn generate_and_import_dump_containing_vectors() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
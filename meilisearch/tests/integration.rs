@@ -6,6 +6,7 @@ mod dumps;
 mod features;
 mod index;
 mod logs;
+mod recommend;
 mod search;
 mod settings;
 mod similar;
@@ -0,0 +1,189 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        {
+            "title": "Shazam!",
+            "id": "287947",
+            // Three semantic properties:
+            // 1. magic, anything that reminds you of magic
+            // 2. authority, anything that inspires command
+            // 3. horror, anything that inspires fear or dread
+            "_vectors": { "manual": [0.8, 0.4, -0.5]},
+        },
+        {
+            "title": "Captain Marvel",
+            "id": "299537",
+            "_vectors": { "manual": [0.6, 0.8, -0.2] },
+        },
+        {
+            "title": "Escape Room",
+            "id": "522681",
+            "_vectors": { "manual": [0.1, 0.6, 0.8] },
+        },
+        {
+            "title": "How to Train Your Dragon: The Hidden World",
+            "id": "166428",
+            "_vectors": { "manual": [0.7, 0.7, -0.4] },
+        },
+        {
+            "title": "All Quiet on the Western Front",
+            "id": "143",
+            "_vectors": { "manual": [-0.5, 0.3, 0.85] },
+        }
+    ])
+});
+
+async fn index_with_documents(server: &Server) -> Index<'_> {
+    let index = server.index("test");
+    let (_, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        },
+        "filterableAttributes": ["title"]}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await;
+
+    let (response, code) = index.add_documents(DOCUMENTS.clone(), None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    index
+}
+
+fn ids(response: &Value) -> String {
+    json_string!(response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["id"].clone())
+        .collect::<Vec<_>>())
+}
+
+#[actix_rt::test]
+async fn positive_and_negative_examples() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    // the examples are never recommended
+    let (response, code) =
+        index.recommend_post(json!({"positive": ["287947"], "negative": ["143"]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(ids(&response), @r###"
+    [
+      "166428",
+      "299537",
+      "522681"
+    ]
+    "###);
+    snapshot!(response["estimatedTotalHits"], @"3");
+
+    // a raw vector is an example too
+    let (response, code) = index
+        .recommend_post(json!({"positive": ["287947"], "negativeVectors": [[0.6, 0.8, -0.2]]}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(ids(&response), @r###"
+    [
+      "166428",
+      "299537",
+      "522681",
+      "143"
+    ]
+    "###);
+
+    let (response, code) = index
+        .recommend_post(json!({
+            "positive": ["287947"],
+            "negative": ["143"],
+            "filter": "title != 'How to Train Your Dragon: The Hidden World'",
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(ids(&response), @r###"
+    [
+      "299537",
+      "522681"
+    ]
+    "###);
+
+    let (response, code) = index
+        .recommend_post(json!({
+            "positive": ["287947"],
+            "negative": ["143"],
+            "rankingScoreThreshold": 0.5,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(ids(&response), @r###"
+    [
+      "166428",
+      "299537"
+    ]
+    "###);
+}
+
+#[actix_rt::test]
+async fn errors() {
+    let server = Server::new().await;
+    let index = index_with_documents(&server).await;
+
+    let (response, code) = index.recommend_post(json!({"negative": ["143"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value: at least one positive example is required in `positive` or `positiveVectors`.",
+      "code": "invalid_recommend_positive",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recommend_positive"
+    }
+    "###);
+
+    let (response, code) = index.recommend_post(json!({"positive": ["unknown"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Document `unknown` not found.",
+      "code": "not_found_recommend_id",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#not_found_recommend_id"
+    }
+    "###);
+
+    let (response, code) =
+        index.recommend_post(json!({"positive": ["143"], "negativeVectors": [[0.1, 0.2]]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.negativeVectors`: expected vectors of 3 dimensions, got a vector of 2 dimensions.",
+      "code": "invalid_recommend_negative",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recommend_negative"
+    }
+    "###);
+
+    let (response, code) =
+        index.recommend_post(json!({"positive": ["143"], "negativeWeight": -1.0})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.negativeWeight`: expected a positive float, got `-1`.",
+      "code": "invalid_recommend_negative_weight",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_recommend_negative_weight"
+    }
+    "###);
+}
@@ -304,3 +304,55 @@ async fn distinct_at_search_time() {
     snapshot!(response["totalPages"], @"1");
     snapshot!(response["totalHits"], @"3");
 }
+
+#[actix_rt::test]
+async fn group_by_at_search_time() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = DOCUMENTS.clone();
+    let (task, _) = index.add_documents(documents, Some(DOCUMENT_PRIMARY_KEY)).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _) =
+        index.update_settings_filterable_attributes(json!([DOCUMENT_DISTINCT_KEY])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    fn get_groups(response: &Value) -> Vec<(String, Vec<String>)> {
+        let groups_array = response["groups"]
+            .as_array()
+            .unwrap_or_else(|| panic!("{}", &serde_json::to_string_pretty(&response).unwrap()));
+        groups_array
+            .iter()
+            .map(|g| {
+                let hits = g["hits"].as_array().unwrap();
+                let ids = hits.iter().map(|h| h[DOCUMENT_PRIMARY_KEY].to_string()).collect();
+                (g["group"].as_str().unwrap().to_string(), ids)
+            })
+            .collect()
+    }
+
+    let (response, code) = index
+        .search_post(json!({"groupBy": DOCUMENT_DISTINCT_KEY, "hitsPerGroup": 2, "limit": 3}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @r###"[("123456", ["1", "2"]), ("789012", ["4", "5"]), ("456789", ["6", "7"])]"###);
+    snapshot!(response["hits"], @"[]");
+    snapshot!(response["estimatedTotalHits"], @"10");
+
+    let (response, code) = index
+        .search_post(
+            json!({"groupBy": DOCUMENT_DISTINCT_KEY, "hitsPerGroup": 2, "offset": 3, "limit": 2}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @r###"[("987654", ["8"]), ("234567", ["9", "10"])]"###);
+
+    // the groups are counted rather than the documents
+    let (response, code) = index
+        .search_post(json!({"groupBy": DOCUMENT_DISTINCT_KEY, "hitsPerGroup": 3, "page": 1, "hitsPerPage": 2}))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(format!("{:?}", get_groups(&response)), @r###"[("123456", ["1", "2", "3"]), ("789012", ["4", "5"])]"###);
+    snapshot!(response["totalHits"], @"6");
+    snapshot!(response["totalPages"], @"3");
+}
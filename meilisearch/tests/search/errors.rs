@@ -338,6 +338,34 @@ async fn search_bad_sort() {
     // Can't make the `sort` fail with a get search since it'll accept anything as a strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_ranking_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) = index.search_post(json!({"rankingRules": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.rankingRules`: expected an array, but found a string: `\"doggo\"`",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"rankingRules": ["words", "doggo"]})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[1]`: `doggo` ranking rule is invalid. Valid ranking rules are words, typo, sort, proximity, attribute, exactness and custom ranking rules.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_show_matches_position() {
     let server = Server::new_shared();
@@ -384,6 +412,90 @@ async fn search_bad_facets() {
     // Can't make the `attributes_to_highlight` fail with a get search since it'll accept anything as an array of strings.
 }
 
+#[actix_rt::test]
+async fn search_bad_facet_ranges() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"price": {"interval": 0}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetRanges.price`: The `interval` must be strictly greater than 0.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"facetRanges": {"price": {"interval": 10, "ranges": [{"to": 10}]}}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.facetRanges.price`: `interval` and `ranges` cannot be used together.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"price": {"ranges": [{"from": "a"}]}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value type at `.facetRanges.price.ranges[0].from`: expected a number, but found a string: `\"a\"`",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    // in a GET search the facet ranges must be a JSON object
+    let (response, code) = index.search_get("?facetRanges=price").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value in parameter `facetRanges`: expected a JSON object: expected value at line 1 column 1",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+    let (response, code) = index
+        .search_get(
+            &yaup::to_string(&json!({ "facetRanges": r#"{"price":{"interval":0}}"# })).unwrap(),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.price`: The `interval` must be strictly greater than 0.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+
+    // the facet must be filterable
+    let (task, _code) = index.create(None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (response, code) =
+        index.search_post(json!({"facetRanges": {"price": {"interval": 10}}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid facet ranges, this index does not have configured filterable attributes.",
+      "code": "invalid_search_facet_ranges",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_facet_ranges"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn search_bad_threshold() {
     let server = Server::new_shared();
@@ -646,7 +758,7 @@ async fn filter_invalid_syntax_object() {
         .search(json!({"filter": "title & Glass"}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -669,7 +781,7 @@ async fn filter_invalid_syntax_array() {
         .search(json!({"filter": ["title & Glass"]}), |response, code| {
             snapshot!(response, @r###"
             {
-              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `_geoRadius`, or `_geoBoundingBox` at `title & Glass`.\n1:14 title & Glass",
+              "message": "Was expecting an operation `=`, `!=`, `>=`, `>`, `<=`, `<`, `IN`, `NOT IN`, `TO`, `EXISTS`, `NOT EXISTS`, `IS NULL`, `IS NOT NULL`, `IS EMPTY`, `IS NOT EMPTY`, `CONTAINS`, `NOT CONTAINS`, `STARTS WITH`, `NOT STARTS WITH`, `ENDS WITH`, `NOT ENDS WITH`, `_geoRadius`, `_geoBoundingBox`, or `_geoPolygon` at `title & Glass`.\n1:14 title & Glass",
               "code": "invalid_search_filter",
               "type": "invalid_request",
               "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -755,7 +867,7 @@ async fn filter_reserved_geo_attribute_array() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -777,7 +889,7 @@ async fn filter_reserved_geo_attribute_string() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
+        "message": "`_geo` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:13 _geo = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -799,7 +911,7 @@ async fn filter_reserved_attribute_array() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+        "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -821,7 +933,7 @@ async fn filter_reserved_attribute_string() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
+       "message": "`_geoDistance` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:21 _geoDistance = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -843,7 +955,7 @@ async fn filter_reserved_geo_point_array() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+        "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -865,7 +977,7 @@ async fn filter_reserved_geo_point_string() {
     index.wait_task(task.uid()).await;
 
     let expected_response = json!({
-       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)` or `_geoBoundingBox([latitude, longitude], [latitude, longitude])` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
+       "message": "`_geoPoint` is a reserved keyword and thus can't be used as a filter expression. Use the `_geoRadius(latitude, longitude, distance)`, `_geoBoundingBox([latitude, longitude], [latitude, longitude])` or `_geoPolygon([latitude, longitude], [latitude, longitude], [latitude, longitude], ...)` built-in rules to filter on `_geo` coordinates.\n1:18 _geoPoint = Glass",
         "code": "invalid_search_filter",
         "type": "invalid_request",
         "link": "https://docs.meilisearch.com/errors#invalid_search_filter"
@@ -905,6 +1017,26 @@ async fn sort_geo_reserved_attribute() {
         .await;
 }
 
+#[actix_rt::test]
+async fn sort_bad_random_seed() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    index
+        .search(json!({"sort": ["_random(seed):asc"]}), |response, code| {
+            snapshot!(code, @"400 Bad Request");
+            snapshot!(json_string!(response), @r###"
+            {
+              "message": "Invalid syntax for the random parameter: expected expression formated like `_random(seed)`, with a positive integer seed, found `_random(seed)`.",
+              "code": "invalid_search_sort",
+              "type": "invalid_request",
+              "link": "https://docs.meilisearch.com/errors#invalid_search_sort"
+            }
+            "###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn sort_reserved_attribute() {
     let server = Server::new_shared();
@@ -1211,3 +1343,81 @@ async fn search_with_contains_without_enabling_the_feature() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn group_by_at_search_time() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+    let (task, _) = index.update_settings_filterable_attributes(json!(["color"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) = index.search_post(json!({"groupBy": "doggo.truc"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Attribute `doggo.truc` is not filterable and thus, cannot be used to group the results. Available filterable attributes are: `color`.",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"groupBy": "color", "distinct": "color"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Using `groupBy` is not compatible with `distinct`.\n Hint: remove `distinct` from the request to group the results with `groupBy`.",
+      "code": "invalid_search_group_by",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_group_by"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"groupBy": "color", "hitsPerGroup": "doggo"})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value type at `.hitsPerGroup`: expected a positive integer, but found a string: `\"doggo\"`",
+      "code": "invalid_search_hits_per_group",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hits_per_group"
+    }
+    "###);
+
+    let (response, code) = index.search_post(json!({"groupBy": "color", "hitsPerGroup": 0})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.hitsPerGroup`: the value of `hitsPerGroup` is invalid, expected an integer between `1` and `1000`.",
+      "code": "invalid_search_hits_per_group",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hits_per_group"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({"groupBy": "color", "hitsPerGroup": 18446744073709551615u64}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.hitsPerGroup`: the value of `hitsPerGroup` is invalid, expected an integer between `1` and `1000`.",
+      "code": "invalid_search_hits_per_group",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hits_per_group"
+    }
+    "###);
+
+    let (response, code) = index.search_get("?groupBy=color&hitsPerGroup=0").await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value in parameter `hitsPerGroup`: the value of `hitsPerGroup` is invalid, expected an integer between `1` and `1000`.",
+      "code": "invalid_search_hits_per_group",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_hits_per_group"
+    }
+    "###);
+}
@@ -184,3 +184,153 @@ async fn bug_4640() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn geo_polygon() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = DOCUMENTS.clone();
+    index.update_settings_filterable_attributes(json!(["_geo"])).await;
+    index.add_documents(documents, None).await;
+    index.wait_task(1).await;
+
+    // a triangle around northern Italy
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([46.5, 7.0], [46.5, 12.5], [44.0, 9.5])",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 2
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    index
+        .search(
+            json!({
+                "filter": "_geoPolygon([46.5, 7.0], [46.5, 12.5], [46.5, 12.5])",
+            }),
+            |response, code| {
+                assert_eq!(code, 400, "{}", response);
+                assert_eq!(response["code"], "invalid_search_filter");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn geo_multiple_points_per_document() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        {
+            "id": 1,
+            "_geo": [
+                { "lat": 34.0522, "lng": -118.2437 },
+                { "lat": 45.4777599, "lng": 9.1967508 }
+            ]
+        },
+        {
+            "id": 2,
+            "_geo": { "lat": 45.4642, "lng": 9.19 }
+        },
+        {
+            "id": 3,
+            "_geo": [
+                { "lat": 48.8566, "lng": 2.3522 },
+                { "lat": 34.0522, "lng": -118.2437 }
+            ]
+        }
+    ]);
+    index
+        .update_settings(json!({"filterableAttributes": ["_geo"], "sortableAttributes": ["_geo"]}))
+        .await;
+    let (ret, _code) = index.add_documents(documents, None).await;
+    index.wait_task(ret.uid()).await;
+
+    // the distance of a document is the distance of its nearest point
+    index
+        .search(
+            json!({
+                "sort": ["_geoPoint(45.4777599, 9.1967508):asc"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1,
+                    "_geo": [
+                      {
+                        "lat": 34.0522,
+                        "lng": -118.2437
+                      },
+                      {
+                        "lat": 45.4777599,
+                        "lng": 9.1967508
+                      }
+                    ],
+                    "_geoDistance": 0
+                  },
+                  {
+                    "id": 2,
+                    "_geo": {
+                      "lat": 45.4642,
+                      "lng": 9.19
+                    },
+                    "_geoDistance": 1597
+                  },
+                  {
+                    "id": 3,
+                    "_geo": [
+                      {
+                        "lat": 48.8566,
+                        "lng": 2.3522
+                      },
+                      {
+                        "lat": 34.0522,
+                        "lng": -118.2437
+                      }
+                    ],
+                    "_geoDistance": 639039
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // a document matches as soon as one of its points matches
+    index
+        .search(
+            json!({
+                "filter": "_geoRadius(34.0522, -118.2437, 1000)",
+                "attributesToRetrieve": ["id"],
+            }),
+            |response, code| {
+                assert_eq!(code, 200, "{}", response);
+                snapshot!(json_string!(response["hits"]), @r###"
+                [
+                  {
+                    "id": 1
+                  },
+                  {
+                    "id": 3
+                  }
+                ]
+                "###);
+            },
+        )
+        .await;
+}
@@ -183,6 +183,41 @@ async fn limit_offset() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 1);
 }
 
+#[actix_rt::test]
+async fn reciprocal_rank_fusion() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    // the keyword search ranks 2, 3, 1 and the semantic search ranks 3, 2, 1
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.4, "fusion": "rrf"}, "showRankingScore": true, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"id":"2","_rankingScore":0.99354841680296},{"id":"3","_rankingScore":0.9903226100629375},{"id":"1","_rankingScore":0.9682539971101851}]"###);
+    snapshot!(response["semanticHitCount"], @"0");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.6, "fusion": "rrf"}, "showRankingScoreDetails": true, "attributesToRetrieve": ["id"]}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["id"], @r###""3""###);
+    snapshot!(response["hits"][0]["_rankingScoreDetails"]["reciprocalRankFusion"], @r###"{"order":0,"k":60,"rank":1,"keywordRank":2,"semanticRank":1,"score":0.9935483874813202}"###);
+    snapshot!(response["semanticHitCount"], @"3");
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"semanticRatio": 0.6, "fusion": "rrf", "k": 0}, "showRankingScoreDetails": true, "attributesToRetrieve": ["id"], "offset": 1, "limit": 1}),
+        )
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"][0]["id"], @r###""2""###);
+    snapshot!(response["hits"][0]["_rankingScoreDetails"]["reciprocalRankFusion"], @r###"{"order":0,"k":0,"rank":2,"keywordRank":1,"semanticRank":2,"score":0.699999988079071}"###);
+}
+
 #[actix_rt::test]
 async fn simple_search_hf() {
     let server = Server::new().await;
@@ -264,6 +299,38 @@ async fn distribution_shift() {
     snapshot!(response["hits"], @r###"[{"title":"Captain Marvel","desc":"a Shazam ersatz","id":"3","_vectors":{"default":{"embeddings":[[2.0,3.0]],"regenerate":false}},"_rankingScore":0.19161224365234375},{"title":"Captain Planet","desc":"He's not part of the Marvel Cinematic Universe","id":"2","_vectors":{"default":{"embeddings":[[1.0,2.0]],"regenerate":false}},"_rankingScore":1.1920928955078125e-7},{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_vectors":{"default":{"embeddings":[[1.0,3.0]],"regenerate":false}},"_rankingScore":1.1920928955078125e-7}]"###);
 }
 
+#[actix_rt::test]
+async fn distance() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let search = json!({"q": "Captain", "vector": [1.0, 1.0], "showRankingScore": true, "hybrid": {"semanticRatio": 1.0}});
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "default": { "distance": "euclidean" } } }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"], @r###"{"embedders":{"default":{"distance":"euclidean"}}}"###);
+
+    let (response, code) = index.search_post(search.clone()).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"title":"Captain Planet","desc":"He's not part of the Marvel Cinematic Universe","id":"2","_rankingScore":0.5},{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_rankingScore":0.3333333432674408},{"title":"Captain Marvel","desc":"a Shazam ersatz","id":"3","_rankingScore":0.30901700258255005}]"###);
+
+    // the embeddings are kept when going back to the cosine distance
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "default": { "distance": "cosine" } } }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) = index.search_post(search).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["hits"], @r###"[{"title":"Captain Marvel","desc":"a Shazam ersatz","id":"3","_rankingScore":0.990290343761444},{"title":"Captain Planet","desc":"He's not part of the Marvel Cinematic Universe","id":"2","_rankingScore":0.974341630935669},{"title":"Shazam!","desc":"a Captain Marvel ersatz","id":"1","_rankingScore":0.9472135901451112}]"###);
+}
+
 #[actix_rt::test]
 async fn highlighter() {
     let server = Server::new().await;
@@ -391,6 +458,52 @@ async fn invalid_semantic_ratio() {
     "###);
 }
 
+#[actix_rt::test]
+async fn invalid_fusion() {
+    let server = Server::new().await;
+    let index = index_with_documents_user_provided(&server, &SIMPLE_SEARCH_DOCUMENTS_VEC).await;
+
+    let (response, code) = index
+        .search_post(json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"fusion": "rank"}}))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Unknown value `rank` at `.hybrid.fusion`: expected one of `score`, `rrf`",
+      "code": "invalid_search_fusion",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_fusion"
+    }
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"k": 60}})).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value at `.hybrid`: `k` can only be used with the `rrf` fusion.",
+      "code": "invalid_search_fusion_k",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_fusion_k"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(
+            json!({"q": "Captain", "vector": [1.0, 1.0], "hybrid": {"fusion": "rrf", "k": -1}}),
+        )
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response, @r###"
+    {
+      "message": "Invalid value type at `.hybrid.k`: expected a positive integer, but found a negative integer: `-1`",
+      "code": "invalid_search_fusion_k",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_fusion_k"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn single_document() {
     let server = Server::new().await;
@@ -14,13 +14,14 @@ mod multi;
 mod pagination;
 mod restrict_searchable;
 mod search_queue;
+mod suggest;
 
 use meilisearch::Opt;
 use tempfile::TempDir;
 
 use crate::common::{
     default_settings, shared_index_with_documents, shared_index_with_nested_documents, Server,
-    DOCUMENTS, FRUITS_DOCUMENTS, NESTED_DOCUMENTS, SCORE_DOCUMENTS, VECTOR_DOCUMENTS,
+    Value, DOCUMENTS, FRUITS_DOCUMENTS, NESTED_DOCUMENTS, SCORE_DOCUMENTS, VECTOR_DOCUMENTS,
 };
 use crate::json;
 
@@ -436,6 +437,34 @@ async fn search_with_multiple_sort() {
     assert_eq!(response["hits"].as_array().unwrap().len(), 5);
 }
 
+#[actix_rt::test]
+async fn search_with_random_sort() {
+    let index = shared_index_with_documents().await;
+    let ids = |response: &Value| -> Vec<String> {
+        response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].to_string()).collect()
+    };
+
+    let (response, code) = index.search_post(json!({ "sort": ["_random(42)"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    let shuffled = ids(&response);
+    assert_eq!(shuffled.len(), 5);
+
+    // the same seed always gives the same order, even across pages
+    let (response, code) = index.search_post(json!({ "sort": ["_random(42):asc"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), shuffled);
+    let (response, code) =
+        index.search_post(json!({ "sort": ["_random(42)"], "offset": 2, "limit": 2 })).await;
+    assert_eq!(code, 200, "{}", response);
+    assert_eq!(ids(&response), shuffled[2..4]);
+
+    let (response, code) = index.search_post(json!({ "sort": ["_random(42):desc"] })).await;
+    assert_eq!(code, 200, "{}", response);
+    let mut reversed = ids(&response);
+    reversed.reverse();
+    assert_eq!(reversed, shuffled);
+}
+
 #[actix_rt::test]
 async fn search_facet_distribution() {
     let index = shared_index_with_documents().await;
@@ -533,6 +562,331 @@ async fn search_facet_distribution() {
         .await;
 }
 
+#[actix_rt::test]
+async fn search_facet_ranges() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "price": 5 },
+        { "id": 2, "price": 12 },
+        { "id": 3, "price": 18.5 },
+        { "id": 4, "price": 25 },
+        { "id": 5, "price": 40 },
+        { "id": 6, "price": "free" },
+        { "id": 7 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings_filterable_attributes(json!(["price"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    let (response, code) =
+        index.search_post(json!({ "facetRanges": { "price": { "interval": 10 } } })).await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetRanges"]), @r###"
+    {
+      "price": [
+        {
+          "from": 0.0,
+          "to": 10.0,
+          "count": 1
+        },
+        {
+          "from": 10.0,
+          "to": 20.0,
+          "count": 2
+        },
+        {
+          "from": 20.0,
+          "to": 30.0,
+          "count": 1
+        },
+        {
+          "from": 30.0,
+          "to": 40.0,
+          "count": 0
+        },
+        {
+          "from": 40.0,
+          "to": 50.0,
+          "count": 1
+        }
+      ]
+    }
+    "###);
+
+    // in a GET search the facet ranges are the same JSON object, as a string
+    let (get_response, code) = index
+        .search_get(
+            &yaup::to_string(&json!({ "facetRanges": r#"{ "price": { "interval": 10 } }"# }))
+                .unwrap(),
+        )
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    assert_eq!(get_response["facetRanges"], response["facetRanges"]);
+
+    // the counts only take the documents matching the search into account
+    let (response, code) = index
+        .search_post(json!({
+            "filter": "price >= 10",
+            "facetRanges": {
+                "price": { "ranges": [{ "to": 15 }, { "from": 15, "to": 30 }, { "from": 30 }] }
+            }
+        }))
+        .await;
+    meili_snap::snapshot!(code, @"200 OK");
+    meili_snap::snapshot!(meili_snap::json_string!(response["facetRanges"]), @r###"
+    {
+      "price": [
+        {
+          "to": 15.0,
+          "count": 1
+        },
+        {
+          "from": 15.0,
+          "to": 30.0,
+          "count": 2
+        },
+        {
+          "from": 30.0,
+          "count": 1
+        }
+      ]
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_corrected_query() {
+    let index = shared_index_with_documents().await;
+
+    index
+        .search(
+            json!({"q": "Capitain Marvel", "attributesToRetrieve": ["title"], "didYouMean": true}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["hits"], @r###"[{"title":"Captain Marvel"}]"###);
+                meili_snap::snapshot!(response["correctedQuery"], @r###""captain marvel""###);
+            },
+        )
+        .await;
+
+    // the last word is corrected as a full word, not completed
+    index
+        .search(
+            json!({"q": "Captain Marvell", "attributesToRetrieve": ["title"], "didYouMean": true}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["hits"], @r###"[{"title":"Captain Marvel"}]"###);
+                meili_snap::snapshot!(response["correctedQuery"], @r###""captain marvel""###);
+            },
+        )
+        .await;
+
+    // not asked for
+    index
+        .search(
+            json!({"q": "Capitain Marvel", "attributesToRetrieve": ["title"]}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["correctedQuery"], @"null");
+            },
+        )
+        .await;
+
+    // nothing to correct
+    index
+        .search(
+            json!({"q": "Captain Marvel", "attributesToRetrieve": ["title"], "didYouMean": true}),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                meili_snap::snapshot!(response["correctedQuery"], @"null");
+            },
+        )
+        .await;
+}
+
+#[actix_rt::test]
+async fn search_with_ranking_rules() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "title": "the dog", "rank": 3 },
+        { "id": 2, "title": "the dog and the cat", "rank": 1 },
+        { "id": 3, "title": "the cat", "rank": 2 },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings(json!({ "sortableAttributes": ["rank"] })).await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "q": "the dog",
+                "rankingRules": ["desc(rank)", "words"],
+                "showRankingScoreDetails": true,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                let hits: Vec<_> = response["hits"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|hit| {
+                        let rules: Vec<_> =
+                            hit["_rankingScoreDetails"].as_object().unwrap().keys().collect();
+                        (&hit["id"], rules)
+                    })
+                    .collect();
+                meili_snap::snapshot!(meili_snap::json_string!(hits), @r###"
+                [
+                  [
+                    1,
+                    [
+                      "rank:desc",
+                      "words"
+                    ]
+                  ],
+                  [
+                    3,
+                    [
+                      "rank:desc",
+                      "words"
+                    ]
+                  ],
+                  [
+                    2,
+                    [
+                      "rank:desc",
+                      "words"
+                    ]
+                  ]
+                ]
+                "###);
+            },
+        )
+        .await;
+
+    // the custom ranking rules must be sortable
+    let (response, code) =
+        index.search_post(json!({ "q": "dog", "rankingRules": ["asc(title)"] })).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Attribute `title` is not sortable and thus, cannot be used in the ranking rules of a search. Available sortable attributes are: `rank`.",
+      "code": "invalid_search_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_ranking_rules"
+    }
+    "###);
+}
+
+#[actix_rt::test]
+async fn search_datetime_attributes() {
+    let server = Server::new_shared();
+    let index = server.unique_index();
+
+    let documents = json!([
+        { "id": 1, "publishedAt": "2024-01-31T08:00:00Z" },
+        { "id": 2, "publishedAt": "2023-06-15T12:30:00+02:00" },
+        { "id": 3, "publishedAt": "2024-03-01T00:00:00.5Z" },
+        { "id": 4, "publishedAt": "not a date" },
+        { "id": 5, "publishedAt": "2999-01-01T00:00:00Z" },
+    ]);
+    let (task, _code) = index.add_documents(documents, None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index
+        .update_settings(json!({
+            "filterableAttributes": ["publishedAt"],
+            "sortableAttributes": ["publishedAt"],
+            "datetimeAttributes": ["publishedAt"],
+        }))
+        .await;
+    index.wait_task(task.uid()).await.succeeded();
+
+    index
+        .search(
+            json!({
+                "filter": "publishedAt >= 2024-01-01T00:00:00Z AND publishedAt < NOW",
+                "sort": ["publishedAt:desc"],
+                "facets": ["publishedAt"],
+                "showRankingScoreDetails": true,
+            }),
+            |response, code| {
+                meili_snap::snapshot!(code, @"200 OK");
+                let sort_values: Vec<_> = response["hits"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|hit| {
+                        (&hit["id"], &hit["_rankingScoreDetails"]["publishedAt:desc"]["value"])
+                    })
+                    .collect();
+                meili_snap::snapshot!(meili_snap::json_string!(sort_values), @r###"
+                [
+                  [
+                    3,
+                    "2024-03-01T00:00:00.5Z"
+                  ],
+                  [
+                    1,
+                    "2024-01-31T08:00:00Z"
+                  ]
+                ]
+                "###);
+                meili_snap::snapshot!(meili_snap::json_string!(response["facetStats"]), @r###"
+                {
+                  "publishedAt": {
+                    "min": "2024-01-31T08:00:00Z",
+                    "max": "2024-03-01T00:00:00.5Z"
+                  }
+                }
+                "###);
+            },
+        )
+        .await;
+
+    // the datetimes are compared as instants, whatever their offset
+    index
+        .search(json!({ "filter": "publishedAt = 2023-06-15T10:30:00Z" }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 2,
+                "publishedAt": "2023-06-15T12:30:00+02:00"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    // the values that are not datetimes are still filterable as strings
+    index
+        .search(json!({ "filter": "publishedAt = 'not a date'" }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 4,
+                "publishedAt": "not a date"
+              }
+            ]
+            "###);
+        })
+        .await;
+
+    index
+        .search(json!({ "filter": "publishedAt > yesterday" }), |response, code| {
+            meili_snap::snapshot!(code, @"400 Bad Request");
+            meili_snap::snapshot!(response["code"], @r###""invalid_search_filter""###);
+        })
+        .await;
+}
+
 #[actix_rt::test]
 async fn displayed_attributes() {
     let server = Server::new().await;
@@ -1595,3 +1949,38 @@ async fn simple_search_with_strange_synonyms() {
         })
         .await;
 }
+
+#[actix_rt::test]
+async fn expired_documents_are_hidden() {
+    let server = Server::new().await;
+    let index = server.index("test");
+
+    let documents = json!([
+        { "id": 0, "title": "expired", "expiresAt": "2000-01-01T00:00:00Z" },
+        { "id": 1, "title": "not expired", "expiresAt": "4000-01-01T00:00:00Z" },
+        { "id": 2, "title": "expired timestamp", "expiresAt": 946684800 },
+        { "id": 3, "title": "never expires" },
+    ]);
+    index.add_documents(documents, None).await;
+    index.wait_task(0).await;
+
+    let (response, code) = index.update_settings(json!({ "expiryAttribute": "expiresAt" })).await;
+    meili_snap::snapshot!(code, @"202 Accepted");
+    index.wait_task(response.uid()).await;
+
+    index
+        .search(json!({ "attributesToRetrieve": ["id"] }), |response, code| {
+            meili_snap::snapshot!(code, @"200 OK");
+            meili_snap::snapshot!(meili_snap::json_string!(response["hits"]), @r###"
+            [
+              {
+                "id": 1
+              },
+              {
+                "id": 3
+              }
+            ]
+            "###);
+        })
+        .await;
+}
@@ -133,3 +133,41 @@ async fn ensure_placeholder_search_hit_count_valid() {
             .await;
     }
 }
+
+#[actix_rt::test]
+async fn search_after_walks_all_the_hits() {
+    let index = shared_index_with_documents().await;
+
+    let (response, code) = index.search_post(json!({"limit": 100})).await;
+    assert_eq!(code, 200, "{}", response);
+    let expected: Vec<_> =
+        response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()).collect();
+    assert!(response.get("nextSearchAfter").is_none());
+
+    let mut ids = Vec::new();
+    let mut search_after = String::new();
+    loop {
+        let (response, code) =
+            index.search_post(json!({"limit": 2, "searchAfter": search_after})).await;
+        assert_eq!(code, 200, "{}", response);
+        ids.extend(response["hits"].as_array().unwrap().iter().map(|h| h["id"].clone()));
+        match response.get("nextSearchAfter") {
+            Some(next) => search_after = next.as_str().unwrap().to_string(),
+            None => break,
+        }
+    }
+    assert_eq!(ids, expected);
+}
+
+#[actix_rt::test]
+async fn search_after_errors() {
+    let index = shared_index_with_documents().await;
+
+    let (response, code) = index.search_post(json!({"searchAfter": "zz"})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_search_after");
+
+    let (response, code) = index.search_post(json!({"searchAfter": "", "page": 2})).await;
+    assert_eq!(code, 400, "{}", response);
+    assert_eq!(response["code"], "invalid_search_search_after");
+}
@@ -0,0 +1,123 @@
+use meili_snap::{json_string, snapshot};
+use once_cell::sync::Lazy;
+
+use crate::common::index::Index;
+use crate::common::{Server, Shared, Value};
+use crate::json;
+
+static DOCUMENTS: Lazy<Value> = Lazy::new(|| {
+    json!([
+        { "id": 0, "title": "Harry Potter", "genre": "fantasy" },
+        { "id": 1, "title": "Harry Potter and the Potion", "genre": "fantasy" },
+        { "id": 2, "title": "The Potato Cookbook", "genre": "cooking" },
+        { "id": 3, "title": "Pottery", "description": "how to make a pot", "genre": "craft" },
+    ])
+});
+
+async fn index_with_documents<'a>(server: &'a Server<Shared>, documents: &Value) -> Index<'a> {
+    let index = server.unique_index();
+
+    let (task, _code) = index.add_documents(documents.clone(), None).await;
+    index.wait_task(task.uid()).await.succeeded();
+    let (task, _code) = index.update_settings_filterable_attributes(json!(["genre"])).await;
+    index.wait_task(task.uid()).await.succeeded();
+    index
+}
+
+#[actix_rt::test]
+async fn suggest() {
+    let server = Server::new_shared();
+    let index = index_with_documents(server, &DOCUMENTS).await;
+
+    let (response, code) = index.suggest(json!({ "q": "pot" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response, { ".processingTimeMs" => "[time]" }), @r###"
+    {
+      "suggestions": [
+        {
+          "value": "potter",
+          "count": 2
+        },
+        {
+          "value": "pot",
+          "count": 1
+        },
+        {
+          "value": "potato",
+          "count": 1
+        },
+        {
+          "value": "potion",
+          "count": 1
+        },
+        {
+          "value": "pottery",
+          "count": 1
+        }
+      ],
+      "query": "pot",
+      "processingTimeMs": "[time]"
+    }
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "pot", "limit": 2 })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "value": "potter",
+        "count": 2
+      },
+      {
+        "value": "pot",
+        "count": 1
+      }
+    ]
+    "###);
+
+    // a finished last word has no completion
+    let (response, code) = index.suggest(json!({ "q": "harry " })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @"[]");
+}
+
+#[actix_rt::test]
+async fn suggest_with_filter_and_attributes_to_search_on() {
+    let server = Server::new_shared();
+    let index = index_with_documents(server, &DOCUMENTS).await;
+
+    let (response, code) = index.suggest(json!({ "q": "pot", "filter": "genre = fantasy" })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "value": "potter",
+        "count": 2
+      },
+      {
+        "value": "potion",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.suggest(json!({ "q": "pot", "attributesToSearchOn": ["description"] })).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["suggestions"]), @r###"
+    [
+      {
+        "value": "pot",
+        "count": 1
+      }
+    ]
+    "###);
+
+    let (response, code) = index.suggest(json!({ "q": "pot", "filter": "title = a" })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_filter""###);
+
+    let (response, code) = index.suggest(json!({ "q": "pot", "limit": -1 })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(response["code"], @r###""invalid_search_limit""###);
+}
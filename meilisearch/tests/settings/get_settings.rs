@@ -11,6 +11,8 @@ static DEFAULT_SETTINGS_VALUES: Lazy<HashMap<&'static str, Value>> = Lazy::new(|
     map.insert("searchable_attributes", json!(["*"]));
     map.insert("localized_attributes", json!(null));
     map.insert("filterable_attributes", json!([]));
+    map.insert("datetime_attributes", json!([]));
+    map.insert("expiry_attribute", json!(null));
     map.insert("distinct_attribute", json!(null));
     map.insert(
         "ranking_rules",
@@ -56,11 +58,13 @@ async fn get_settings() {
     let (response, code) = index.settings().await;
     assert_eq!(code, 200);
     let settings = response.as_object().unwrap();
-    assert_eq!(settings.keys().len(), 17);
+    assert_eq!(settings.keys().len(), 19);
     assert_eq!(settings["displayedAttributes"], json!(["*"]));
     assert_eq!(settings["searchableAttributes"], json!(["*"]));
     assert_eq!(settings["filterableAttributes"], json!([]));
     assert_eq!(settings["sortableAttributes"], json!([]));
+    assert_eq!(settings["datetimeAttributes"], json!([]));
+    assert_eq!(settings["expiryAttribute"], json!(null));
     assert_eq!(settings["distinctAttribute"], json!(null));
     assert_eq!(
         settings["rankingRules"],
@@ -152,6 +156,8 @@ async fn secrets_are_hidden_in_settings() {
       ],
       "filterableAttributes": [],
       "sortableAttributes": [],
+      "datetimeAttributes": [],
+      "expiryAttribute": null,
       "rankingRules": [
         "words",
         "typo",
@@ -410,6 +416,8 @@ macro_rules! test_setting_routes {
 
 test_setting_routes!(
     filterable_attributes put,
+    datetime_attributes put,
+    expiry_attribute put,
     displayed_attributes put,
     localized_attributes put,
     searchable_attributes put,
@@ -443,6 +451,39 @@ async fn error_set_invalid_ranking_rules() {
     "###);
 }
 
+#[actix_rt::test]
+async fn set_decay_and_boost_ranking_rules() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    index.create(None).await;
+
+    let (response, _code) = index
+        .update_settings(json!({
+            "rankingRules": ["words", "decay(publishedAt, scale=30d)", "boost(popularity,log)"]
+        }))
+        .await;
+    index.wait_task(response.uid()).await.succeeded();
+
+    let (response, code) = index.settings().await;
+    assert_eq!(code, 200);
+    assert_eq!(
+        response["rankingRules"],
+        json!(["words", "decay(publishedAt, origin=now, scale=30d)", "boost(popularity, log)"])
+    );
+
+    let (response, code) =
+        index.update_settings(json!({ "rankingRules": ["decay(publishedAt, scale=soon)"]})).await;
+    meili_snap::snapshot!(code, @"400 Bad Request");
+    meili_snap::snapshot!(meili_snap::json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rankingRules[0]`: `decay(publishedAt, origin=now, scale=soon)` ranking rule is invalid. The scale must be a positive number optionally followed by a time unit (`s`, `m`, `h`, `d` or `w`), found `soon`.",
+      "code": "invalid_settings_ranking_rules",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_ranking_rules"
+    }
+    "###);
+}
+
 #[actix_rt::test]
 async fn set_and_reset_distinct_attribute_with_dedicated_route() {
     let server = Server::new().await;
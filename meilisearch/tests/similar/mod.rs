@@ -737,3 +737,67 @@ async fn limit_and_offset() {
         )
         .await;
 }
+
+#[actix_rt::test]
+async fn similar_to_vector() {
+    let server = Server::new().await;
+    let index = server.index("test");
+    let (_, code) = server.set_features(json!({"vectorStore": true})).await;
+    snapshot!(code, @"200 OK");
+
+    let (response, code) = index
+        .update_settings(json!({
+        "embedders": {
+            "manual": {
+                "source": "userProvided",
+                "dimensions": 3,
+            }
+        },
+        "filterableAttributes": ["release_year"]}))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    server.wait_task(response.uid()).await;
+
+    let documents = DOCUMENTS.clone();
+    let (value, code) = index.add_documents(documents, None).await;
+    snapshot!(code, @"202 Accepted");
+    index.wait_task(value.uid()).await;
+
+    // unlike a document, a vector doesn't exclude anything from the results
+    let (response, code) = index.similar_post(json!({"vector": [-0.5, 0.3, 0.85]})).await;
+    snapshot!(code, @"200 OK");
+    snapshot!(response["id"], @"null");
+    snapshot!(ids(&response), @r###"
+    [
+      "143",
+      "522681",
+      "299537",
+      "166428",
+      "287947"
+    ]
+    "###);
+
+    let (response, code) = index
+        .similar_post(json!({
+            "vector": [-0.5, 0.3, 0.85],
+            "filter": "release_year = 2019",
+            "limit": 1,
+            "offset": 1,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(ids(&response), @r###"
+    [
+      "299537"
+    ]
+    "###);
+}
+
+fn ids(response: &Value) -> String {
+    json_string!(response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["id"].clone())
+        .collect::<Vec<_>>())
+}
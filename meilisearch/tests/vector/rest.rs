@@ -2043,3 +2043,252 @@ async fn searchable_reindex() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn chunked_documents() {
+    let mock_server = MockServer::start().await;
+
+    let text_to_embedding: BTreeMap<_, _> = vec![
+        // chunk -> embedding
+        ("the quick brown", [1.0, 0.0, 0.0]),
+        ("brown fox jumps", [0.0, 1.0, 0.0]),
+        ("jumps over dogs", [0.0, 0.0, 1.0]),
+    ]
+    .into_iter()
+    .collect();
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let text: String = req.body_json().unwrap();
+            match text_to_embedding.get(text.as_str()) {
+                Some(embedding) => {
+                    ResponseTemplate::new(200).set_body_json(json!({ "data": embedding }))
+                }
+                None => ResponseTemplate::new(404)
+                    .set_body_json(json!({"error": "text not found", "text": text})),
+            }
+        })
+        .mount(&mock_server)
+        .await;
+
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "embedders": {
+              "rest": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "dimensions": 3,
+                  "request": "{{text}}",
+                  "response": {
+                    "data": "{{embedding}}"
+                  },
+                  "chunking": { "field": "text", "size": 3, "overlap": 1 },
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .add_documents(json!({ "id": 1, "text": "the quick brown fox jumps over dogs" }), None)
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (documents, _code) = index
+        .get_all_documents(GetAllDocumentsOptions { retrieve_vectors: true, ..Default::default() })
+        .await;
+    snapshot!(json_string!(documents["results"][0]["_vectors"]), @r###"
+    {
+      "rest": {
+        "embeddings": [
+          [
+            1.0,
+            0.0,
+            0.0
+          ],
+          [
+            0.0,
+            1.0,
+            0.0
+          ],
+          [
+            0.0,
+            0.0,
+            1.0
+          ]
+        ],
+        "regenerate": true
+      }
+    }
+    "###);
+
+    for (vector, chunk) in [([0.0, 0.1, 1.0], "2"), ([0.1, 1.0, 0.0], "1")] {
+        let (response, code) = index
+            .search_post(json!({
+                "vector": vector,
+                "hybrid": { "semanticRatio": 1.0, "embedder": "rest" },
+                "showRankingScoreDetails": true,
+            }))
+            .await;
+        snapshot!(code, @"200 OK");
+        assert_eq!(
+            response["hits"][0]["_rankingScoreDetails"]["vectorSort"]["chunk"].to_string(),
+            chunk
+        );
+    }
+}
+
+#[actix_rt::test]
+async fn rest_reranker() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/"))
+        .respond_with(move |req: &Request| {
+            let body: serde_json::Value = req.body_json().unwrap();
+            if body["query"].as_str() != Some("kefir") {
+                return ResponseTemplate::new(400)
+                    .set_body_json(json!({"error": "unexpected query", "query": body["query"]}));
+            }
+            let scores: Vec<f32> = body["documents"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|text| if text.as_str().unwrap().contains("dog") { 0.75 } else { 0.25 })
+                .collect();
+            ResponseTemplate::new(200).set_body_json(json!({ "scores": scores }))
+        })
+        .mount(&mock_server)
+        .await;
+
+    let server = get_server_vector().await;
+    let index = server.index("doggo");
+
+    let (response, code) = index
+        .update_settings(json!({
+          "rerankers": {
+              "rest": {
+                  "source": "rest",
+                  "url": mock_server.uri(),
+                  "request": { "query": "{{query}}", "documents": ["{{text}}", "{{..}}"] },
+                  "response": { "scores": ["{{score}}", "{{..}}"] },
+                  "documentTemplate": "{{doc.name}}",
+              },
+          },
+        }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .add_documents(
+            json!([
+                { "id": 1, "name": "kefir" },
+                { "id": 2, "name": "kefir the dog" },
+                { "id": 3, "name": "intel" },
+            ]),
+            None,
+        )
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let task = server.wait_task(response.uid()).await;
+    snapshot!(task["status"], @r###""succeeded""###);
+
+    let (response, code) = index
+        .search_post(json!({
+            "q": "kefir",
+            "rerank": { "reranker": "rest" },
+            "showRankingScoreDetails": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"].as_array().unwrap().iter().map(|hit| hit["id"].clone()).collect::<Vec<_>>()), @r###"
+    [
+      2,
+      1
+    ]
+    "###);
+    snapshot!(response["hits"][0]["_rankingScoreDetails"]["rerank"], @r###"{"order":0,"reranker":"rest","rank":1,"score":0.75}"###);
+
+    // the ranking scores keep decreasing with the position of the reranked hits
+    let (response, code) = index
+        .search_post(json!({
+            "q": "kefir",
+            "rerank": { "reranker": "rest" },
+            "showRankingScore": true,
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    let scores: Vec<f64> = response["hits"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|hit| hit["_rankingScore"].as_f64().unwrap())
+        .collect();
+    assert!(scores.windows(2).all(|scores| scores[0] >= scores[1]), "{scores:?}");
+
+    // only the first hit is reranked, the page starts after it
+    let (response, code) = index
+        .search_post(json!({
+            "q": "kefir",
+            "offset": 1,
+            "rerank": { "reranker": "rest", "topK": 1 },
+        }))
+        .await;
+    snapshot!(code, @"200 OK");
+    snapshot!(json_string!(response["hits"]), @r###"
+    [
+      {
+        "id": 2,
+        "name": "kefir the dog"
+      }
+    ]
+    "###);
+
+    let (response, code) =
+        index.search_post(json!({ "q": "kefir", "rerank": { "reranker": "unknown" } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Cannot find reranker with name `unknown`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "q": "kefir", "rerank": { "reranker": "rest", "topK": 0 } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rerank.topK`: the value of `topK` is invalid, expected an integer between `1` and `1000`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+
+    let (response, code) = index
+        .search_post(json!({ "q": "kefir", "rerank": { "reranker": "rest", "topK": 1000000000 } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "Invalid value at `.rerank.topK`: the value of `topK` is invalid, expected an integer between `1` and `1000`.",
+      "code": "invalid_search_rerank",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_search_rerank"
+    }
+    "###);
+}
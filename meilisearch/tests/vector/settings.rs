@@ -228,3 +228,146 @@ async fn reset_embedder_documents() {
     }
     "###);
 }
+
+#[actix_rt::test]
+async fn quantize_embedder() {
+    let server = Server::new().await;
+    let index = generate_default_user_provided_documents(&server).await;
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "manual": { "quantization": "binary" } } }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+    snapshot!(response["details"], @r###"{"embedders":{"manual":{"quantization":"binary"}}}"###);
+
+    // the full-precision embeddings are lost, so the quantization can't be changed
+    for quantization in [json!("int8"), json!(null)] {
+        let (response, code) = index
+            .update_settings(json!({ "embedders": { "manual": { "quantization": quantization } } }))
+            .await;
+        snapshot!(code, @"202 Accepted");
+        let response = server.wait_task(response.uid()).await;
+        snapshot!(response["status"], @r###""failed""###);
+        snapshot!(json_string!(response["error"]), @r###"
+        {
+          "message": "`.embedders.manual.quantization`: Cannot change the quantization of a quantized embedder, the full-precision embeddings are lost.\n - Hint: Remove the embedder and add it back to index the full-precision embeddings again.",
+          "code": "invalid_settings_embedders",
+          "type": "invalid_request",
+          "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+        }
+        "###);
+    }
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "dot": {
+            "source": "userProvided",
+            "dimensions": 3,
+            "distance": "dotProduct",
+            "quantization": "binary",
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.embedders.dot.quantization`: The `dotProduct` distance does not support binary quantization.",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    // the int8 quantization works with every distance
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "dot": {
+            "source": "userProvided",
+            "dimensions": 3,
+            "distance": "dotProduct",
+            "quantization": "int8",
+            "rescore": true,
+        } } }))
+        .await;
+    snapshot!(code, @"202 Accepted");
+    let response = server.wait_task(response.uid()).await;
+    snapshot!(response["status"], @r###""succeeded""###);
+
+    let (response, code) =
+        index.update_settings(json!({ "embedders": { "dot": { "quantization": "float" } } })).await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response["code"]), @r###""invalid_settings_embedders""###);
+}
+
+#[actix_rt::test]
+async fn onnx_embedder_settings() {
+    let server = Server::new().await;
+    let index = generate_default_user_provided_documents(&server).await;
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "onnx": {
+            "source": "onnx",
+            "pooling": "cls",
+            "normalize": true,
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.embedders.onnx`: Missing field `model` (note: this field is mandatory for source onnx)",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    // the models are only read from the models directory
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "onnx": {
+            "source": "onnx",
+            "model": "../../etc/model.onnx",
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.embedders.onnx.model`: Invalid path `../../etc/model.onnx`. The paths of the ONNX models and tokenizers must be relative to the models directory and must not contain `..`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "onnx": {
+            "source": "onnx",
+            "model": "model.onnx",
+            "tokenizer": "/etc/tokenizer.json",
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.embedders.onnx.tokenizer`: Invalid path `/etc/tokenizer.json`. The paths of the ONNX models and tokenizers must be relative to the models directory and must not contain `..`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+
+    let (response, code) = index
+        .update_settings(json!({ "embedders": { "manual": {
+            "source": "userProvided",
+            "dimensions": 3,
+            "pooling": "mean",
+        } } }))
+        .await;
+    snapshot!(code, @"400 Bad Request");
+    snapshot!(json_string!(response), @r###"
+    {
+      "message": "`.embedders.manual`: Field `pooling` unavailable for source `userProvided` (only available for sources: `onnx`). Available fields: `source`, `dimensions`, `distribution`, `distance`, `quantization`, `rescore`",
+      "code": "invalid_settings_embedders",
+      "type": "invalid_request",
+      "link": "https://docs.meilisearch.com/errors#invalid_settings_embedders"
+    }
+    "###);
+}
@@ -60,6 +60,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 universe,
                 &None,
                 &None,
+                1,
                 GeoSortStrategy::default(),
                 0,
                 20,
@@ -69,6 +70,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 TimeBudget::max(),
                 None,
                 None,
+                None,
             )?;
             if let Some((logger, dir)) = detailed_logger {
                 logger.finish(&mut ctx, Path::new(dir))?;
@@ -44,6 +44,9 @@ impl From<AscDescError> for CriterionError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 CriterionError::ReservedNameForFilter { name: "_geoBoundingBox".to_string() }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_random") => {
+                CriterionError::ReservedNameForSort { name: "_random".to_string() }
+            }
             AscDescError::ReservedKeyword { name } => CriterionError::ReservedName { name },
         }
     }
@@ -53,6 +56,8 @@ impl From<AscDescError> for CriterionError {
 pub enum Member {
     Field(String),
     Geo([f64; 2]),
+    /// A shuffle of the documents, always the same for a given seed.
+    Random(u64),
 }
 
 impl FromStr for Member {
@@ -77,6 +82,16 @@ impl FromStr for Member {
                 }
                 Ok(Member::Geo([lat, lng]))
             }
+            None if text.starts_with("_random(") => {
+                match text.strip_prefix("_random(").and_then(|text| text.strip_suffix(')')) {
+                    Some(seed) => seed
+                        .trim()
+                        .parse()
+                        .map(Member::Random)
+                        .map_err(|_| AscDescError::ReservedKeyword { name: text.to_string() }),
+                    None => Err(AscDescError::ReservedKeyword { name: text.to_string() }),
+                }
+            }
             None => {
                 if is_reserved_keyword(text)
                     || text.starts_with("_geoRadius(")
@@ -97,6 +112,7 @@ impl fmt::Display for Member {
         match self {
             Member::Field(name) => f.write_str(name),
             Member::Geo([lat, lng]) => write!(f, "_geoPoint({}, {})", lat, lng),
+            Member::Random(seed) => write!(f, "_random({})", seed),
         }
     }
 }
@@ -105,14 +121,14 @@ impl Member {
     pub fn field(&self) -> Option<&str> {
         match self {
             Member::Field(field) => Some(field),
-            Member::Geo(_) => None,
+            Member::Geo(_) | Member::Random(_) => None,
         }
     }
 
     pub fn geo_point(&self) -> Option<&[f64; 2]> {
         match self {
             Member::Geo(point) => Some(point),
-            Member::Field(_) => None,
+            Member::Field(_) | Member::Random(_) => None,
         }
     }
 }
@@ -143,6 +159,8 @@ impl FromStr for AscDesc {
         match text.rsplit_once(':') {
             Some((left, "asc")) => Ok(AscDesc::Asc(left.parse()?)),
             Some((left, "desc")) => Ok(AscDesc::Desc(left.parse()?)),
+            // the order of a random sort doesn't matter, so it is optional
+            _ if text.starts_with("_random(") => Ok(AscDesc::Asc(text.parse()?)),
             _ => Err(AscDescError::InvalidSyntax { name: text.to_string() }),
         }
     }
@@ -157,6 +175,11 @@ pub enum SortError {
     BadGeoPointUsage { name: String },
     #[error("Invalid syntax for the sort parameter: expected expression ending by `:asc` or `:desc`, found `{name}`.")]
     InvalidName { name: String },
+    #[error(
+        "Invalid syntax for the random parameter: expected expression formated like \
+                    `_random(seed)`, with a positive integer seed, found `{name}`."
+    )]
+    BadRandomUsage { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression.")]
     ReservedName { name: String },
     #[error("`{name}` is a reserved keyword and thus can't be used as a sort expression. \
@@ -184,6 +207,9 @@ impl From<AscDescError> for SortError {
             AscDescError::ReservedKeyword { name } if name.starts_with("_geoBoundingBox") => {
                 SortError::ReservedNameForFilter { name: String::from("_geoBoundingBox") }
             }
+            AscDescError::ReservedKeyword { name } if name.starts_with("_random") => {
+                SortError::BadRandomUsage { name }
+            }
             AscDescError::ReservedKeyword { name } => SortError::ReservedName { name },
         }
     }
@@ -222,6 +248,9 @@ mod tests {
             ("_geoPoint(42.0002, 59.895):desc", Desc(Geo([42.0002, 59.895]))),
             ("_geoPoint(42., 59.):desc", Desc(Geo([42., 59.]))),
             ("truc(12, 13):desc", Desc(Field(S("truc(12, 13)")))),
+            ("_random(42)", Asc(Random(42))),
+            ("_random( 42 ):asc", Asc(Random(42))),
+            ("_random(0):desc", Desc(Random(0))),
         ];
 
         for (req, expected) in valid_req {
@@ -274,6 +303,10 @@ mod tests {
                 "_geoDistance(12, -2021):desc",
                 ReservedKeyword { name: S("_geoDistance(12, -2021)") },
             ),
+            ("_random:asc", ReservedKeyword { name: S("_random") }),
+            ("_random(-1)", ReservedKeyword { name: S("_random(-1)") }),
+            ("_random(seed):asc", ReservedKeyword { name: S("_random(seed)") }),
+            ("_random(1, 2)", ReservedKeyword { name: S("_random(1, 2)") }),
         ];
 
         for (req, expected_error) in invalid_req {
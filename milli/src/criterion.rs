@@ -22,6 +22,8 @@ pub enum CriterionError {
 `{name}` can only be used for filtering at search time"
     )]
     ReservedNameForFilter { name: String },
+    #[error("`{rule}` ranking rule is invalid. {reason}")]
+    InvalidFunction { rule: String, reason: String },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -45,6 +47,10 @@ pub enum Criterion {
     Asc(String),
     /// Sorted by the decreasing value of the field specified.
     Desc(String),
+    /// Sorted by the decreasing closeness of the value of the field specified to an origin.
+    Decay(Decay),
+    /// Sorted by the decreasing value of the field specified, scaled by a modifier.
+    Boost(Boost),
 }
 
 impl Criterion {
@@ -52,11 +58,167 @@ impl Criterion {
     pub fn field_name(&self) -> Option<&str> {
         match self {
             Criterion::Asc(name) | Criterion::Desc(name) => Some(name),
+            Criterion::Decay(Decay { field, .. }) | Criterion::Boost(Boost { field, .. }) => {
+                Some(field)
+            }
             _otherwise => None,
         }
     }
 }
 
+/// A `decay(field, origin=now, scale=30d)` ranking rule.
+///
+/// The score of a document halves every `scale` its value gets away from the `origin`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Decay {
+    pub field: String,
+    /// Either `now`, a number or an RFC 3339 datetime.
+    pub origin: String,
+    /// A number, optionally followed by one of the `s`, `m`, `h`, `d` or `w` time units.
+    pub scale: String,
+}
+
+impl Decay {
+    /// Returns the origin of the decay, `None` meaning the time of the search.
+    pub fn origin(&self) -> Result<Option<f64>, CriterionError> {
+        if self.origin == "now" {
+            return Ok(None);
+        }
+        match self.origin.parse::<f64>().ok().or_else(|| crate::parse_datetime(&self.origin)) {
+            Some(origin) if origin.is_finite() => Ok(Some(origin)),
+            _ => Err(self.invalid(format!(
+                "The origin must be `now`, a number or an RFC 3339 datetime, found `{}`.",
+                self.origin
+            ))),
+        }
+    }
+
+    /// Returns the scale of the decay, a number of seconds if a time unit was specified.
+    pub fn scale(&self) -> Result<f64, CriterionError> {
+        let (number, unit) = match self.scale.char_indices().last() {
+            Some((i, 's')) => (&self.scale[..i], 1.0),
+            Some((i, 'm')) => (&self.scale[..i], 60.0),
+            Some((i, 'h')) => (&self.scale[..i], 60.0 * 60.0),
+            Some((i, 'd')) => (&self.scale[..i], 24.0 * 60.0 * 60.0),
+            Some((i, 'w')) => (&self.scale[..i], 7.0 * 24.0 * 60.0 * 60.0),
+            _ => (self.scale.as_str(), 1.0),
+        };
+        match number.parse::<f64>().map(|number| number * unit) {
+            Ok(scale) if scale.is_finite() && scale > 0.0 => Ok(scale),
+            _ => Err(self.invalid(format!(
+                "The scale must be a positive number optionally followed by a time unit (`s`, `m`, `h`, `d` or `w`), found `{}`.",
+                self.scale
+            ))),
+        }
+    }
+
+    fn invalid(&self, reason: String) -> CriterionError {
+        CriterionError::InvalidFunction { rule: Criterion::Decay(self.clone()).to_string(), reason }
+    }
+}
+
+/// A `boost(field, log)` ranking rule.
+///
+/// The score of a document is its value scaled by the modifier,
+/// relative to the highest value of the field scaled the same way.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Boost {
+    pub field: String,
+    pub modifier: BoostModifier,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BoostModifier {
+    #[default]
+    Linear,
+    Log,
+    Sqrt,
+}
+
+impl BoostModifier {
+    /// Applies the modifier to a value, negative values being considered as zero.
+    pub fn apply(&self, value: f64) -> f64 {
+        let value = value.max(0.0);
+        match self {
+            BoostModifier::Linear => value,
+            BoostModifier::Log => value.ln_1p(),
+            BoostModifier::Sqrt => value.sqrt(),
+        }
+    }
+}
+
+impl fmt::Display for BoostModifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoostModifier::Linear => f.write_str("linear"),
+            BoostModifier::Log => f.write_str("log"),
+            BoostModifier::Sqrt => f.write_str("sqrt"),
+        }
+    }
+}
+
+/// Parses the `decay(...)` and `boost(...)` ranking rules,
+/// returns `None` if the text is not one of these functions.
+fn parse_function(text: &str) -> Option<Result<Criterion, CriterionError>> {
+    let (name, arguments) = text.strip_suffix(')')?.split_once('(')?;
+    let invalid = |reason: &str| CriterionError::InvalidFunction {
+        rule: text.to_string(),
+        reason: reason.to_string(),
+    };
+
+    let mut arguments = arguments.split(',').map(str::trim);
+    let field = arguments.next().unwrap_or_default().to_string();
+    let criterion = match name.trim() {
+        "decay" => {
+            let mut decay = Decay { field, origin: "now".to_string(), scale: String::new() };
+            for argument in arguments {
+                match argument.split_once('=').map(|(k, v)| (k.trim(), v.trim())) {
+                    Some(("origin", origin)) => decay.origin = origin.to_string(),
+                    Some(("scale", scale)) => decay.scale = scale.to_string(),
+                    _ => {
+                        return Some(Err(invalid(&format!(
+                            "Expected `origin=<value>` or `scale=<value>`, found `{argument}`."
+                        ))))
+                    }
+                }
+            }
+            if decay.scale.is_empty() {
+                return Some(Err(invalid("The `scale` parameter is missing.")));
+            }
+            if let Err(error) = decay.origin().and_then(|_| decay.scale()) {
+                return Some(Err(error));
+            }
+            Criterion::Decay(decay)
+        }
+        "boost" => {
+            let modifier = match arguments.next() {
+                None | Some("linear") => BoostModifier::Linear,
+                Some("log") => BoostModifier::Log,
+                Some("sqrt") => BoostModifier::Sqrt,
+                Some(modifier) => {
+                    return Some(Err(invalid(&format!(
+                        "The modifier must be one of `linear`, `log` or `sqrt`, found `{modifier}`."
+                    ))))
+                }
+            };
+            if arguments.next().is_some() {
+                return Some(Err(invalid("Expected at most a field and a modifier.")));
+            }
+            Criterion::Boost(Boost { field, modifier })
+        }
+        _ => return None,
+    };
+
+    let field = criterion.field_name().unwrap_or_default();
+    if field.is_empty() {
+        Some(Err(invalid("The field is missing.")))
+    } else if crate::error::is_reserved_keyword(field) {
+        Some(Err(CriterionError::ReservedName { name: field.to_string() }))
+    } else {
+        Some(Ok(criterion))
+    }
+}
+
 impl FromStr for Criterion {
     type Err = CriterionError;
 
@@ -68,12 +230,18 @@ impl FromStr for Criterion {
             "attribute" => Ok(Criterion::Attribute),
             "sort" => Ok(Criterion::Sort),
             "exactness" => Ok(Criterion::Exactness),
-            text => match AscDesc::from_str(text)? {
-                AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
-                AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
-                AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
-                    Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
-                }
+            text => match parse_function(text) {
+                Some(criterion) => criterion,
+                None => match AscDesc::from_str(text)? {
+                    AscDesc::Asc(Member::Field(field)) => Ok(Criterion::Asc(field)),
+                    AscDesc::Desc(Member::Field(field)) => Ok(Criterion::Desc(field)),
+                    AscDesc::Asc(Member::Geo(_)) | AscDesc::Desc(Member::Geo(_)) => {
+                        Err(CriterionError::ReservedNameForSort { name: "_geoPoint".to_string() })?
+                    }
+                    AscDesc::Asc(Member::Random(_)) | AscDesc::Desc(Member::Random(_)) => {
+                        Err(CriterionError::ReservedNameForSort { name: "_random".to_string() })?
+                    }
+                },
             },
         }
     }
@@ -103,6 +271,10 @@ impl fmt::Display for Criterion {
             Exactness => f.write_str("exactness"),
             Asc(attr) => write!(f, "{}:asc", attr),
             Desc(attr) => write!(f, "{}:desc", attr),
+            Decay(decay) => {
+                write!(f, "decay({}, origin={}, scale={})", decay.field, decay.origin, decay.scale)
+            }
+            Boost(boost) => write!(f, "boost({}, {})", boost.field, boost.modifier),
         }
     }
 }
@@ -129,6 +301,35 @@ mod tests {
             ("truc:machin:desc", Criterion::Desc(S("truc:machin"))),
             ("hello-world!:desc", Criterion::Desc(S("hello-world!"))),
             ("it's spacy over there:asc", Criterion::Asc(S("it's spacy over there"))),
+            (
+                "decay(publishedAt, origin=now, scale=30d)",
+                Criterion::Decay(Decay {
+                    field: S("publishedAt"),
+                    origin: S("now"),
+                    scale: S("30d"),
+                }),
+            ),
+            (
+                "decay(price,scale=2.5,origin=100)",
+                Criterion::Decay(Decay { field: S("price"), origin: S("100"), scale: S("2.5") }),
+            ),
+            (
+                "decay(date, origin=2024-01-01T00:00:00Z, scale=1w)",
+                Criterion::Decay(Decay {
+                    field: S("date"),
+                    origin: S("2024-01-01T00:00:00Z"),
+                    scale: S("1w"),
+                }),
+            ),
+            (
+                "boost(popularity)",
+                Criterion::Boost(Boost { field: S("popularity"), modifier: BoostModifier::Linear }),
+            ),
+            (
+                "boost(popularity, log)",
+                Criterion::Boost(Boost { field: S("popularity"), modifier: BoostModifier::Log }),
+            ),
+            ("decay(price):asc", Criterion::Asc(S("decay(price)"))),
         ];
 
         for (input, expected) in valid_criteria {
@@ -141,6 +342,8 @@ mod tests {
                 res
             );
             assert_eq!(res.unwrap(), expected);
+            // the criteria are displayed in a form that can be parsed back
+            assert_eq!(expected.to_string().parse::<Criterion>().unwrap(), expected);
         }
 
         let invalid_criteria = [
@@ -164,6 +367,48 @@ mod tests {
                 "_geoBoundingBox([42, 75], [75, 59]):asc",
                 ReservedNameForFilter { name: S("_geoBoundingBox") },
             ),
+            (
+                "decay(price)",
+                InvalidFunction {
+                    rule: S("decay(price)"),
+                    reason: S("The `scale` parameter is missing."),
+                },
+            ),
+            (
+                "decay(price, scale=10, offset=2)",
+                InvalidFunction {
+                    rule: S("decay(price, scale=10, offset=2)"),
+                    reason: S("Expected `origin=<value>` or `scale=<value>`, found `offset=2`."),
+                },
+            ),
+            (
+                "decay(publishedAt, origin=yesterday, scale=1d)",
+                InvalidFunction {
+                    rule: S("decay(publishedAt, origin=yesterday, scale=1d)"),
+                    reason: S("The origin must be `now`, a number or an RFC 3339 datetime, found `yesterday`."),
+                },
+            ),
+            (
+                "decay(publishedAt, scale=0d)",
+                InvalidFunction {
+                    rule: S("decay(publishedAt, origin=now, scale=0d)"),
+                    reason: S("The scale must be a positive number optionally followed by a time unit (`s`, `m`, `h`, `d` or `w`), found `0d`."),
+                },
+            ),
+            (
+                "boost(popularity, square)",
+                InvalidFunction {
+                    rule: S("boost(popularity, square)"),
+                    reason: S("The modifier must be one of `linear`, `log` or `sqrt`, found `square`."),
+                },
+            ),
+            (
+                "boost( , log)",
+                InvalidFunction { rule: S("boost( , log)"), reason: S("The field is missing.") },
+            ),
+            ("boost(_geo)", ReservedName { name: S("_geo") }),
+            ("_random(42):asc", ReservedNameForSort { name: S("_random") }),
+            ("_random:desc", ReservedNameForSort { name: S("_random") }),
         ];
 
         for (input, expected) in invalid_criteria {
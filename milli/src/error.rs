@@ -14,7 +14,8 @@ use crate::thread_pool_no_abort::PanicCatched;
 use crate::{CriterionError, DocumentId, FieldId, Object, SortError};
 
 pub fn is_reserved_keyword(keyword: &str) -> bool {
-    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox"].contains(&keyword)
+    ["_geo", "_geoDistance", "_geoPoint", "_geoRadius", "_geoBoundingBox", "_random"]
+        .contains(&keyword)
 }
 
 #[derive(Error, Debug)]
@@ -67,6 +68,8 @@ pub enum InternalError {
     InvalidMatchingWords,
     #[error(transparent)]
     ArroyError(#[from] arroy::Error),
+    #[error("While upgrading the vector store: {0}")]
+    ArroyUpgradeError(#[from] arroy_v04::Error),
     #[error(transparent)]
     VectorEmbeddingError(#[from] crate::vector::Error),
 }
@@ -114,6 +117,11 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         invalid_facets_name: BTreeSet<String>,
         valid_facets_name: BTreeSet<String>,
     },
+    #[error("Invalid facet ranges, {}", format_invalid_filter_distribution(.invalid_facets_name, .valid_facets_name))]
+    InvalidFacetRanges {
+        invalid_facets_name: BTreeSet<String>,
+        valid_facets_name: BTreeSet<String>,
+    },
     #[error(transparent)]
     InvalidGeoField(#[from] GeoError),
     #[error("Invalid vector dimensions: expected: `{}`, found: `{}`.", .expected, .found)]
@@ -122,6 +130,10 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidVectorsMapType { document_id: String, value: Value },
     #[error("Bad embedder configuration in the document with id: `{document_id}`. {error}")]
     InvalidVectorsEmbedderConf { document_id: String, error: deserr::errors::JsonError },
+    #[error("Invalid operator in the document with id: `{document_id}`. {message}")]
+    InvalidDocumentFieldOperator { document_id: String, message: String },
+    #[error("The `_expectedVersion` field in the document with id: `{document_id}` is invalid. Was expecting a positive integer, but instead got `{value}`.")]
+    InvalidDocumentExpectedVersion { document_id: Value, value: Value },
     #[error("{0}")]
     InvalidFilter(String),
     #[error("Invalid type for filter subexpression: expected: {}, found: {1}.", .0.join(", "))]
@@ -137,6 +149,21 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidSortableAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not sortable and thus, cannot be used in the ranking rules of a search. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured sortable attributes.".to_string(),
+            false => format!("Available sortable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidRankingRuleSortableAttribute {
+        field: String,
+        valid_fields: BTreeSet<String>,
+        hidden_fields: bool,
+    },
     #[error("Attribute `{}` is not filterable and thus, cannot be used as distinct attribute. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -148,6 +175,17 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
         }
     )]
     InvalidDistinctAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
+    #[error("Attribute `{}` is not filterable and thus, cannot be used to group the results. {}",
+        .field,
+        match .valid_fields.is_empty() {
+            true => "This index does not have configured filterable attributes.".to_string(),
+            false => format!("Available filterable attributes are: `{}{}`.",
+                    valid_fields.iter().map(AsRef::as_ref).collect::<Vec<&str>>().join(", "),
+                    .hidden_fields.then_some(", <..hidden-attributes>").unwrap_or(""),
+                ),
+        }
+    )]
+    InvalidGroupByAttribute { field: String, valid_fields: BTreeSet<String>, hidden_fields: bool },
     #[error("Attribute `{}` is not facet-searchable. {}",
         .field,
         match .valid_fields.is_empty() {
@@ -236,6 +274,8 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     },
     #[error("`.embedders.{embedder_name}.model`: Invalid model `{model}` for OpenAI. Supported models: {:?}", crate::vector::openai::EmbeddingModel::supported_models())]
     InvalidOpenAiModel { embedder_name: String, model: String },
+    #[error("`.embedders.{embedder_name}.{field}`: Invalid path `{path}`. The paths of the ONNX models and tokenizers must be relative to the models directory and must not contain `..`")]
+    InvalidOnnxModelPath { embedder_name: String, field: &'static str, path: String },
     #[error("`.embedders.{embedder_name}`: Missing field `{field}` (note: this field is mandatory for source {source_})")]
     MissingFieldForSource {
         field: &'static str,
@@ -260,8 +300,43 @@ only composed of alphanumeric characters (a-z A-Z 0-9), hyphens (-) and undersco
     InvalidSettingsDimensions { embedder_name: String },
     #[error("`.embedders.{embedder_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
     InvalidSettingsDocumentTemplateMaxBytes { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.quantization`: Cannot change the quantization of a quantized embedder, the full-precision embeddings are lost.\n - Hint: Remove the embedder and add it back to index the full-precision embeddings again.")]
+    InvalidChangeQuantization { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.quantization`: The `{distance}` distance does not support binary quantization.")]
+    InvalidBinaryQuantizationDistance { embedder_name: String, distance: String },
+    #[error("`.embedders.{embedder_name}.chunking.size`: `size` cannot be zero")]
+    InvalidSettingsChunkingSize { embedder_name: String },
+    #[error("`.embedders.{embedder_name}.chunking.overlap`: `overlap` must be smaller than `size`, got an overlap of {overlap} for a size of {size}")]
+    InvalidSettingsChunkingOverlap { embedder_name: String, overlap: usize, size: usize },
     #[error("`.embedders.{embedder_name}.url`: could not parse `{url}`: {inner_error}")]
     InvalidUrl { embedder_name: String, inner_error: url::ParseError, url: String },
+    #[error("Cannot find reranker with name `{0}`.")]
+    InvalidReranker(String),
+    #[error("`.rerankers.{reranker_name}`: Field `{field}` unavailable for source `{source_}`. Available fields: {}",
+        allowed_fields_for_source
+         .iter()
+         .map(|accepted| format!("`{}`", accepted))
+         .collect::<Vec<String>>()
+         .join(", ")
+    )]
+    InvalidFieldForRerankerSource {
+        reranker_name: String,
+        source_: crate::vector::settings::RerankerSource,
+        field: &'static str,
+        allowed_fields_for_source: &'static [&'static str],
+    },
+    #[error("`.rerankers.{reranker_name}`: Missing field `{field}` (note: this field is mandatory for source {source_})")]
+    MissingFieldForRerankerSource {
+        field: &'static str,
+        source_: crate::vector::settings::RerankerSource,
+        reranker_name: String,
+    },
+    #[error("`.rerankers.{0}.documentTemplate`: Invalid template: {1}.")]
+    InvalidPromptForReranker(String, crate::prompt::error::NewPromptError),
+    #[error("`.rerankers.{reranker_name}.documentTemplateMaxBytes`: `documentTemplateMaxBytes` cannot be zero")]
+    InvalidSettingsRerankerDocumentTemplateMaxBytes { reranker_name: String },
+    #[error("`.rerankers.{reranker_name}.url`: could not parse `{url}`: {inner_error}")]
+    InvalidRerankerUrl { reranker_name: String, inner_error: url::ParseError, url: String },
     #[error("Document editions cannot modify a document's primary key")]
     DocumentEditionCannotModifyPrimaryKey,
     #[error("Document editions must keep documents as objects")]
@@ -297,6 +372,7 @@ impl From<arroy::Error> for Error {
             | arroy::Error::InvalidItemAppend
             | arroy::Error::UnmatchingDistance { .. }
             | arroy::Error::NeedBuild(_)
+            | arroy::Error::BuildCancelled
             | arroy::Error::MissingKey { .. }
             | arroy::Error::MissingMetadata(_) => {
                 Error::InternalError(InternalError::ArroyError(value))
@@ -305,6 +381,16 @@ impl From<arroy::Error> for Error {
     }
 }
 
+impl From<arroy_v04::Error> for Error {
+    fn from(value: arroy_v04::Error) -> Self {
+        match value {
+            arroy_v04::Error::Heed(heed) => heed.into(),
+            arroy_v04::Error::Io(io) => io.into(),
+            value => Error::InternalError(InternalError::ArroyUpgradeError(value)),
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum GeoError {
     #[error("The `_geo` field in the document with the id: `{document_id}` is not an object. Was expecting an object with the `_geo.lat` and `_geo.lng` fields but instead got `{value}`.")]
@@ -2,10 +2,12 @@ use std::borrow::Cow;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
 use std::fs::File;
+use std::ops::Bound;
 use std::path::Path;
 
 use heed::types::*;
 use heed::{CompactionOption, Database, RoTxn, RwTxn, Unspecified};
+use rand::SeedableRng;
 use roaring::RoaringBitmap;
 use rstar::RTree;
 use serde::{Deserialize, Serialize};
@@ -20,8 +22,11 @@ use crate::heed_codec::facet::{
 use crate::heed_codec::{BEU16StrCodec, FstSetCodec, StrBEU16Codec, StrRefCodec};
 use crate::order_by_map::OrderByMap;
 use crate::proximity::ProximityPrecision;
+use crate::search::facet::facet_min_value;
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
-use crate::vector::{Embedding, EmbeddingConfig};
+use crate::vector::rerank::RerankerConfig;
+use crate::vector::{ArroyWrapper, Distance, Embedding, EmbeddingConfig};
 use crate::{
     default_criteria, CboRoaringBitmapCodec, Criterion, DocumentId, ExternalDocumentsIds,
     FacetDistribution, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldIdWordCountCodec,
@@ -32,6 +37,15 @@ use crate::{
 pub const DEFAULT_MIN_WORD_LEN_ONE_TYPO: u8 = 5;
 pub const DEFAULT_MIN_WORD_LEN_TWO_TYPOS: u8 = 9;
 
+/// The field of the documents returned with their version.
+pub const RESERVED_VERSION_FIELD_NAME: &str = "_version";
+/// The field of the documents to add that skips them when they aren't at this version anymore.
+pub const RESERVED_EXPECTED_VERSION_FIELD_NAME: &str = "_expectedVersion";
+
+/// The version of the format of the arroy trees, the indexes without a version
+/// have their trees written by arroy 0.4.
+pub const VECTOR_STORE_VERSION: u32 = 1;
+
 pub mod main_key {
     pub const CRITERIA_KEY: &str = "criteria";
     pub const DISPLAYED_FIELDS_KEY: &str = "displayed-fields";
@@ -40,6 +54,8 @@ pub mod main_key {
     pub const HIDDEN_FACETED_FIELDS_KEY: &str = "hidden-faceted-fields";
     pub const FILTERABLE_FIELDS_KEY: &str = "filterable-fields";
     pub const SORTABLE_FIELDS_KEY: &str = "sortable-fields";
+    pub const DATETIME_FIELDS_KEY: &str = "datetime-fields";
+    pub const EXPIRY_FIELD_KEY: &str = "expiry-field";
     pub const FIELD_DISTRIBUTION_KEY: &str = "fields-distribution";
     pub const FIELDS_IDS_MAP_KEY: &str = "fields-ids-map";
     pub const FIELDIDS_WEIGHTS_MAP_KEY: &str = "fieldids-weights-map";
@@ -68,8 +84,10 @@ pub mod main_key {
     pub const PAGINATION_MAX_TOTAL_HITS: &str = "pagination-max-total-hits";
     pub const PROXIMITY_PRECISION: &str = "proximity-precision";
     pub const EMBEDDING_CONFIGS: &str = "embedding_configs";
+    pub const RERANKER_CONFIGS: &str = "reranker_configs";
     pub const SEARCH_CUTOFF: &str = "search_cutoff";
     pub const LOCALIZED_ATTRIBUTES_RULES: &str = "localized_attributes_rules";
+    pub const VECTOR_STORE_VERSION: &str = "vector-store-version";
 }
 
 pub mod db_name {
@@ -96,8 +114,10 @@ pub mod db_name {
     pub const FIELD_ID_DOCID_FACET_F64S: &str = "field-id-docid-facet-f64s";
     pub const FIELD_ID_DOCID_FACET_STRINGS: &str = "field-id-docid-facet-strings";
     pub const VECTOR_EMBEDDER_CATEGORY_ID: &str = "vector-embedder-category-id";
+    pub const VECTOR_EMBEDDER_DISTANCE: &str = "vector-embedder-distance";
     pub const VECTOR_ARROY: &str = "vector-arroy";
     pub const DOCUMENTS: &str = "documents";
+    pub const DOCUMENTS_VERSIONS: &str = "documents-versions";
 }
 
 #[derive(Clone)]
@@ -161,11 +181,15 @@ pub struct Index {
 
     /// Maps an embedder name to its id in the arroy store.
     pub embedder_category_id: Database<Str, U8>,
+    /// Maps an embedder name to the distance of its arroy trees, cosine if missing.
+    pub embedder_distance: Database<Str, SerdeJson<Distance>>,
     /// Vector store based on arroy™.
-    pub vector_arroy: arroy::Database<arroy::distances::Angular>,
+    pub vector_arroy: arroy::Database<arroy::distances::Cosine>,
 
     /// Maps the document id to the document as an obkv store.
     pub(crate) documents: Database<BEU32, ObkvCodec>,
+    /// Maps the document id to its version, incremented on every write of the document.
+    pub documents_versions: Database<BEU32, BEU64>,
 }
 
 impl Index {
@@ -177,7 +201,7 @@ impl Index {
     ) -> Result<Index> {
         use db_name::*;
 
-        options.max_dbs(25);
+        options.max_dbs(26);
 
         let env = unsafe { options.open(path) }?;
         let mut wtxn = env.write_txn()?;
@@ -218,14 +242,16 @@ impl Index {
         // vector stuff
         let embedder_category_id =
             env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_CATEGORY_ID))?;
+        let embedder_distance = env.create_database(&mut wtxn, Some(VECTOR_EMBEDDER_DISTANCE))?;
         let vector_arroy = env.create_database(&mut wtxn, Some(VECTOR_ARROY))?;
 
         let documents = env.create_database(&mut wtxn, Some(DOCUMENTS))?;
+        let documents_versions = env.create_database(&mut wtxn, Some(DOCUMENTS_VERSIONS))?;
         wtxn.commit()?;
 
         Index::set_creation_dates(&env, main, created_at, updated_at)?;
 
-        Ok(Index {
+        let index = Index {
             env,
             main,
             external_documents_ids,
@@ -250,8 +276,13 @@ impl Index {
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id,
+            embedder_distance,
             documents,
-        })
+            documents_versions,
+        };
+        index.upgrade_vector_store()?;
+
+        Ok(index)
     }
 
     pub fn new<P: AsRef<Path>>(options: heed::EnvOpenOptions, path: P) -> Result<Index> {
@@ -276,6 +307,34 @@ impl Index {
         Ok(())
     }
 
+    /// Rewrites the arroy trees of the embedders in the format of the current version of arroy,
+    /// when they were written by a previous version.
+    pub(crate) fn upgrade_vector_store(&self) -> Result<()> {
+        let main = self.main.remap_types::<Str, BEU32>();
+        let rtxn = self.read_txn()?;
+        if main.get(&rtxn, main_key::VECTOR_STORE_VERSION)? == Some(VECTOR_STORE_VERSION) {
+            return Ok(());
+        }
+        drop(rtxn);
+
+        let mut wtxn = self.write_txn()?;
+        let embedders: Vec<String> = self
+            .embedder_category_id
+            .iter(&wtxn)?
+            .map(|row| row.map(|(name, _)| name.to_owned()))
+            .collect::<heed::Result<_>>()?;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        for name in embedders {
+            if let Some(arroy) = self.arroy_wrapper(&wtxn, &name)? {
+                tracing::info!(embedder = name, "upgrading the arroy trees of the embedder");
+                arroy.upgrade_from_arroy_v0_4(&mut wtxn, &mut rng)?;
+            }
+        }
+        main.put(&mut wtxn, main_key::VECTOR_STORE_VERSION, &VECTOR_STORE_VERSION)?;
+        wtxn.commit()?;
+        Ok(())
+    }
+
     /// Create a write transaction to be able to write into the index.
     pub fn write_txn(&self) -> heed::Result<RwTxn<'_>> {
         self.env.write_txn()
@@ -859,6 +918,120 @@ impl Index {
         Ok(fields.into_iter().filter_map(|name| fields_ids_map.id(&name)).collect())
     }
 
+    /* datetime fields */
+
+    /// Writes the datetime fields names in the database.
+    pub(crate) fn put_datetime_fields(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        fields: &HashSet<String>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<_>>().put(
+            wtxn,
+            main_key::DATETIME_FIELDS_KEY,
+            fields,
+        )
+    }
+
+    /// Deletes the datetime fields names in the database.
+    pub(crate) fn delete_datetime_fields(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::DATETIME_FIELDS_KEY)
+    }
+
+    /// Returns the datetime fields names.
+    ///
+    /// The RFC 3339 strings of these fields are indexed as numbers, which makes them filterable and
+    /// sortable chronologically.
+    pub fn datetime_fields(&self, rtxn: &RoTxn<'_>) -> heed::Result<HashSet<String>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<_>>()
+            .get(rtxn, main_key::DATETIME_FIELDS_KEY)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the names of all the fields whose RFC 3339 strings are indexed as numbers,
+    /// the datetime fields and the expiry field.
+    pub fn indexed_datetime_fields(&self, rtxn: &RoTxn<'_>) -> heed::Result<HashSet<String>> {
+        let mut fields = self.datetime_fields(rtxn)?;
+        if let Some(expiry_field) = self.expiry_field(rtxn)? {
+            fields.insert(expiry_field.to_owned());
+        }
+        Ok(fields)
+    }
+
+    /// Identical to `indexed_datetime_fields`, but returns ids instead.
+    pub fn datetime_fields_ids(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<FieldId>> {
+        let fields = self.indexed_datetime_fields(rtxn)?;
+        let fields_ids_map = self.fields_ids_map(rtxn)?;
+        Ok(fields.into_iter().filter_map(|name| fields_ids_map.id(&name)).collect())
+    }
+
+    /* expiry field */
+
+    /// Writes the name of the field holding the expiry date of the documents.
+    pub(crate) fn put_expiry_field(&self, wtxn: &mut RwTxn<'_>, field: &str) -> heed::Result<()> {
+        self.main.remap_types::<Str, Str>().put(wtxn, main_key::EXPIRY_FIELD_KEY, field)
+    }
+
+    /// Deletes the expiry field name in the database.
+    pub(crate) fn delete_expiry_field(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    /// Returns the name of the field holding the expiry date of the documents.
+    ///
+    /// The expiry dates are RFC 3339 strings or numbers of seconds since the Unix epoch.
+    pub fn expiry_field<'t>(&self, rtxn: &'t RoTxn<'_>) -> heed::Result<Option<&'t str>> {
+        self.main.remap_types::<Str, Str>().get(rtxn, main_key::EXPIRY_FIELD_KEY)
+    }
+
+    /// Returns the ids of the documents whose expiry date is at or before `now`.
+    ///
+    /// The documents without an expiry date, or with one that isn't a date, never expire.
+    pub fn expired_documents_ids(
+        &self,
+        rtxn: &RoTxn<'_>,
+        now: time::OffsetDateTime,
+    ) -> Result<RoaringBitmap> {
+        let Some(expiry_field) = self.expiry_field(rtxn)? else {
+            return Ok(RoaringBitmap::new());
+        };
+        let Some(field_id) = self.fields_ids_map(rtxn)?.id(expiry_field) else {
+            return Ok(RoaringBitmap::new());
+        };
+
+        let now = now.unix_timestamp_nanos() as f64 / 1_000_000_000.0;
+        let mut docids = RoaringBitmap::new();
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            rtxn,
+            self.facet_id_f64_docids,
+            field_id,
+            &Bound::Unbounded,
+            &Bound::Included(now),
+            None,
+            &mut docids,
+        )?;
+        Ok(docids)
+    }
+
+    /// Returns the earliest expiry date of the documents, if any document can expire.
+    pub fn next_expiry(&self, rtxn: &RoTxn<'_>) -> Result<Option<time::OffsetDateTime>> {
+        let Some(expiry_field) = self.expiry_field(rtxn)? else {
+            return Ok(None);
+        };
+        let Some(field_id) = self.fields_ids_map(rtxn)?.id(expiry_field) else {
+            return Ok(None);
+        };
+
+        let documents_ids = self.documents_ids(rtxn)?;
+        let Some(seconds) = facet_min_value(self, rtxn, field_id, documents_ids)? else {
+            return Ok(None);
+        };
+        let nanos = (seconds * 1_000_000_000.0) as i128;
+        Ok(time::OffsetDateTime::from_unix_timestamp_nanos(nanos).ok())
+    }
+
     /* faceted fields */
 
     /// Writes the faceted fields in the database.
@@ -902,14 +1075,17 @@ impl Index {
 
     /// Returns the user defined faceted fields names.
     ///
-    /// The user faceted fields are the union of all the filterable, sortable, distinct, and Asc/Desc fields.
+    /// The user faceted fields are the union of all the filterable, sortable, distinct, expiry, and ranking rules fields.
     pub fn user_defined_faceted_fields(&self, rtxn: &RoTxn<'_>) -> Result<HashSet<String>> {
         let filterable_fields = self.filterable_fields(rtxn)?;
         let sortable_fields = self.sortable_fields(rtxn)?;
         let distinct_field = self.distinct_field(rtxn)?;
+        let expiry_field = self.expiry_field(rtxn)?;
         let asc_desc_fields =
             self.criteria(rtxn)?.into_iter().filter_map(|criterion| match criterion {
                 Criterion::Asc(field) | Criterion::Desc(field) => Some(field),
+                Criterion::Decay(crate::Decay { field, .. })
+                | Criterion::Boost(crate::Boost { field, .. }) => Some(field),
                 _otherwise => None,
             });
 
@@ -919,6 +1095,9 @@ impl Index {
         if let Some(field) = distinct_field {
             faceted_fields.insert(field.to_owned());
         }
+        if let Some(field) = expiry_field {
+            faceted_fields.insert(field.to_owned());
+        }
 
         Ok(faceted_fields)
     }
@@ -1283,6 +1462,21 @@ impl Index {
         self.iter_documents(rtxn, self.documents_ids(rtxn)?)
     }
 
+    /// Returns the version of the document, incremented on every write of the document.
+    ///
+    /// The missing documents are at version `0`, and the documents that weren't written
+    /// since the versions were introduced are at version `1`.
+    pub fn document_version(&self, rtxn: &RoTxn<'_>, docid: DocumentId) -> heed::Result<u64> {
+        match self.documents_versions.get(rtxn, &docid)? {
+            Some(version) => Ok(version),
+            None => {
+                let exists =
+                    self.documents.remap_data_type::<DecodeIgnore>().get(rtxn, &docid)?.is_some();
+                Ok(exists as u64)
+            }
+        }
+    }
+
     pub fn external_id_of<'a, 't: 'a>(
         &'a self,
         rtxn: &'t RoTxn<'t>,
@@ -1610,20 +1804,46 @@ impl Index {
             .unwrap_or_default())
     }
 
-    pub fn arroy_readers<'a>(
-        &'a self,
-        rtxn: &'a RoTxn<'a>,
-        embedder_id: u8,
-    ) -> impl Iterator<Item = Result<arroy::Reader<'a, arroy::distances::Angular>>> + 'a {
-        crate::vector::arroy_db_range_for_embedder(embedder_id).map_while(move |k| {
-            arroy::Reader::open(rtxn, k, self.vector_arroy)
-                .map(Some)
-                .or_else(|e| match e {
-                    arroy::Error::MissingMetadata(_) => Ok(None),
-                    e => Err(e.into()),
-                })
-                .transpose()
-        })
+    pub(crate) fn put_reranker_configs(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        configs: &BTreeMap<String, RerankerConfig>,
+    ) -> heed::Result<()> {
+        self.main.remap_types::<Str, SerdeJson<BTreeMap<String, RerankerConfig>>>().put(
+            wtxn,
+            main_key::RERANKER_CONFIGS,
+            configs,
+        )
+    }
+
+    pub(crate) fn delete_reranker_configs(&self, wtxn: &mut RwTxn<'_>) -> heed::Result<bool> {
+        self.main.remap_key_type::<Str>().delete(wtxn, main_key::RERANKER_CONFIGS)
+    }
+
+    pub fn reranker_configs(&self, rtxn: &RoTxn<'_>) -> Result<BTreeMap<String, RerankerConfig>> {
+        Ok(self
+            .main
+            .remap_types::<Str, SerdeJson<BTreeMap<String, RerankerConfig>>>()
+            .get(rtxn, main_key::RERANKER_CONFIGS)?
+            .unwrap_or_default())
+    }
+
+    /// Returns the arroy trees of the embedder, typed with their distance and quantization.
+    pub fn arroy_wrapper(
+        &self,
+        rtxn: &RoTxn<'_>,
+        embedder_name: &str,
+    ) -> Result<Option<ArroyWrapper>> {
+        let Some(embedder_index) = self.embedder_category_id.get(rtxn, embedder_name)? else {
+            return Ok(None);
+        };
+        let distance = self.embedder_distance.get(rtxn, embedder_name)?.unwrap_or_default();
+        let quantization = self
+            .embedding_configs(rtxn)?
+            .iter()
+            .find(|config| config.name == embedder_name)
+            .and_then(|config| config.config.quantization);
+        Ok(Some(ArroyWrapper::new(self.vector_arroy, embedder_index, distance, quantization)))
     }
 
     pub(crate) fn put_search_cutoff(&self, wtxn: &mut RwTxn<'_>, cutoff: u64) -> heed::Result<()> {
@@ -1644,31 +1864,17 @@ impl Index {
         docid: DocumentId,
     ) -> Result<BTreeMap<String, Vec<Embedding>>> {
         let mut res = BTreeMap::new();
+        let embedding_configs = self.embedding_configs(rtxn)?;
         for row in self.embedder_category_id.iter(rtxn)? {
-            let (embedder_name, embedder_id) = row?;
-            let embedder_id = (embedder_id as u16) << 8;
-            let mut embeddings = Vec::new();
-            'vectors: for i in 0..=u8::MAX {
-                let reader = arroy::Reader::open(rtxn, embedder_id | (i as u16), self.vector_arroy)
-                    .map(Some)
-                    .or_else(|e| match e {
-                        arroy::Error::MissingMetadata(_) => Ok(None),
-                        e => Err(e),
-                    })
-                    .transpose();
-
-                let Some(reader) = reader else {
-                    break 'vectors;
-                };
-
-                let embedding = reader?.item_vector(rtxn, docid)?;
-                if let Some(embedding) = embedding {
-                    embeddings.push(embedding)
-                } else {
-                    break 'vectors;
-                }
-            }
-
+            let (embedder_name, embedder_index) = row?;
+            let distance = self.embedder_distance.get(rtxn, embedder_name)?.unwrap_or_default();
+            let quantization = embedding_configs
+                .iter()
+                .find(|config| config.name == embedder_name)
+                .and_then(|config| config.config.quantization);
+            let wrapper =
+                ArroyWrapper::new(self.vector_arroy, embedder_index, distance, quantization);
+            let embeddings = wrapper.item_vectors(rtxn, docid)?;
             res.insert(embedder_name.to_owned(), embeddings);
         }
         Ok(res)
@@ -2673,8 +2879,11 @@ pub(crate) mod tests {
             candidates: _,
             document_scores: _,
             mut documents_ids,
+            cursor: _,
             degraded: _,
             used_negative_operator: _,
+            corrected_query: _,
+            groups: _,
         } = search.execute().unwrap();
         let primary_key_id = index.fields_ids_map(&rtxn).unwrap().id("primary_key").unwrap();
         documents_ids.sort_unstable();
@@ -2941,4 +3150,86 @@ pub(crate) mod tests {
             .unwrap();
         assert!(results.candidates.is_empty());
     }
+
+    #[test]
+    fn expired_documents_are_hidden() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_expiry_field(S("expiresAt"));
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "name": "doggo", "expiresAt": "2000-01-01T00:00:00Z" },
+                { "id": 1, "name": "doggo", "expiresAt": "4000-01-01T00:00:00Z" },
+                { "id": 2, "name": "doggo", "expiresAt": 946684800 },
+                { "id": 3, "name": "doggo", "expiresAt": "tomorrow" },
+                { "id": 4, "name": "doggo" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let now = time::OffsetDateTime::now_utc();
+        let expired = index.expired_documents_ids(&rtxn, now).unwrap();
+        assert_eq!(expired.into_iter().collect::<Vec<_>>(), vec![0, 2]);
+
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("doggo").execute().unwrap();
+        assert_eq!(documents_ids, vec![1, 3, 4]);
+
+        // in the year 5000 the second document is expired too
+        let later = time::macros::datetime!(5000-01-01 00:00 UTC);
+        let expired = index.expired_documents_ids(&rtxn, later).unwrap();
+        assert_eq!(expired.into_iter().collect::<Vec<_>>(), vec![0, 1, 2]);
+        let next_expiry = index.next_expiry(&rtxn).unwrap();
+        assert_eq!(next_expiry, Some(time::macros::datetime!(2000-01-01 00:00 UTC)));
+        drop(rtxn);
+
+        index.update_settings(|settings| settings.reset_expiry_field()).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        assert!(index.expired_documents_ids(&rtxn, later).unwrap().is_empty());
+        assert_eq!(index.next_expiry(&rtxn).unwrap(), None);
+        let SearchResult { documents_ids, .. } =
+            index.search(&rtxn).query("doggo").execute().unwrap();
+        assert_eq!(documents_ids, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn upgrade_arroy_trees_written_by_arroy_v0_4() {
+        use heed::types::Str;
+        use rand::SeedableRng;
+
+        use crate::index::main_key;
+
+        let index = TempIndex::new();
+
+        // an embedder whose tree was written by arroy 0.4, before the versioning of the trees
+        let mut wtxn = index.write_txn().unwrap();
+        index.embedder_category_id.put(&mut wtxn, "default", &0).unwrap();
+        let database: arroy_v04::Database<arroy_v04::distances::Angular> =
+            index.vector_arroy.remap_types();
+        let writer = arroy_v04::Writer::new(database, 0, 3);
+        writer.add_item(&mut wtxn, 0, &[1.0, 0.0, 0.0]).unwrap();
+        writer.add_item(&mut wtxn, 1, &[0.0, 1.0, 0.0]).unwrap();
+        let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+        writer.build(&mut wtxn, &mut rng, None).unwrap();
+        index
+            .main
+            .remap_key_type::<Str>()
+            .delete(&mut wtxn, main_key::VECTOR_STORE_VERSION)
+            .unwrap();
+        wtxn.commit().unwrap();
+
+        index.upgrade_vector_store().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let arroy = index.arroy_wrapper(&rtxn, "default").unwrap().unwrap();
+        let nns = arroy.nns_by_vector(&rtxn, &[0.9, 0.1, 0.0], 2, None).unwrap();
+        assert_eq!(nns.iter().map(|(item, _, _)| *item).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(arroy.item_vectors(&rtxn, 1).unwrap(), [vec![0.0, 1.0, 0.0]]);
+    }
 }
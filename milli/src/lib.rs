@@ -46,10 +46,14 @@ pub use search::new::{
 };
 use serde_json::Value;
 pub use thread_pool_no_abort::{PanicCatched, ThreadPoolNoAbort, ThreadPoolNoAbortBuilder};
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
 pub use {charabia as tokenizer, heed, rhai};
 
 pub use self::asc_desc::{AscDesc, AscDescError, Member, SortError};
-pub use self::criterion::{default_criteria, Criterion, CriterionError};
+pub use self::criterion::{
+    default_criteria, Boost, BoostModifier, Criterion, CriterionError, Decay,
+};
 pub use self::error::{
     Error, FieldIdMapMissingEntry, InternalError, SerializationError, UserError,
 };
@@ -66,10 +70,14 @@ pub use self::index::Index;
 pub use self::localized_attributes_rules::LocalizedAttributesRule;
 use self::localized_attributes_rules::LocalizedFieldIds;
 pub use self::search::facet::{FacetValueHit, SearchForFacetValues};
+pub use self::search::hybrid::HybridFusion;
+pub use self::search::recommend::Recommend;
 pub use self::search::similar::Similar;
 pub use self::search::{
-    FacetDistribution, Filter, FormatOptions, MatchBounds, MatcherBuilder, MatchingWords, OrderBy,
-    Search, SearchResult, SemanticSearch, TermsMatchingStrategy, DEFAULT_VALUES_PER_FACET,
+    FacetDistribution, FacetRange, FacetRanges, Filter, FormatOptions, InvalidSearchCursor,
+    MatchBounds, MatcherBuilder, MatchingWords, OrderBy, Search, SearchCursor,
+    SearchForSuggestions, SearchGroup, SearchResult, SemanticSearch, Suggestion,
+    TermsMatchingStrategy, DEFAULT_SUGGESTIONS_LIMIT, DEFAULT_VALUES_PER_FACET,
 };
 
 pub type Result<T> = std::result::Result<T, error::Error>;
@@ -368,6 +376,22 @@ pub fn normalize_facet(original: &str) -> String {
     CompatibilityDecompositionNormalizer.normalize_str(original.trim()).to_lowercase()
 }
 
+/// Parses an RFC 3339 datetime into the number of seconds since the Unix epoch,
+/// which is how the values of the datetime fields are stored in the facet databases.
+pub fn parse_datetime(datetime: &str) -> Option<f64> {
+    let datetime = OffsetDateTime::parse(datetime, &Rfc3339).ok()?;
+    Some(datetime.unix_timestamp_nanos() as f64 / 1_000_000_000.0)
+}
+
+/// Formats a number of seconds since the Unix epoch as an RFC 3339 datetime in UTC.
+///
+/// The precision is limited to the microsecond as the facet databases store the datetimes as `f64`s.
+pub fn format_datetime(timestamp: f64) -> Option<String> {
+    let micros = (timestamp * 1_000_000.0).round() as i128;
+    let datetime = OffsetDateTime::from_unix_timestamp_nanos(micros.checked_mul(1_000)?).ok()?;
+    datetime.format(&Rfc3339).ok()
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -445,4 +469,19 @@ mod tests {
 
         assert_eq!(&actual, expected);
     }
+
+    #[test]
+    fn test_datetime_conversion() {
+        assert_eq!(parse_datetime("2024-01-31T08:00:00Z"), Some(1706688000.0));
+        assert_eq!(parse_datetime("2024-01-31T10:00:00+02:00"), Some(1706688000.0));
+        assert_eq!(parse_datetime("1970-01-01T00:00:00.25Z"), Some(0.25));
+        assert_eq!(parse_datetime("2024-01-31"), None);
+        assert_eq!(parse_datetime("1706688000"), None);
+
+        assert_eq!(format_datetime(1706688000.0).as_deref(), Some("2024-01-31T08:00:00Z"));
+        assert_eq!(format_datetime(-0.5).as_deref(), Some("1969-12-31T23:59:59.5Z"));
+        let timestamp = parse_datetime("2024-01-31T10:00:00.123456+02:00").unwrap();
+        assert_eq!(format_datetime(timestamp).as_deref(), Some("2024-01-31T08:00:00.123456Z"));
+        assert_eq!(format_datetime(f64::MAX), None);
+    }
 }
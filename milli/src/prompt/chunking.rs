@@ -0,0 +1,114 @@
+use charabia::TokenizerBuilder;
+use deserr::Deserr;
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of chunks embedded for a single document, the rest of its text being ignored.
+///
+/// A document cannot have more embeddings per embedder.
+pub const MAX_CHUNKS: usize = u8::MAX as usize;
+
+/// Describes how the text rendered for a document is split into chunks that are embedded separately.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct Chunking {
+    /// The field of the documents to chunk instead of the rendered document template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[deserr(default)]
+    pub field: Option<String>,
+    #[serde(default)]
+    #[deserr(default)]
+    pub unit: ChunkingUnit,
+    /// The number of units of a chunk.
+    pub size: usize,
+    /// The number of units shared by two consecutive chunks.
+    #[serde(default)]
+    #[deserr(default)]
+    pub overlap: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum ChunkingUnit {
+    /// The words of the text, as tokenized for the keyword search.
+    #[default]
+    Tokens,
+    Characters,
+}
+
+impl Chunking {
+    /// Splits the text into overlapping chunks, keeping at most [`MAX_CHUNKS`] chunks.
+    ///
+    /// A chunk starts at the beginning of its first unit and ends at the end of its last unit,
+    /// so that the text between the units is kept.
+    pub fn split<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        let boundaries: Vec<(usize, usize)> = match self.unit {
+            ChunkingUnit::Tokens => {
+                let tokenizer = TokenizerBuilder::default().into_tokenizer();
+                tokenizer
+                    .tokenize(text)
+                    .filter(|token| token.is_word())
+                    .map(|token| (token.byte_start, token.byte_end))
+                    .collect()
+            }
+            ChunkingUnit::Characters => {
+                text.char_indices().map(|(start, c)| (start, start + c.len_utf8())).collect()
+            }
+        };
+
+        let size = self.size.max(1);
+        let step = size.saturating_sub(self.overlap).max(1);
+        let mut chunks = Vec::new();
+        let mut first = 0;
+        while first < boundaries.len() && chunks.len() < MAX_CHUNKS {
+            let last = (first + size).min(boundaries.len()) - 1;
+            chunks.push(&text[boundaries[first].0..boundaries[last].1]);
+            if last == boundaries.len() - 1 {
+                break;
+            }
+            first += step;
+        }
+        chunks
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Chunking, ChunkingUnit};
+
+    fn chunking(unit: ChunkingUnit, size: usize, overlap: usize) -> Chunking {
+        Chunking { field: None, unit, size, overlap }
+    }
+
+    #[test]
+    fn split_tokens() {
+        let text = "The quick brown fox, jumps over the lazy dog.";
+
+        let chunks = chunking(ChunkingUnit::Tokens, 4, 1).split(text);
+        assert_eq!(chunks, ["The quick brown fox", "fox, jumps over the", "the lazy dog"]);
+
+        let chunks = chunking(ChunkingUnit::Tokens, 20, 5).split(text);
+        assert_eq!(chunks, ["The quick brown fox, jumps over the lazy dog"]);
+
+        assert!(chunking(ChunkingUnit::Tokens, 4, 1).split(" ... ").is_empty());
+    }
+
+    #[test]
+    fn split_characters() {
+        let text = "インテル ザー ビーグル";
+
+        let chunks = chunking(ChunkingUnit::Characters, 5, 2).split(text);
+        assert_eq!(chunks, ["インテル ", "ル ザー ", "ー ビーグ", "ーグル"]);
+
+        let chunks = chunking(ChunkingUnit::Characters, 5, 0).split(text);
+        assert_eq!(chunks, ["インテル ", "ザー ビー", "グル"]);
+    }
+
+    #[test]
+    fn split_max_chunks() {
+        let text = "a".repeat(1000);
+        let chunks = chunking(ChunkingUnit::Characters, 2, 1).split(&text);
+        assert_eq!(chunks.len(), super::MAX_CHUNKS);
+    }
+}
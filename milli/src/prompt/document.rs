@@ -48,6 +48,18 @@ impl<'a> Document<'a> {
         Self(out_data)
     }
 
+    /// A document as it is stored in the index, outside of the indexing.
+    pub fn from_stored(data: obkv::KvReaderU16<'a>, inverted_field_map: &'a FieldsIdsMap) -> Self {
+        let mut out_data = BTreeMap::new();
+        for (fid, raw) in data {
+            let Some(name) = inverted_field_map.name(fid) else {
+                continue;
+            };
+            out_data.insert(name, (raw, ParsedValue::empty()));
+        }
+        Self(out_data)
+    }
+
     fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
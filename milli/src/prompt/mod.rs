@@ -1,3 +1,4 @@
+mod chunking;
 mod context;
 mod document;
 pub(crate) mod error;
@@ -9,6 +10,7 @@ use std::convert::TryFrom;
 use std::num::NonZeroUsize;
 use std::ops::Deref;
 
+pub use chunking::{Chunking, ChunkingUnit, MAX_CHUNKS};
 use error::{NewPromptError, RenderPromptError};
 
 use self::context::Context;
@@ -20,17 +22,20 @@ pub struct Prompt {
     template: liquid::Template,
     template_text: String,
     max_bytes: Option<NonZeroUsize>,
+    chunking: Option<Chunking>,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PromptData {
     pub template: String,
     pub max_bytes: Option<NonZeroUsize>,
+    #[serde(default)]
+    pub chunking: Option<Chunking>,
 }
 
 impl From<Prompt> for PromptData {
     fn from(value: Prompt) -> Self {
-        Self { template: value.template_text, max_bytes: value.max_bytes }
+        Self { template: value.template_text, max_bytes: value.max_bytes, chunking: value.chunking }
     }
 }
 
@@ -38,7 +43,9 @@ impl TryFrom<PromptData> for Prompt {
     type Error = NewPromptError;
 
     fn try_from(value: PromptData) -> Result<Self, Self::Error> {
-        Prompt::new(value.template, value.max_bytes)
+        let mut prompt = Prompt::new(value.template, value.max_bytes)?;
+        prompt.chunking = value.chunking;
+        Ok(prompt)
     }
 }
 
@@ -49,6 +56,7 @@ impl Clone for Prompt {
             template: new_template(&template_text).unwrap(),
             template_text,
             max_bytes: self.max_bytes,
+            chunking: self.chunking.clone(),
         }
     }
 }
@@ -79,13 +87,18 @@ impl Default for Prompt {
             template: default_template(),
             template_text: default_template_text().into(),
             max_bytes: Some(default_max_bytes()),
+            chunking: None,
         }
     }
 }
 
 impl Default for PromptData {
     fn default() -> Self {
-        Self { template: default_template_text().into(), max_bytes: Some(default_max_bytes()) }
+        Self {
+            template: default_template_text().into(),
+            max_bytes: Some(default_max_bytes()),
+            chunking: None,
+        }
     }
 }
 
@@ -99,6 +112,7 @@ impl Prompt {
                 .map_err(NewPromptError::cannot_parse_template)?,
             template_text: template,
             max_bytes,
+            chunking: None,
         };
 
         // render template with special object that's OK with `doc.*` and `fields.*`
@@ -109,6 +123,10 @@ impl Prompt {
         Ok(this)
     }
 
+    /// Renders the text of the document.
+    ///
+    /// When the prompt chunks the documents, the text is not truncated and is the value of the chunked field
+    /// if there is one.
     pub fn render(
         &self,
         document: obkv::KvReaderU16<'_>,
@@ -116,15 +134,75 @@ impl Prompt {
         field_id_map: &FieldsIdsMapWithMetadata,
     ) -> Result<String, RenderPromptError> {
         let document = Document::new(document, side, field_id_map);
-        let context = Context::new(&document, field_id_map);
+        self.render_document(&document, field_id_map)
+    }
+
+    /// Renders the text of a document stored in the index, as [`Self::render`] does for the documents
+    /// being indexed.
+    pub fn render_stored(
+        &self,
+        document: obkv::KvReaderU16<'_>,
+        field_id_map: &FieldsIdsMapWithMetadata,
+    ) -> Result<String, RenderPromptError> {
+        let document = Document::from_stored(document, field_id_map);
+        self.render_document(&document, field_id_map)
+    }
 
+    fn render_document(
+        &self,
+        document: &Document<'_>,
+        field_id_map: &FieldsIdsMapWithMetadata,
+    ) -> Result<String, RenderPromptError> {
+        if let Some(field) = self.chunking.as_ref().and_then(|chunking| chunking.field.as_deref()) {
+            return Ok(render_field(document, field));
+        }
+
+        let context = Context::new(document, field_id_map);
         let mut rendered =
             self.template.render(&context).map_err(RenderPromptError::missing_context)?;
-        if let Some(max_bytes) = self.max_bytes {
+        // the whole text is chunked
+        if let (None, Some(max_bytes)) = (&self.chunking, self.max_bytes) {
             truncate(&mut rendered, max_bytes.get());
         }
         Ok(rendered)
     }
+
+    /// Returns the texts to embed for the rendered text of a document,
+    /// its chunks if the prompt chunks the documents.
+    pub fn chunks<'t>(&self, rendered: &'t str) -> Vec<&'t str> {
+        match &self.chunking {
+            Some(chunking) => chunking.split(rendered),
+            None => vec![rendered],
+        }
+    }
+}
+
+/// Renders the value of a field of the document, following the dots of its name into the nested objects,
+/// the elements of an array being rendered on separate lines.
+fn render_field(document: &Document<'_>, field: &str) -> String {
+    use liquid::ObjectView;
+
+    let value = ObjectView::get(document, field).or_else(|| {
+        let mut segments = field.split('.');
+        let mut value = ObjectView::get(document, segments.next()?)?;
+        for segment in segments {
+            value = value.as_object()?.get(segment)?;
+        }
+        Some(value)
+    });
+
+    match value {
+        Some(value) => match value.as_array() {
+            Some(array) => array
+                .values()
+                .map(|value| value.to_kstr().to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+            None if value.is_nil() => String::new(),
+            None => value.to_kstr().to_string(),
+        },
+        None => String::new(),
+    }
 }
 
 fn truncate(s: &mut String, max_bytes: usize) {
@@ -17,6 +17,9 @@ pub enum ScoreDetails {
     Sort(Sort),
     Vector(Vector),
     GeoSort(GeoSort),
+    FieldScore(FieldScore),
+    ReciprocalRankFusion(ReciprocalRankFusion),
+    Rerank(Rerank),
 
     /// Returned when we don't have the time to finish applying all the subsequent ranking-rules
     Skipped,
@@ -53,6 +56,9 @@ impl ScoreDetails {
             ScoreDetails::Sort(_) => None,
             ScoreDetails::GeoSort(_) => None,
             ScoreDetails::Vector(_) => None,
+            ScoreDetails::FieldScore(details) => Some(details.rank),
+            ScoreDetails::ReciprocalRankFusion(_) => None,
+            ScoreDetails::Rerank(_) => None,
             ScoreDetails::Skipped => Some(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -101,6 +107,9 @@ impl ScoreDetails {
             ScoreDetails::Vector(vector) => {
                 RankOrValue::Score(vector.similarity.as_ref().map(|s| *s as f64).unwrap_or(0.0f64))
             }
+            ScoreDetails::FieldScore(details) => RankOrValue::Rank(details.rank),
+            ScoreDetails::ReciprocalRankFusion(details) => RankOrValue::Score(details.score),
+            ScoreDetails::Rerank(details) => RankOrValue::Score(details.ranking_score),
             ScoreDetails::Skipped => RankOrValue::Rank(Rank { rank: 0, max_rank: 1 }),
         }
     }
@@ -219,8 +228,17 @@ impl ScoreDetails {
                             if details.ascending { "asc" } else { "desc" }
                         )
                     };
-                    let value =
-                        if details.redacted { "<hidden>".into() } else { details.value.clone() };
+                    let value = if details.redacted {
+                        "<hidden>".into()
+                    } else if details.datetime {
+                        details
+                            .value
+                            .as_f64()
+                            .and_then(crate::format_datetime)
+                            .map_or_else(|| details.value.clone(), serde_json::Value::String)
+                    } else {
+                        details.value.clone()
+                    };
                     let sort_details = serde_json::json!({
                         "order": order,
                         "value": value,
@@ -251,13 +269,51 @@ impl ScoreDetails {
                 ScoreDetails::Vector(s) => {
                     let similarity = s.similarity.as_ref();
 
-                    let details = serde_json::json!({
+                    let mut details = serde_json::json!({
                         "order": order,
                         "similarity": similarity,
                     });
+                    if let Some(chunk) = s.chunk {
+                        details["chunk"] = chunk.into();
+                    }
                     details_map.insert("vectorSort".into(), details);
                     order += 1;
                 }
+                ScoreDetails::FieldScore(details) => {
+                    let rule = if details.redacted {
+                        format!("<hidden-rule-{order}>")
+                    } else {
+                        details.rule.clone()
+                    };
+                    let field_score_details = serde_json::json!({
+                        "order": order,
+                        "score": details.rank.local_score(),
+                    });
+                    details_map.insert(rule, field_score_details);
+                    order += 1;
+                }
+                ScoreDetails::ReciprocalRankFusion(details) => {
+                    let fusion_details = serde_json::json!({
+                        "order": order,
+                        "k": details.k,
+                        "rank": details.rank,
+                        "keywordRank": details.keyword_rank,
+                        "semanticRank": details.semantic_rank,
+                        "score": details.score,
+                    });
+                    details_map.insert("reciprocalRankFusion".into(), fusion_details);
+                    order += 1;
+                }
+                ScoreDetails::Rerank(details) => {
+                    let rerank_details = serde_json::json!({
+                        "order": order,
+                        "reranker": details.reranker,
+                        "rank": details.rank,
+                        "score": details.score,
+                    });
+                    details_map.insert("rerank".into(), rerank_details);
+                    order += 1;
+                }
                 ScoreDetails::Skipped => {
                     details_map
                         .insert("skipped".to_string(), serde_json::json!({ "order": order }));
@@ -421,6 +477,8 @@ pub struct Sort {
     pub ascending: bool,
     pub redacted: bool,
     pub value: serde_json::Value,
+    /// Whether the field is a datetime field, whose numeric values are displayed as RFC 3339 datetimes.
+    pub datetime: bool,
 }
 
 impl PartialOrd for Sort {
@@ -483,9 +541,46 @@ impl PartialOrd for GeoSort {
     }
 }
 
+/// The score given by a `decay` or `boost` ranking rule to the value of a numeric field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldScore {
+    /// The ranking rule, as displayed in the settings.
+    pub rule: String,
+    pub redacted: bool,
+    pub rank: Rank,
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub struct Vector {
     pub similarity: Option<f32>,
+    /// The index of the chunk of the document nearest to the query, when the embedder chunks the documents.
+    pub chunk: Option<usize>,
+}
+
+/// The ranks of a document in the results of a hybrid search merged by reciprocal rank.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReciprocalRankFusion {
+    pub k: u32,
+    /// The position of the document in the merged results, starting at 1.
+    pub rank: u32,
+    /// The position of the document in the keyword results, if it was part of them.
+    pub keyword_rank: Option<u32>,
+    /// The position of the document in the semantic results, if it was part of them.
+    pub semantic_rank: Option<u32>,
+    pub score: f64,
+}
+
+/// The score given by a reranker to one of the first hits of a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rerank {
+    pub reranker: String,
+    /// The position of the document in the reranked hits, starting at 1.
+    pub rank: u32,
+    /// The score returned by the reranker.
+    pub score: f64,
+    /// The score of the reranker rescaled between the ranking scores of the reranked hits,
+    /// so that the ranking scores keep decreasing with the position of the hits.
+    pub ranking_score: f64,
 }
 
 impl GeoSort {
@@ -0,0 +1,99 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::DocumentId;
+
+/// The version of the cursor format, bumped whenever the layout of [`SearchCursor`] changes
+/// so that stale cursors are rejected instead of silently resuming at the wrong place.
+const CURSOR_FORMAT_VERSION: u8 = 1;
+
+/// An opaque position in the ranked list of results of a search, used to resume a search
+/// right after the last returned document without re-ranking the documents before it.
+///
+/// The position is made of the index of the bucket that contained the document for each
+/// ranking rule that was iterated, followed by the id of the document in the innermost bucket.
+/// Documents inside a bucket are always returned in increasing docid order, which makes this
+/// position stable as long as the index, the query and the ranking rules do not change.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchCursor {
+    pub buckets: Vec<u32>,
+    pub docid: DocumentId,
+}
+
+impl SearchCursor {
+    /// A cursor for a list of documents sorted by docid, such as the one used to browse documents.
+    pub fn from_docid(docid: DocumentId) -> Self {
+        Self { buckets: Vec::new(), docid }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("`{0}` is not a valid cursor, cursors must be reused as returned by a previous request.")]
+pub struct InvalidSearchCursor(pub String);
+
+impl fmt::Display for SearchCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = bincode::serialize(&(CURSOR_FORMAT_VERSION, &self.buckets, self.docid))
+            .map_err(|_| fmt::Error)?;
+        for byte in bytes {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SearchCursor {
+    type Err = InvalidSearchCursor;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || InvalidSearchCursor(s.to_string());
+
+        if s.len() % 2 != 0 || !s.is_ascii() {
+            return Err(error());
+        }
+        let bytes = (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| error())?;
+
+        let (version, buckets, docid): (u8, Vec<u32>, DocumentId) =
+            bincode::deserialize(&bytes).map_err(|_| error())?;
+        if version != CURSOR_FORMAT_VERSION {
+            return Err(error());
+        }
+
+        Ok(SearchCursor { buckets, docid })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cursor_roundtrip() {
+        let cursor = SearchCursor { buckets: vec![0, 3, 12], docid: 42 };
+        let token = cursor.to_string();
+        assert_eq!(token.parse::<SearchCursor>().unwrap(), cursor);
+
+        let cursor = SearchCursor::from_docid(7);
+        assert_eq!(cursor.to_string().parse::<SearchCursor>().unwrap(), cursor);
+    }
+
+    #[test]
+    fn invalid_cursor() {
+        assert!("".parse::<SearchCursor>().is_err());
+        assert!("abc".parse::<SearchCursor>().is_err());
+        assert!("zz".parse::<SearchCursor>().is_err());
+        assert!("ébc".parse::<SearchCursor>().is_err());
+
+        // wrong version
+        let bytes = bincode::serialize(&(0u8, Vec::<u32>::new(), 0u32)).unwrap();
+        let token: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        assert!(token.parse::<SearchCursor>().is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use std::collections::{BTreeMap, HashMap, HashSet};
-use std::ops::ControlFlow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::ops::{Bound, ControlFlow};
 use std::{fmt, mem};
 
 use heed::types::Bytes;
@@ -17,6 +17,8 @@ use crate::heed_codec::{BytesRefCodec, StrRefCodec};
 use crate::search::facet::facet_distribution_iter::{
     count_iterate_over_facet_distribution, lexicographically_iterate_over_facet_distribution,
 };
+use crate::search::facet::facet_range_search::find_docids_of_facet_within_bounds;
+use crate::search::facet::{facet_max_value, facet_min_value};
 use crate::{FieldId, Index, Result};
 
 /// The default number of values by facets that will
@@ -37,6 +39,23 @@ pub enum OrderBy {
     Count,
 }
 
+/// The buckets in which the numeric values of a facet are counted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FacetRanges {
+    /// Consecutive buckets of `interval` width, aligned on the multiples of `interval`,
+    /// going from the smallest to the biggest value of the candidates.
+    Histogram { interval: f64 },
+    /// Explicit buckets, they can overlap or leave holes between them.
+    Ranges(Vec<FacetRange>),
+}
+
+/// A range of facet values, the lower bound is included and the upper bound is excluded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FacetRange {
+    pub from: Option<f64>,
+    pub to: Option<f64>,
+}
+
 pub struct FacetDistribution<'a> {
     facets: Option<HashMap<String, OrderBy>>,
     candidates: Option<RoaringBitmap>,
@@ -92,6 +111,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         facet_type: FacetType,
+        datetime: bool,
         candidates: &RoaringBitmap,
         distribution: &mut IndexMap<String, u64>,
     ) -> heed::Result<()> {
@@ -112,7 +132,8 @@ impl<'a> FacetDistribution<'a> {
 
                     for result in iter {
                         let ((_, _, value), ()) = result?;
-                        *lexicographic_distribution.entry(value.to_string()).or_insert(0) += 1;
+                        let value = number_facet_value(value, datetime);
+                        *lexicographic_distribution.entry(value).or_insert(0) += 1;
 
                         if lexicographic_distribution.len() - distribution_prelength
                             == self.max_values_per_facet
@@ -165,6 +186,7 @@ impl<'a> FacetDistribution<'a> {
     fn facet_numbers_distribution_from_facet_levels(
         &self,
         field_id: FieldId,
+        datetime: bool,
         candidates: &RoaringBitmap,
         order_by: OrderBy,
         distribution: &mut IndexMap<String, u64>,
@@ -181,7 +203,7 @@ impl<'a> FacetDistribution<'a> {
             candidates,
             |facet_key, nbr_docids, _| {
                 let facet_key = OrderedF64Codec::bytes_decode(facet_key).unwrap();
-                distribution.insert(facet_key.to_string(), nbr_docids);
+                distribution.insert(number_facet_value(facet_key, datetime), nbr_docids);
                 if distribution.len() == self.max_values_per_facet {
                     Ok(ControlFlow::Break(()))
                 } else {
@@ -233,6 +255,7 @@ impl<'a> FacetDistribution<'a> {
         &self,
         field_id: FieldId,
         order_by: OrderBy,
+        datetime: bool,
     ) -> heed::Result<IndexMap<String, u64>> {
         use FacetType::{Number, String};
 
@@ -241,8 +264,20 @@ impl<'a> FacetDistribution<'a> {
             (OrderBy::Lexicographic, Some(cnd)) if cnd.len() <= CANDIDATES_THRESHOLD => {
                 // Classic search, candidates were specified, we must return facet values only related
                 // to those candidates. We also enter here for facet strings for performance reasons.
-                self.facet_distribution_from_documents(field_id, Number, cnd, &mut distribution)?;
-                self.facet_distribution_from_documents(field_id, String, cnd, &mut distribution)?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    Number,
+                    datetime,
+                    cnd,
+                    &mut distribution,
+                )?;
+                self.facet_distribution_from_documents(
+                    field_id,
+                    String,
+                    datetime,
+                    cnd,
+                    &mut distribution,
+                )?;
             }
             _ => {
                 let universe;
@@ -256,6 +291,7 @@ impl<'a> FacetDistribution<'a> {
 
                 self.facet_numbers_distribution_from_facet_levels(
                     field_id,
+                    datetime,
                     candidates,
                     order_by,
                     &mut distribution,
@@ -332,9 +368,105 @@ impl<'a> FacetDistribution<'a> {
         Ok(distribution)
     }
 
+    /// Counts the candidates whose numeric values fall in each bucket of the given facets.
+    ///
+    /// The counts are computed by exploring the facet levels, in the same way the range
+    /// filters are, rather than by iterating over every facet value.
+    pub fn compute_ranges(
+        &self,
+        facets: &BTreeMap<String, FacetRanges>,
+    ) -> Result<BTreeMap<String, Vec<(FacetRange, u64)>>> {
+        let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
+        let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+
+        let invalid_fields: BTreeSet<_> = facets
+            .keys()
+            .filter(|facet| !crate::is_faceted(facet, &filterable_fields))
+            .cloned()
+            .collect();
+        if !invalid_fields.is_empty() {
+            return Err(UserError::InvalidFacetRanges {
+                invalid_facets_name: invalid_fields,
+                valid_facets_name: filterable_fields.into_iter().collect(),
+            }
+            .into());
+        }
+
+        let universe;
+        let candidates = match &self.candidates {
+            Some(cnd) => cnd,
+            None => {
+                universe = self.index.documents_ids(self.rtxn)?;
+                &universe
+            }
+        };
+
+        let mut distribution = BTreeMap::new();
+        for (name, facet_ranges) in facets {
+            // the attribute can be filterable without being in any document yet
+            let field_id = fields_ids_map.id(name);
+            let ranges = match facet_ranges {
+                FacetRanges::Ranges(ranges) => ranges.clone(),
+                FacetRanges::Histogram { interval } => match field_id {
+                    Some(fid) => {
+                        let min = facet_min_value(self.index, self.rtxn, fid, candidates.clone())?;
+                        let max = facet_max_value(self.index, self.rtxn, fid, candidates.clone())?;
+                        match min.zip(max) {
+                            Some((min, max)) => {
+                                histogram_ranges(min, max, *interval, self.max_values_per_facet)
+                            }
+                            None => Vec::new(),
+                        }
+                    }
+                    None => Vec::new(),
+                },
+            };
+
+            let mut counts = Vec::with_capacity(ranges.len());
+            for range in ranges {
+                let count = match field_id {
+                    Some(fid) => self.facet_number_count_within_range(fid, range, candidates)?,
+                    None => 0,
+                };
+                counts.push((range, count));
+            }
+            distribution.insert(name.clone(), counts);
+        }
+
+        Ok(distribution)
+    }
+
+    fn facet_number_count_within_range(
+        &self,
+        field_id: FieldId,
+        range: FacetRange,
+        candidates: &RoaringBitmap,
+    ) -> Result<u64> {
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from >= to {
+                return Ok(0);
+            }
+        }
+        let left = range.from.map_or(Bound::Unbounded, Bound::Included);
+        let right = range.to.map_or(Bound::Unbounded, Bound::Excluded);
+
+        let mut docids = RoaringBitmap::new();
+        find_docids_of_facet_within_bounds::<OrderedF64Codec>(
+            self.rtxn,
+            self.index.facet_id_f64_docids,
+            field_id,
+            &left,
+            &right,
+            Some(candidates),
+            &mut docids,
+        )?;
+        Ok(docids.len())
+    }
+
     pub fn execute(&self) -> Result<BTreeMap<String, IndexMap<String, u64>>> {
         let fields_ids_map = self.index.fields_ids_map(self.rtxn)?;
         let filterable_fields = self.index.filterable_fields(self.rtxn)?;
+        let datetime_fields = self.index.indexed_datetime_fields(self.rtxn)?;
 
         let fields = match self.facets {
             Some(ref facets) => {
@@ -364,7 +496,7 @@ impl<'a> FacetDistribution<'a> {
                     .as_ref()
                     .and_then(|facets| facets.get(name).copied())
                     .unwrap_or(self.default_order_by);
-                let values = self.facet_values(fid, order_by)?;
+                let values = self.facet_values(fid, order_by, datetime_fields.contains(name))?;
                 distribution.insert(name.to_string(), values);
             }
         }
@@ -373,6 +505,31 @@ impl<'a> FacetDistribution<'a> {
     }
 }
 
+/// Returns how a number is displayed in the facet distribution,
+/// the numbers of the datetime fields are displayed as RFC 3339 datetimes.
+fn number_facet_value(number: f64, datetime: bool) -> String {
+    match datetime.then(|| crate::format_datetime(number)).flatten() {
+        Some(datetime) => datetime,
+        None => number.to_string(),
+    }
+}
+
+/// Returns the consecutive buckets of `interval` width covering the values from `min` to `max`,
+/// but never more than `max_ranges` buckets.
+fn histogram_ranges(min: f64, max: f64, interval: f64, max_ranges: usize) -> Vec<FacetRange> {
+    let first = (min / interval).floor();
+    let mut ranges = Vec::new();
+    for i in 0..max_ranges {
+        let from = (first + i as f64) * interval;
+        if from > max {
+            break;
+        }
+        let to = (first + i as f64 + 1.0) * interval;
+        ranges.push(FacetRange { from: Some(from), to: Some(to) });
+    }
+    ranges
+}
+
 impl fmt::Debug for FacetDistribution<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let FacetDistribution {
@@ -395,6 +552,7 @@ impl fmt::Debug for FacetDistribution<'_> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::iter;
 
     use big_s::S;
@@ -402,7 +560,7 @@ mod tests {
 
     use crate::documents::documents_batch_reader_from_objects;
     use crate::index::tests::TempIndex;
-    use crate::{milli_snap, FacetDistribution, OrderBy};
+    use crate::{milli_snap, FacetDistribution, FacetRange, FacetRanges, OrderBy};
 
     #[test]
     fn few_candidates_few_facet_values() {
@@ -677,6 +835,82 @@ mod tests {
         milli_snap!(format!("{map:?}"), "candidates_217_777", @r###"{"colour": (217.0, 776.0)}"###);
     }
 
+    #[test]
+    fn facet_ranges() {
+        let mut index = TempIndex::new_with_map_size(4096 * 10_000);
+        index.index_documents_config.autogenerate_docids = true;
+
+        index
+            .update_settings(|settings| settings.set_filterable_fields(hashset! { S("colour") }))
+            .unwrap();
+
+        let mut documents = vec![];
+        for i in 0..1000 {
+            let document = serde_json::json!({
+                "colour": i,
+            })
+            .as_object()
+            .unwrap()
+            .clone();
+            documents.push(document);
+        }
+        // the string values are not counted in any bucket
+        documents.push(serde_json::json!({ "colour": "Red" }).as_object().unwrap().clone());
+
+        let documents = documents_batch_reader_from_objects(documents);
+
+        index.add_documents(documents).unwrap();
+
+        let txn = index.read_txn().unwrap();
+
+        let range = |from, to| FacetRange { from, to };
+        let ranges = BTreeMap::from([(
+            S("colour"),
+            FacetRanges::Ranges(vec![
+                range(None, Some(100.)),
+                range(Some(100.), Some(250.)),
+                range(Some(900.), None),
+                range(Some(5.), Some(5.)),
+            ]),
+        )]);
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((0..1001).collect())
+            .compute_ranges(&ranges)
+            .unwrap();
+        let counts: Vec<_> = map["colour"].iter().map(|(_, count)| *count).collect();
+        assert_eq!(counts, vec![100, 150, 100, 0]);
+
+        let histogram = BTreeMap::from([(S("colour"), FacetRanges::Histogram { interval: 250. })]);
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .compute_ranges(&histogram)
+            .unwrap();
+        assert_eq!(
+            map["colour"],
+            vec![
+                (range(Some(0.), Some(250.)), 33),
+                (range(Some(250.), Some(500.)), 250),
+                (range(Some(500.), Some(750.)), 250),
+                (range(Some(750.), Some(1000.)), 27),
+            ]
+        );
+
+        // the number of buckets of an histogram is bounded by the max values per facet
+        let map = FacetDistribution::new(&txn, &index)
+            .candidates((217..777).collect())
+            .max_values_per_facet(2)
+            .compute_ranges(&histogram)
+            .unwrap();
+        assert_eq!(
+            map["colour"],
+            vec![(range(Some(0.), Some(250.)), 33), (range(Some(250.), Some(500.)), 250)]
+        );
+
+        let unknown = BTreeMap::from([(S("size"), FacetRanges::Histogram { interval: 1. })]);
+        let error = FacetDistribution::new(&txn, &index).compute_ranges(&unknown).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid facet ranges"), "{error}");
+    }
+
     #[test]
     fn facet_stats_array() {
         let mut index = TempIndex::new_with_map_size(4096 * 10_000);
@@ -1,25 +1,33 @@
 use std::collections::HashSet;
 use std::fmt::{Debug, Display};
+use std::mem;
 use std::ops::Bound::{self, Excluded, Included};
 
 use either::Either;
 pub use filter_parser::{Condition, Error as FPError, FilterCondition, Token};
-use heed::types::LazyDecode;
+use heed::types::{Bytes, LazyDecode};
 use memchr::memmem::Finder;
 use roaring::{MultiOps, RoaringBitmap};
+use rstar::AABB;
 use serde_json::Value;
+use time::OffsetDateTime;
 
 use super::facet_range_search;
 use crate::error::{Error, UserError};
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, OrderedF64Codec,
 };
+use crate::heed_codec::StrRefCodec;
 use crate::index::db_name::FACET_ID_STRING_DOCIDS;
 use crate::{
     distance_between_two_points, lat_lng_to_xyz, FieldId, Index, InternalError, Result,
-    SerializationError,
+    SerializationError, MAX_FACET_VALUE_LENGTH,
 };
 
+/// The margin added around the envelope of a polygon to make sure that
+/// rounding errors never exclude a point lying right on its border.
+const POLYGON_ENVELOPE_MARGIN: f64 = 1e-9;
+
 /// The maximum number of filters the filter AST can process.
 const MAX_FILTER_DEPTH: usize = 2000;
 
@@ -33,6 +41,7 @@ pub enum BadGeoError {
     Lat(f64),
     Lng(f64),
     BoundingBoxTopIsBelowBottom(f64, f64),
+    DegeneratePolygon,
 }
 
 impl std::error::Error for BadGeoError {}
@@ -43,6 +52,10 @@ impl Display for BadGeoError {
             Self::BoundingBoxTopIsBelowBottom(top, bottom) => {
                 write!(f, "The top latitude `{top}` is below the bottom latitude `{bottom}`.")
             }
+            Self::DegeneratePolygon => write!(
+                f,
+                "The polygon is degenerate. A polygon must have at least three distinct points that are not all aligned."
+            ),
             Self::Lat(lat) => write!(
                 f,
                 "Bad latitude `{}`. Latitude must be contained between -90 and 90 degrees. ",
@@ -230,7 +243,8 @@ impl<'a> Filter<'a> {
     pub fn evaluate(&self, rtxn: &heed::RoTxn<'_>, index: &Index) -> Result<RoaringBitmap> {
         // to avoid doing this for each recursive call we're going to do it ONCE ahead of time
         let filterable_fields = index.filterable_fields(rtxn)?;
-        self.inner_evaluate(rtxn, index, &filterable_fields, None)
+        let datetime_fields = index.indexed_datetime_fields(rtxn)?;
+        self.inner_evaluate(rtxn, index, &filterable_fields, &datetime_fields, None)
     }
 
     fn evaluate_operator(
@@ -239,27 +253,33 @@ impl<'a> Filter<'a> {
         field_id: FieldId,
         universe: Option<&RoaringBitmap>,
         operator: &Condition<'a>,
+        datetime_now: Option<OffsetDateTime>,
     ) -> Result<RoaringBitmap> {
         let numbers_db = index.facet_id_f64_docids;
         let strings_db = index.facet_id_string_docids;
 
+        // The values compared to a datetime field are datetimes, that can be relative to now.
+        let parse_number = |token: &Token<'a>| -> Result<f64> {
+            let number = match datetime_now {
+                Some(now) => token.parse_datetime(now)?,
+                None => token.parse_finite_float()?,
+            };
+            Ok(number)
+        };
+
         // Make sure we always bound the ranges with the field id and the level,
         // as the facets values are all in the same database and prefixed by the
         // field id and the level.
 
         let (left, right) = match operator {
-            Condition::GreaterThan(val) => {
-                (Excluded(val.parse_finite_float()?), Included(f64::MAX))
-            }
+            Condition::GreaterThan(val) => (Excluded(parse_number(val)?), Included(f64::MAX)),
             Condition::GreaterThanOrEqual(val) => {
-                (Included(val.parse_finite_float()?), Included(f64::MAX))
-            }
-            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(val.parse_finite_float()?)),
-            Condition::LowerThanOrEqual(val) => {
-                (Included(f64::MIN), Included(val.parse_finite_float()?))
+                (Included(parse_number(val)?), Included(f64::MAX))
             }
+            Condition::LowerThan(val) => (Included(f64::MIN), Excluded(parse_number(val)?)),
+            Condition::LowerThanOrEqual(val) => (Included(f64::MIN), Included(parse_number(val)?)),
             Condition::Between { from, to } => {
-                (Included(from.parse_finite_float()?), Included(to.parse_finite_float()?))
+                (Included(parse_number(from)?), Included(parse_number(to)?))
             }
             Condition::Null => {
                 let is_null = index.null_faceted_documents_ids(rtxn, field_id)?;
@@ -285,7 +305,7 @@ impl<'a> Filter<'a> {
                     )?
                     .map(|v| v.bitmap)
                     .unwrap_or_default();
-                let number = val.parse_finite_float().ok();
+                let number = parse_number(val).ok();
                 let number_docids = match number {
                     Some(n) => numbers_db
                         .get(rtxn, &FacetGroupKey { field_id, level: 0, left_bound: n })?
@@ -297,44 +317,54 @@ impl<'a> Filter<'a> {
             }
             Condition::NotEqual(val) => {
                 let operator = Condition::Equal(val.clone());
-                let docids = Self::evaluate_operator(rtxn, index, field_id, None, &operator)?;
+                let docids =
+                    Self::evaluate_operator(rtxn, index, field_id, None, &operator, datetime_now)?;
                 let all_ids = index.documents_ids(rtxn)?;
                 return Ok(all_ids - docids);
             }
             Condition::Contains { keyword: _, word } => {
                 let value = crate::normalize_facet(word.value());
                 let finder = Finder::new(&value);
-                let base = FacetGroupKey { field_id, level: 0, left_bound: "" };
-                let docids = strings_db
-                    .prefix_iter(rtxn, &base)?
-                    .remap_data_type::<LazyDecode<FacetGroupValueCodec>>()
-                    .filter_map(|result| -> Option<Result<RoaringBitmap>> {
-                        match result {
-                            Ok((FacetGroupKey { left_bound, .. }, lazy_group_value)) => {
-                                if finder.find(left_bound.as_bytes()).is_some() {
-                                    Some(lazy_group_value.decode().map(|gv| gv.bitmap).map_err(
-                                        |_| {
-                                            InternalError::from(SerializationError::Decoding {
-                                                db_name: Some(FACET_ID_STRING_DOCIDS),
-                                            })
-                                            .into()
-                                        },
-                                    ))
-                                } else {
-                                    None
-                                }
-                            }
-                            Err(_e) => {
-                                Some(Err(InternalError::from(SerializationError::Decoding {
-                                    db_name: Some(FACET_ID_STRING_DOCIDS),
-                                })
-                                .into()))
-                            }
-                        }
-                    })
-                    .union()?;
-
-                return Ok(docids);
+                return Self::facet_string_docids_matching(rtxn, strings_db, field_id, "", |s| {
+                    finder.find(s.as_bytes()).is_some()
+                });
+            }
+            Condition::StartsWith { keyword: _, word } => {
+                // The facet strings are ordered, the ones starting with
+                // the prefix are all stored next to each others.
+                let prefix = crate::normalize_facet(word.value());
+                return Self::facet_string_docids_matching(
+                    rtxn,
+                    strings_db,
+                    field_id,
+                    &prefix,
+                    |_| true,
+                );
+            }
+            Condition::EndsWith { keyword: _, word } => {
+                // The facet strings are truncated to `MAX_FACET_VALUE_LENGTH` bytes in the
+                // facet databases, the end of the longest ones must be read from the original values.
+                let suffix = crate::normalize_facet(word.value());
+                let is_truncated = |s: &str| s.len() >= MAX_FACET_VALUE_LENGTH;
+                let docids =
+                    Self::facet_string_docids_matching(rtxn, strings_db, field_id, "", |s| {
+                        !is_truncated(s) && s.ends_with(&suffix)
+                    })?;
+                let truncated_docids = Self::facet_string_docids_matching(
+                    rtxn,
+                    strings_db,
+                    field_id,
+                    "",
+                    is_truncated,
+                )?;
+                let truncated_docids = Self::original_facet_string_docids_matching(
+                    rtxn,
+                    index,
+                    field_id,
+                    truncated_docids - &docids,
+                    |s| crate::normalize_facet(s).ends_with(&suffix),
+                )?;
+                return Ok(docids | truncated_docids);
             }
         };
 
@@ -351,6 +381,71 @@ impl<'a> Filter<'a> {
         Ok(output)
     }
 
+    /// Returns the union of the documents ids of the normalized facet strings
+    /// of the field that start with `prefix` and for which `matches` returns `true`.
+    fn facet_string_docids_matching(
+        rtxn: &heed::RoTxn<'_>,
+        strings_db: heed::Database<FacetGroupKeyCodec<StrRefCodec>, FacetGroupValueCodec>,
+        field_id: FieldId,
+        prefix: &str,
+        matches: impl Fn(&str) -> bool,
+    ) -> Result<RoaringBitmap> {
+        let base = FacetGroupKey { field_id, level: 0, left_bound: prefix };
+        strings_db
+            .prefix_iter(rtxn, &base)?
+            .remap_data_type::<LazyDecode<FacetGroupValueCodec>>()
+            .filter_map(|result| -> Option<Result<RoaringBitmap>> {
+                match result {
+                    Ok((FacetGroupKey { left_bound, .. }, lazy_group_value)) => {
+                        if matches(left_bound) {
+                            Some(lazy_group_value.decode().map(|gv| gv.bitmap).map_err(|_| {
+                                InternalError::from(SerializationError::Decoding {
+                                    db_name: Some(FACET_ID_STRING_DOCIDS),
+                                })
+                                .into()
+                            }))
+                        } else {
+                            None
+                        }
+                    }
+                    Err(_e) => Some(Err(InternalError::from(SerializationError::Decoding {
+                        db_name: Some(FACET_ID_STRING_DOCIDS),
+                    })
+                    .into())),
+                }
+            })
+            .union()
+    }
+
+    /// Returns the documents ids among `candidates` with at least one original, non-truncated,
+    /// string value in the field for which `matches` returns `true`.
+    fn original_facet_string_docids_matching(
+        rtxn: &heed::RoTxn<'_>,
+        index: &Index,
+        field_id: FieldId,
+        candidates: RoaringBitmap,
+        matches: impl Fn(&str) -> bool,
+    ) -> Result<RoaringBitmap> {
+        let mut docids = RoaringBitmap::new();
+        let mut key_buffer: Vec<_> = field_id.to_be_bytes().to_vec();
+        for docid in candidates {
+            key_buffer.truncate(mem::size_of::<FieldId>());
+            key_buffer.extend_from_slice(&docid.to_be_bytes());
+            let iter = index
+                .field_id_docid_facet_strings
+                .remap_key_type::<Bytes>()
+                .prefix_iter(rtxn, &key_buffer)?;
+            for result in iter {
+                let (_, original_value) = result?;
+                if matches(original_value) {
+                    docids.insert(docid);
+                    break;
+                }
+            }
+        }
+        Ok(docids)
+    }
+
     /// Aggregates the documents ids that are part of the specified range automatically
     /// going deeper through the levels.
     fn explore_facet_number_levels(
@@ -382,6 +477,7 @@ impl<'a> Filter<'a> {
         rtxn: &heed::RoTxn<'_>,
         index: &Index,
         filterable_fields: &HashSet<String>,
+        datetime_fields: &HashSet<String>,
         universe: Option<&RoaringBitmap>,
     ) -> Result<RoaringBitmap> {
         if universe.map_or(false, |u| u.is_empty()) {
@@ -395,6 +491,7 @@ impl<'a> Filter<'a> {
                     rtxn,
                     index,
                     filterable_fields,
+                    datetime_fields,
                     universe,
                 )?;
                 match universe {
@@ -408,10 +505,21 @@ impl<'a> Filter<'a> {
             FilterCondition::In { fid, els } => {
                 if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let datetime_now =
+                        datetime_fields.contains(fid.value()).then(OffsetDateTime::now_utc);
                     if let Some(fid) = field_ids_map.id(fid.value()) {
                         els.iter()
                             .map(|el| Condition::Equal(el.clone()))
-                            .map(|op| Self::evaluate_operator(rtxn, index, fid, universe, &op))
+                            .map(|op| {
+                                Self::evaluate_operator(
+                                    rtxn,
+                                    index,
+                                    fid,
+                                    universe,
+                                    &op,
+                                    datetime_now,
+                                )
+                            })
                             .union()
                     } else {
                         Ok(RoaringBitmap::new())
@@ -426,8 +534,10 @@ impl<'a> Filter<'a> {
             FilterCondition::Condition { fid, op } => {
                 if crate::is_faceted(fid.value(), filterable_fields) {
                     let field_ids_map = index.fields_ids_map(rtxn)?;
+                    let datetime_now =
+                        datetime_fields.contains(fid.value()).then(OffsetDateTime::now_utc);
                     if let Some(fid) = field_ids_map.id(fid.value()) {
-                        Self::evaluate_operator(rtxn, index, fid, universe, op)
+                        Self::evaluate_operator(rtxn, index, fid, universe, op, datetime_now)
                     } else {
                         Ok(RoaringBitmap::new())
                     }
@@ -441,7 +551,16 @@ impl<'a> Filter<'a> {
             FilterCondition::Or(subfilters) => subfilters
                 .iter()
                 .cloned()
-                .map(|f| Self::inner_evaluate(&f.into(), rtxn, index, filterable_fields, universe))
+                .map(|f| {
+                    Self::inner_evaluate(
+                        &f.into(),
+                        rtxn,
+                        index,
+                        filterable_fields,
+                        datetime_fields,
+                        universe,
+                    )
+                })
                 .union(),
             FilterCondition::And(subfilters) => {
                 let mut subfilters_iter = subfilters.iter();
@@ -451,6 +570,7 @@ impl<'a> Filter<'a> {
                         rtxn,
                         index,
                         filterable_fields,
+                        datetime_fields,
                         universe,
                     )?;
                     for f in subfilters_iter {
@@ -465,6 +585,7 @@ impl<'a> Filter<'a> {
                             rtxn,
                             index,
                             filterable_fields,
+                            datetime_fields,
                             Some(&bitmap),
                         )?;
                     }
@@ -542,97 +663,37 @@ impl<'a> Filter<'a> {
                         ))?;
                     }
 
-                    // Instead of writing a custom `GeoBoundingBox` filter we're simply going to re-use the range
-                    // filter to create the following filter;
-                    // `_geo.lat {top_right[0]} TO {bottom_left[0]} AND _geo.lng {top_right[1]} TO {bottom_left[1]}`
-                    // As we can see, we need to use a bunch of tokens that don't exist in the original filter,
-                    // thus we're going to create tokens that point to a random span but contain our text.
-
-                    let geo_lat_token = Token::new(
-                        top_right_point[0].original_span(),
-                        Some("_geo.lat".to_string()),
-                    );
-
-                    let condition_lat = FilterCondition::Condition {
-                        fid: geo_lat_token,
-                        op: Condition::Between {
-                            from: bottom_left_point[0].clone(),
-                            to: top_right_point[0].clone(),
-                        },
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
                     };
 
-                    let selected_lat = Filter { condition: condition_lat }.inner_evaluate(
-                        rtxn,
-                        index,
-                        filterable_fields,
-                        universe,
-                    )?;
-
-                    let geo_lng_token = Token::new(
-                        top_right_point[1].original_span(),
-                        Some("_geo.lng".to_string()),
-                    );
-                    let selected_lng = if top_right[1] < bottom_left[1] {
-                        // In this case the bounding box is wrapping around the earth (going from 180 to -180).
-                        // We need to update the lng part of the filter from;
-                        // `_geo.lng {top_right[1]} TO {bottom_left[1]}` to
-                        // `_geo.lng {bottom_left[1]} TO 180 AND _geo.lng -180 TO {top_right[1]}`
-
-                        let min_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("-180.0".to_string()),
-                        );
-                        let max_lng_token = Token::new(
-                            top_right_point[1].original_span(),
-                            Some("180.0".to_string()),
-                        );
-
-                        let condition_left = FilterCondition::Condition {
-                            fid: geo_lng_token.clone(),
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: max_lng_token,
-                            },
-                        };
-                        let left = Filter { condition: condition_left }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?;
-
-                        let condition_right = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: min_lng_token,
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        let right = Filter { condition: condition_right }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?;
-
-                        left | right
+                    // When the bounding box is wrapping around the earth (going from 180 to -180)
+                    // it is split in two boxes, one on each side of the antimeridian.
+                    let lng_ranges = if top_right[1] < bottom_left[1] {
+                        vec![(bottom_left[1], 180.0), (-180.0, top_right[1])]
                     } else {
-                        let condition_lng = FilterCondition::Condition {
-                            fid: geo_lng_token,
-                            op: Condition::Between {
-                                from: bottom_left_point[1].clone(),
-                                to: top_right_point[1].clone(),
-                            },
-                        };
-                        Filter { condition: condition_lng }.inner_evaluate(
-                            rtxn,
-                            index,
-                            filterable_fields,
-                            universe,
-                        )?
+                        vec![(bottom_left[1], top_right[1])]
                     };
 
-                    Ok(selected_lat & selected_lng)
+                    // A document can have several points, we must look at the points themselves
+                    // rather than at the latitudes and longitudes of the document separately.
+                    let mut result = RoaringBitmap::new();
+                    for (min_lng, max_lng) in lng_ranges {
+                        let envelope =
+                            polygon_envelope(&[[bottom_left[0], min_lng], [top_right[0], max_lng]]);
+                        let selected = rtree
+                            .locate_in_envelope(&envelope)
+                            .filter(|point| {
+                                let [lat, lng] = point.data.1;
+                                (bottom_left[0]..=top_right[0]).contains(&lat)
+                                    && (min_lng..=max_lng).contains(&lng)
+                            })
+                            .map(|point| point.data.0);
+                        result.extend(selected);
+                    }
+
+                    Ok(result)
                 } else {
                     Err(top_right_point[0].as_external_error(
                         FilterError::AttributeNotFilterable {
@@ -642,8 +703,121 @@ impl<'a> Filter<'a> {
                     ))?
                 }
             }
+            FilterCondition::GeoPolygon { points } => {
+                if filterable_fields.contains("_geo") {
+                    let mut polygon: Vec<[f64; 2]> = Vec::with_capacity(points.len());
+                    for [lat_token, lng_token] in points {
+                        let point =
+                            [lat_token.parse_finite_float()?, lng_token.parse_finite_float()?];
+                        if !(-90.0..=90.0).contains(&point[0]) {
+                            return Err(lat_token.as_external_error(BadGeoError::Lat(point[0])))?;
+                        }
+                        if !(-180.0..=180.0).contains(&point[1]) {
+                            return Err(lng_token.as_external_error(BadGeoError::Lng(point[1])))?;
+                        }
+                        // repeating a vertex doesn't change the shape of the polygon
+                        if polygon.last() != Some(&point) {
+                            polygon.push(point);
+                        }
+                    }
+                    // the polygon is implicitly closed, but users may also close it themselves
+                    if polygon.len() > 1 && polygon.first() == polygon.last() {
+                        polygon.pop();
+                    }
+                    if polygon.len() < 3 || polygon_area(&polygon) <= f64::EPSILON {
+                        return Err(points[0][0].as_external_error(BadGeoError::DegeneratePolygon))?;
+                    }
+
+                    let rtree = match index.geo_rtree(rtxn)? {
+                        Some(rtree) => rtree,
+                        None => return Ok(RoaringBitmap::new()),
+                    };
+
+                    // The rtree pre-selects the points contained in the envelope of the polygon,
+                    // then we only keep the ones that are really inside of it.
+                    let result = rtree
+                        .locate_in_envelope(&polygon_envelope(&polygon))
+                        .filter(|point| polygon_contains(&polygon, &point.data.1))
+                        .map(|point| point.data.0)
+                        .collect();
+
+                    Ok(result)
+                } else {
+                    Err(points[0][0].as_external_error(FilterError::AttributeNotFilterable {
+                        attribute: "_geo",
+                        filterable_fields: filterable_fields.clone(),
+                    }))?
+                }
+            }
+        }
+    }
+}
+
+/// Returns the area of the polygon in squared degrees, using the shoelace formula.
+fn polygon_area(polygon: &[[f64; 2]]) -> f64 {
+    let previous = polygon.iter().cycle().skip(polygon.len() - 1);
+    let double_area: f64 = polygon
+        .iter()
+        .zip(previous)
+        .map(|([lat, lng], [prev_lat, prev_lng])| prev_lat * lng - lat * prev_lng)
+        .sum();
+    double_area.abs() / 2.0
+}
+
+/// Returns `true` if the point is inside the polygon, using the even-odd rule.
+///
+/// The edges of the polygon are straight lines in the latitude/longitude plane.
+fn polygon_contains(polygon: &[[f64; 2]], &[lat, lng]: &[f64; 2]) -> bool {
+    let previous = polygon.iter().cycle().skip(polygon.len() - 1);
+    let mut inside = false;
+    for (&[lat_a, lng_a], &[lat_b, lng_b]) in polygon.iter().zip(previous) {
+        // does the edge cross the meridian of the point, north of the point?
+        if (lng_a > lng) != (lng_b > lng)
+            && lat < (lat_b - lat_a) * (lng - lng_a) / (lng_b - lng_a) + lat_a
+        {
+            inside = !inside;
         }
     }
+    inside
+}
+
+/// Returns the smallest box of the cartesian space containing all the points of the polygon.
+///
+/// The polygon is contained in the rectangle made of its extreme latitudes and longitudes.
+/// The cartesian coordinates reach their extreme values on this rectangle either on its
+/// borders or on the equator and the meridians of longitude -180, -90, 0, 90 and 180.
+fn polygon_envelope(polygon: &[[f64; 2]]) -> AABB<[f64; 3]> {
+    let (mut min_lat, mut max_lat) = (f64::INFINITY, f64::NEG_INFINITY);
+    let (mut min_lng, mut max_lng) = (f64::INFINITY, f64::NEG_INFINITY);
+    for &[lat, lng] in polygon {
+        min_lat = min_lat.min(lat);
+        max_lat = max_lat.max(lat);
+        min_lng = min_lng.min(lng);
+        max_lng = max_lng.max(lng);
+    }
+
+    let mut lats = vec![min_lat, max_lat];
+    if min_lat < 0.0 && 0.0 < max_lat {
+        lats.push(0.0);
+    }
+    let mut lngs = vec![min_lng, max_lng];
+    lngs.extend(
+        [-180.0, -90.0, 0.0, 90.0, 180.0].into_iter().filter(|l| min_lng < *l && *l < max_lng),
+    );
+
+    let mut lower = [f64::INFINITY; 3];
+    let mut upper = [f64::NEG_INFINITY; 3];
+    for &lat in &lats {
+        for &lng in &lngs {
+            let xyz = lat_lng_to_xyz(&[lat, lng]);
+            for ((lo, hi), coord) in lower.iter_mut().zip(upper.iter_mut()).zip(xyz) {
+                *lo = lo.min(coord - POLYGON_ENVELOPE_MARGIN);
+                *hi = hi.max(coord + POLYGON_ENVELOPE_MARGIN);
+            }
+        }
+    }
+
+    AABB::from_corners(lower, upper)
 }
 
 impl<'a> From<FilterCondition<'a>> for Filter<'a> {
@@ -663,7 +837,7 @@ mod tests {
     use roaring::RoaringBitmap;
 
     use crate::index::tests::TempIndex;
-    use crate::Filter;
+    use crate::{Filter, MAX_FACET_VALUE_LENGTH};
 
     #[test]
     fn empty_db() {
@@ -1030,6 +1204,141 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn geo_polygon() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, "name": "inside", "_geo": { "lat": 45.47, "lng": 9.19 } },
+              { "id": 2, "name": "in the notch", "_geo": { "lat": 45.52, "lng": 9.19 } },
+              { "id": 3, "name": "just outside", "_geo": { "lat": 45.42, "lng": 9.28 } },
+              { "id": 4, "name": "far away", "_geo": { "lat": -12.0, "lng": 120.0 } },
+              { "id": 5, "name": "inside too", "_geo": { "lat": 45.45, "lng": 9.12 } },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a polygon with a notch on its north side, the second document is in the notch
+        let polygon = "_geoPolygon([45.40, 9.10], [45.55, 9.10], [45.55, 9.17], [45.50, 9.17], [45.50, 9.21], [45.55, 9.21], [45.55, 9.25], [45.40, 9.25])";
+        let filter = Filter::from_str(polygon).unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 4]);
+
+        // closing the polygon explicitly doesn't change the result
+        let closed = polygon.replace(")", ", [45.40, 9.10])");
+        let filter = Filter::from_str(&closed).unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 4]);
+
+        let negated = format!("NOT {polygon}");
+        let filter = Filter::from_str(&negated).unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn geo_multiple_points() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("_geo") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+              { "id": 1, "_geo": [{ "lat": 45.47, "lng": 9.19 }, { "lat": -12.0, "lng": 120.0 }] },
+              { "id": 2, "_geo": { "lat": 45.47, "lng": 120.0 } },
+              { "id": 3, "_geo": [{ "lat": 10.0, "lng": 9.19 }, { "lat": 45.47, "lng": -60.0 }] },
+              { "id": 4, "_geo": [{ "lat": -12.0, "lng": 120.0 }] },
+              { "id": 5, "_geo": [] },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a document matches as soon as one of its points matches
+        let filter = Filter::from_str("_geoRadius(45.47, 9.19, 1000)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0]);
+
+        let filter = Filter::from_str("_geoRadius(-12.0, 120.0, 1000)").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 3]);
+
+        // the latitude of a point and the longitude of another point must not be mixed up
+        let filter = Filter::from_str("_geoBoundingBox([46, 10], [45, 9])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0]);
+
+        let filter = Filter::from_str("_geoBoundingBox([-10, 121], [-13, 119])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0, 3]);
+
+        // wrapping around the antimeridian
+        let filter = Filter::from_str("_geoBoundingBox([50, -50], [40, 110])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+        let filter =
+            Filter::from_str("_geoPolygon([45, 9], [46, 9], [46, 10], [45, 10])").unwrap().unwrap();
+        let documents_ids = filter.evaluate(&rtxn, &index).unwrap();
+        assert_eq!(documents_ids.into_iter().collect::<Vec<_>>(), vec![0]);
+    }
+
+    #[test]
+    fn geo_polygon_error() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_searchable_fields(vec![S("_geo"), S("price")]); // to keep the fields order
+                settings.set_filterable_fields(hashset! { S("_geo"), S("price") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+
+        // a point has a bad latitude
+        let filter =
+            Filter::from_str("_geoPolygon([10, 10], [90.0000001, 10], [10, 20])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad latitude `90.0000001`. Latitude must be contained between -90 and 90 degrees."
+        ));
+
+        // a point has a bad longitude
+        let filter = Filter::from_str("_geoPolygon([10, 10], [20, 10], [10, -180.000001])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().contains(
+            "Bad longitude `-180.000001`. Longitude must be contained between -180 and 180 degrees."
+        ));
+
+        // only two distinct points
+        let filter = Filter::from_str("_geoPolygon([10, 10], [20, 10], [20, 10], [10, 10])")
+            .unwrap()
+            .unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("The polygon is degenerate."), "{}", error);
+
+        // all the points are aligned
+        let filter =
+            Filter::from_str("_geoPolygon([10, 10], [20, 20], [30, 30])").unwrap().unwrap();
+        let error = filter.evaluate(&rtxn, &index).unwrap_err();
+        assert!(error.to_string().starts_with("The polygon is degenerate."), "{}", error);
+    }
+
     #[test]
     fn filter_depth() {
         // generates a big (2 MiB) filter with too much of ORs.
@@ -1110,6 +1419,143 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn filter_datetime() {
+        use time::format_description::well_known::Rfc3339;
+        use time::{Duration, OffsetDateTime};
+
+        let index = TempIndex::new();
+
+        let yesterday = (OffsetDateTime::now_utc() - Duration::days(1)).format(&Rfc3339).unwrap();
+        index
+            .add_documents(documents!([
+                { "id": 0, "createdAt": "2024-01-01T00:00:00Z" },
+                { "id": 1, "createdAt": "2024-01-15T12:00:00+02:00" },
+                { "id": 2, "createdAt": yesterday },
+                { "id": 3, "createdAt": "not a datetime" },
+                { "id": 4, "createdAt": 1704067200 },
+            ]))
+            .unwrap();
+
+        // the datetime fields are declared after the documents to make sure they are reindexed
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("createdAt") });
+                settings.set_datetime_fields(hashset! { S("createdAt") });
+            })
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).map(|docids| docids.iter().collect::<Vec<_>>())
+        };
+
+        assert_eq!(evaluate("createdAt > 2024-01-10T00:00:00Z").unwrap(), vec![1, 2]);
+        assert_eq!(evaluate("createdAt <= '2024-01-15T10:00:00Z'").unwrap(), vec![0, 1, 4]);
+        assert_eq!(evaluate("createdAt = 2024-01-01T00:00:00Z").unwrap(), vec![0, 4]);
+        assert_eq!(evaluate("createdAt IN [2024-01-01T01:00:00+01:00, 42]").unwrap(), vec![0, 4]);
+        assert_eq!(evaluate("createdAt > NOW-7d").unwrap(), vec![2]);
+        assert_eq!(evaluate("createdAt NOW-7d TO NOW").unwrap(), vec![2]);
+        assert_eq!(evaluate("createdAt < NOW-2d").unwrap(), vec![0, 1, 4]);
+        assert_eq!(evaluate("createdAt = 'not a datetime'").unwrap(), vec![3]);
+        let error = evaluate("createdAt > yesterday").unwrap_err();
+        assert!(
+            error.to_string().starts_with(
+                "Expected a datetime in the RFC 3339 format such as `2024-01-31T08:00:00Z`, a Unix timestamp, or a datetime relative to now such as `NOW-7d`, but instead got `yesterday`."
+            ),
+            "{error}"
+        );
+        drop(rtxn);
+
+        // once the field is not a datetime field anymore its values are strings again
+        index.update_settings(|settings| settings.reset_datetime_fields()).unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("createdAt = '2024-01-01T00:00:00Z'").unwrap().unwrap();
+        assert_eq!(filter.evaluate(&rtxn, &index).unwrap().iter().collect::<Vec<_>>(), vec![0]);
+        let filter = Filter::from_str("createdAt > NOW-7d").unwrap().unwrap();
+        assert!(filter.evaluate(&rtxn, &index).is_err());
+    }
+
+    #[test]
+    fn filter_starts_with_ends_with() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("sku") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "sku": "AB-123" },
+                { "id": 1, "sku": "ab-456" },
+                { "id": 2, "sku": "  Ab-789-XL " },
+                { "id": 3, "sku": "ABC-1" },
+                { "id": 4, "sku": "XAB-2-xl" },
+                { "id": 5, "sku": ["CD-1", "ab-999"] },
+                { "id": 6, "sku": 12 },
+                { "id": 7 },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().iter().collect::<Vec<_>>()
+        };
+
+        // the strings are compared once normalized, whatever the case of the original strings
+        assert_eq!(evaluate("sku STARTS WITH 'AB-'"), vec![0, 1, 2, 5]);
+        assert_eq!(evaluate("sku STARTS WITH ab-"), vec![0, 1, 2, 5]);
+        assert_eq!(evaluate("sku STARTS WITH 'Ab-7'"), vec![2]);
+        assert_eq!(evaluate("sku STARTS WITH AB"), vec![0, 1, 2, 3, 5]);
+        assert_eq!(evaluate("sku STARTS WITH 'ABC-1'"), vec![3]);
+        assert_eq!(evaluate("sku STARTS WITH 'ZZ'"), Vec::<u32>::new());
+        assert_eq!(evaluate("sku STARTS WITH 1"), Vec::<u32>::new());
+        assert_eq!(evaluate("sku NOT STARTS WITH 'ab-'"), vec![3, 4, 6, 7]);
+
+        assert_eq!(evaluate("sku ENDS WITH '-XL'"), vec![2, 4]);
+        assert_eq!(evaluate("sku ENDS WITH 9"), vec![5]);
+        assert_eq!(evaluate("sku ENDS WITH '-1'"), vec![3, 5]);
+        assert_eq!(evaluate("sku NOT ENDS WITH xl"), vec![0, 1, 3, 5, 6, 7]);
+        assert_eq!(evaluate("NOT sku ENDS WITH xl AND sku STARTS WITH ab"), vec![0, 1, 3, 5]);
+    }
+
+    #[test]
+    fn filter_ends_with_truncated_facet_values() {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset! { S("path") });
+            })
+            .unwrap();
+
+        // the facet strings are truncated in the facet databases, but not their ends
+        let long_path = "a/".repeat(MAX_FACET_VALUE_LENGTH);
+        index
+            .add_documents(documents!([
+                { "id": 0, "path": format!("{long_path}kefir.png") },
+                { "id": 1, "path": format!("{long_path}intel.jpg") },
+                { "id": 2, "path": "kefir.png" },
+            ]))
+            .unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let evaluate = |filter: &str| {
+            let filter = Filter::from_str(filter).unwrap().unwrap();
+            filter.evaluate(&rtxn, &index).unwrap().iter().collect::<Vec<_>>()
+        };
+
+        assert_eq!(evaluate("path ENDS WITH '.png'"), vec![0, 2]);
+        assert_eq!(evaluate("path ENDS WITH 'Intel.JPG'"), vec![1]);
+        assert_eq!(evaluate("path NOT ENDS WITH 'kefir.png'"), vec![1]);
+        assert_eq!(evaluate("path STARTS WITH 'a/a/'"), vec![0, 1]);
+    }
+
     #[test]
     fn filter_number() {
         let index = TempIndex::new();
@@ -4,7 +4,9 @@ use heed::types::{Bytes, DecodeIgnore};
 use heed::{BytesDecode, RoTxn};
 use roaring::RoaringBitmap;
 
-pub use self::facet_distribution::{FacetDistribution, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::facet_distribution::{
+    FacetDistribution, FacetRange, FacetRanges, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::filter::{BadGeoError, Filter};
 pub use self::search::{FacetValueHit, SearchForFacetValues};
 use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
@@ -13,7 +15,7 @@ use crate::{Index, Result};
 
 mod facet_distribution;
 mod facet_distribution_iter;
-mod facet_range_search;
+pub(crate) mod facet_range_search;
 mod facet_sort_ascending;
 mod facet_sort_descending;
 mod filter;
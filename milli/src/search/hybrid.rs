@@ -1,11 +1,23 @@
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
 use itertools::Itertools;
 use roaring::RoaringBitmap;
 
-use crate::score_details::{ScoreDetails, ScoreValue, ScoringStrategy};
+use crate::score_details::{self, ScoreDetails, ScoreValue, ScoringStrategy};
 use crate::search::SemanticSearch;
-use crate::{MatchingWords, Result, Search, SearchResult};
+use crate::{DocumentId, MatchingWords, Result, Search, SearchResult};
+
+/// How the results of the keyword and semantic searches of a hybrid search are merged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HybridFusion {
+    /// The documents are merged by their scores, weighted by the semantic ratio.
+    #[default]
+    Score,
+    /// The documents are merged by the reciprocal of their ranks in both searches,
+    /// weighted by the semantic ratio. The `k` constant dampens the weight of the first ranks.
+    ReciprocalRank { k: u32 },
+}
 
 struct ScoreWithRatioResult {
     matching_words: MatchingWords,
@@ -13,6 +25,7 @@ struct ScoreWithRatioResult {
     document_scores: Vec<(u32, ScoreWithRatio)>,
     degraded: bool,
     used_negative_operator: bool,
+    corrected_query: Option<String>,
 }
 
 type ScoreWithRatio = (Vec<ScoreDetails>, f32);
@@ -82,6 +95,7 @@ impl ScoreWithRatioResult {
             document_scores,
             degraded: results.degraded,
             used_negative_operator: results.used_negative_operator,
+            corrected_query: results.corrected_query,
         }
     }
 
@@ -142,18 +156,128 @@ impl ScoreWithRatioResult {
                 candidates: vector_results.candidates | keyword_results.candidates,
                 documents_ids,
                 document_scores,
+                // the merged ranking has no buckets to resume from
+                cursor: None,
                 degraded: vector_results.degraded | keyword_results.degraded,
                 used_negative_operator: vector_results.used_negative_operator
                     | keyword_results.used_negative_operator,
+                corrected_query: keyword_results.corrected_query,
+                groups: Vec::new(),
             },
             semantic_hit_count,
         )
     }
 }
 
+/// The ranks of a document in the keyword and semantic results, with the details of its scores.
+#[derive(Default)]
+struct RankedDocument {
+    keyword: Option<(u32, Vec<ScoreDetails>)>,
+    semantic: Option<(u32, Vec<ScoreDetails>)>,
+}
+
+/// Merges the results of the keyword and semantic searches by reciprocal rank.
+///
+/// A document gets `weight * (k + 1) / (k + rank)` from each list it appears in, so that
+/// a document ranked first by both searches gets a score of `1.0`. It keeps the score details
+/// of the search it owes most of its score to, preceded by the details of the fusion.
+#[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
+fn reciprocal_rank_fusion(
+    vector_results: SearchResult,
+    keyword_results: SearchResult,
+    semantic_ratio: f32,
+    k: u32,
+    from: usize,
+    length: usize,
+) -> (SearchResult, u32) {
+    let mut documents: BTreeMap<DocumentId, RankedDocument> = BTreeMap::new();
+    for (rank, (docid, scores)) in
+        keyword_results.documents_ids.into_iter().zip(keyword_results.document_scores).enumerate()
+    {
+        documents.entry(docid).or_default().keyword = Some((rank as u32 + 1, scores));
+    }
+    for (rank, (docid, scores)) in
+        vector_results.documents_ids.into_iter().zip(vector_results.document_scores).enumerate()
+    {
+        documents.entry(docid).or_default().semantic = Some((rank as u32 + 1, scores));
+    }
+
+    let contribution = |weight: f32, ranked: &Option<(u32, Vec<ScoreDetails>)>| match ranked {
+        Some((rank, _)) => weight as f64 * (k as f64 + 1.0) / (k as f64 + *rank as f64),
+        None => 0.0,
+    };
+    let mut documents: Vec<_> = documents
+        .into_iter()
+        .map(|(docid, document)| {
+            let keyword = contribution(1.0 - semantic_ratio, &document.keyword);
+            let semantic = contribution(semantic_ratio, &document.semantic);
+            (docid, keyword, semantic, document)
+        })
+        .collect();
+    // the documents are already sorted by id, the stable sort breaks the ties by id
+    documents.sort_by(
+        |(_, left_keyword, left_semantic, _), (_, right_keyword, right_semantic, _)| {
+            (right_keyword + right_semantic).total_cmp(&(left_keyword + left_semantic))
+        },
+    );
+
+    let mut semantic_hit_count = 0;
+    let mut documents_ids = Vec::with_capacity(length);
+    let mut document_scores = Vec::with_capacity(length);
+    for (rank, (docid, keyword_score, semantic_score, document)) in
+        documents.into_iter().enumerate().skip(from).take(length)
+    {
+        let fusion = score_details::ReciprocalRankFusion {
+            k,
+            rank: rank as u32 + 1,
+            keyword_rank: document.keyword.as_ref().map(|(rank, _)| *rank),
+            semantic_rank: document.semantic.as_ref().map(|(rank, _)| *rank),
+            score: keyword_score + semantic_score,
+        };
+        let scores = if semantic_score >= keyword_score {
+            semantic_hit_count += 1;
+            document.semantic.map(|(_, scores)| scores)
+        } else {
+            document.keyword.map(|(_, scores)| scores)
+        };
+
+        let mut details = vec![ScoreDetails::ReciprocalRankFusion(fusion)];
+        details.extend(scores.unwrap_or_default());
+        documents_ids.push(docid);
+        document_scores.push(details);
+    }
+
+    (
+        SearchResult {
+            matching_words: keyword_results.matching_words,
+            candidates: vector_results.candidates | keyword_results.candidates,
+            documents_ids,
+            document_scores,
+            // the merged ranking has no buckets to resume from
+            cursor: None,
+            degraded: vector_results.degraded | keyword_results.degraded,
+            used_negative_operator: vector_results.used_negative_operator
+                | keyword_results.used_negative_operator,
+            corrected_query: keyword_results.corrected_query,
+            groups: Vec::new(),
+        },
+        semantic_hit_count,
+    )
+}
+
 impl<'a> Search<'a> {
     #[tracing::instrument(level = "trace", skip_all, target = "search::hybrid")]
-    pub fn execute_hybrid(&self, semantic_ratio: f32) -> Result<(SearchResult, Option<u32>)> {
+    pub fn execute_hybrid(
+        &self,
+        semantic_ratio: f32,
+        fusion: HybridFusion,
+    ) -> Result<(SearchResult, Option<u32>)> {
+        if let Some(rerank) = &self.rerank {
+            let (results, semantic_hit_count) =
+                self.search_to_rerank(rerank.top_k).execute_hybrid(semantic_ratio, fusion)?;
+            return Ok((rerank.apply(self, results)?, semantic_hit_count));
+        }
+
         // TODO: find classier way to achieve that than to reset vector and query params
         // create separate keyword and semantic searches
         let mut search = Search {
@@ -175,13 +299,22 @@ impl<'a> Search<'a> {
             time_budget: self.time_budget.clone(),
             ranking_score_threshold: self.ranking_score_threshold,
             locales: self.locales.clone(),
+            search_after: self.search_after.clone(),
+            did_you_mean: self.did_you_mean,
+            ranking_rules: self.ranking_rules.clone(),
+            // grouping is not supported by the hybrid search
+            group_by: None,
+            rerank: None,
         };
 
         let semantic = search.semantic.take();
         let keyword_results = search.execute()?;
 
-        // completely skip semantic search if the results of the keyword search are good enough
-        if self.results_good_enough(&keyword_results, semantic_ratio) {
+        // completely skip semantic search if the results of the keyword search are good enough,
+        // the ranks of the semantic results always matter to the reciprocal rank fusion
+        if fusion == HybridFusion::Score
+            && self.results_good_enough(&keyword_results, semantic_ratio)
+        {
             return Ok(return_keyword_results(self.limit, self.offset, keyword_results));
         }
 
@@ -217,11 +350,27 @@ impl<'a> Search<'a> {
         // TODO: would be better to have two distinct functions at this point
         let vector_results = search.execute()?;
 
-        let keyword_results = ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
-        let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
-
-        let (merge_results, semantic_hit_count) =
-            ScoreWithRatioResult::merge(vector_results, keyword_results, self.offset, self.limit);
+        let (merge_results, semantic_hit_count) = match fusion {
+            HybridFusion::Score => {
+                let keyword_results =
+                    ScoreWithRatioResult::new(keyword_results, 1.0 - semantic_ratio);
+                let vector_results = ScoreWithRatioResult::new(vector_results, semantic_ratio);
+                ScoreWithRatioResult::merge(
+                    vector_results,
+                    keyword_results,
+                    self.offset,
+                    self.limit,
+                )
+            }
+            HybridFusion::ReciprocalRank { k } => reciprocal_rank_fusion(
+                vector_results,
+                keyword_results,
+                semantic_ratio,
+                k,
+                self.offset,
+                self.limit,
+            ),
+        };
         assert!(merge_results.documents_ids.len() <= self.limit);
         Ok((merge_results, Some(semantic_hit_count)))
     }
@@ -255,8 +404,11 @@ fn return_keyword_results(
         candidates,
         mut documents_ids,
         mut document_scores,
+        cursor,
         degraded,
         used_negative_operator,
+        corrected_query,
+        groups: _,
     }: SearchResult,
 ) -> (SearchResult, Option<u32>) {
     let (documents_ids, document_scores) = if offset >= documents_ids.len() ||
@@ -281,8 +433,11 @@ fn return_keyword_results(
             candidates,
             documents_ids,
             document_scores,
+            cursor,
             degraded,
             used_negative_operator,
+            corrected_query,
+            groups: Vec::new(),
         },
         Some(0),
     )
@@ -6,14 +6,24 @@ use levenshtein_automata::{LevenshteinAutomatonBuilder as LevBuilder, DFA};
 use once_cell::sync::Lazy;
 use roaring::bitmap::RoaringBitmap;
 
-pub use self::facet::{FacetDistribution, Filter, OrderBy, DEFAULT_VALUES_PER_FACET};
+pub use self::cursor::{InvalidSearchCursor, SearchCursor};
+pub use self::facet::{
+    FacetDistribution, FacetRange, FacetRanges, Filter, OrderBy, DEFAULT_VALUES_PER_FACET,
+};
 pub use self::new::matches::{FormatOptions, MatchBounds, MatcherBuilder, MatchingWords};
-use self::new::{execute_vector_search, PartialSearchResult};
+use self::new::{
+    corrected_query, distinct_value, distinct_value_docids, execute_vector_search,
+    PartialSearchResult,
+};
+pub use self::rerank::RerankSearch;
+pub use self::suggest::{SearchForSuggestions, Suggestion, DEFAULT_SUGGESTIONS_LIMIT};
+use crate::prompt::Prompt;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::vector::rerank::Reranker;
 use crate::vector::Embedder;
 use crate::{
-    execute_search, filtered_universe, AscDesc, DefaultSearchLogger, DocumentId, Error, Index,
-    Result, SearchContext, TimeBudget, UserError,
+    execute_search, filtered_universe, AscDesc, Criterion, DefaultSearchLogger, DocumentId, Error,
+    Index, Result, SearchContext, TimeBudget, UserError,
 };
 
 // Building these factories is not free.
@@ -21,11 +31,15 @@ static LEVDIST0: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(0, true));
 static LEVDIST1: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(1, true));
 static LEVDIST2: Lazy<LevBuilder> = Lazy::new(|| LevBuilder::new(2, true));
 
+mod cursor;
 pub mod facet;
 mod fst_utils;
 pub mod hybrid;
 pub mod new;
+pub mod recommend;
+mod rerank;
 pub mod similar;
+mod suggest;
 
 #[derive(Debug, Clone)]
 pub struct SemanticSearch {
@@ -54,6 +68,11 @@ pub struct Search<'a> {
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<Vec<Language>>,
+    search_after: Option<SearchCursor>,
+    did_you_mean: Option<u64>,
+    ranking_rules: Option<Vec<Criterion>>,
+    group_by: Option<(String, usize)>,
+    rerank: Option<RerankSearch>,
 }
 
 impl<'a> Search<'a> {
@@ -77,6 +96,11 @@ impl<'a> Search<'a> {
             locales: None,
             time_budget: TimeBudget::max(),
             ranking_score_threshold: None,
+            search_after: None,
+            did_you_mean: None,
+            ranking_rules: None,
+            group_by: None,
+            rerank: None,
         }
     }
 
@@ -168,6 +192,50 @@ impl<'a> Search<'a> {
         self
     }
 
+    /// Resume the search right after the document pointed by the cursor,
+    /// the offset is then relative to this document.
+    pub fn search_after(&mut self, cursor: SearchCursor) -> &mut Search<'a> {
+        self.search_after = Some(cursor);
+        self
+    }
+
+    /// Computes a corrected query when the search matches `max_hits` documents or less.
+    pub fn did_you_mean(&mut self, max_hits: u64) -> &mut Search<'a> {
+        self.did_you_mean = Some(max_hits);
+        self
+    }
+
+    /// Ranks the documents with these ranking rules instead of those of the index.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> &mut Search<'a> {
+        self.ranking_rules = Some(ranking_rules);
+        self
+    }
+
+    /// Groups the documents by the value of the field, returning `limit` groups of up to
+    /// `hits_per_group` documents, see [`SearchResult::groups`].
+    ///
+    /// The field is used as the distinct attribute, the candidates counting the groups.
+    /// Grouping is not supported by the hybrid search.
+    pub fn group_by(&mut self, field: String, hits_per_group: usize) -> &mut Search<'a> {
+        self.group_by = Some((field, hits_per_group));
+        self
+    }
+
+    /// Reorders the first `top_k` hits by the relevancy of their documents to the query,
+    /// as scored by the reranker on the texts rendered by the prompt.
+    ///
+    /// Placeholder searches are not reranked, and reranking is not supported by the grouping.
+    pub fn rerank(
+        &mut self,
+        reranker_name: String,
+        reranker: Arc<Reranker>,
+        prompt: Prompt,
+        top_k: usize,
+    ) -> &mut Search<'a> {
+        self.rerank = Some(RerankSearch { reranker_name, reranker, prompt, top_k });
+        self
+    }
+
     pub fn execute_for_candidates(&self, has_vector_search: bool) -> Result<RoaringBitmap> {
         if has_vector_search {
             let ctx = SearchContext::new(self.index, self.rtxn)?;
@@ -178,86 +246,238 @@ impl<'a> Search<'a> {
     }
 
     pub fn execute(&self) -> Result<SearchResult> {
+        if let Some(rerank) = &self.rerank {
+            let results = self.search_to_rerank(rerank.top_k).execute()?;
+            return rerank.apply(self, results);
+        }
+
         let mut ctx = SearchContext::new(self.index, self.rtxn)?;
 
         if let Some(searchable_attributes) = self.searchable_attributes {
             ctx.attributes_to_search_on(searchable_attributes)?;
         }
 
-        if let Some(distinct) = &self.distinct {
+        if let Some(ranking_rules) = &self.ranking_rules {
+            ctx.ranking_rules(ranking_rules.clone())?;
+        }
+
+        // the best document of each group is found by using the grouped field as distinct
+        let distinct = match &self.group_by {
+            Some((field, _)) => Some(field.clone()),
+            None => self.distinct.clone(),
+        };
+
+        if let Some(distinct) = &distinct {
             let filterable_fields = ctx.index.filterable_fields(ctx.txn)?;
             if !crate::is_faceted(distinct, &filterable_fields) {
                 let (valid_fields, hidden_fields) =
                     ctx.index.remove_hidden_fields(ctx.txn, filterable_fields)?;
-                return Err(Error::UserError(UserError::InvalidDistinctAttribute {
-                    field: distinct.clone(),
-                    valid_fields,
-                    hidden_fields,
+                let field = distinct.clone();
+                return Err(Error::UserError(match self.group_by {
+                    Some(_) => {
+                        UserError::InvalidGroupByAttribute { field, valid_fields, hidden_fields }
+                    }
+                    None => {
+                        UserError::InvalidDistinctAttribute { field, valid_fields, hidden_fields }
+                    }
                 }));
             }
         }
 
         let universe = filtered_universe(ctx.index, ctx.txn, &self.filter)?;
+        let group_universe = self.group_by.is_some().then(|| universe.clone());
         let PartialSearchResult {
             located_query_terms,
             candidates,
             documents_ids,
             document_scores,
+            cursor,
             degraded,
             used_negative_operator,
-        } = match self.semantic.as_ref() {
+        } = self.execute_partial(
+            &mut ctx,
+            universe,
+            &distinct,
+            1,
+            self.offset,
+            self.limit,
+            self.exhaustive_number_hits,
+            self.search_after.as_ref(),
+        )?;
+
+        let (documents_ids, document_scores, groups, degraded) =
+            match (&self.group_by, group_universe) {
+                (Some((field, hits_per_group)), Some(universe)) => {
+                    let (documents_ids, document_scores, groups, groups_degraded) = self
+                        .execute_groups(
+                            &mut ctx,
+                            field,
+                            *hits_per_group,
+                            universe,
+                            documents_ids,
+                            document_scores,
+                        )?;
+                    (documents_ids, document_scores, groups, degraded | groups_degraded)
+                }
+                _ => (documents_ids, document_scores, Vec::new(), degraded),
+            };
+
+        let corrected_query = match (&located_query_terms, self.did_you_mean) {
+            (Some(located_query_terms), Some(max_hits)) if candidates.len() <= max_hits => {
+                corrected_query(&mut ctx, located_query_terms)?
+            }
+            _ => None,
+        };
+
+        // consume context and located_query_terms to build MatchingWords.
+        let matching_words = match located_query_terms {
+            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
+            None => MatchingWords::default(),
+        };
+
+        Ok(SearchResult {
+            matching_words,
+            candidates,
+            document_scores,
+            documents_ids,
+            cursor,
+            degraded,
+            used_negative_operator,
+            corrected_query,
+            groups,
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn execute_partial(
+        &self,
+        ctx: &mut SearchContext<'_>,
+        universe: RoaringBitmap,
+        distinct: &Option<String>,
+        hits_per_distinct: usize,
+        offset: usize,
+        limit: usize,
+        exhaustive_number_hits: bool,
+        search_after: Option<&SearchCursor>,
+    ) -> Result<PartialSearchResult> {
+        match self.semantic.as_ref() {
             Some(SemanticSearch { vector: Some(vector), embedder_name, embedder }) => {
                 execute_vector_search(
-                    &mut ctx,
+                    ctx,
                     vector,
                     self.scoring_strategy,
                     universe,
                     &self.sort_criteria,
-                    &self.distinct,
+                    distinct,
+                    hits_per_distinct,
                     self.geo_strategy,
-                    self.offset,
-                    self.limit,
+                    offset,
+                    limit,
                     embedder_name,
                     embedder,
                     self.time_budget.clone(),
                     self.ranking_score_threshold,
-                )?
+                    search_after,
+                )
             }
             _ => execute_search(
-                &mut ctx,
+                ctx,
                 self.query.as_deref(),
                 self.terms_matching_strategy,
                 self.scoring_strategy,
-                self.exhaustive_number_hits,
+                exhaustive_number_hits,
                 universe,
                 &self.sort_criteria,
-                &self.distinct,
+                distinct,
+                hits_per_distinct,
                 self.geo_strategy,
-                self.offset,
-                self.limit,
+                offset,
+                limit,
                 Some(self.words_limit),
                 &mut DefaultSearchLogger,
                 &mut DefaultSearchLogger,
                 self.time_budget.clone(),
                 self.ranking_score_threshold,
                 self.locales.as_ref(),
-            )?,
+                search_after,
+            ),
+        }
+    }
+
+    /// Returns up to `hits_per_group` documents for the group of each of the given documents,
+    /// these documents being the best of their group.
+    ///
+    /// The documents of the groups are searched again among the documents sharing a value
+    /// with the best ones, keeping up to `hits_per_group` documents for each value.
+    #[allow(clippy::type_complexity)]
+    fn execute_groups(
+        &self,
+        ctx: &mut SearchContext<'_>,
+        field: &str,
+        hits_per_group: usize,
+        universe: RoaringBitmap,
+        documents_ids: Vec<DocumentId>,
+        document_scores: Vec<Vec<ScoreDetails>>,
+    ) -> Result<(Vec<DocumentId>, Vec<Vec<ScoreDetails>>, Vec<SearchGroup>, bool)> {
+        let Some(field_id) = ctx.index.fields_ids_map(ctx.txn)?.id(field) else {
+            // no document has the field, each document is its own group
+            let groups = documents_ids
+                .iter()
+                .map(|_| SearchGroup { value: serde_json::Value::Null, len: 1 })
+                .collect();
+            return Ok((documents_ids, document_scores, groups, false));
         };
 
-        // consume context and located_query_terms to build MatchingWords.
-        let matching_words = match located_query_terms {
-            Some(located_query_terms) => MatchingWords::new(ctx, located_query_terms),
-            None => MatchingWords::default(),
+        let mut groups = Vec::with_capacity(documents_ids.len());
+        let mut groups_docids = Vec::with_capacity(documents_ids.len());
+        for &docid in &documents_ids {
+            let value = distinct_value(ctx.index, ctx.txn, field_id, docid)?;
+            groups.push(SearchGroup { value, len: 0 });
+            groups_docids.push(distinct_value_docids(ctx.index, ctx.txn, field_id, docid)?);
+        }
+
+        let (documents_ids, document_scores, degraded) = if hits_per_group > 1 {
+            let mut groups_universe = RoaringBitmap::new();
+            for docids in &groups_docids {
+                groups_universe |= docids;
+            }
+            groups_universe &= universe;
+
+            let PartialSearchResult { documents_ids, document_scores, degraded, .. } = self
+                .execute_partial(
+                    ctx,
+                    groups_universe,
+                    &Some(field.to_owned()),
+                    hits_per_group,
+                    0,
+                    groups.len().saturating_mul(hits_per_group),
+                    false,
+                    None,
+                )?;
+            (documents_ids, document_scores, degraded)
+        } else {
+            (documents_ids, document_scores, false)
         };
 
-        Ok(SearchResult {
-            matching_words,
-            candidates,
-            document_scores,
-            documents_ids,
-            degraded,
-            used_negative_operator,
-        })
+        // sort the documents by group, keeping the ranking order inside of each group
+        let mut groups_hits = vec![Vec::new(); groups.len()];
+        for (docid, scores) in documents_ids.into_iter().zip(document_scores) {
+            if let Some(group) = groups_docids.iter().position(|docids| docids.contains(docid)) {
+                groups_hits[group].push((docid, scores));
+            }
+        }
+
+        let mut documents_ids = Vec::new();
+        let mut document_scores = Vec::new();
+        for (group, hits) in groups.iter_mut().zip(groups_hits) {
+            group.len = hits.len().min(hits_per_group);
+            for (docid, scores) in hits.into_iter().take(hits_per_group) {
+                documents_ids.push(docid);
+                document_scores.push(scores);
+            }
+        }
+
+        Ok((documents_ids, document_scores, groups, degraded))
     }
 }
 
@@ -282,6 +502,11 @@ impl fmt::Debug for Search<'_> {
             time_budget,
             ranking_score_threshold,
             locales,
+            search_after,
+            did_you_mean,
+            ranking_rules,
+            group_by,
+            rerank,
         } = self;
         f.debug_struct("Search")
             .field("query", query)
@@ -303,6 +528,11 @@ impl fmt::Debug for Search<'_> {
             .field("time_budget", time_budget)
             .field("ranking_score_threshold", ranking_score_threshold)
             .field("locales", locales)
+            .field("search_after", search_after)
+            .field("did_you_mean", did_you_mean)
+            .field("ranking_rules", ranking_rules)
+            .field("group_by", group_by)
+            .field("rerank.reranker_name", &rerank.as_ref().map(|rerank| &rerank.reranker_name))
             .finish()
     }
 }
@@ -313,8 +543,25 @@ pub struct SearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    /// The position of the last returned document, to be used to fetch the next page.
+    pub cursor: Option<SearchCursor>,
     pub degraded: bool,
     pub used_negative_operator: bool,
+    /// The query with its misspelled words corrected, see [`Search::did_you_mean`].
+    pub corrected_query: Option<String>,
+    /// The groups of the documents, see [`Search::group_by`].
+    ///
+    /// The documents are sorted by group, each group covering the next `len` documents.
+    pub groups: Vec<SearchGroup>,
+}
+
+/// A group of documents sharing a value of the grouped field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchGroup {
+    /// The value of the field of the best document of the group, `null` if it has none.
+    pub value: serde_json::Value,
+    /// The number of documents of the group.
+    pub len: usize,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -1,16 +1,21 @@
+use std::cmp::Ordering;
+
 use roaring::RoaringBitmap;
 
 use super::logger::SearchLogger;
 use super::ranking_rules::{BoxRankingRule, RankingRuleQueryTrait};
 use super::SearchContext;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
-use crate::search::new::distinct::{apply_distinct_rule, distinct_single_docid, DistinctOutput};
+use crate::search::cursor::SearchCursor;
+use crate::search::new::distinct::{DistinctOutput, DistinctRule};
 use crate::{Result, TimeBudget};
 
 pub struct BucketSortOutput {
     pub docids: Vec<u32>,
     pub scores: Vec<Vec<ScoreDetails>>,
     pub all_candidates: RoaringBitmap,
+    /// The position of the last returned document, `None` if no document was returned.
+    pub cursor: Option<SearchCursor>,
 
     pub degraded: bool,
 }
@@ -23,6 +28,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     mut ranking_rules: Vec<BoxRankingRule<'ctx, Q>>,
     query: &Q,
     distinct: Option<&str>,
+    hits_per_distinct: usize,
     universe: &RoaringBitmap,
     from: usize,
     length: usize,
@@ -30,6 +36,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     logger: &mut dyn SearchLogger<Q>,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<BucketSortOutput> {
     logger.initial_query(query);
     logger.ranking_rules(&ranking_rules);
@@ -45,17 +52,20 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     } else {
         None
     };
+    let mut distinct_rule =
+        distinct_fid.map(|distinct_fid| DistinctRule::new(distinct_fid, hits_per_distinct));
 
     if universe.len() < from as u64 {
         return Ok(BucketSortOutput {
             docids: vec![],
             scores: vec![],
             all_candidates: universe.clone(),
+            cursor: None,
             degraded: false,
         });
     }
     if ranking_rules.is_empty() {
-        if let Some(distinct_fid) = distinct_fid {
+        if let Some(distinct_rule) = &mut distinct_rule {
             let mut excluded = RoaringBitmap::new();
             let mut results = vec![];
             for docid in universe.iter() {
@@ -66,7 +76,12 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     continue;
                 }
 
-                distinct_single_docid(ctx.index, ctx.txn, distinct_fid, docid, &mut excluded)?;
+                distinct_rule.keep(ctx.index, ctx.txn, docid, &mut excluded)?;
+                // documents up to the cursor were returned by a previous page,
+                // but they must still exclude the documents sharing their distinct value.
+                if search_after.map_or(false, |cursor| docid <= cursor.docid) {
+                    continue;
+                }
                 results.push(docid);
             }
 
@@ -83,14 +98,21 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); results.len()],
+                cursor: results.last().copied().map(SearchCursor::from_docid),
                 docids: results,
                 all_candidates,
                 degraded: false,
             });
         } else {
-            let docids: Vec<u32> = universe.iter().skip(from).take(length).collect();
+            let docids: Vec<u32> = universe
+                .iter()
+                .skip_while(|&docid| search_after.map_or(false, |cursor| docid <= cursor.docid))
+                .skip(from)
+                .take(length)
+                .collect();
             return Ok(BucketSortOutput {
                 scores: vec![Default::default(); docids.len()],
+                cursor: docids.last().copied().map(SearchCursor::from_docid),
                 docids,
                 all_candidates: universe.clone(),
                 degraded: false,
@@ -105,6 +127,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     ranking_rules[0].start_iteration(ctx, logger, universe, query)?;
 
     let mut ranking_rule_scores: Vec<ScoreDetails> = vec![];
+    // The index of the bucket being explored for each ranking rule, in lockstep with `ranking_rule_scores`.
+    let mut ranking_rule_buckets: Vec<u32> = vec![];
+    // The number of buckets already returned by each ranking rule in its current iteration.
+    let mut ranking_rule_bucket_counts: Vec<u32> = vec![0; ranking_rules_len];
+    // Whether we are still exploring the buckets leading to the `search_after` cursor.
+    // All the documents ranked before the cursor are skipped without being sorted further.
+    let mut following_cursor = search_after.is_some();
 
     let mut ranking_rule_universes: Vec<RoaringBitmap> =
         vec![RoaringBitmap::default(); ranking_rules_len];
@@ -136,6 +165,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             }
             if ranking_rule_scores.len() > cur_ranking_rule_index {
                 ranking_rule_scores.pop();
+                ranking_rule_buckets.pop();
             }
         };
     }
@@ -144,9 +174,13 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
     let mut valid_docids = vec![];
     let mut valid_scores = vec![];
     let mut cur_offset = 0usize;
+    let mut cursor = None;
 
     macro_rules! maybe_add_to_results {
         ($candidates:expr) => {
+            maybe_add_to_results!($candidates, None)
+        };
+        ($candidates:expr, $after_docid:expr) => {
             maybe_add_to_results(
                 ctx,
                 from,
@@ -159,9 +193,12 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 &mut ranking_rules,
                 cur_ranking_rule_index,
                 &mut cur_offset,
-                distinct_fid,
+                &mut distinct_rule,
                 &ranking_rule_scores,
+                &ranking_rule_buckets,
                 $candidates,
+                $after_docid,
+                &mut cursor,
             )?;
         };
     }
@@ -171,6 +208,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             loop {
                 let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
                 ranking_rule_scores.push(ScoreDetails::Skipped);
+                ranking_rule_buckets.push(ranking_rule_bucket_counts[cur_ranking_rule_index]);
 
                 // remove candidates from the universe without adding them to result if their score is below the threshold
                 if let Some(ranking_score_threshold) = ranking_score_threshold {
@@ -182,9 +220,17 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                     }
                 }
 
-                maybe_add_to_results!(bucket);
+                // the documents up to the cursor were returned by previous pages, degraded or not
+                let after_docid = docid_after_cursor(
+                    search_after,
+                    &mut following_cursor,
+                    cur_ranking_rule_index,
+                    ranking_rule_bucket_counts[cur_ranking_rule_index],
+                );
+                maybe_add_to_results!(bucket, after_docid);
 
                 ranking_rule_scores.pop();
+                ranking_rule_buckets.pop();
 
                 if cur_ranking_rule_index == 0 {
                     break;
@@ -197,6 +243,7 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 scores: valid_scores,
                 docids: valid_docids,
                 all_candidates,
+                cursor,
                 degraded: true,
             });
         }
@@ -208,7 +255,18 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
                 && ranking_rule_universes[cur_ranking_rule_index].len() == 1)
         {
             let bucket = std::mem::take(&mut ranking_rule_universes[cur_ranking_rule_index]);
-            maybe_add_to_results!(bucket);
+            // the remaining documents form the next bucket of this ranking rule,
+            // its index is part of the path recorded in the cursor
+            let bucket_index = ranking_rule_bucket_counts[cur_ranking_rule_index];
+            ranking_rule_buckets.push(bucket_index);
+            let after_docid = docid_after_cursor(
+                search_after,
+                &mut following_cursor,
+                cur_ranking_rule_index,
+                bucket_index,
+            );
+            maybe_add_to_results!(bucket, after_docid);
+            ranking_rule_buckets.pop();
             back!();
             continue;
         }
@@ -223,7 +281,10 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
             continue;
         };
 
+        let bucket_index = ranking_rule_bucket_counts[cur_ranking_rule_index];
+        ranking_rule_bucket_counts[cur_ranking_rule_index] += 1;
         ranking_rule_scores.push(next_bucket.score);
+        ranking_rule_buckets.push(bucket_index);
 
         logger.next_bucket_ranking_rule(
             cur_ranking_rule_index,
@@ -249,16 +310,47 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
 
         ranking_rule_universes[cur_ranking_rule_index] -= &next_bucket.candidates;
 
-        if cur_ranking_rule_index == ranking_rules_len - 1
-            || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
-            || cur_offset + (next_bucket.candidates.len() as usize) < from
+        let mut leads_to_cursor = false;
+        if let Some(search_after) = search_after.filter(|_| following_cursor) {
+            match search_after.buckets.get(cur_ranking_rule_index).map(|b| bucket_index.cmp(b)) {
+                // the whole bucket was returned by previous pages, skip it without sorting it
+                Some(Ordering::Less) => {
+                    maybe_add_to_results!(next_bucket.candidates, Some(u32::MAX));
+                    ranking_rule_scores.pop();
+                    ranking_rule_buckets.pop();
+                    continue;
+                }
+                Some(Ordering::Equal)
+                    if cur_ranking_rule_index + 1 < search_after.buckets.len()
+                        && cur_ranking_rule_index != ranking_rules_len - 1 =>
+                {
+                    leads_to_cursor = true;
+                }
+                // this is the bucket containing the cursor, only keep the documents after it
+                Some(Ordering::Equal) | None => {
+                    following_cursor = false;
+                    maybe_add_to_results!(next_bucket.candidates, Some(search_after.docid));
+                    ranking_rule_scores.pop();
+                    ranking_rule_buckets.pop();
+                    continue;
+                }
+                Some(Ordering::Greater) => following_cursor = false,
+            }
+        }
+
+        if !leads_to_cursor
+            && (cur_ranking_rule_index == ranking_rules_len - 1
+                || (scoring_strategy == ScoringStrategy::Skip && next_bucket.candidates.len() <= 1)
+                || cur_offset + (next_bucket.candidates.len() as usize) < from)
         {
             maybe_add_to_results!(next_bucket.candidates);
             ranking_rule_scores.pop();
+            ranking_rule_buckets.pop();
             continue;
         }
 
         cur_ranking_rule_index += 1;
+        ranking_rule_bucket_counts[cur_ranking_rule_index] = 0;
         ranking_rule_universes[cur_ranking_rule_index].clone_from(&next_bucket.candidates);
         logger.start_iteration_ranking_rule(
             cur_ranking_rule_index,
@@ -278,12 +370,44 @@ pub fn bucket_sort<'ctx, Q: RankingRuleQueryTrait>(
         docids: valid_docids,
         scores: valid_scores,
         all_candidates,
+        cursor,
         degraded: false,
     })
 }
 
+/// Returns the docid up to which the documents of a bucket of the ranking rule at
+/// `ranking_rule_index` were returned by previous pages, when the search is still following
+/// the `search_after` cursor.
+///
+/// The whole bucket was returned when it comes before the bucket of the cursor, `u32::MAX`
+/// is returned then. The cursor is no longer followed once its bucket has been reached.
+fn docid_after_cursor(
+    search_after: Option<&SearchCursor>,
+    following_cursor: &mut bool,
+    ranking_rule_index: usize,
+    bucket_index: u32,
+) -> Option<u32> {
+    let search_after = search_after.filter(|_| *following_cursor)?;
+    match search_after.buckets.get(ranking_rule_index).map(|b| bucket_index.cmp(b)) {
+        // the whole bucket was returned by previous pages
+        Some(Ordering::Less) => Some(u32::MAX),
+        // this is the bucket containing the cursor, only keep the documents after it
+        Some(Ordering::Equal) | None => {
+            *following_cursor = false;
+            Some(search_after.docid)
+        }
+        Some(Ordering::Greater) => {
+            *following_cursor = false;
+            None
+        }
+    }
+}
+
 /// Add the candidates to the results. Take `distinct`, `from`, `length`, and `cur_offset`
 /// into account and inform the logger.
+///
+/// When `after_docid` is set, the candidates up to this docid are only used to apply
+/// the distinct rule: they were returned by a previous page.
 #[allow(clippy::too_many_arguments)]
 fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     ctx: &mut SearchContext<'ctx>,
@@ -302,14 +426,16 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
 
     cur_offset: &mut usize,
 
-    distinct_fid: Option<u16>,
+    distinct_rule: &mut Option<DistinctRule>,
     ranking_rule_scores: &[ScoreDetails],
+    ranking_rule_buckets: &[u32],
     candidates: RoaringBitmap,
+    after_docid: Option<u32>,
+    cursor: &mut Option<SearchCursor>,
 ) -> Result<()> {
     // First apply the distinct rule on the candidates, reducing the universes if necessary
-    let candidates = if let Some(distinct_fid) = distinct_fid {
-        let DistinctOutput { remaining, excluded } =
-            apply_distinct_rule(ctx, distinct_fid, &candidates)?;
+    let candidates = if let Some(distinct_rule) = distinct_rule {
+        let DistinctOutput { remaining, excluded } = distinct_rule.apply(ctx, &candidates)?;
         for universe in ranking_rule_universes.iter_mut() {
             *universe -= &excluded;
             *all_candidates -= &excluded;
@@ -320,6 +446,15 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     };
     *all_candidates |= &candidates;
 
+    let candidates = match after_docid {
+        Some(after_docid) => {
+            let mut candidates = candidates;
+            candidates.remove_range(..=after_docid);
+            candidates
+        }
+        None => candidates,
+    };
+
     // if the candidates are empty, there is nothing to do;
     if candidates.is_empty() {
         return Ok(());
@@ -347,6 +482,7 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
             );
             let candidates =
                 candidates.iter().take(length - valid_docids.len()).copied().collect::<Vec<_>>();
+            update_cursor(cursor, ranking_rule_buckets, &candidates);
             logger.add_to_results(&candidates);
             valid_docids.extend_from_slice(&candidates);
             valid_scores
@@ -355,6 +491,7 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     } else {
         // if we have passed the offset already, add some of the documents (up to the limit)
         let candidates = candidates.iter().take(length - valid_docids.len()).collect::<Vec<u32>>();
+        update_cursor(cursor, ranking_rule_buckets, &candidates);
         logger.add_to_results(&candidates);
         valid_docids.extend_from_slice(&candidates);
         valid_scores
@@ -364,3 +501,10 @@ fn maybe_add_to_results<'ctx, Q: RankingRuleQueryTrait>(
     *cur_offset += candidates.len() as usize;
     Ok(())
 }
+
+/// Point the cursor to the last document added to the results.
+fn update_cursor(cursor: &mut Option<SearchCursor>, buckets: &[u32], candidates: &[u32]) {
+    if let Some(&docid) = candidates.last() {
+        *cursor = Some(SearchCursor { buckets: buckets.to_vec(), docid });
+    }
+}
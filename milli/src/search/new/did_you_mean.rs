@@ -0,0 +1,38 @@
+use super::query_term::LocatedQueryTerm;
+use super::SearchContext;
+use crate::Result;
+
+/// Builds the query in which every word that matches no word of the index
+/// is replaced by its most frequent typo derivation.
+///
+/// The corrected query is made of the normalized words of the query, the stop words
+/// and negative words are not part of it. Returns `None` if no word needs a correction.
+pub fn corrected_query(
+    ctx: &mut SearchContext<'_>,
+    located_query_terms: &[LocatedQueryTerm],
+) -> Result<Option<String>> {
+    let mut words = Vec::with_capacity(located_query_terms.len());
+    let mut corrected = false;
+
+    for located_term in located_query_terms {
+        let term = located_term.value;
+        // the ngrams are built from the words of the query, they are not part of it
+        if ctx.term_interner.get(term).is_ngram() {
+            continue;
+        }
+        if let Some(phrase) = ctx.term_interner.get(term).original_phrase() {
+            words.push(format!("\"{}\"", phrase.description(ctx)));
+            continue;
+        }
+
+        match term.most_frequent_correction(ctx)? {
+            Some(correction) => {
+                words.push(ctx.word_interner.get(correction).clone());
+                corrected = true;
+            }
+            None => words.push(ctx.term_interner.get(term).original_word(ctx)),
+        }
+    }
+
+    Ok(corrected.then(|| words.join(" ")))
+}
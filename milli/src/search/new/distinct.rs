@@ -1,12 +1,16 @@
+use std::collections::HashMap;
+
 use heed::types::{Bytes, Str, Unit};
-use heed::{Database, RoPrefix, RoTxn};
+use heed::{BytesDecode, Database, RoPrefix, RoTxn};
 use roaring::RoaringBitmap;
 
 const FID_SIZE: usize = 2;
 const DOCID_SIZE: usize = 4;
 
+use crate::facet::FacetType;
 use crate::heed_codec::facet::{
     FacetGroupKey, FacetGroupKeyCodec, FacetGroupValueCodec, FieldDocIdFacetCodec,
+    FieldDocIdFacetF64Codec,
 };
 use crate::heed_codec::BytesRefCodec;
 use crate::{Index, Result, SearchContext};
@@ -47,26 +51,137 @@ pub fn distinct_single_docid(
     docid: u32,
     excluded: &mut RoaringBitmap,
 ) -> Result<()> {
-    for item in facet_string_values(docid, field_id, index, txn)? {
-        let ((_, _, facet_value), _) = item?;
-        if let Some(facet_docids) = facet_value_docids(
-            index.facet_id_string_docids.remap_types(),
-            txn,
-            field_id,
-            facet_value,
-        )? {
-            *excluded |= facet_docids;
+    DistinctRule::new(field_id, 1).keep(index, txn, docid, excluded)
+}
+
+/// The distinct rule keeping up to `limit` documents for each distinct value of a field,
+/// instead of exactly one.
+///
+/// The documents must be given in ranking order across calls, so that the best documents
+/// of each value are kept. A document is kept as long as none of its values was already
+/// kept `limit` times.
+pub struct DistinctRule {
+    field_id: u16,
+    limit: usize,
+    /// The number of documents kept for each value of the field, by facet key.
+    kept_values: HashMap<(FacetType, Vec<u8>), usize>,
+    kept: RoaringBitmap,
+}
+
+impl DistinctRule {
+    pub fn new(field_id: u16, limit: usize) -> Self {
+        Self { field_id, limit, kept_values: HashMap::new(), kept: RoaringBitmap::new() }
+    }
+
+    /// Return a [`DistinctOutput`] containing:
+    /// - `remaining`: the documents of `candidates` kept by the rule.
+    /// - `excluded`: the set of document ids that contain a value for the field that
+    /// was kept `limit` times, excluding the documents kept by the rule.
+    pub fn apply(
+        &mut self,
+        ctx: &mut SearchContext<'_>,
+        candidates: &RoaringBitmap,
+    ) -> Result<DistinctOutput> {
+        let mut excluded = RoaringBitmap::new();
+        let mut remaining = RoaringBitmap::new();
+        for docid in candidates {
+            if excluded.contains(docid) {
+                continue;
+            }
+            self.keep(ctx.index, ctx.txn, docid, &mut excluded)?;
+            remaining.push(docid);
         }
+        excluded -= &self.kept;
+        Ok(DistinctOutput { remaining, excluded })
     }
-    for item in facet_number_values(docid, field_id, index, txn)? {
-        let ((_, _, facet_value), _) = item?;
-        if let Some(facet_docids) =
-            facet_value_docids(index.facet_id_f64_docids.remap_types(), txn, field_id, facet_value)?
-        {
-            *excluded |= facet_docids;
+
+    /// Keep a single document, adding the documents of its values that were kept
+    /// `limit` times to `excluded`.
+    pub fn keep(
+        &mut self,
+        index: &Index,
+        txn: &RoTxn<'_>,
+        docid: u32,
+        excluded: &mut RoaringBitmap,
+    ) -> Result<()> {
+        let field_id = self.field_id;
+        for item in facet_string_values(docid, field_id, index, txn)? {
+            let ((_, _, facet_value), _) = item?;
+            if self.limit_reached(FacetType::String, facet_value) {
+                if let Some(facet_docids) = facet_value_docids(
+                    index.facet_id_string_docids.remap_types(),
+                    txn,
+                    field_id,
+                    facet_value,
+                )? {
+                    *excluded |= facet_docids;
+                }
+            }
         }
+        for item in facet_number_values(docid, field_id, index, txn)? {
+            let ((_, _, facet_value), _) = item?;
+            if self.limit_reached(FacetType::Number, facet_value) {
+                if let Some(facet_docids) = facet_value_docids(
+                    index.facet_id_f64_docids.remap_types(),
+                    txn,
+                    field_id,
+                    facet_value,
+                )? {
+                    *excluded |= facet_docids;
+                }
+            }
+        }
+        self.kept.insert(docid);
+        Ok(())
+    }
+
+    /// Count one more kept document for the value, returning whether it was kept `limit` times.
+    fn limit_reached(&mut self, facet_type: FacetType, facet_value: &[u8]) -> bool {
+        if self.limit <= 1 {
+            return true;
+        }
+        let count = self.kept_values.entry((facet_type, facet_value.to_vec())).or_default();
+        *count += 1;
+        *count >= self.limit
+    }
+}
+
+/// Return the docids sharing a value of the field with the given document,
+/// including the document itself.
+pub fn distinct_value_docids(
+    index: &Index,
+    txn: &RoTxn<'_>,
+    field_id: u16,
+    docid: u32,
+) -> Result<RoaringBitmap> {
+    let mut docids = RoaringBitmap::new();
+    distinct_single_docid(index, txn, field_id, docid, &mut docids)?;
+    docids.insert(docid);
+    Ok(docids)
+}
+
+/// Return the first value of the field of the given document, its first string
+/// value or else its first number value, `null` if it has none.
+pub fn distinct_value(
+    index: &Index,
+    txn: &RoTxn<'_>,
+    field_id: u16,
+    docid: u32,
+) -> Result<serde_json::Value> {
+    if let Some(item) = facet_string_values(docid, field_id, index, txn)?.next() {
+        let (_, original) = item?;
+        return Ok(serde_json::Value::String(original.to_owned()));
+    }
+    let key = facet_values_prefix_key(field_id, docid);
+    let mut numbers =
+        index.field_id_docid_facet_f64s.remap_key_type::<Bytes>().prefix_iter(txn, &key)?;
+    if let Some(item) = numbers.next() {
+        let (key, ()) = item?;
+        let (_, _, number) =
+            FieldDocIdFacetF64Codec::bytes_decode(key).map_err(heed::Error::Decoding)?;
+        return Ok(serde_json::Number::from_f64(number).into());
     }
-    Ok(())
+    Ok(serde_json::Value::Null)
 }
 
 /// Return all the docids containing the given value in the given field
@@ -0,0 +1,188 @@
+use heed::BytesDecode;
+use roaring::RoaringBitmap;
+use time::OffsetDateTime;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::heed_codec::facet::{FacetGroupKeyCodec, OrderedF64Codec};
+use crate::heed_codec::BytesRefCodec;
+use crate::score_details::{self, Rank, ScoreDetails};
+use crate::search::facet::{ascending_facet_sort, facet_max_value};
+use crate::{Boost, BoostModifier, Criterion, Decay, FieldId, Index, Result};
+
+/// The number of distinct scores given by the `decay` and `boost` ranking rules.
+///
+/// Documents whose scores round to the same level end up in the same bucket,
+/// so that the next ranking rules can still order them.
+const SCORE_LEVELS: u32 = 20;
+
+enum ScoreFunction {
+    /// The score halves every `scale` away from the `origin`.
+    Decay { origin: f64, scale: f64 },
+    /// The modified value relative to the modified highest value of the field.
+    Boost { modifier: BoostModifier, max: f64 },
+}
+
+impl ScoreFunction {
+    fn score(&self, value: f64) -> f64 {
+        let score = match *self {
+            ScoreFunction::Decay { origin, scale } => 0.5f64.powf((value - origin).abs() / scale),
+            ScoreFunction::Boost { modifier, max } if max > 0.0 => modifier.apply(value) / max,
+            ScoreFunction::Boost { .. } => 0.0,
+        };
+        score.clamp(0.0, 1.0)
+    }
+}
+
+/// A ranking rule turning the numeric values of a field into a score
+/// that blends with the scores of the other ranking rules.
+///
+/// Documents are returned from the best score level to the worst,
+/// documents without a numeric value for the field being returned last.
+pub struct FieldScore<Query> {
+    rule: String,
+    field_id: Option<FieldId>,
+    function: ScoreFunction,
+    redacted: bool,
+    original_query: Option<Query>,
+    /// The documents and rank of each score level, the best level last.
+    buckets: Vec<(u32, RoaringBitmap)>,
+}
+
+impl<Query> FieldScore<Query> {
+    pub fn decay(index: &Index, rtxn: &heed::RoTxn<'_>, decay: Decay) -> Result<Self> {
+        let origin = match decay.origin()? {
+            Some(origin) => origin,
+            None => OffsetDateTime::now_utc().unix_timestamp_nanos() as f64 / 1_000_000_000.0,
+        };
+        let function = ScoreFunction::Decay { origin, scale: decay.scale()? };
+        Self::new(index, rtxn, Criterion::Decay(decay), function)
+    }
+
+    pub fn boost(index: &Index, rtxn: &heed::RoTxn<'_>, boost: Boost) -> Result<Self> {
+        let fields_ids_map = index.fields_ids_map(rtxn)?;
+        let max = match fields_ids_map.id(&boost.field) {
+            Some(field_id) => {
+                facet_max_value(index, rtxn, field_id, index.documents_ids(rtxn)?)?.unwrap_or(0.0)
+            }
+            None => 0.0,
+        };
+        let function =
+            ScoreFunction::Boost { modifier: boost.modifier, max: boost.modifier.apply(max) };
+        Self::new(index, rtxn, Criterion::Boost(boost), function)
+    }
+
+    fn new(
+        index: &Index,
+        rtxn: &heed::RoTxn<'_>,
+        criterion: Criterion,
+        function: ScoreFunction,
+    ) -> Result<Self> {
+        let field_name = criterion.field_name().unwrap_or_default();
+        let field_id = index.fields_ids_map(rtxn)?.id(field_name);
+        let redacted = match index.displayed_fields(rtxn)? {
+            Some(displayed_fields) => !displayed_fields.contains(&field_name),
+            None => false,
+        };
+
+        Ok(Self {
+            rule: criterion.to_string(),
+            field_id,
+            function,
+            redacted,
+            original_query: None,
+            buckets: Vec::new(),
+        })
+    }
+
+    fn output(
+        &self,
+        query: Query,
+        rank: u32,
+        candidates: RoaringBitmap,
+    ) -> RankingRuleOutput<Query> {
+        RankingRuleOutput {
+            query,
+            candidates,
+            score: ScoreDetails::FieldScore(score_details::FieldScore {
+                rule: self.rule.clone(),
+                redacted: self.redacted,
+                rank: Rank { rank, max_rank: SCORE_LEVELS },
+            }),
+        }
+    }
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for FieldScore<Query> {
+    fn id(&self) -> String {
+        self.rule.clone()
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::field_score")]
+    fn start_iteration(
+        &mut self,
+        ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        let mut levels = vec![RoaringBitmap::new(); SCORE_LEVELS as usize + 1];
+        if let Some(field_id) = self.field_id {
+            let number_db =
+                ctx.index.facet_id_f64_docids.remap_key_type::<FacetGroupKeyCodec<BytesRefCodec>>();
+            for result in
+                ascending_facet_sort(ctx.txn, number_db, field_id, parent_candidates.clone())?
+            {
+                let (docids, bytes) = result?;
+                let value = OrderedF64Codec::bytes_decode(bytes).map_err(heed::Error::Decoding)?;
+                let level = (self.function.score(value) * SCORE_LEVELS as f64).round() as usize;
+                levels[level] |= docids;
+            }
+        }
+
+        // A document with several values gets the score of its best value.
+        // The documents of the two lowest levels are returned with the documents without a value,
+        // with the lowest non-zero rank.
+        let mut scored = RoaringBitmap::new();
+        let mut buckets = Vec::new();
+        for (level, mut docids) in levels.into_iter().enumerate().skip(2).rev() {
+            docids -= &scored;
+            scored |= &docids;
+            if !docids.is_empty() {
+                buckets.push((level as u32, docids));
+            }
+        }
+        buckets.reverse();
+
+        self.original_query = Some(parent_query.clone());
+        self.buckets = buckets;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::field_score")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        while let Some((rank, mut candidates)) = self.buckets.pop() {
+            candidates &= universe;
+            if !candidates.is_empty() {
+                return Ok(Some(self.output(query, rank, candidates)));
+            }
+        }
+        Ok(Some(self.output(query, 1, universe.clone())))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::field_score")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.buckets.clear();
+    }
+}
@@ -1,4 +1,5 @@
-use std::collections::VecDeque;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::iter::FromIterator;
 
 use heed::types::{Bytes, Unit};
@@ -115,24 +116,11 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
         debug_assert!(self.field_ids.is_some(), "fill_buffer can't be called without the lat&lng");
         debug_assert!(self.cached_sorted_docids.is_empty());
 
-        // lazily initialize the rtree if needed by the strategy, and cache it in `self.rtree`
-        let rtree = if self.strategy.use_rtree(geo_candidates.len() as usize) {
-            if let Some(rtree) = self.rtree.as_ref() {
-                // get rtree from cache
-                Some(rtree)
-            } else {
-                let rtree = ctx.index.geo_rtree(ctx.txn)?.expect("geo candidates but no rtree");
-                // insert rtree in cache and returns it.
-                // Can't use `get_or_insert_with` because getting the rtree from the DB is a fallible operation.
-                Some(&*self.rtree.insert(rtree))
-            }
-        } else {
-            None
-        };
-
         let cache_size = self.strategy.cache_size();
-        if let Some(rtree) = rtree {
+        if self.strategy.use_rtree(geo_candidates.len() as usize) {
+            let rtree = cached_rtree(&mut self.rtree, ctx)?;
             if self.ascending {
+                // the first point we find for a document is its nearest point
                 let point = lat_lng_to_xyz(&self.point);
                 for point in rtree.nearest_neighbor_iter(&point) {
                     if geo_candidates.contains(point.data.0) {
@@ -142,6 +130,15 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
                         }
                     }
                 }
+            } else if rtree.size() as u64 > self.geo_candidates.len() {
+                // Some documents contain several points. Starting from the opposite of the
+                // queried point would find the farthest point of a document first, whereas
+                // documents must be sorted by their nearest point.
+                let mut documents = nearest_points(rtree, geo_candidates, self.point);
+                documents.sort_by_cached_key(|(_, p)| {
+                    distance_between_two_points(&self.point, p) as usize
+                });
+                self.cached_sorted_docids.extend(documents);
             } else {
                 // in the case of the desc geo sort we look for the closest point to the opposite of the queried point
                 // and we insert the points in reverse order they get reversed when emptying the cache later on
@@ -159,10 +156,21 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
             // the iterative version
             let [lat, lng] = self.field_ids.unwrap();
 
-            let mut documents = geo_candidates
-                .iter()
-                .map(|id| -> Result<_> { Ok((id, geo_value(id, lat, lng, ctx.index, ctx.txn)?)) })
-                .collect::<Result<Vec<(u32, [f64; 2])>>>()?;
+            let mut documents = Vec::with_capacity(geo_candidates.len() as usize);
+            let mut multiple_points_docids = RoaringBitmap::new();
+            for id in geo_candidates {
+                match geo_value(id, lat, lng, ctx.index, ctx.txn)? {
+                    Some(point) => documents.push((id, point)),
+                    None => {
+                        multiple_points_docids.insert(id);
+                    }
+                }
+            }
+            // the nearest point of the documents containing several points is found in the rtree
+            if !multiple_points_docids.is_empty() {
+                let rtree = cached_rtree(&mut self.rtree, ctx)?;
+                documents.extend(nearest_points(rtree, &multiple_points_docids, self.point));
+            }
             // computing the distance between two points is expensive thus we cache the result
             documents
                 .sort_by_cached_key(|(_, p)| distance_between_two_points(&self.point, p) as usize);
@@ -173,24 +181,77 @@ impl<Q: RankingRuleQueryTrait> GeoSort<Q> {
     }
 }
 
+/// Returns the rtree cached in `cache`, reading it from the database the first time.
+fn cached_rtree<'a>(
+    cache: &'a mut Option<RTree<GeoPoint>>,
+    ctx: &SearchContext<'_>,
+) -> Result<&'a RTree<GeoPoint>> {
+    match cache {
+        Some(rtree) => Ok(rtree),
+        None => {
+            let rtree = ctx.index.geo_rtree(ctx.txn)?.expect("geo candidates but no rtree");
+            // Can't use `get_or_insert_with` because getting the rtree from the DB is a fallible operation.
+            Ok(cache.insert(rtree))
+        }
+    }
+}
+
+/// Returns the point of each of the `candidates` documents that is the nearest to `target`.
+///
+/// This is done by scanning the whole rtree, thus it must only be used for the documents
+/// containing several points.
+fn nearest_points(
+    rtree: &RTree<GeoPoint>,
+    candidates: &RoaringBitmap,
+    target: [f64; 2],
+) -> Vec<(u32, [f64; 2])> {
+    let mut nearest: HashMap<u32, (f64, [f64; 2])> = HashMap::new();
+    for point in rtree.iter() {
+        let (id, lat_lng) = point.data;
+        if !candidates.contains(id) {
+            continue;
+        }
+        let distance = distance_between_two_points(&target, &lat_lng);
+        match nearest.entry(id) {
+            Entry::Occupied(mut entry) => {
+                if distance < entry.get().0 {
+                    entry.insert((distance, lat_lng));
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((distance, lat_lng));
+            }
+        }
+    }
+    let mut documents: Vec<_> = nearest.into_iter().map(|(id, (_, point))| (id, point)).collect();
+    // the hashmap is unordered, documents at the same distance must be sorted by id
+    documents.sort_unstable_by_key(|(id, _)| *id);
+    documents
+}
+
 /// Extracts the lat and long values from a single document.
 ///
 /// If it is not able to find it in the facet number index it will extract it
 /// from the facet string index and parse it as f64 (as the geo extraction behaves).
+///
+/// Returns `None` if the document contains several points.
 fn geo_value(
     docid: u32,
     field_lat: u16,
     field_lng: u16,
     index: &Index,
     rtxn: &RoTxn<'_>,
-) -> Result<[f64; 2]> {
-    let extract_geo = |geo_field: u16| -> Result<f64> {
-        match facet_number_values(docid, geo_field, index, rtxn)?.next() {
-            Some(Ok(((_, _, geo), ()))) => Ok(geo),
+) -> Result<Option<[f64; 2]>> {
+    let extract_geo = |geo_field: u16| -> Result<Option<f64>> {
+        let mut numbers = facet_number_values(docid, geo_field, index, rtxn)?;
+        match numbers.next() {
+            // a document with several points has several latitudes or longitudes
+            Some(Ok(_)) if numbers.next().is_some() => Ok(None),
+            Some(Ok(((_, _, geo), ()))) => Ok(Some(geo)),
             Some(Err(e)) => Err(e.into()),
             None => match facet_string_values(docid, geo_field, index, rtxn)?.next() {
                 Some(Ok((_, geo))) => {
-                    Ok(geo.parse::<f64>().expect("cannot parse geo field as f64"))
+                    Ok(Some(geo.parse::<f64>().expect("cannot parse geo field as f64")))
                 }
                 Some(Err(e)) => Err(e.into()),
                 None => panic!("A geo faceted document doesn't contain any lat or lng"),
@@ -198,10 +259,10 @@ fn geo_value(
         }
     };
 
-    let lat = extract_geo(field_lat)?;
-    let lng = extract_geo(field_lng)?;
-
-    Ok([lat, lng])
+    match (extract_geo(field_lat)?, extract_geo(field_lng)?) {
+        (Some(lat), Some(lng)) => Ok(Some([lat, lng])),
+        _ => Ok(None),
+    }
 }
 
 impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for GeoSort<Q> {
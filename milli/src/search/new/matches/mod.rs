@@ -529,6 +529,7 @@ mod tests {
                 universe,
                 &None,
                 &None,
+                1,
                 crate::search::new::GeoSortStrategy::default(),
                 0,
                 100,
@@ -538,6 +539,7 @@ mod tests {
                 TimeBudget::max(),
                 None,
                 None,
+                None,
             )
             .unwrap();
 
@@ -1,5 +1,6 @@
 mod bucket_sort;
 mod db_cache;
+mod did_you_mean;
 mod distinct;
 mod geo_sort;
 mod graph_based_ranking_rule;
@@ -9,12 +10,14 @@ mod logger;
 pub mod matches;
 mod query_graph;
 mod query_term;
+mod random_sort;
 mod ranking_rule_graph;
 mod ranking_rules;
 mod resolve_query_graph;
 mod small_bitmap;
 
 mod exact_attribute;
+mod field_score;
 mod sort;
 mod vector_sort;
 
@@ -26,7 +29,10 @@ use std::collections::HashSet;
 use bucket_sort::{bucket_sort, BucketSortOutput};
 use charabia::{Language, TokenizerBuilder};
 use db_cache::DatabaseCache;
+pub use did_you_mean::corrected_query;
+pub use distinct::{distinct_value, distinct_value_docids};
 use exact_attribute::ExactAttribute;
+use field_score::FieldScore;
 use graph_based_ranking_rule::{Exactness, Fid, Position, Proximity, Typo};
 use heed::RoTxn;
 use interner::{DedupInterner, Interner};
@@ -36,12 +42,14 @@ use query_graph::{QueryGraph, QueryNode};
 use query_term::{
     located_query_terms_from_tokens, ExtractedTokens, LocatedQueryTerm, Phrase, QueryTerm,
 };
+use random_sort::RandomSort;
 use ranking_rules::{
     BoxRankingRule, PlaceholderQuery, RankingRule, RankingRuleOutput, RankingRuleQueryTrait,
 };
 use resolve_query_graph::{compute_query_graph_docids, PhraseDocIdsCache};
 use roaring::RoaringBitmap;
 use sort::Sort;
+use time::OffsetDateTime;
 
 use self::distinct::facet_string_values;
 use self::geo_sort::GeoSort;
@@ -51,11 +59,12 @@ use self::interner::Interned;
 use self::vector_sort::VectorSort;
 use crate::localized_attributes_rules::LocalizedFieldIds;
 use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::search::cursor::SearchCursor;
 use crate::search::new::distinct::apply_distinct_rule;
 use crate::vector::Embedder;
 use crate::{
-    AscDesc, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy, TimeBudget,
-    UserError, Weight,
+    AscDesc, Criterion, DocumentId, FieldId, Filter, Index, Member, Result, TermsMatchingStrategy,
+    TimeBudget, UserError, Weight,
 };
 
 /// A structure used throughout the execution of a search query.
@@ -68,6 +77,7 @@ pub struct SearchContext<'ctx> {
     pub term_interner: Interner<QueryTerm>,
     pub phrase_docids: PhraseDocIdsCache,
     pub restricted_fids: Option<RestrictedFids>,
+    pub ranking_rules: Option<Vec<Criterion>>,
 }
 
 impl<'ctx> SearchContext<'ctx> {
@@ -94,6 +104,7 @@ impl<'ctx> SearchContext<'ctx> {
             term_interner: <_>::default(),
             phrase_docids: <_>::default(),
             restricted_fids: None,
+            ranking_rules: None,
         })
     }
 
@@ -153,6 +164,41 @@ impl<'ctx> SearchContext<'ctx> {
 
         Ok(())
     }
+
+    /// Replaces the ranking rules of the index by the given ones for this search only.
+    pub fn ranking_rules(&mut self, ranking_rules: Vec<Criterion>) -> Result<()> {
+        let settings_ranking_rules = self.index.criteria(self.txn)?;
+        let sortable_fields = self.index.sortable_fields(self.txn)?;
+
+        for criterion in &ranking_rules {
+            let Some(field) = criterion.field_name() else { continue };
+            // The fields of the index ranking rules are always faceted.
+            let in_settings = settings_ranking_rules
+                .iter()
+                .any(|criterion| criterion.field_name() == Some(field));
+            if !in_settings && !crate::is_faceted(field, &sortable_fields) {
+                let (valid_fields, hidden_fields) =
+                    self.index.remove_hidden_fields(self.txn, sortable_fields)?;
+                return Err(UserError::InvalidRankingRuleSortableAttribute {
+                    field: field.to_string(),
+                    valid_fields,
+                    hidden_fields,
+                }
+                .into());
+            }
+        }
+
+        self.ranking_rules = Some(ranking_rules);
+        Ok(())
+    }
+
+    /// The ranking rules of this search, those of the index unless they were replaced.
+    fn criteria(&self) -> Result<Vec<Criterion>> {
+        match &self.ranking_rules {
+            Some(ranking_rules) => Ok(ranking_rules.clone()),
+            None => Ok(self.index.criteria(self.txn)?),
+        }
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, PartialOrd, Ord, Eq)]
@@ -270,7 +316,7 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
     let mut sorted_fields = HashSet::new();
     let mut geo_sorted = false;
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, PlaceholderQuery>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             // These rules need a query to have an effect; ignore them in placeholder search
@@ -307,6 +353,12 @@ fn get_ranking_rules_for_placeholder_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(FieldScore::decay(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(boost) => {
+                ranking_rules.push(Box::new(FieldScore::boost(ctx.index, ctx.txn, boost)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -330,7 +382,7 @@ fn get_ranking_rules_for_vector<'ctx>(
     let mut vector = false;
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, PlaceholderQuery>> = vec![];
 
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         match rr {
             crate::Criterion::Words
@@ -380,6 +432,12 @@ fn get_ranking_rules_for_vector<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(FieldScore::decay(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(boost) => {
+                ranking_rules.push(Box::new(FieldScore::boost(ctx.index, ctx.txn, boost)?));
+            }
         }
     }
 
@@ -409,7 +467,7 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
     }
 
     let mut ranking_rules: Vec<BoxRankingRule<'ctx, QueryGraph>> = vec![];
-    let settings_ranking_rules = ctx.index.criteria(ctx.txn)?;
+    let settings_ranking_rules = ctx.criteria()?;
     for rr in settings_ranking_rules {
         // Add Words before any of: typo, proximity, attribute
         match rr {
@@ -490,6 +548,12 @@ fn get_ranking_rules_for_query_graph_search<'ctx>(
                 sorted_fields.insert(field_name.clone());
                 ranking_rules.push(Box::new(Sort::new(ctx.index, ctx.txn, field_name, false)?));
             }
+            crate::Criterion::Decay(decay) => {
+                ranking_rules.push(Box::new(FieldScore::decay(ctx.index, ctx.txn, decay)?));
+            }
+            crate::Criterion::Boost(boost) => {
+                ranking_rules.push(Box::new(FieldScore::boost(ctx.index, ctx.txn, boost)?));
+            }
         }
     }
     Ok(ranking_rules)
@@ -545,6 +609,12 @@ fn resolve_sort_criteria<'ctx, Query: RankingRuleQueryTrait>(
                     false,
                 )?));
             }
+            AscDesc::Asc(Member::Random(seed)) => {
+                ranking_rules.push(Box::new(RandomSort::new(seed, true)));
+            }
+            AscDesc::Desc(Member::Random(seed)) => {
+                ranking_rules.push(Box::new(RandomSort::new(seed, false)));
+            }
         };
     }
     Ok(())
@@ -556,11 +626,14 @@ pub fn filtered_universe(
     txn: &RoTxn<'_>,
     filters: &Option<Filter<'_>>,
 ) -> Result<RoaringBitmap> {
-    Ok(if let Some(filters) = filters {
+    let mut universe = if let Some(filters) = filters {
         filters.evaluate(txn, index)?
     } else {
         index.documents_ids(txn)?
-    })
+    };
+    // the expired documents are hidden until they are deleted.
+    universe -= index.expired_documents_ids(txn, OffsetDateTime::now_utc())?;
+    Ok(universe)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -571,6 +644,7 @@ pub fn execute_vector_search(
     universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
+    hits_per_distinct: usize,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
@@ -578,6 +652,7 @@ pub fn execute_vector_search(
     embedder: &Embedder,
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
@@ -597,11 +672,12 @@ pub fn execute_vector_search(
     let placeholder_search_logger: &mut dyn SearchLogger<PlaceholderQuery> =
         &mut placeholder_search_logger;
 
-    let BucketSortOutput { docids, scores, all_candidates, degraded } = bucket_sort(
+    let BucketSortOutput { docids, scores, all_candidates, cursor, degraded } = bucket_sort(
         ctx,
         ranking_rules,
         &PlaceholderQuery,
         distinct.as_deref(),
+        hits_per_distinct,
         &universe,
         from,
         length,
@@ -609,6 +685,7 @@ pub fn execute_vector_search(
         placeholder_search_logger,
         time_budget,
         ranking_score_threshold,
+        search_after,
     )?;
 
     Ok(PartialSearchResult {
@@ -616,6 +693,7 @@ pub fn execute_vector_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms: None,
+        cursor,
         degraded,
         used_negative_operator: false,
     })
@@ -632,6 +710,7 @@ pub fn execute_search(
     mut universe: RoaringBitmap,
     sort_criteria: &Option<Vec<AscDesc>>,
     distinct: &Option<String>,
+    hits_per_distinct: usize,
     geo_strategy: geo_sort::Strategy,
     from: usize,
     length: usize,
@@ -641,6 +720,7 @@ pub fn execute_search(
     time_budget: TimeBudget,
     ranking_score_threshold: Option<f64>,
     locales: Option<&Vec<Language>>,
+    search_after: Option<&SearchCursor>,
 ) -> Result<PartialSearchResult> {
     check_sort_criteria(ctx, sort_criteria.as_ref())?;
 
@@ -758,6 +838,7 @@ pub fn execute_search(
             ranking_rules,
             &graph,
             distinct.as_deref(),
+            hits_per_distinct,
             &universe,
             from,
             length,
@@ -765,6 +846,7 @@ pub fn execute_search(
             query_graph_logger,
             time_budget,
             ranking_score_threshold,
+            search_after,
         )?
     } else {
         let ranking_rules =
@@ -774,6 +856,7 @@ pub fn execute_search(
             ranking_rules,
             &PlaceholderQuery,
             distinct.as_deref(),
+            hits_per_distinct,
             &universe,
             from,
             length,
@@ -781,10 +864,12 @@ pub fn execute_search(
             placeholder_search_logger,
             time_budget,
             ranking_score_threshold,
+            search_after,
         )?
     };
 
-    let BucketSortOutput { docids, scores, mut all_candidates, degraded } = bucket_sort_output;
+    let BucketSortOutput { docids, scores, mut all_candidates, cursor, degraded } =
+        bucket_sort_output;
     let fields_ids_map = ctx.index.fields_ids_map(ctx.txn)?;
 
     // The candidates is the universe unless the exhaustive number of hits
@@ -807,6 +892,7 @@ pub fn execute_search(
         document_scores: scores,
         documents_ids: docids,
         located_query_terms,
+        cursor,
         degraded,
         used_negative_operator,
     })
@@ -828,7 +914,7 @@ fn check_sort_criteria(
 
     // We check that the sort ranking rule exists and throw an
     // error if we try to use it and that it doesn't.
-    let sort_ranking_rule_missing = !ctx.index.criteria(ctx.txn)?.contains(&crate::Criterion::Sort);
+    let sort_ranking_rule_missing = !ctx.criteria()?.contains(&crate::Criterion::Sort);
     if sort_ranking_rule_missing {
         return Err(UserError::SortRankingRuleMissing.into());
     }
@@ -872,6 +958,7 @@ pub struct PartialSearchResult {
     pub candidates: RoaringBitmap,
     pub documents_ids: Vec<DocumentId>,
     pub document_scores: Vec<Vec<ScoreDetails>>,
+    pub cursor: Option<SearchCursor>,
 
     pub degraded: bool,
     pub used_negative_operator: bool,
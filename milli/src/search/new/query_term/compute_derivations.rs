@@ -10,7 +10,7 @@ use super::{OneTypoTerm, Phrase, QueryTerm, ZeroTypoTerm};
 use crate::search::fst_utils::{Complement, Intersection, StartsWith, Union};
 use crate::search::new::interner::{DedupInterner, Interned};
 use crate::search::new::query_term::{Lazy, TwoTypoTerm};
-use crate::search::new::{limits, SearchContext};
+use crate::search::new::{limits, SearchContext, Word};
 use crate::search::{build_dfa, get_first};
 use crate::{Result, MAX_WORD_LENGTH};
 
@@ -426,3 +426,58 @@ fn split_best_frequency(
 
     Ok(best.map(|(_, left, right)| (left, right)))
 }
+
+impl Interned<QueryTerm> {
+    /// Returns the word within the typo budget of the term that is contained in the most documents.
+    ///
+    /// Returns `None` if the term matches words of the index without typos, or if none of
+    /// its typo derivations exists in the index. A prefix term is corrected as a full word,
+    /// the words it is a typo of a prefix of are completions rather than corrections.
+    pub fn most_frequent_correction(
+        self,
+        ctx: &mut SearchContext<'_>,
+    ) -> Result<Option<Interned<String>>> {
+        let term = ctx.term_interner.get(self);
+        let ZeroTypoTerm { phrase, exact, prefix_of, synonyms, use_prefix_db } = &term.zero_typo;
+        if phrase.is_some()
+            || exact.is_some()
+            || !prefix_of.is_empty()
+            || !synonyms.is_empty()
+            || use_prefix_db.is_some()
+        {
+            return Ok(None);
+        }
+
+        if term.is_prefix {
+            let word = ctx.word_interner.get(term.original).clone();
+            let max_typo = term.max_levenshtein_distance;
+            let full_word =
+                partially_initialized_term_from_word(ctx, &word, max_typo, false, false)?;
+            let full_word = ctx.term_interner.push(full_word);
+            return full_word.most_frequent_correction(ctx);
+        }
+
+        self.compute_fully_if_needed(ctx)?;
+        let term = ctx.term_interner.get(self);
+        let mut derivations = Vec::new();
+        if let Lazy::Init(OneTypoTerm { one_typo, .. }) = &term.one_typo {
+            derivations.extend(one_typo.iter().copied());
+        }
+        if let Lazy::Init(TwoTypoTerm { two_typos }) = &term.two_typo {
+            derivations.extend(two_typos.iter().copied());
+        }
+
+        // on equal frequencies, the words with the fewest typos win
+        let mut best = None;
+        let mut best_frequency = 0;
+        for word in derivations {
+            let frequency =
+                ctx.word_docids(None, Word::Derived(word))?.map_or(0, |docids| docids.len());
+            if frequency > best_frequency {
+                best = Some(word);
+                best_frequency = frequency;
+            }
+        }
+        Ok(best)
+    }
+}
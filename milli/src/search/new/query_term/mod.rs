@@ -477,6 +477,9 @@ impl QueryTerm {
     pub fn is_prefix(&self) -> bool {
         self.is_prefix
     }
+    pub fn is_ngram(&self) -> bool {
+        self.ngram_words.is_some()
+    }
     pub fn original_word(&self, ctx: &SearchContext<'_>) -> String {
         ctx.word_interner.get(self.original).clone()
     }
@@ -0,0 +1,170 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use roaring::RoaringBitmap;
+
+use super::logger::SearchLogger;
+use super::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait, SearchContext};
+use crate::score_details::{self, ScoreDetails};
+use crate::{DocumentId, Result};
+
+/// The number of documents of the first batch sorted by their random key.
+///
+/// Each following batch is twice as large as the previous one.
+const FIRST_BATCH_SIZE: usize = 64;
+
+/// A ranking rule shuffling the documents, each document being in its own bucket.
+///
+/// The position of a document only depends on the seed and its id, so that the same seed
+/// gives the same order whatever the other documents of the universe, which keeps the
+/// pagination stable.
+///
+/// The random key of the documents is reported as the value of a sort,
+/// so that shuffled results can be merged like sorted ones.
+///
+/// The whole universe is not sorted: the documents are returned by batches, each batch being
+/// the documents with the lowest (or highest) keys among the ones not returned yet, selected
+/// with a heap bounded by the size of the batch.
+pub struct RandomSort<Query> {
+    seed: u64,
+    is_ascending: bool,
+    original_query: Option<Query>,
+    /// The documents not returned yet, that are not part of the current batch.
+    remaining: RoaringBitmap,
+    /// The current batch of documents with their random key, the first to return last.
+    batch: Vec<(u32, DocumentId)>,
+    /// The size of the next batch.
+    batch_size: usize,
+}
+
+impl<Query> RandomSort<Query> {
+    pub fn new(seed: u64, is_ascending: bool) -> Self {
+        Self {
+            seed,
+            is_ascending,
+            original_query: None,
+            remaining: RoaringBitmap::new(),
+            batch: Vec::new(),
+            batch_size: FIRST_BATCH_SIZE,
+        }
+    }
+
+    fn score(&self, value: serde_json::Value) -> ScoreDetails {
+        ScoreDetails::Sort(score_details::Sort {
+            field_name: format!("_random({})", self.seed),
+            ascending: self.is_ascending,
+            redacted: false,
+            value,
+            datetime: false,
+        })
+    }
+
+    /// Moves the next documents to return from `remaining` to `batch`.
+    fn next_batch(&mut self) {
+        let keys = self.remaining.iter().map(|docid| (random_key(self.seed, docid), docid));
+        let mut batch = if self.is_ascending {
+            smallest(keys, self.batch_size)
+        } else {
+            smallest(keys.map(Reverse), self.batch_size)
+                .into_iter()
+                .map(|Reverse(key)| key)
+                .collect()
+        };
+        batch.reverse();
+
+        for (_, docid) in &batch {
+            self.remaining.remove(*docid);
+        }
+        self.batch = batch;
+        self.batch_size = self.batch_size.saturating_mul(2);
+    }
+}
+
+/// Returns the `count` smallest items, sorted.
+fn smallest<T: Ord>(items: impl Iterator<Item = T>, count: usize) -> Vec<T> {
+    // a max-heap whose largest item is dropped as soon as it holds too many items
+    let mut heap = BinaryHeap::new();
+    for item in items {
+        heap.push(item);
+        if heap.len() > count {
+            heap.pop();
+        }
+    }
+    heap.into_sorted_vec()
+}
+
+/// Returns the random key of a document for the given seed.
+fn random_key(seed: u64, docid: DocumentId) -> u32 {
+    // The finalizer of the SplitMix64 generator, which spreads the bits of its input.
+    fn mix(mut x: u64) -> u64 {
+        x = (x ^ (x >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        x = (x ^ (x >> 27)).wrapping_mul(0x94d049bb133111eb);
+        x ^ (x >> 31)
+    }
+    (mix(seed ^ mix(docid as u64)) >> 32) as u32
+}
+
+impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for RandomSort<Query> {
+    fn id(&self) -> String {
+        let Self { seed, is_ascending, .. } = self;
+        format!("_random({seed}):{}", if *is_ascending { "asc" } else { "desc" })
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::random_sort")]
+    fn start_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        parent_candidates: &RoaringBitmap,
+        parent_query: &Query,
+    ) -> Result<()> {
+        self.original_query = Some(parent_query.clone());
+        self.remaining = parent_candidates.clone();
+        self.batch.clear();
+        self.batch_size = FIRST_BATCH_SIZE;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::random_sort")]
+    fn next_bucket(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+        universe: &RoaringBitmap,
+    ) -> Result<Option<RankingRuleOutput<Query>>> {
+        let query = self.original_query.as_ref().unwrap().clone();
+        loop {
+            while let Some((key, docid)) = self.batch.pop() {
+                if universe.contains(docid) {
+                    return Ok(Some(RankingRuleOutput {
+                        query,
+                        candidates: RoaringBitmap::from_iter([docid]),
+                        score: self.score(key.into()),
+                    }));
+                }
+            }
+            // the documents that left the universe will never be returned
+            self.remaining &= universe;
+            if self.remaining.is_empty() {
+                break;
+            }
+            self.next_batch();
+        }
+        Ok(Some(RankingRuleOutput {
+            query,
+            candidates: universe.clone(),
+            score: self.score(serde_json::Value::Null),
+        }))
+    }
+
+    #[tracing::instrument(level = "trace", skip_all, target = "search::random_sort")]
+    fn end_iteration(
+        &mut self,
+        _ctx: &mut SearchContext<'ctx>,
+        _logger: &mut dyn SearchLogger<Query>,
+    ) {
+        self.original_query = None;
+        self.remaining.clear();
+        self.batch.clear();
+    }
+}
@@ -52,6 +52,7 @@ pub struct Sort<'ctx, Query> {
     original_query: Option<Query>,
     iter: Option<RankingRuleOutputIterWrapper<'ctx, Query>>,
     must_redact: bool,
+    is_datetime: bool,
 }
 impl<'ctx, Query> Sort<'ctx, Query> {
     pub fn new(
@@ -63,6 +64,7 @@ impl<'ctx, Query> Sort<'ctx, Query> {
         let fields_ids_map = index.fields_ids_map(rtxn)?;
         let field_id = fields_ids_map.id(&field_name);
         let must_redact = Self::must_redact(index, rtxn, &field_name)?;
+        let is_datetime = index.indexed_datetime_fields(rtxn)?.contains(&field_name);
 
         Ok(Self {
             field_name,
@@ -71,6 +73,7 @@ impl<'ctx, Query> Sort<'ctx, Query> {
             original_query: None,
             iter: None,
             must_redact,
+            is_datetime,
         })
     }
 
@@ -165,6 +168,7 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                 let ascending = self.is_ascending;
                 let field_name = self.field_name.clone();
                 let must_redact = self.must_redact;
+                let is_datetime = self.is_datetime;
                 RankingRuleOutputIterWrapper::new(Box::new(number_iter.chain(string_iter).map(
                     move |r| {
                         let (docids, value) = r?;
@@ -176,6 +180,7 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                                 ascending,
                                 redacted: must_redact,
                                 value,
+                                datetime: is_datetime,
                             }),
                         })
                     },
@@ -209,6 +214,7 @@ impl<'ctx, Query: RankingRuleQueryTrait> RankingRule<'ctx, Query> for Sort<'ctx,
                     ascending: self.is_ascending,
                     redacted: self.must_redact,
                     value: serde_json::Value::Null,
+                    datetime: self.is_datetime,
                 }),
             }))
         }
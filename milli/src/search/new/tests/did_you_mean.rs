@@ -0,0 +1,100 @@
+/*!
+This module tests the corrected query returned by the search:
+
+1. A corrected query is only returned when it is asked for and few documents match
+2. Only the words that match no word of the index are corrected
+3. A word is corrected with its most frequent typo derivation
+4. Words that are not typo tolerant and phrases are kept as they are
+5. The last word of the query is corrected as a full word, not completed
+*/
+
+use crate::index::tests::TempIndex;
+use crate::{Criterion, Search, SearchResult, TermsMatchingStrategy};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_criteria(vec![Criterion::Words, Criterion::Typo]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox jumps over the lazy dog" },
+            { "id": 1, "text": "the quick brown foxes" },
+            { "id": 2, "text": "the quick brow fox" },
+            { "id": 3, "text": "the lazy cat" },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn corrected_query(index: &TempIndex, query: &str, max_hits: Option<u64>) -> Option<String> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.terms_matching_strategy(TermsMatchingStrategy::All);
+    if let Some(max_hits) = max_hits {
+        s.did_you_mean(max_hits);
+    }
+    let SearchResult { corrected_query, .. } = s.execute().unwrap();
+    corrected_query
+}
+
+#[test]
+fn test_did_you_mean_most_frequent_correction() {
+    let index = create_index();
+
+    // `browm` is one typo away from `brown` and `brow`, `brown` is the most frequent
+    assert_eq!(
+        corrected_query(&index, "quikc browm fox", Some(5)).as_deref(),
+        Some("quick brown fox")
+    );
+    // the words of the index are not corrected
+    assert_eq!(corrected_query(&index, "quick brow fox", Some(5)), None);
+    // `lazi` is too short to be typo tolerant and phrases are kept as they are
+    assert_eq!(
+        corrected_query(&index, "\"the lazy\" lazi quikc", Some(5)).as_deref(),
+        Some("\"the lazy\" lazi quick")
+    );
+}
+
+#[test]
+fn test_did_you_mean_max_hits() {
+    let index = create_index();
+
+    // not asked for
+    assert_eq!(corrected_query(&index, "quikc", None), None);
+    // `quikc` matches the 3 documents containing `quick`
+    assert_eq!(corrected_query(&index, "quikc", Some(2)), None);
+    assert_eq!(corrected_query(&index, "quikc", Some(3)).as_deref(), Some("quick"));
+}
+
+#[test]
+fn test_did_you_mean_last_word() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the wizard" },
+            { "id": 1, "text": "wizardry" },
+            { "id": 2, "text": "wizardry school" },
+            { "id": 3, "text": "wizardry book" },
+        ]))
+        .unwrap();
+
+    // `wizzard` is a typo of a prefix of the more frequent `wizardry`, but it is a typo of `wizard`
+    assert_eq!(corrected_query(&index, "wizzard", Some(5)).as_deref(), Some("wizard"));
+    assert_eq!(corrected_query(&index, "the wizzard", Some(5)).as_deref(), Some("the wizard"));
+}
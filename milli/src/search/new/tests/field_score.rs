@@ -0,0 +1,114 @@
+/*!
+This module tests the `decay` and `boost` ranking rules:
+
+1. the documents are sorted by the score of the value of the field, the documents
+   without a value being returned last
+2. a document with several values is scored according to its best value
+3. the documents whose scores are close are ordered by the next ranking rules
+4. the score blends with the scores of the other ranking rules
+*/
+
+use big_s::S;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{Boost, BoostModifier, Criterion, Decay, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the fox", "price": 100, "popularity": 1000 },
+            { "id": 1, "text": "the quick fox", "price": 110, "popularity": 10 },
+            { "id": 2, "text": "the quick brown fox", "price": 150, "popularity": 100 },
+            { "id": 3, "text": "the fox", "price": 300, "popularity": 0 },
+            { "id": 4, "text": "the fox", "popularity": 10 },
+            { "id": 5, "text": "the fox", "price": [300, 105], "popularity": 1 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn decay() -> Criterion {
+    Criterion::Decay(Decay { field: S("price"), origin: S("100"), scale: S("10") })
+}
+
+fn boost(modifier: BoostModifier) -> Criterion {
+    Criterion::Boost(Boost { field: S("popularity"), modifier })
+}
+
+fn search(index: &TempIndex, criteria: Vec<Criterion>, query: &str) -> (Vec<u32>, Vec<f64>) {
+    index.update_settings(|s| s.set_criteria(criteria.clone())).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query(query);
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { documents_ids, document_scores, .. } = s.execute().unwrap();
+    let scores =
+        document_scores.iter().map(|scores| ScoreDetails::global_score(scores.iter())).collect();
+    (documents_ids, scores)
+}
+
+#[test]
+fn test_decay() {
+    let index = create_index();
+
+    let (documents_ids, scores) = search(&index, vec![decay()], "");
+    // the score of the price of the document 2 is too low to be distinguished from no price
+    assert_eq!(documents_ids, vec![0, 5, 1, 2, 3, 4]);
+    assert_eq!(scores, vec![1.0, 14.0 / 20.0, 10.0 / 20.0, 1.0 / 20.0, 1.0 / 20.0, 1.0 / 20.0]);
+
+    let (documents_ids, _) = search(&index, vec![decay(), Criterion::Desc(S("price"))], "");
+    assert_eq!(documents_ids, vec![0, 5, 1, 3, 2, 4]);
+}
+
+#[test]
+fn test_boost() {
+    let index = create_index();
+
+    let (documents_ids, scores) = search(&index, vec![boost(BoostModifier::Log)], "");
+    assert_eq!(documents_ids, vec![0, 2, 1, 4, 5, 3]);
+    assert_eq!(scores, vec![1.0, 13.0 / 20.0, 7.0 / 20.0, 7.0 / 20.0, 2.0 / 20.0, 1.0 / 20.0]);
+
+    let (documents_ids, scores) = search(&index, vec![boost(BoostModifier::Linear)], "");
+    assert_eq!(documents_ids, vec![0, 2, 1, 3, 4, 5]);
+    assert_eq!(scores, vec![1.0, 2.0 / 20.0, 1.0 / 20.0, 1.0 / 20.0, 1.0 / 20.0, 1.0 / 20.0]);
+}
+
+#[test]
+fn test_score_blends_with_relevancy() {
+    let index = create_index();
+
+    let criteria = vec![Criterion::Words, boost(BoostModifier::Log)];
+    let (documents_ids, scores) = search(&index, criteria, "fox quick");
+    assert_eq!(documents_ids, vec![2, 1, 0, 4, 5, 3]);
+    // the documents are boosted within the score range of their number of matching words
+    assert_eq!(
+        scores,
+        vec![33.0 / 40.0, 27.0 / 40.0, 20.0 / 40.0, 7.0 / 40.0, 2.0 / 40.0, 1.0 / 40.0]
+    );
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.query("fox quick");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    let SearchResult { document_scores, .. } = s.execute().unwrap();
+    let details = ScoreDetails::to_json_map(document_scores[0].iter());
+    assert_eq!(
+        serde_json::Value::Object(details),
+        serde_json::json!({
+            "words": { "order": 0, "matchingWords": 2, "maxMatchingWords": 2, "score": 1.0 },
+            "boost(popularity, log)": { "order": 1, "score": 0.65 },
+        })
+    );
+}
@@ -307,3 +307,52 @@ fn geo_sort_without_any_geo_faceted_documents() {
     insta::assert_snapshot!(format!("{ids:?}"), @"[0, 2, 3]");
     insta::assert_snapshot!(format!("{scores:#?}"));
 }
+
+#[test]
+fn geo_sort_with_multiple_points_per_document() {
+    let index = create_index();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "_geo": [{ "lat": 0, "lng": 10 }, { "lat": 0, "lng": 1 }] },
+            { "id": 1, "_geo": { "lat": 0, "lng": 2 } },
+            { "id": 2, "_geo": [{ "lat": 0, "lng": 3 }, { "lat": "0", "lng": "-0.5" }] },
+            { "id": 3, "_geo": [{ "lat": 0, "lng": 4 }] },
+            { "id": 4, "_geo": [] },
+        ]))
+        .unwrap();
+
+    let rtxn = index.read_txn().unwrap();
+
+    let mut s = Search::new(&rtxn, &index);
+    s.scoring_strategy(crate::score_details::ScoringStrategy::Detailed);
+
+    // the documents are sorted by their nearest point, which is also the one in the score
+    let nearest_points = |scores: Vec<Vec<ScoreDetails>>| -> Vec<Option<[f64; 2]>> {
+        scores
+            .into_iter()
+            .map(|details| {
+                details.into_iter().find_map(|detail| match detail {
+                    ScoreDetails::GeoSort(geo_sort) => Some(geo_sort.value),
+                    _ => None,
+                })
+            })
+            .map(Option::flatten)
+            .collect()
+    };
+
+    s.sort_criteria(vec![AscDesc::Asc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[2, 0, 1, 3, 4]");
+    insta::assert_snapshot!(format!("{:?}", nearest_points(scores)), @"[Some([0.0, -0.5]), Some([0.0, 1.0]), Some([0.0, 2.0]), Some([0.0, 4.0]), None]");
+
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 0.]))]);
+    let (ids, scores) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[3, 1, 0, 2, 4]");
+    insta::assert_snapshot!(format!("{:?}", nearest_points(scores)), @"[Some([0.0, 4.0]), Some([0.0, 2.0]), Some([0.0, 1.0]), Some([0.0, -0.5]), None]");
+
+    // a point far away from the others doesn't make a document farther
+    s.sort_criteria(vec![AscDesc::Desc(Member::Geo([0., 9.]))]);
+    let (ids, _) = execute_iterative_and_rtree_returns_the_same(&rtxn, &index, &mut s);
+    insta::assert_snapshot!(format!("{ids:?}"), @"[1, 2, 3, 0, 4]");
+}
@@ -0,0 +1,123 @@
+/*!
+This module tests the grouping of the results by the value of a field:
+
+1. up to `hits_per_group` documents are returned for each group, in ranking order
+2. the groups are ordered by their best document and paginated with `offset` and `limit`
+3. the candidates count the groups rather than the documents
+4. a document without a value for the field is its own group
+*/
+
+use big_s::S;
+use maplit::hashset;
+use serde_json::json;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Criterion, Error, Member, Search, SearchGroup, SearchResult, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("product") });
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Sort]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "product": "a", "rank": 10 },
+            { "id": 1, "product": "b", "rank": 9 },
+            { "id": 2, "product": "a", "rank": 8 },
+            { "id": 3, "product": "c", "rank": 7 },
+            { "id": 4, "product": "a", "rank": 6 },
+            { "id": 5, "product": "b", "rank": 5 },
+            { "id": 6, "rank": 4 },
+            { "id": 7, "product": "c", "rank": 3 },
+            { "id": 8, "product": "a", "rank": 2 },
+            { "id": 9, "product": 1, "rank": 1 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    hits_per_group: usize,
+    offset: usize,
+    limit: usize,
+) -> (Vec<u32>, Vec<(serde_json::Value, usize)>, u64) {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.sort_criteria(vec![AscDesc::Desc(Member::Field(S("rank")))]);
+    s.group_by(S("product"), hits_per_group);
+    s.offset(offset);
+    s.limit(limit);
+    let SearchResult { documents_ids, groups, candidates, .. } = s.execute().unwrap();
+    let groups = groups.into_iter().map(|SearchGroup { value, len }| (value, len)).collect();
+    (documents_ids, groups, candidates.len())
+}
+
+#[test]
+fn test_group_by() {
+    let index = create_index();
+
+    let (documents_ids, groups, candidates) = search(&index, 2, 0, 10);
+    assert_eq!(documents_ids, vec![0, 2, 1, 5, 3, 7, 6, 9]);
+    assert_eq!(
+        groups,
+        vec![(json!("a"), 2), (json!("b"), 2), (json!("c"), 2), (json!(null), 1), (json!(1.0), 1)]
+    );
+    assert_eq!(candidates, 5);
+
+    let (documents_ids, groups, _) = search(&index, 3, 0, 1);
+    assert_eq!(documents_ids, vec![0, 2, 4]);
+    assert_eq!(groups, vec![(json!("a"), 3)]);
+
+    let (documents_ids, groups, _) = search(&index, 1, 0, 10);
+    assert_eq!(documents_ids, vec![0, 1, 3, 6, 9]);
+    assert!(groups.iter().all(|(_, len)| *len == 1));
+}
+
+#[test]
+fn test_group_by_pagination() {
+    let index = create_index();
+
+    let (documents_ids, groups, candidates) = search(&index, 2, 1, 2);
+    assert_eq!(documents_ids, vec![1, 5, 3, 7]);
+    assert_eq!(groups, vec![(json!("b"), 2), (json!("c"), 2)]);
+    assert_eq!(candidates, 5);
+
+    let (documents_ids, groups, _) = search(&index, 2, 5, 2);
+    assert!(documents_ids.is_empty());
+    assert!(groups.is_empty());
+}
+
+#[test]
+fn test_group_by_without_ranking_rules() {
+    let index = create_index();
+    index.update_settings(|s| s.set_criteria(vec![])).unwrap();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.group_by(S("product"), 2);
+    let SearchResult { documents_ids, groups, .. } = s.execute().unwrap();
+    // the documents are ranked by id
+    assert_eq!(documents_ids, vec![0, 2, 1, 5, 3, 7, 6, 9]);
+    assert_eq!(groups.len(), 5);
+}
+
+#[test]
+fn test_group_by_not_filterable() {
+    let index = create_index();
+
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.group_by(S("rank"), 2);
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::InvalidGroupByAttribute { .. })));
+}
@@ -1,9 +1,12 @@
 pub mod attribute_fid;
 pub mod attribute_position;
 pub mod cutoff;
+pub mod did_you_mean;
 pub mod distinct;
 pub mod exactness;
+pub mod field_score;
 pub mod geo_sort;
+pub mod group_by;
 pub mod integration;
 #[cfg(feature = "all-tokenizations")]
 #[cfg(not(feature = "chinese-pinyin"))]
@@ -11,6 +14,9 @@ pub mod language;
 pub mod ngram_split_words;
 pub mod proximity;
 pub mod proximity_typo;
+pub mod random_sort;
+pub mod ranking_rules;
+pub mod search_after;
 pub mod sort;
 pub mod stop_words;
 pub mod typo;
@@ -0,0 +1,125 @@
+/*!
+This module tests the `_random(seed)` sort:
+
+1. the documents are shuffled, the same seed always giving the same order
+2. the order is stable across pages
+3. the relative order of two documents doesn't depend on the other documents
+4. `:desc` reverses the order
+5. the order is the same when the documents are shuffled by several batches
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::{AscDesc, Filter, Member, Search, SearchResult};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_filterable_fields(hashset! { S("even") });
+        })
+        .unwrap();
+
+    let documents: Vec<_> = (0..50u32)
+        .map(|id| serde_json::json!({ "id": id, "text": "hello", "even": id % 2 == 0 }))
+        .collect();
+    index.add_documents(documents!(documents)).unwrap();
+
+    index
+}
+
+fn search(
+    index: &TempIndex,
+    sort: AscDesc,
+    filter: Option<&str>,
+    offset: usize,
+    limit: usize,
+) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.sort_criteria(vec![sort]);
+    if let Some(filter) = filter {
+        s.filter(Filter::from_str(filter).unwrap().unwrap());
+    }
+    s.offset(offset);
+    s.limit(limit);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    documents_ids
+}
+
+#[test]
+fn test_random_sort_is_reproducible() {
+    let index = create_index();
+
+    let shuffled = search(&index, AscDesc::Asc(Member::Random(42)), None, 0, 100);
+    assert_eq!(shuffled.len(), 50);
+    assert_ne!(shuffled, (0..50).collect::<Vec<_>>());
+    let mut sorted = shuffled.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..50).collect::<Vec<_>>());
+
+    assert_eq!(search(&index, AscDesc::Asc(Member::Random(42)), None, 0, 100), shuffled);
+    assert_ne!(search(&index, AscDesc::Asc(Member::Random(43)), None, 0, 100), shuffled);
+
+    let mut reversed = search(&index, AscDesc::Desc(Member::Random(42)), None, 0, 100);
+    reversed.reverse();
+    assert_eq!(reversed, shuffled);
+}
+
+#[test]
+fn test_random_sort_pagination() {
+    let index = create_index();
+
+    let sort = AscDesc::Asc(Member::Random(7));
+    let all = search(&index, sort.clone(), Some("even = true"), 0, 100);
+    assert_eq!(all.len(), 25);
+    assert!(all.iter().all(|id| id % 2 == 0));
+
+    let pages: Vec<_> = (0..25)
+        .step_by(6)
+        .flat_map(|offset| search(&index, sort.clone(), Some("even = true"), offset, 6))
+        .collect();
+    assert_eq!(pages, all);
+
+    // the filtered documents keep the same relative order as without the filter
+    let unfiltered = search(&index, sort, None, 0, 100);
+    let even: Vec<_> = unfiltered.into_iter().filter(|id| id % 2 == 0).collect();
+    assert_eq!(even, all);
+}
+
+#[test]
+fn test_random_sort_several_batches() {
+    let index = create_index();
+    let shuffled = search(&index, AscDesc::Asc(Member::Random(42)), None, 0, 100);
+
+    // many more documents than the first batch of shuffled documents
+    let documents: Vec<_> = (50..1000u32)
+        .map(|id| serde_json::json!({ "id": id, "text": "hello", "even": id % 2 == 0 }))
+        .collect();
+    index.add_documents(documents!(documents)).unwrap();
+
+    let all = search(&index, AscDesc::Asc(Member::Random(42)), None, 0, 1000);
+    assert_eq!(all.len(), 1000);
+    let mut sorted = all.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..1000).collect::<Vec<_>>());
+
+    // the first documents keep their relative order
+    let first: Vec<_> = all.iter().copied().filter(|id| *id < 50).collect();
+    assert_eq!(first, shuffled);
+
+    let mut reversed = search(&index, AscDesc::Desc(Member::Random(42)), None, 0, 1000);
+    reversed.reverse();
+    assert_eq!(reversed, all);
+
+    let pages: Vec<_> = (0..1000)
+        .step_by(150)
+        .flat_map(|offset| search(&index, AscDesc::Asc(Member::Random(42)), None, offset, 150))
+        .collect();
+    assert_eq!(pages, all);
+}
@@ -0,0 +1,97 @@
+/*!
+This module tests the ranking rules given at search time:
+
+1. they replace the ranking rules of the index for this search only
+2. they are reflected in the score details
+3. the fields of the custom ranking rules must be sortable or part of the ranking rules of the index
+4. the `sort` ranking rule can only be used at search time if it is part of these ranking rules
+*/
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::{ScoreDetails, ScoringStrategy};
+use crate::{AscDesc, Criterion, Error, Member, Search, SearchResult, UserError};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_criteria(vec![Criterion::Words, Criterion::Asc(S("price"))]);
+        })
+        .unwrap();
+
+    index
+        .add_documents(documents!([
+            { "id": 0, "text": "the quick brown fox", "rank": 2, "price": 30 },
+            { "id": 1, "text": "the quick fox", "rank": 0, "price": 10 },
+            { "id": 2, "text": "the fox", "rank": 1, "price": 20 },
+        ]))
+        .unwrap();
+
+    index
+}
+
+fn search(index: &TempIndex, ranking_rules: Option<Vec<Criterion>>) -> crate::Result<SearchResult> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    s.query("the quick brown fox");
+    s.scoring_strategy(ScoringStrategy::Detailed);
+    if let Some(ranking_rules) = ranking_rules {
+        s.ranking_rules(ranking_rules);
+    }
+    s.execute()
+}
+
+#[test]
+fn test_ranking_rules_replace_the_index_ones() {
+    let index = create_index();
+
+    let SearchResult { documents_ids, .. } = search(&index, None).unwrap();
+    assert_eq!(documents_ids, vec![0, 1, 2]);
+
+    let SearchResult { documents_ids, document_scores, .. } =
+        search(&index, Some(vec![Criterion::Desc(S("rank"))])).unwrap();
+    assert_eq!(documents_ids, vec![0, 2, 1]);
+    // only the given ranking rules were applied
+    for scores in document_scores {
+        assert!(matches!(scores.as_slice(), [ScoreDetails::Sort(_)]));
+    }
+
+    // the fields of the ranking rules of the index can be used even if they are not sortable
+    let SearchResult { documents_ids, .. } =
+        search(&index, Some(vec![Criterion::Desc(S("price")), Criterion::Words])).unwrap();
+    assert_eq!(documents_ids, vec![0, 2, 1]);
+
+    // the index ranking rules are still used by the next searches
+    let SearchResult { documents_ids, .. } = search(&index, None).unwrap();
+    assert_eq!(documents_ids, vec![0, 1, 2]);
+}
+
+#[test]
+fn test_ranking_rules_errors() {
+    let index = create_index();
+
+    let error = search(&index, Some(vec![Criterion::Asc(S("text"))])).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::UserError(UserError::InvalidRankingRuleSortableAttribute { ref field, .. }) if field == "text"
+    ));
+
+    // `sort` must be part of the ranking rules given at search time
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, &index);
+    s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+    s.ranking_rules(vec![Criterion::Words]);
+    let error = s.execute().unwrap_err();
+    assert!(matches!(error, Error::UserError(UserError::SortRankingRuleMissing)));
+
+    s.ranking_rules(vec![Criterion::Sort]);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, vec![1, 2, 0]);
+}
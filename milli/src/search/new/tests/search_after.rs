@@ -0,0 +1,252 @@
+/*!
+This module tests the `search_after` cursor:
+
+1. walking the results page by page with the cursor returns the same documents, in the same order,
+   as a single search returning all of them
+2. the offset is relative to the cursor
+3. the cursor works with a placeholder search, with and without ranking rules
+4. the cursor works with the distinct attribute
+5. paging through the whole index returns every document exactly once, even when the last
+   document of a bucket is returned without asking the ranking rule for its bucket
+6. the cursor is applied to the degraded searches running out of time
+*/
+
+use std::time::Duration;
+
+use big_s::S;
+use maplit::hashset;
+
+use crate::index::tests::TempIndex;
+use crate::score_details::ScoringStrategy;
+use crate::{AscDesc, Criterion, Member, Search, SearchCursor, SearchResult, TimeBudget};
+
+fn create_index() -> TempIndex {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_searchable_fields(vec!["text".to_owned()]);
+            s.set_sortable_fields(hashset! { S("rank") });
+            s.set_filterable_fields(hashset! { S("letter") });
+            s.set_criteria(vec![
+                Criterion::Words,
+                Criterion::Typo,
+                Criterion::Sort,
+                Criterion::Proximity,
+                Criterion::Desc(S("rank")),
+            ]);
+        })
+        .unwrap();
+
+    let documents: Vec<_> = (0..60usize)
+        .map(|id| {
+            let text = match id % 5 {
+                0 => "the quick brown fox jumps over the lazy dog",
+                1 => "the quick brown fox",
+                2 => "the quikc brown fox jumps",
+                3 => "the lazy dog sleeps",
+                _ => "a fox",
+            };
+            let letter = ["A", "B", "C", "D"][id % 4];
+            serde_json::json!({
+                "id": id,
+                "text": text,
+                "rank": id % 7,
+                "letter": letter,
+            })
+        })
+        .collect();
+    index.add_documents(documents!(documents)).unwrap();
+
+    index
+}
+
+/// Fetches all the pages of `page_size` documents using the cursor of the previous page.
+fn walk_pages(configure: impl Fn(&mut Search), index: &TempIndex, page_size: usize) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut documents_ids = Vec::new();
+    let mut cursor: Option<SearchCursor> = None;
+    loop {
+        let mut s = Search::new(&txn, index);
+        configure(&mut s);
+        s.limit(page_size);
+        if let Some(cursor) = cursor.take() {
+            // the cursor is always passed around as an opaque string
+            s.search_after(cursor.to_string().parse().unwrap());
+        }
+        let SearchResult { documents_ids: page, cursor: next, .. } = s.execute().unwrap();
+        documents_ids.extend_from_slice(&page);
+        if page.len() < page_size {
+            break;
+        }
+        cursor = next;
+        assert!(cursor.is_some());
+    }
+    documents_ids
+}
+
+fn all_at_once(configure: impl Fn(&mut Search), index: &TempIndex) -> Vec<u32> {
+    let txn = index.read_txn().unwrap();
+    let mut s = Search::new(&txn, index);
+    configure(&mut s);
+    s.limit(1000);
+    s.execute().unwrap().documents_ids
+}
+
+#[test]
+fn test_search_after_query() {
+    let index = create_index();
+
+    for scoring_strategy in [ScoringStrategy::Skip, ScoringStrategy::Detailed] {
+        let configure = |s: &mut Search| {
+            s.query("the quick brown fox");
+            s.scoring_strategy(scoring_strategy);
+        };
+        let expected = all_at_once(configure, &index);
+        assert!(expected.len() > 10);
+        for page_size in [1, 3, 7, 50] {
+            assert_eq!(walk_pages(configure, &index, page_size), expected);
+        }
+    }
+}
+
+#[test]
+fn test_search_after_sort() {
+    let index = create_index();
+
+    let configure = |s: &mut Search| {
+        s.query("fox");
+        s.sort_criteria(vec![AscDesc::Asc(Member::Field(S("rank")))]);
+    };
+    let expected = all_at_once(configure, &index);
+    for page_size in [1, 4, 9] {
+        assert_eq!(walk_pages(configure, &index, page_size), expected);
+    }
+}
+
+#[test]
+fn test_search_after_placeholder() {
+    let index = create_index();
+
+    let expected = all_at_once(|_| (), &index);
+    assert_eq!(expected.len(), 60);
+    for page_size in [1, 6, 11] {
+        assert_eq!(walk_pages(|_| (), &index, page_size), expected);
+    }
+
+    // without any ranking rule the documents are returned in docid order
+    index.update_settings(|s| s.set_criteria(vec![Criterion::Words])).unwrap();
+    let expected = all_at_once(|_| (), &index);
+    for page_size in [1, 6, 11] {
+        assert_eq!(walk_pages(|_| (), &index, page_size), expected);
+    }
+}
+
+#[test]
+fn test_search_after_offset() {
+    let index = create_index();
+    let txn = index.read_txn().unwrap();
+
+    let configure = |s: &mut Search| {
+        s.query("the lazy dog");
+    };
+    let expected = all_at_once(configure, &index);
+
+    let mut s = Search::new(&txn, &index);
+    configure(&mut s);
+    s.limit(5);
+    let SearchResult { cursor, .. } = s.execute().unwrap();
+
+    let mut s = Search::new(&txn, &index);
+    configure(&mut s);
+    s.search_after(cursor.unwrap());
+    s.offset(3);
+    s.limit(4);
+    let SearchResult { documents_ids, .. } = s.execute().unwrap();
+    assert_eq!(documents_ids, expected[8..12]);
+}
+
+#[test]
+fn test_search_after_distinct() {
+    let index = create_index();
+
+    for query in ["the quick brown fox", ""] {
+        let configure = |s: &mut Search| {
+            s.query(query);
+            s.distinct(S("letter"));
+        };
+        let expected = all_at_once(configure, &index);
+        assert_eq!(expected.len(), 4);
+        assert_eq!(walk_pages(configure, &index, 1), expected);
+    }
+}
+
+#[test]
+fn test_search_after_whole_index_skip_scoring() {
+    let index = TempIndex::new();
+
+    index
+        .update_settings(|s| {
+            s.set_primary_key("id".to_owned());
+            s.set_sortable_fields(hashset! { S("group"), S("rank") });
+            s.set_criteria(vec![Criterion::Desc(S("group")), Criterion::Asc(S("rank"))]);
+        })
+        .unwrap();
+
+    // the documents of a group are interleaved with the other groups and
+    // their ranks are not in docid order
+    let documents: Vec<_> = (0..60usize)
+        .map(|id| serde_json::json!({ "id": id, "group": id % 3, "rank": (id * 7) % 13 }))
+        .collect();
+    index.add_documents(documents!(documents)).unwrap();
+
+    let configure = |s: &mut Search| {
+        s.scoring_strategy(ScoringStrategy::Skip);
+    };
+    let expected = all_at_once(configure, &index);
+    assert_eq!(expected.len(), 60);
+    for page_size in [1, 2, 5, 7] {
+        let mut documents_ids = walk_pages(configure, &index, page_size);
+        assert_eq!(documents_ids, expected);
+
+        // no duplicates and no gaps
+        documents_ids.sort_unstable();
+        assert_eq!(documents_ids, (0..60).collect::<Vec<u32>>());
+    }
+}
+
+#[test]
+fn test_search_after_degraded() {
+    let index = create_index();
+    index.update_settings(|s| s.set_search_cutoff(0)).unwrap();
+    let cutoff = index.search_cutoff(&index.read_txn().unwrap()).unwrap().unwrap();
+
+    for query in ["the quick brown fox", ""] {
+        let configure = |s: &mut Search| {
+            s.query(query);
+            s.time_budget(TimeBudget::new(Duration::from_millis(cutoff)));
+        };
+
+        let expected = {
+            let txn = index.read_txn().unwrap();
+            let mut s = Search::new(&txn, &index);
+            configure(&mut s);
+            s.limit(1000);
+            let SearchResult { documents_ids, degraded, .. } = s.execute().unwrap();
+            assert!(degraded);
+            documents_ids
+        };
+        assert!(expected.len() > 10);
+
+        for page_size in [1, 4, 9] {
+            let mut documents_ids = walk_pages(configure, &index, page_size);
+            assert_eq!(documents_ids, expected);
+
+            // no duplicates
+            documents_ids.sort_unstable();
+            documents_ids.dedup();
+            assert_eq!(documents_ids.len(), expected.len());
+        }
+    }
+}
@@ -5,17 +5,25 @@ use roaring::RoaringBitmap;
 
 use super::ranking_rules::{RankingRule, RankingRuleOutput, RankingRuleQueryTrait};
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::{DistributionShift, Embedder};
+use crate::vector::parsed_vectors::{ParsedVectors, RESERVED_VECTORS_FIELD_NAME};
+use crate::vector::{ArroyWrapper, DistributionShift, Embedder};
 use crate::{DocumentId, Result, SearchContext, SearchLogger};
 
 pub struct VectorSort<Q: RankingRuleQueryTrait> {
     query: Option<Q>,
     target: Vec<f32>,
     vector_candidates: RoaringBitmap,
-    cached_sorted_docids: std::vec::IntoIter<(DocumentId, f32)>,
+    /// The nearest documents with the index of their nearest embedding.
+    cached_sorted_docids: std::vec::IntoIter<(DocumentId, usize, f32)>,
     limit: usize,
     distribution_shift: Option<DistributionShift>,
-    embedder_index: u8,
+    arroy: ArroyWrapper,
+    embedder_name: String,
+    /// Whether the quantized distances are replaced with the distances to the
+    /// full-precision embeddings kept in the documents.
+    rescore: bool,
+    /// Whether the embeddings of the documents are the embeddings of the chunks of their text.
+    chunked: bool,
 }
 
 impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
@@ -27,11 +35,14 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         embedder_name: &str,
         embedder: &Embedder,
     ) -> Result<Self> {
-        let embedder_index = ctx
+        let arroy = ctx
             .index
-            .embedder_category_id
-            .get(ctx.txn, embedder_name)?
+            .arroy_wrapper(ctx.txn, embedder_name)?
             .ok_or_else(|| crate::UserError::InvalidEmbedder(embedder_name.to_owned()))?;
+        let embedding_configs = ctx.index.embedding_configs(ctx.txn)?;
+        let config = embedding_configs.iter().find(|config| config.name == embedder_name);
+        let rescore = config.is_some_and(|config| config.config.rescored());
+        let chunked = config.is_some_and(|config| config.config.prompt.chunking.is_some());
 
         Ok(Self {
             query: None,
@@ -40,7 +51,10 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
             cached_sorted_docids: Default::default(),
             limit,
             distribution_shift: embedder.distribution(),
-            embedder_index,
+            arroy,
+            embedder_name: embedder_name.to_owned(),
+            rescore,
+            chunked,
         })
     }
 
@@ -49,21 +63,53 @@ impl<Q: RankingRuleQueryTrait> VectorSort<Q> {
         ctx: &mut SearchContext<'_>,
         vector_candidates: &RoaringBitmap,
     ) -> Result<()> {
-        let readers: std::result::Result<Vec<_>, _> =
-            ctx.index.arroy_readers(ctx.txn, self.embedder_index).collect();
-        let readers = readers?;
+        let mut results =
+            self.arroy.nns_by_vector(ctx.txn, &self.target, self.limit, Some(vector_candidates))?;
+        if self.rescore {
+            self.rescore(ctx, &mut results)?;
+        }
+        self.cached_sorted_docids = results.into_iter();
 
-        let target = &self.target;
-        let mut results = Vec::new();
+        Ok(())
+    }
 
-        for reader in readers.iter() {
-            let nns_by_vector =
-                reader.nns_by_vector(ctx.txn, target, self.limit, None, Some(vector_candidates))?;
-            results.extend(nns_by_vector.into_iter());
+    /// Replaces the distances to the quantized embeddings with the distances to the full-precision
+    /// embeddings kept in the documents, for the documents that have some.
+    fn rescore(
+        &self,
+        ctx: &SearchContext<'_>,
+        results: &mut [(DocumentId, usize, f32)],
+    ) -> Result<()> {
+        let Some(vectors_fid) = ctx.index.fields_ids_map(ctx.txn)?.id(RESERVED_VECTORS_FIELD_NAME)
+        else {
+            return Ok(());
+        };
+        let distance = self.arroy.distance();
+
+        for (docid, embedding_index, docid_distance) in results.iter_mut() {
+            let Some(obkv) = ctx.index.documents.get(ctx.txn, docid)? else { continue };
+            let Some(vectors) = obkv.get(vectors_fid) else { continue };
+            let Ok(ParsedVectors(mut vectors)) = ParsedVectors::from_bytes(vectors) else {
+                continue;
+            };
+            let Some(embeddings) = vectors
+                .remove(&self.embedder_name)
+                .and_then(|vectors| vectors.into_array_of_vectors())
+            else {
+                continue;
+            };
+            let full_precision = embeddings
+                .iter()
+                .map(|embedding| distance.distance(&self.target, embedding))
+                .enumerate()
+                .min_by_key(|(_, distance)| OrderedFloat(*distance));
+            if let Some((index, full_precision)) = full_precision {
+                *embedding_index = index;
+                *docid_distance = full_precision;
+            }
         }
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
-        self.cached_sorted_docids = results.into_iter();
 
+        results.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
         Ok(())
     }
 }
@@ -104,13 +150,16 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    chunk: None,
+                }),
             }));
         }
 
-        for (docid, distance) in self.cached_sorted_docids.by_ref() {
+        for (docid, embedding_index, distance) in self.cached_sorted_docids.by_ref() {
             if vector_candidates.contains(docid) {
-                let score = 1.0 - distance;
+                let score = self.arroy.distance().similarity(distance);
                 let score = self
                     .distribution_shift
                     .map(|distribution| distribution.shift(score))
@@ -118,7 +167,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
                 return Ok(Some(RankingRuleOutput {
                     query,
                     candidates: RoaringBitmap::from_iter([docid]),
-                    score: ScoreDetails::Vector(score_details::Vector { similarity: Some(score) }),
+                    score: ScoreDetails::Vector(score_details::Vector {
+                        similarity: Some(score),
+                        chunk: self.chunked.then_some(embedding_index),
+                    }),
                 }));
             }
         }
@@ -134,7 +186,10 @@ impl<'ctx, Q: RankingRuleQueryTrait> RankingRule<'ctx, Q> for VectorSort<Q> {
             return Ok(Some(RankingRuleOutput {
                 query,
                 candidates: universe.clone(),
-                score: ScoreDetails::Vector(score_details::Vector { similarity: None }),
+                score: ScoreDetails::Vector(score_details::Vector {
+                    similarity: None,
+                    chunk: None,
+                }),
             }));
         }
 
@@ -0,0 +1,165 @@
+use std::sync::Arc;
+
+use roaring::RoaringBitmap;
+
+use crate::vector::{Embedder, Embedding};
+use crate::{DocumentId, Filter, Index, Result, SearchResult, Similar, UserError};
+
+/// Searches the documents near a target vector built from positive and negative examples.
+///
+/// The examples are documents of the index or raw vectors. The target is the mean of the
+/// positive examples, minus the mean of the negative examples weighted by `negative_weight`.
+/// A document with several embeddings counts as one example, the mean of its embeddings.
+/// When the embedder is quantized, these are the approximate embeddings of the trees.
+///
+/// The example documents are never part of the results.
+pub struct Recommend<'a> {
+    positive: Vec<DocumentId>,
+    negative: Vec<DocumentId>,
+    positive_vectors: Vec<Embedding>,
+    negative_vectors: Vec<Embedding>,
+    negative_weight: f32,
+    // this should be linked to the String in the query
+    filter: Option<Filter<'a>>,
+    offset: usize,
+    limit: usize,
+    rtxn: &'a heed::RoTxn<'a>,
+    index: &'a Index,
+    embedder_name: String,
+    embedder: Arc<Embedder>,
+    ranking_score_threshold: Option<f64>,
+}
+
+impl<'a> Recommend<'a> {
+    pub fn new(
+        offset: usize,
+        limit: usize,
+        index: &'a Index,
+        rtxn: &'a heed::RoTxn<'a>,
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+    ) -> Self {
+        Self {
+            positive: Vec::new(),
+            negative: Vec::new(),
+            positive_vectors: Vec::new(),
+            negative_vectors: Vec::new(),
+            negative_weight: 1.0,
+            filter: None,
+            offset,
+            limit,
+            rtxn,
+            index,
+            embedder_name,
+            embedder,
+            ranking_score_threshold: None,
+        }
+    }
+
+    pub fn positive(&mut self, documents: Vec<DocumentId>, vectors: Vec<Embedding>) -> &mut Self {
+        self.positive = documents;
+        self.positive_vectors = vectors;
+        self
+    }
+
+    pub fn negative(&mut self, documents: Vec<DocumentId>, vectors: Vec<Embedding>) -> &mut Self {
+        self.negative = documents;
+        self.negative_vectors = vectors;
+        self
+    }
+
+    pub fn negative_weight(&mut self, negative_weight: f32) -> &mut Self {
+        self.negative_weight = negative_weight;
+        self
+    }
+
+    pub fn filter(&mut self, filter: Filter<'a>) -> &mut Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    pub fn ranking_score_threshold(&mut self, ranking_score_threshold: f64) -> &mut Self {
+        self.ranking_score_threshold = Some(ranking_score_threshold);
+        self
+    }
+
+    pub fn execute(&self) -> Result<SearchResult> {
+        let arroy = self
+            .index
+            .arroy_wrapper(self.rtxn, &self.embedder_name)?
+            .ok_or_else(|| UserError::InvalidEmbedder(self.embedder_name.to_owned()))?;
+
+        let dimensions = self.embedder.dimensions();
+        for vector in self.positive_vectors.iter().chain(&self.negative_vectors) {
+            if vector.len() != dimensions {
+                return Err(UserError::InvalidVectorDimensions {
+                    expected: dimensions,
+                    found: vector.len(),
+                }
+                .into());
+            }
+        }
+
+        // we never want to receive the example documents
+        let examples: RoaringBitmap = self.positive.iter().chain(&self.negative).copied().collect();
+
+        let mut positive = self.positive_vectors.clone();
+        for &docid in &self.positive {
+            positive.extend(mean(&arroy.item_vectors(self.rtxn, docid)?));
+        }
+        let mut negative = self.negative_vectors.clone();
+        for &docid in &self.negative {
+            negative.extend(mean(&arroy.item_vectors(self.rtxn, docid)?));
+        }
+
+        // none of the positive examples has an embedding to compare the documents to
+        let Some(mut target) = mean(&positive) else {
+            return Ok(SearchResult {
+                matching_words: Default::default(),
+                candidates: RoaringBitmap::new(),
+                documents_ids: Vec::new(),
+                document_scores: Vec::new(),
+                cursor: None,
+                degraded: false,
+                used_negative_operator: false,
+                corrected_query: None,
+                groups: Vec::new(),
+            });
+        };
+        if let Some(negative) = mean(&negative) {
+            for (target, negative) in target.iter_mut().zip(negative) {
+                *target -= self.negative_weight * negative;
+            }
+        }
+
+        let mut similar = Similar::from_embeddings(
+            vec![target],
+            self.offset,
+            self.limit,
+            self.index,
+            self.rtxn,
+            self.embedder_name.clone(),
+            self.embedder.clone(),
+        );
+        if let Some(filter) = &self.filter {
+            similar.filter(filter.clone());
+        }
+        if let Some(ranking_score_threshold) = self.ranking_score_threshold {
+            similar.ranking_score_threshold(ranking_score_threshold);
+        }
+        similar.exclude(examples);
+        similar.execute()
+    }
+}
+
+/// The mean of the vectors, `None` when there is no vector.
+fn mean(vectors: &[Embedding]) -> Option<Embedding> {
+    let (first, others) = vectors.split_first()?;
+    let mut mean = first.clone();
+    for vector in others {
+        mean.iter_mut().zip(vector).for_each(|(mean, x)| *mean += x);
+    }
+    let count = vectors.len() as f32;
+    mean.iter_mut().for_each(|x| *x /= count);
+    Some(mean)
+}
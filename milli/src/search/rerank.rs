@@ -0,0 +1,168 @@
+use std::sync::Arc;
+
+use super::{Search, SearchResult};
+use crate::prompt::{FieldsIdsMapWithMetadata, Prompt};
+use crate::score_details::{self, ScoreDetails};
+use crate::vector::rerank::Reranker;
+use crate::{Result, UserError};
+
+/// Reranks the first hits of a search, see [`Search::rerank`].
+#[derive(Clone)]
+pub struct RerankSearch {
+    pub reranker_name: String,
+    pub reranker: Arc<Reranker>,
+    pub prompt: Prompt,
+    pub top_k: usize,
+}
+
+impl std::fmt::Debug for RerankSearch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RerankSearch")
+            .field("reranker_name", &self.reranker_name)
+            .field("reranker", &self.reranker)
+            .field("top_k", &self.top_k)
+            .finish()
+    }
+}
+
+impl<'a> Search<'a> {
+    /// The search fetching the hits to rerank: starting from the first hit, it returns
+    /// the hits to rerank and those of the requested page.
+    pub(super) fn search_to_rerank(&self, top_k: usize) -> Search<'a> {
+        Search {
+            query: self.query.clone(),
+            filter: self.filter.clone(),
+            offset: 0,
+            limit: (self.offset + self.limit).max(top_k),
+            sort_criteria: self.sort_criteria.clone(),
+            distinct: self.distinct.clone(),
+            searchable_attributes: self.searchable_attributes,
+            geo_strategy: self.geo_strategy,
+            terms_matching_strategy: self.terms_matching_strategy,
+            scoring_strategy: self.scoring_strategy,
+            words_limit: self.words_limit,
+            exhaustive_number_hits: self.exhaustive_number_hits,
+            rtxn: self.rtxn,
+            index: self.index,
+            semantic: self.semantic.clone(),
+            time_budget: self.time_budget.clone(),
+            ranking_score_threshold: self.ranking_score_threshold,
+            locales: self.locales.clone(),
+            search_after: self.search_after.clone(),
+            did_you_mean: self.did_you_mean,
+            ranking_rules: self.ranking_rules.clone(),
+            group_by: None,
+            rerank: None,
+        }
+    }
+}
+
+impl RerankSearch {
+    /// Reorders the first `top_k` hits of the results by the scores of the reranker,
+    /// then keeps the hits of the requested page.
+    ///
+    /// The hits that are not reranked keep their order and scores, after the reranked ones.
+    /// The ranking scores of the reranked hits are the scores of the reranker rescaled between
+    /// the ranking scores of these hits, so that they keep decreasing with the position of the hits.
+    pub(super) fn apply(
+        &self,
+        search: &Search<'_>,
+        mut results: SearchResult,
+    ) -> Result<SearchResult> {
+        // a placeholder search has no query to compare the documents to
+        if let Some(query) = search.query.as_deref() {
+            let top_k = self.top_k.min(results.documents_ids.len());
+
+            let fields_ids_map = search.index.fields_ids_map(search.rtxn)?;
+            let searchable_fields_ids = search.index.searchable_fields_ids(search.rtxn)?;
+            let fields_ids_map =
+                FieldsIdsMapWithMetadata::new(&fields_ids_map, &searchable_fields_ids);
+
+            let mut texts = Vec::with_capacity(top_k);
+            for entry in search
+                .index
+                .iter_documents(search.rtxn, results.documents_ids[..top_k].iter().copied())?
+            {
+                let (_docid, document) = entry?;
+                let text = self
+                    .prompt
+                    .render_stored(document, &fields_ids_map)
+                    .map_err(UserError::from)?;
+                texts.push(text);
+            }
+            let texts_len = texts.len();
+
+            let span = tracing::trace_span!(target: "search::rerank", "rerank", top_k);
+            let scores = span
+                .in_scope(|| self.reranker.rerank(query, texts, &search.time_budget))
+                .map_err(crate::vector::Error::from)?;
+            // the reranker stops early when the search runs out of time,
+            // only the hits it had the time to score are reranked
+            let top_k = scores.len();
+            if top_k < texts_len {
+                results.degraded = true;
+            }
+
+            // the reranked hits share the range of ranking scores they had before being reranked,
+            // above the ranking scores of the hits that follow them
+            let ranking_scores = results.document_scores[..top_k]
+                .iter()
+                .map(|details| ScoreDetails::global_score(details.iter()));
+            let (min_ranking_score, max_ranking_score) = ranking_scores
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), score| {
+                    (min.min(score), max.max(score))
+                });
+            let (min_score, max_score) =
+                scores.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &score| {
+                    (min.min(score), max.max(score))
+                });
+            let rescale = |score: f32| {
+                let normalized = if max_score > min_score {
+                    ((score - min_score) / (max_score - min_score)) as f64
+                } else {
+                    1.0
+                };
+                min_ranking_score + normalized * (max_ranking_score - min_ranking_score)
+            };
+
+            let mut reranked: Vec<_> = results
+                .documents_ids
+                .drain(..top_k)
+                .zip(results.document_scores.drain(..top_k))
+                .zip(scores)
+                .collect();
+            // the sort is stable, the documents of equal scores keep the order of the search
+            reranked.sort_by(|(_, left), (_, right)| right.total_cmp(left));
+
+            let (mut documents_ids, mut document_scores): (Vec<_>, Vec<_>) = reranked
+                .into_iter()
+                .enumerate()
+                .map(|(rank, ((docid, mut details), score))| {
+                    let rerank = score_details::Rerank {
+                        reranker: self.reranker_name.clone(),
+                        rank: rank as u32 + 1,
+                        score: score as f64,
+                        ranking_score: rescale(score),
+                    };
+                    details.insert(0, ScoreDetails::Rerank(rerank));
+                    (docid, details)
+                })
+                .unzip();
+            documents_ids.append(&mut results.documents_ids);
+            document_scores.append(&mut results.document_scores);
+            results.documents_ids = documents_ids;
+            results.document_scores = document_scores;
+        }
+
+        // keep the requested page only
+        let offset = search.offset.min(results.documents_ids.len());
+        results.documents_ids.drain(..offset);
+        results.documents_ids.truncate(search.limit);
+        results.document_scores.drain(..offset);
+        results.document_scores.truncate(search.limit);
+        // the cursor points to the last hit of the search, not of the page
+        results.cursor = None;
+
+        Ok(results)
+    }
+}
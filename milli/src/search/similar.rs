@@ -4,11 +4,21 @@ use ordered_float::OrderedFloat;
 use roaring::RoaringBitmap;
 
 use crate::score_details::{self, ScoreDetails};
-use crate::vector::Embedder;
+use crate::vector::{Embedder, Embedding};
 use crate::{filtered_universe, DocumentId, Filter, Index, Result, SearchResult};
 
+/// What the returned documents are similar to.
+enum Target {
+    /// A document of the index, that is never returned.
+    Document(DocumentId),
+    /// Embeddings that are not part of the index, such as the embeddings of a text.
+    ///
+    /// A document is as near as its nearest embedding to any of these embeddings.
+    Embeddings(Vec<Embedding>),
+}
+
 pub struct Similar<'a> {
-    id: DocumentId,
+    target: Target,
     // this should be linked to the String in the query
     filter: Option<Filter<'a>>,
     offset: usize,
@@ -18,6 +28,7 @@ pub struct Similar<'a> {
     embedder_name: String,
     embedder: Arc<Embedder>,
     ranking_score_threshold: Option<f64>,
+    excluded: RoaringBitmap,
 }
 
 impl<'a> Similar<'a> {
@@ -29,9 +40,42 @@ impl<'a> Similar<'a> {
         rtxn: &'a heed::RoTxn<'a>,
         embedder_name: String,
         embedder: Arc<Embedder>,
+    ) -> Self {
+        Self::with_target(Target::Document(id), offset, limit, index, rtxn, embedder_name, embedder)
+    }
+
+    /// Searches the documents similar to embeddings that are not part of the index.
+    pub fn from_embeddings(
+        embeddings: Vec<Embedding>,
+        offset: usize,
+        limit: usize,
+        index: &'a Index,
+        rtxn: &'a heed::RoTxn<'a>,
+        embedder_name: String,
+        embedder: Arc<Embedder>,
+    ) -> Self {
+        Self::with_target(
+            Target::Embeddings(embeddings),
+            offset,
+            limit,
+            index,
+            rtxn,
+            embedder_name,
+            embedder,
+        )
+    }
+
+    fn with_target(
+        target: Target,
+        offset: usize,
+        limit: usize,
+        index: &'a Index,
+        rtxn: &'a heed::RoTxn<'a>,
+        embedder_name: String,
+        embedder: Arc<Embedder>,
     ) -> Self {
         Self {
-            id,
+            target,
             filter: None,
             offset,
             limit,
@@ -40,6 +84,7 @@ impl<'a> Similar<'a> {
             embedder_name,
             embedder,
             ranking_score_threshold: None,
+            excluded: RoaringBitmap::new(),
         }
     }
 
@@ -53,50 +98,56 @@ impl<'a> Similar<'a> {
         self
     }
 
+    /// Documents that are never returned, in addition to the target document.
+    pub fn exclude(&mut self, documents: RoaringBitmap) -> &mut Self {
+        self.excluded = documents;
+        self
+    }
+
     pub fn execute(&self) -> Result<SearchResult> {
         let mut universe = filtered_universe(self.index, self.rtxn, &self.filter)?;
 
         // we never want to receive the docid
-        universe.remove(self.id);
+        if let Target::Document(id) = self.target {
+            universe.remove(id);
+        }
+        universe -= &self.excluded;
 
         let universe = universe;
 
-        let embedder_index =
-            self.index
-                .embedder_category_id
-                .get(self.rtxn, &self.embedder_name)?
-                .ok_or_else(|| crate::UserError::InvalidEmbedder(self.embedder_name.to_owned()))?;
-
-        let readers: std::result::Result<Vec<_>, _> =
-            self.index.arroy_readers(self.rtxn, embedder_index).collect();
-
-        let readers = readers?;
-
-        let mut results = Vec::new();
-
-        for reader in readers.iter() {
-            let nns_by_item = reader.nns_by_item(
-                self.rtxn,
-                self.id,
-                self.limit + self.offset + 1,
-                None,
-                Some(&universe),
-            )?;
-            if let Some(mut nns_by_item) = nns_by_item {
-                results.append(&mut nns_by_item);
-            } else {
-                break;
-            }
-        }
+        let arroy = self
+            .index
+            .arroy_wrapper(self.rtxn, &self.embedder_name)?
+            .ok_or_else(|| crate::UserError::InvalidEmbedder(self.embedder_name.to_owned()))?;
 
-        results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+        let results = match &self.target {
+            Target::Document(id) => {
+                arroy.nns_by_item(self.rtxn, *id, self.limit + self.offset + 1, Some(&universe))?
+            }
+            Target::Embeddings(embeddings) => {
+                let mut results = Vec::new();
+                for embedding in embeddings {
+                    let nns = arroy.nns_by_vector(
+                        self.rtxn,
+                        embedding,
+                        self.limit + self.offset,
+                        Some(&universe),
+                    )?;
+                    results.extend(nns.into_iter().map(|(docid, _, distance)| (docid, distance)));
+                }
+                results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+                results
+            }
+        };
 
         let mut documents_ids = Vec::with_capacity(self.limit);
         let mut document_scores = Vec::with_capacity(self.limit);
         // list of documents we've already seen, so that we don't return the same document multiple times.
         // initialized to the target document, that we never want to return.
         let mut documents_seen = RoaringBitmap::new();
-        documents_seen.insert(self.id);
+        if let Target::Document(id) = self.target {
+            documents_seen.insert(id);
+        }
 
         let mut candidates = universe;
 
@@ -108,15 +159,17 @@ impl<'a> Similar<'a> {
             // take **after** filter and skip so that we get exactly limit elements if available
             .take(self.limit)
         {
-            let score = 1.0 - distance;
+            let score = arroy.distance().similarity(distance);
             let score = self
                 .embedder
                 .distribution()
                 .map(|distribution| distribution.shift(score))
                 .unwrap_or(score);
 
-            let score_details =
-                vec![ScoreDetails::Vector(score_details::Vector { similarity: Some(score) })];
+            let score_details = vec![ScoreDetails::Vector(score_details::Vector {
+                similarity: Some(score),
+                chunk: None,
+            })];
 
             let score = ScoreDetails::global_score(score_details.iter());
 
@@ -139,8 +192,11 @@ impl<'a> Similar<'a> {
             candidates,
             documents_ids,
             document_scores,
+            cursor: None,
             degraded: false,
             used_negative_operator: false,
+            corrected_query: None,
+            groups: Vec::new(),
         })
     }
 }
@@ -0,0 +1,303 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use charabia::{TokenKind, TokenizerBuilder};
+use fst::automaton::{Automaton, Str};
+use fst::{IntoStreamer, Streamer};
+use roaring::RoaringBitmap;
+
+use crate::{FieldId, Index, Result, Search, SearchContext};
+
+/// The default number of suggestions returned by the suggest route.
+pub const DEFAULT_SUGGESTIONS_LIMIT: usize = 10;
+
+/// Completes the last word of a query with the words of the index
+/// that start with it, the most frequent words first.
+pub struct SearchForSuggestions<'a> {
+    search_query: Search<'a>,
+    limit: usize,
+}
+
+#[derive(Debug, Clone, serde::Serialize, PartialEq)]
+pub struct Suggestion {
+    /// The word completing the last word of the query
+    pub value: String,
+    /// The number of documents matching the query that contain this word
+    pub count: u64,
+}
+
+impl<'a> SearchForSuggestions<'a> {
+    /// The query, filter and attributes to search on of the `search_query` restrict
+    /// the documents in which the completions of the last word of the query are counted.
+    pub fn new(search_query: Search<'a>) -> SearchForSuggestions<'a> {
+        SearchForSuggestions { search_query, limit: DEFAULT_SUGGESTIONS_LIMIT }
+    }
+
+    pub fn limit(&mut self, limit: usize) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn execute(&self) -> Result<Vec<Suggestion>> {
+        let index = self.search_query.index;
+        let rtxn = self.search_query.rtxn;
+
+        let query = match self.search_query.query.as_deref() {
+            Some(query) if self.limit != 0 => query,
+            _ => return Ok(Vec::new()),
+        };
+        let prefix = match last_word(index, rtxn, query)? {
+            Some(prefix) => prefix,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut ctx = SearchContext::new(index, rtxn)?;
+        if let Some(searchable_attributes) = self.search_query.searchable_attributes {
+            ctx.attributes_to_search_on(searchable_attributes)?;
+        }
+        let restricted_fids: Option<Vec<FieldId>> = ctx.restricted_fids.as_ref().map(|fids| {
+            fids.tolerant.iter().chain(fids.exact.iter()).map(|(fid, _)| *fid).collect()
+        });
+
+        let mut candidates = self.search_query.execute_for_candidates(false)?;
+
+        // The documents containing the most frequent prefixes are precomputed,
+        // we use them to stop early when no candidate contains the prefix.
+        if index.words_prefixes_fst(rtxn)?.contains(&prefix) {
+            let prefix_docids = match &restricted_fids {
+                Some(fids) => {
+                    let mut docids = RoaringBitmap::new();
+                    for &fid in fids {
+                        let key = (prefix.as_str(), fid);
+                        docids |= index.word_prefix_fid_docids.get(rtxn, &key)?.unwrap_or_default();
+                    }
+                    docids
+                }
+                None => index.word_prefix_docids.get(rtxn, prefix.as_str())?.unwrap_or_default(),
+            };
+            candidates &= prefix_docids;
+        }
+        if candidates.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        // A min-heap keeping the `limit` most frequent words,
+        // words with the same frequency are sorted alphabetically.
+        let mut best = BinaryHeap::new();
+        let words_fst = index.words_fst(rtxn)?;
+        let mut stream = words_fst.search(Str::new(&prefix).starts_with()).into_stream();
+        while let Some(word) = stream.next() {
+            // the most frequent words among the ones already scanned are returned when
+            // the search runs out of time
+            if self.search_query.time_budget.exceeded() {
+                break;
+            }
+            let word = std::str::from_utf8(word)?;
+            let docids = match &restricted_fids {
+                Some(fids) => {
+                    let mut docids = RoaringBitmap::new();
+                    for &fid in fids {
+                        docids |=
+                            index.word_fid_docids.get(rtxn, &(word, fid))?.unwrap_or_default();
+                    }
+                    docids
+                }
+                None => index.word_docids.get(rtxn, word)?.unwrap_or_default(),
+            };
+            let count = candidates.intersection_len(&docids);
+            if count != 0 {
+                best.push(Reverse((count, Reverse(word.to_string()))));
+                if best.len() > self.limit {
+                    best.pop();
+                }
+            }
+        }
+
+        Ok(best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse((count, Reverse(value)))| Suggestion { value, count })
+            .collect())
+    }
+}
+
+/// Returns the normalized last word of the query, if the query does not end with a separator.
+fn last_word(index: &Index, rtxn: &heed::RoTxn<'_>, query: &str) -> Result<Option<String>> {
+    let mut tokbuilder = TokenizerBuilder::<&[u8]>::new();
+
+    let separators = index.allowed_separators(rtxn)?;
+    let separators: Option<Vec<_>> =
+        separators.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref separators) = separators {
+        tokbuilder.separators(separators);
+    }
+
+    let dictionary = index.dictionary(rtxn)?;
+    let dictionary: Option<Vec<_>> =
+        dictionary.as_ref().map(|x| x.iter().map(String::as_str).collect());
+    if let Some(ref dictionary) = dictionary {
+        tokbuilder.words_dict(dictionary);
+    }
+
+    let tokenizer = tokbuilder.build();
+    let last_token = tokenizer.tokenize(query).last();
+    Ok(last_token.and_then(|token| match token.kind {
+        TokenKind::Word | TokenKind::StopWord if !token.lemma().is_empty() => {
+            Some(token.lemma().to_string())
+        }
+        _ => None,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use big_s::S;
+    use maplit::hashset;
+
+    use crate::index::tests::TempIndex;
+    use crate::{Filter, Search, SearchForSuggestions};
+
+    fn create_index() -> TempIndex {
+        let index = TempIndex::new();
+
+        index
+            .update_settings(|s| {
+                s.set_primary_key(S("id"));
+                s.set_searchable_fields(vec![S("title"), S("description")]);
+                s.set_filterable_fields(hashset! { S("genre") });
+            })
+            .unwrap();
+
+        index
+            .add_documents(documents!([
+                { "id": 0, "title": "Harry Potter", "description": "a young wizard", "genre": "fantasy" },
+                { "id": 1, "title": "Harry Potter and the Potion", "description": "wizards", "genre": "fantasy" },
+                { "id": 2, "title": "The Potato Cookbook", "description": "potatoes for everyone", "genre": "cooking" },
+                { "id": 3, "title": "Pottery", "description": "how to make a pot", "genre": "craft" },
+                { "id": 4, "title": "Poetry", "description": "the potter's poems", "genre": "poetry" },
+            ]))
+            .unwrap();
+
+        index
+    }
+
+    fn suggest(index: &TempIndex, configure: impl Fn(&mut Search)) -> Vec<(String, u64)> {
+        let rtxn = index.read_txn().unwrap();
+        let mut search = Search::new(&rtxn, index);
+        configure(&mut search);
+        let suggestions = SearchForSuggestions::new(search).execute().unwrap();
+        suggestions.into_iter().map(|s| (s.value, s.count)).collect()
+    }
+
+    #[test]
+    fn suggest_by_frequency() {
+        let index = create_index();
+
+        let suggestions = suggest(&index, |s| {
+            s.query("pot");
+        });
+        insta::assert_debug_snapshot!(suggestions, @r###"
+        [
+            (
+                "potter",
+                3,
+            ),
+            (
+                "pot",
+                1,
+            ),
+            (
+                "potato",
+                1,
+            ),
+            (
+                "potatoes",
+                1,
+            ),
+            (
+                "potion",
+                1,
+            ),
+            (
+                "pottery",
+                1,
+            ),
+        ]
+        "###);
+
+        // the completions are counted in the documents matching the rest of the query
+        let suggestions = suggest(&index, |s| {
+            s.query("harry pot");
+        });
+        insta::assert_debug_snapshot!(suggestions, @r###"
+        [
+            (
+                "potter",
+                2,
+            ),
+            (
+                "potion",
+                1,
+            ),
+        ]
+        "###);
+
+        // there is nothing to complete when the last word is finished
+        assert!(suggest(&index, |s| {
+            s.query("pot ");
+        })
+        .is_empty());
+        assert!(suggest(&index, |_| ()).is_empty());
+    }
+
+    #[test]
+    fn suggest_with_filter_and_attributes() {
+        let index = create_index();
+
+        let rtxn = index.read_txn().unwrap();
+        let filter = Filter::from_str("genre = fantasy").unwrap().unwrap();
+        let mut search = Search::new(&rtxn, &index);
+        search.query("pot");
+        search.filter(filter);
+        let mut suggest = SearchForSuggestions::new(search);
+        suggest.limit(1);
+        let suggestions = suggest.execute().unwrap();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!((suggestions[0].value.as_str(), suggestions[0].count), ("potter", 2));
+        drop(rtxn);
+
+        // the heap of suggestions is not sized by the limit
+        let rtxn = index.read_txn().unwrap();
+        let mut search = Search::new(&rtxn, &index);
+        search.query("pot");
+        let mut suggest = SearchForSuggestions::new(search);
+        suggest.limit(usize::MAX);
+        assert_eq!(suggest.execute().unwrap().len(), 6);
+        drop(rtxn);
+
+        // the completions are only looked for in the attributes to search on
+        let rtxn = index.read_txn().unwrap();
+        let attributes = vec![S("description")];
+        let mut search = Search::new(&rtxn, &index);
+        search.query("pot");
+        search.searchable_attributes(&attributes);
+        let suggestions = SearchForSuggestions::new(search).execute().unwrap();
+        let suggestions: Vec<_> = suggestions.into_iter().map(|s| (s.value, s.count)).collect();
+        insta::assert_debug_snapshot!(suggestions, @r###"
+        [
+            (
+                "pot",
+                1,
+            ),
+            (
+                "potatoes",
+                1,
+            ),
+            (
+                "potter",
+                1,
+            ),
+        ]
+        "###);
+    }
+}
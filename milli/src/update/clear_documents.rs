@@ -47,7 +47,9 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
             field_id_docid_facet_strings,
             vector_arroy,
             embedder_category_id: _,
+            embedder_distance: _,
             documents,
+            documents_versions,
         } = self.index;
 
         let empty_roaring = RoaringBitmap::default();
@@ -95,6 +97,7 @@ impl<'t, 'i> ClearDocuments<'t, 'i> {
         vector_arroy.clear(self.wtxn)?;
 
         documents.clear(self.wtxn)?;
+        documents_versions.clear(self.wtxn)?;
 
         Ok(number_of_documents)
     }
@@ -10,6 +10,7 @@ use crate::documents::{
     EnrichedDocumentsBatchReader, PrimaryKey, DEFAULT_PRIMARY_KEY,
 };
 use crate::error::{GeoError, InternalError, UserError};
+use crate::index::RESERVED_EXPECTED_VERSION_FIELD_NAME;
 use crate::update::index_documents::{obkv_to_object, writer_into_reader};
 use crate::{FieldId, Index, Result};
 
@@ -17,7 +18,8 @@ use crate::{FieldId, Index, Result};
 ///  - we can infer a primary key,
 ///  - all the documents id exist and are extracted,
 ///  - the validity of them but also,
-///  - the validity of the `_geo` field depending on the settings.
+///  - the validity of the `_geo` field depending on the settings,
+///  - the validity of the `_expectedVersion` field.
 ///
 /// # Panics
 ///
@@ -103,6 +105,8 @@ pub fn enrich_documents_batch<R: Read + Seek>(
         _otherwise => None,
     };
 
+    let expected_version_field_id = documents_batch_index.id(RESERVED_EXPECTED_VERSION_FIELD_NAME);
+
     let mut count = 0;
     while let Some(document) = cursor.next_document()? {
         let document_id = match fetch_or_generate_document_id(
@@ -123,6 +127,15 @@ pub fn enrich_documents_batch<R: Read + Seek>(
             }
         }
 
+        if let Some(expected_version) = expected_version_field_id.and_then(|fid| document.get(fid))
+        {
+            if let Err(user_error) =
+                validate_expected_version_from_json(&document_id, expected_version)?
+            {
+                return Ok(Err(user_error));
+            }
+        }
+
         let document_id = serde_json::to_vec(&document_id).map_err(InternalError::SerdeJson)?;
         external_ids.insert(count.to_be_bytes(), document_id)?;
 
@@ -232,32 +245,60 @@ pub fn extract_finite_float_from_value(value: Value) -> StdResult<f64, Value> {
     }
 }
 
+/// Validates the `_geo` field of a document, which is either a single point
+/// or an array of points.
 pub fn validate_geo_from_json(id: &DocumentId, bytes: &[u8]) -> Result<StdResult<(), GeoError>> {
-    use GeoError::*;
     let debug_id = || {
         serde_json::from_slice(id.value().as_bytes()).unwrap_or_else(|_| Value::from(id.debug()))
     };
     match serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)? {
+        Value::Null => Ok(Ok(())),
+        Value::Array(points) => Ok(points.into_iter().try_for_each(|point| match point {
+            // a null point is not allowed inside of an array
+            Value::Null => Err(GeoError::NotAnObject { document_id: debug_id(), value: point }),
+            point => validate_geo_point(point, debug_id),
+        })),
+        point => Ok(validate_geo_point(point, debug_id)),
+    }
+}
+
+/// Validates that the `_expectedVersion` of a document is a positive integer.
+fn validate_expected_version_from_json(
+    id: &DocumentId,
+    bytes: &[u8],
+) -> Result<StdResult<(), UserError>> {
+    let value: Value = serde_json::from_slice(bytes).map_err(InternalError::SerdeJson)?;
+    match value.as_u64() {
+        Some(_) => Ok(Ok(())),
+        None => {
+            let document_id = serde_json::from_slice(id.value().as_bytes())
+                .unwrap_or_else(|_| Value::from(id.debug()));
+            Ok(Err(UserError::InvalidDocumentExpectedVersion { document_id, value }))
+        }
+    }
+}
+
+fn validate_geo_point(point: Value, debug_id: impl Fn() -> Value) -> StdResult<(), GeoError> {
+    use GeoError::*;
+    match point {
         Value::Object(mut object) => match (object.remove("lat"), object.remove("lng")) {
             (Some(lat), Some(lng)) => {
                 match (extract_finite_float_from_value(lat), extract_finite_float_from_value(lng)) {
-                    (Ok(_), Ok(_)) if !object.is_empty() => Ok(Err(UnexpectedExtraFields {
-                        document_id: debug_id(),
-                        value: object.into(),
-                    })),
-                    (Ok(_), Ok(_)) => Ok(Ok(())),
-                    (Err(value), Ok(_)) => Ok(Err(BadLatitude { document_id: debug_id(), value })),
-                    (Ok(_), Err(value)) => Ok(Err(BadLongitude { document_id: debug_id(), value })),
+                    (Ok(_), Ok(_)) if !object.is_empty() => {
+                        Err(UnexpectedExtraFields { document_id: debug_id(), value: object.into() })
+                    }
+                    (Ok(_), Ok(_)) => Ok(()),
+                    (Err(value), Ok(_)) => Err(BadLatitude { document_id: debug_id(), value }),
+                    (Ok(_), Err(value)) => Err(BadLongitude { document_id: debug_id(), value }),
                     (Err(lat), Err(lng)) => {
-                        Ok(Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng }))
+                        Err(BadLatitudeAndLongitude { document_id: debug_id(), lat, lng })
                     }
                 }
             }
-            (None, Some(_)) => Ok(Err(MissingLatitude { document_id: debug_id() })),
-            (Some(_), None) => Ok(Err(MissingLongitude { document_id: debug_id() })),
-            (None, None) => Ok(Err(MissingLatitudeAndLongitude { document_id: debug_id() })),
+            (None, Some(_)) => Err(MissingLatitude { document_id: debug_id() }),
+            (Some(_), None) => Err(MissingLongitude { document_id: debug_id() }),
+            (None, None) => Err(MissingLatitudeAndLongitude { document_id: debug_id() }),
         },
-        Value::Null => Ok(Ok(())),
-        value => Ok(Err(NotAnObject { document_id: debug_id(), value })),
+        value => Err(NotAnObject { document_id: debug_id(), value }),
     }
 }
@@ -20,7 +20,7 @@ use crate::error::InternalError;
 use crate::facet::value_encoding::f64_into_bytes;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::{create_writer, writer_into_reader};
-use crate::update::settings::InnerIndexSettingsDiff;
+use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::{CboRoaringBitmapCodec, DocumentId, Error, FieldId, Result, MAX_FACET_VALUE_LENGTH};
 
 /// The length of the elements that are always in the buffer when inserting new values.
@@ -40,6 +40,7 @@ pub struct ExtractedFacetValues {
 /// Returns the generated grenad reader containing the docid the fid and the original value as key
 /// and the normalized value as value extracted from the given chunk of documents.
 /// We need the fid of the geofields to correctly parse them as numbers if they were sent as strings initially.
+/// The RFC 3339 strings of the datetime fields are also parsed and extracted as numbers.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -80,7 +81,13 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     let new_faceted_fids: BTreeSet<_> =
         settings_diff.new.faceted_fields_ids.iter().copied().collect();
 
-    if !settings_diff.settings_update_only || old_faceted_fids != new_faceted_fids {
+    let datetime_fields_changed =
+        settings_diff.old.datetime_fields != settings_diff.new.datetime_fields;
+
+    if !settings_diff.settings_update_only
+        || old_faceted_fids != new_faceted_fids
+        || datetime_fields_changed
+    {
         let mut cursor = obkv_documents.into_cursor()?;
         while let Some((docid_bytes, value)) = cursor.move_on_next()? {
             let obkv = obkv::KvReader::new(value);
@@ -113,7 +120,10 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                     }
                     EitherOrBoth::Both(&field_id, _) => {
                         // during settings update, recompute the changing settings only.
-                        if settings_diff.settings_update_only {
+                        if settings_diff.settings_update_only
+                            && is_datetime_field(&settings_diff.old, field_id)
+                                == is_datetime_field(&settings_diff.new, field_id)
+                        {
                             continue;
                         }
 
@@ -163,10 +173,12 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
                         .new
                         .geo_fields_ids
                         .map_or(false, |(lat, lng)| field_id == lat || field_id == lng);
-                    let del_filterable_values =
-                        del_value.map(|value| extract_facet_values(&value, del_geo_support));
-                    let add_filterable_values =
-                        add_value.map(|value| extract_facet_values(&value, add_geo_support));
+                    let del_datetime = is_datetime_field(&settings_diff.old, field_id);
+                    let add_datetime = is_datetime_field(&settings_diff.new, field_id);
+                    let del_filterable_values = del_value
+                        .map(|value| extract_facet_values(&value, del_geo_support, del_datetime));
+                    let add_filterable_values = add_value
+                        .map(|value| extract_facet_values(&value, add_geo_support, add_datetime));
 
                     // Those closures are just here to simplify things a bit.
                     let mut insert_numbers_diff = |del_numbers, add_numbers| {
@@ -301,6 +313,14 @@ pub fn extract_fid_docid_facet_values<R: io::Read + io::Seek>(
     })
 }
 
+/// Returns `true` if the strings of this field must be parsed as RFC 3339 datetimes.
+fn is_datetime_field(settings: &InnerIndexSettings, field_id: FieldId) -> bool {
+    settings
+        .fields_ids_map
+        .name(field_id)
+        .map_or(false, |name| settings.datetime_fields.contains(name))
+}
+
 /// Generates a vector of bytes containing a DelAdd obkv with two bitmaps.
 fn deladd_obkv_cbo_roaring_bitmaps(
     buffer: &mut Vec<u8>,
@@ -517,13 +537,14 @@ enum FilterableValues {
 }
 
 /// Extracts the facet values of a JSON field.
-fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
+fn extract_facet_values(value: &Value, geo_field: bool, datetime_field: bool) -> FilterableValues {
     fn inner_extract_facet_values(
         value: &Value,
         can_recurse: bool,
         output_numbers: &mut Vec<f64>,
         output_strings: &mut Vec<(String, String)>,
         geo_field: bool,
+        datetime_field: bool,
     ) {
         match value {
             Value::Null => (),
@@ -534,6 +555,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                 }
             }
             Value::String(original) => {
+                // the datetimes are only stored as numbers, strings that are not valid datetimes
+                // are kept as strings to be filtered like any other string.
+                if datetime_field {
+                    if let Some(timestamp) = crate::parse_datetime(original) {
+                        output_numbers.push(timestamp);
+                        return;
+                    }
+                }
                 // if we're working on a geofield it MUST be something we can parse or else there was an internal error
                 // in the enrich pipeline. But since the enrich pipeline worked, we want to avoid crashing at all costs.
                 if geo_field {
@@ -557,6 +586,7 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
                             output_numbers,
                             output_strings,
                             geo_field,
+                            datetime_field,
                         );
                     }
                 }
@@ -573,7 +603,14 @@ fn extract_facet_values(value: &Value, geo_field: bool) -> FilterableValues {
         otherwise => {
             let mut numbers = Vec::new();
             let mut strings = Vec::new();
-            inner_extract_facet_values(otherwise, true, &mut numbers, &mut strings, geo_field);
+            inner_extract_facet_values(
+                otherwise,
+                true,
+                &mut numbers,
+                &mut strings,
+                geo_field,
+                datetime_field,
+            );
             FilterableValues::Values { numbers, strings }
         }
     }
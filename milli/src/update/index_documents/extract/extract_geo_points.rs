@@ -1,3 +1,4 @@
+use std::cmp::Ordering;
 use std::fs::File;
 use std::io::{self, BufReader};
 
@@ -13,7 +14,8 @@ use crate::{FieldId, InternalError, Result};
 
 /// Extracts the geographical coordinates contained in each document under the `_geo` field.
 ///
-/// Returns the generated grenad reader containing the docid as key associated to the (latitude, longitude)
+/// Returns the generated grenad reader containing the docid as key associated to the list of
+/// (latitude, longitude) of the document, a document can contain several points.
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_geo_points<R: io::Read + io::Seek>(
     obkv_documents: grenad::Reader<R>,
@@ -48,15 +50,11 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
 
         if del_lat_lng != add_lat_lng {
             let mut obkv = KvWriterDelAdd::memory();
-            if let Some([lat, lng]) = del_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Deletion, bytes)?;
+            if !del_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Deletion, encode_lat_lng(&del_lat_lng))?;
             }
-            if let Some([lat, lng]) = add_lat_lng {
-                #[allow(clippy::drop_non_drop)]
-                let bytes: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
-                obkv.insert(DelAdd::Addition, bytes)?;
+            if !add_lat_lng.is_empty() {
+                obkv.insert(DelAdd::Addition, encode_lat_lng(&add_lat_lng))?;
             }
             let bytes = obkv.into_inner()?;
             writer.insert(docid_bytes, bytes)?;
@@ -66,13 +64,27 @@ pub fn extract_geo_points<R: io::Read + io::Seek>(
     writer_into_reader(writer)
 }
 
+/// Concatenates the (latitude, longitude) of all the points, 16 bytes per point.
+fn encode_lat_lng(points: &[[f64; 2]]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(points.len() * 16);
+    for [lat, lng] in points {
+        #[allow(clippy::drop_non_drop)]
+        let point: [u8; 16] = concat_arrays![lat.to_ne_bytes(), lng.to_ne_bytes()];
+        bytes.extend_from_slice(&point);
+    }
+    bytes
+}
+
 /// Extract the finite floats lat and lng from two bytes slices.
+///
+/// When the `_geo` field is an array of points, the flattened `_geo.lat` and `_geo.lng`
+/// fields are arrays of the same length that are zipped back together.
 fn extract_lat_lng(
     document: &obkv::KvReader<'_, FieldId>,
     settings: &InnerIndexSettings,
     deladd: DelAdd,
     document_id: impl Fn() -> Value,
-) -> Result<Option<[f64; 2]>> {
+) -> Result<Vec<[f64; 2]>> {
     match settings.geo_fields_ids {
         Some((lat_fid, lng_fid)) => {
             let lat = document.get(lat_fid).map(KvReaderDelAdd::new).and_then(|r| r.get(deladd));
@@ -85,19 +97,45 @@ fn extract_lat_lng(
                 (None, Some(_)) => {
                     return Err(GeoError::MissingLongitude { document_id: document_id() }.into())
                 }
-                (None, None) => return Ok(None),
+                (None, None) => return Ok(Vec::new()),
+            };
+            let lat: Value = serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?;
+            let lng: Value = serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?;
+            let pairs = match (lat, lng) {
+                (Value::Array(lats), Value::Array(lngs)) => {
+                    match lats.len().cmp(&lngs.len()) {
+                        Ordering::Less => {
+                            return Err(
+                                GeoError::MissingLatitude { document_id: document_id() }.into()
+                            )
+                        }
+                        Ordering::Greater => {
+                            return Err(
+                                GeoError::MissingLongitude { document_id: document_id() }.into()
+                            )
+                        }
+                        Ordering::Equal => (),
+                    }
+                    lats.into_iter().zip(lngs).collect()
+                }
+                (lat, lng) => vec![(lat, lng)],
             };
-            let lat = extract_finite_float_from_value(
-                serde_json::from_slice(lat).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lat| GeoError::BadLatitude { document_id: document_id(), value: lat })?;
 
-            let lng = extract_finite_float_from_value(
-                serde_json::from_slice(lng).map_err(InternalError::SerdeJson)?,
-            )
-            .map_err(|lng| GeoError::BadLongitude { document_id: document_id(), value: lng })?;
-            Ok(Some([lat, lng]))
+            let mut points = Vec::with_capacity(pairs.len());
+            for (lat, lng) in pairs {
+                let lat = extract_finite_float_from_value(lat).map_err(|lat| {
+                    GeoError::BadLatitude { document_id: document_id(), value: lat }
+                })?;
+                let lng = extract_finite_float_from_value(lng).map_err(|lng| {
+                    GeoError::BadLongitude { document_id: document_id(), value: lng }
+                })?;
+                // the same point is only stored once in the rtree
+                if !points.contains(&[lat, lng]) {
+                    points.push([lat, lng]);
+                }
+            }
+            Ok(points)
         }
-        None => Ok(None),
+        None => Ok(Vec::new()),
     }
 }
@@ -38,6 +38,7 @@ pub struct ExtractedVectorPoints {
     // embedder
     pub embedder_name: String,
     pub embedder: Arc<Embedder>,
+    pub prompt: Arc<Prompt>,
     pub add_to_user_provided: RoaringBitmap,
     pub remove_from_user_provided: RoaringBitmap,
 }
@@ -446,7 +447,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
     for EmbedderVectorExtractor {
         embedder_name,
         embedder,
-        prompt: _,
+        prompt,
         prompts_writer,
         remove_vectors_writer,
         action,
@@ -469,6 +470,7 @@ pub fn extract_vector_points<R: io::Read + io::Seek>(
             remove_vectors: writer_into_reader(remove_vectors_writer)?,
             prompts: writer_into_reader(prompts_writer)?,
             embedder,
+            prompt,
             embedder_name,
             add_to_user_provided,
             remove_from_user_provided,
@@ -679,12 +681,14 @@ fn compare_vectors(a: &[f32], b: &[f32]) -> Ordering {
     a.iter().copied().map(OrderedFloat).cmp(b.iter().copied().map(OrderedFloat))
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(level = "trace", skip_all, target = "indexing::extract")]
 pub fn extract_embeddings<R: io::Read + io::Seek>(
     // docid, prompt
     prompt_reader: grenad::Reader<R>,
     indexer: GrenadParameters,
     embedder: Arc<Embedder>,
+    prompt: &Prompt,
     embedder_name: &str,
     possible_embedding_mistakes: &PossibleEmbeddingMistakes,
     unused_vectors_distribution: &UnusedVectorsDistribution,
@@ -694,11 +698,14 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     let n_vectors_per_chunk = embedder.prompt_count_in_chunk_hint(); // number of vectors in a single chunk
 
     // docid, state with embedding
-    let mut state_writer = create_writer(
-        indexer.chunk_compression_type,
-        indexer.chunk_compression_level,
-        tempfile::tempfile()?,
-    );
+    let mut state_writer = EmbeddingsWriter {
+        writer: create_writer(
+            indexer.chunk_compression_type,
+            indexer.chunk_compression_level,
+            tempfile::tempfile()?,
+        ),
+        current: None,
+    };
 
     let mut chunks = Vec::with_capacity(n_chunks);
     let mut current_chunk = Vec::with_capacity(n_vectors_per_chunk);
@@ -709,19 +716,22 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
     while let Some((key, value)) = cursor.move_on_next()? {
         let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
         // SAFETY: precondition, the grenad value was saved from a string
-        let prompt = unsafe { std::str::from_utf8_unchecked(value) };
-        if current_chunk.len() == current_chunk.capacity() {
-            chunks.push(std::mem::replace(
-                &mut current_chunk,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-            chunks_ids.push(std::mem::replace(
-                &mut current_chunk_ids,
-                Vec::with_capacity(n_vectors_per_chunk),
-            ));
-        };
-        current_chunk.push(prompt.to_owned());
-        current_chunk_ids.push(docid);
+        let rendered = unsafe { std::str::from_utf8_unchecked(value) };
+        // the texts of a document are pushed one after the other, so that their embeddings are consecutive
+        for text in prompt.chunks(rendered) {
+            if current_chunk.len() == current_chunk.capacity() {
+                chunks.push(std::mem::replace(
+                    &mut current_chunk,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+                chunks_ids.push(std::mem::replace(
+                    &mut current_chunk_ids,
+                    Vec::with_capacity(n_vectors_per_chunk),
+                ));
+            };
+            current_chunk.push(text.to_owned());
+            current_chunk_ids.push(docid);
+        }
 
         if chunks.len() == chunks.capacity() {
             let chunked_embeds = embed_chunks(
@@ -738,7 +748,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
                 .flat_map(|docids| docids.iter())
                 .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
             {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings.as_inner()))?;
+                state_writer.push(*docid, embeddings)?;
             }
             chunks_ids.clear();
         }
@@ -759,7 +769,7 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
             .flat_map(|docids| docids.iter())
             .zip(chunked_embeds.iter().flat_map(|embeds| embeds.iter()))
         {
-            state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings.as_inner()))?;
+            state_writer.push(*docid, embeddings)?;
         }
     }
 
@@ -775,12 +785,46 @@ pub fn extract_embeddings<R: io::Read + io::Seek>(
 
         if let Some(embeds) = embeds.first() {
             for (docid, embeddings) in current_chunk_ids.iter().zip(embeds.iter()) {
-                state_writer.insert(docid.to_be_bytes(), cast_slice(embeddings.as_inner()))?;
+                state_writer.push(*docid, embeddings)?;
+            }
+        }
+    }
+
+    state_writer.into_reader()
+}
+
+/// Writes the embeddings of the documents, the embeddings of the chunks of a document
+/// being concatenated into the embeddings of the document.
+struct EmbeddingsWriter {
+    writer: Writer<BufWriter<File>>,
+    /// The document whose embeddings are being pushed, with its embeddings so far.
+    current: Option<(DocumentId, Vec<f32>)>,
+}
+
+impl EmbeddingsWriter {
+    /// Pushes the embeddings of a text of a document, the documents being pushed in ascending order.
+    fn push(&mut self, docid: DocumentId, embeddings: &Embeddings<f32>) -> Result<()> {
+        match &mut self.current {
+            Some((current, data)) if *current == docid => {
+                data.extend_from_slice(embeddings.as_inner());
+            }
+            current => {
+                if let Some((docid, data)) =
+                    current.replace((docid, embeddings.as_inner().to_vec()))
+                {
+                    self.writer.insert(docid.to_be_bytes(), cast_slice(&data))?;
+                }
             }
         }
+        Ok(())
     }
 
-    writer_into_reader(state_writer)
+    fn into_reader(mut self) -> Result<grenad::Reader<BufReader<File>>> {
+        if let Some((docid, data)) = self.current.take() {
+            self.writer.insert(docid.to_be_bytes(), cast_slice(&data))?;
+        }
+        writer_into_reader(self.writer)
+    }
 }
 
 fn embed_chunks(
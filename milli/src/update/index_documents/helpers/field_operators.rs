@@ -0,0 +1,187 @@
+use serde_json::{Number, Value};
+
+use crate::error::InternalError;
+use crate::{Result, UserError};
+
+/// An operator object, like `{ "$inc": 1 }`, updating the previous value of a field
+/// instead of replacing it.
+///
+/// Only the objects with a single key that is one of the operators are operator objects,
+/// any other object is a regular value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldOperator {
+    /// Increments the number, a missing value counts as zero.
+    Inc(Number),
+    /// Appends the value, or all the values of an array, to the array.
+    Push(Value),
+    /// Removes all the occurrences of the value, or of the values of an array, from the array.
+    Pull(Value),
+    /// Sets the value only if the field is missing.
+    Set(Value),
+}
+
+impl FieldOperator {
+    /// Returns the operator of a raw JSON value, if it is an operator object.
+    ///
+    /// The `document_id` is only used to report the invalid operators.
+    pub fn from_slice(value: &[u8], document_id: impl Fn() -> String) -> Result<Option<Self>> {
+        // the values of the documents are compact JSON, it's a cheap way to skip the other values.
+        if !value.starts_with(br#"{"$"#) {
+            return Ok(None);
+        }
+
+        let Value::Object(object) =
+            serde_json::from_slice(value).map_err(InternalError::SerdeJson)?
+        else {
+            return Ok(None);
+        };
+        if object.len() != 1 {
+            return Ok(None);
+        }
+        let (operator, value) = object.into_iter().next().unwrap();
+        let operator = match operator.as_str() {
+            "$inc" => match value {
+                Value::Number(number) => FieldOperator::Inc(number),
+                value => {
+                    return Err(UserError::InvalidDocumentFieldOperator {
+                        document_id: document_id(),
+                        message: format!("`$inc` expects a number, but received `{value}`."),
+                    }
+                    .into())
+                }
+            },
+            "$push" => FieldOperator::Push(value),
+            "$pull" => FieldOperator::Pull(value),
+            "$set" => FieldOperator::Set(value),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(operator))
+    }
+
+    /// Applies the operator on the previous value of the field, `None` when it is missing.
+    ///
+    /// Returns the new value of the field, `None` when it must stay missing.
+    pub fn apply(
+        self,
+        previous: Option<Value>,
+        document_id: impl Fn() -> String,
+    ) -> Result<Option<Value>> {
+        let invalid = |message: String| -> crate::Error {
+            UserError::InvalidDocumentFieldOperator { document_id: document_id(), message }.into()
+        };
+
+        let value = match (self, previous) {
+            (FieldOperator::Inc(increment), None) => Some(Value::Number(increment)),
+            (FieldOperator::Inc(increment), Some(Value::Number(number))) => {
+                let number = match (number.as_i64(), increment.as_i64()) {
+                    (Some(number), Some(increment)) => match number.checked_add(increment) {
+                        Some(sum) => Number::from(sum),
+                        None => {
+                            return Err(invalid(format!(
+                                "`$inc` overflows when adding `{increment}` to `{number}`."
+                            )))
+                        }
+                    },
+                    _ => {
+                        let sum = number.as_f64().unwrap_or_default()
+                            + increment.as_f64().unwrap_or_default();
+                        Number::from_f64(sum).ok_or_else(|| {
+                            invalid(format!("`$inc` results in a non-finite number `{sum}`."))
+                        })?
+                    }
+                };
+                Some(Value::Number(number))
+            }
+            (FieldOperator::Inc(_), Some(previous)) => {
+                return Err(invalid(format!(
+                    "`$inc` can only increment a number, but the field is `{previous}`."
+                )))
+            }
+            (FieldOperator::Push(values), previous) => {
+                let mut array = match previous {
+                    None => Vec::new(),
+                    Some(Value::Array(array)) => array,
+                    Some(previous) => {
+                        return Err(invalid(format!(
+                            "`$push` can only append to an array, but the field is `{previous}`."
+                        )))
+                    }
+                };
+                match values {
+                    Value::Array(values) => array.extend(values),
+                    value => array.push(value),
+                }
+                Some(Value::Array(array))
+            }
+            (FieldOperator::Pull(_), None) => None,
+            (FieldOperator::Pull(values), Some(Value::Array(mut array))) => {
+                let values = match values {
+                    Value::Array(values) => values,
+                    value => vec![value],
+                };
+                array.retain(|value| !values.contains(value));
+                Some(Value::Array(array))
+            }
+            (FieldOperator::Pull(_), Some(previous)) => {
+                return Err(invalid(format!(
+                    "`$pull` can only remove from an array, but the field is `{previous}`."
+                )))
+            }
+            (FieldOperator::Set(value), None) => Some(value),
+            (FieldOperator::Set(_), Some(previous)) => Some(previous),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn apply(operator: Value, previous: Option<Value>) -> Result<Option<Value>> {
+        let operator = serde_json::to_vec(&operator).unwrap();
+        let operator = FieldOperator::from_slice(&operator, || "1".to_string())?.unwrap();
+        operator.apply(previous, || "1".to_string())
+    }
+
+    #[test]
+    fn not_an_operator() {
+        let not_operator = |value: Value| {
+            let value = serde_json::to_vec(&value).unwrap();
+            FieldOperator::from_slice(&value, || "1".to_string()).unwrap().is_none()
+        };
+        assert!(not_operator(json!(1)));
+        assert!(not_operator(json!({ "inc": 1 })));
+        assert!(not_operator(json!({ "$ref": "doggo" })));
+        assert!(not_operator(json!({ "$inc": 1, "$set": 2 })));
+    }
+
+    #[test]
+    fn apply_operators() {
+        assert_eq!(apply(json!({ "$inc": 2 }), None).unwrap(), Some(json!(2)));
+        assert_eq!(apply(json!({ "$inc": 2 }), Some(json!(40))).unwrap(), Some(json!(42)));
+        assert_eq!(apply(json!({ "$inc": -0.5 }), Some(json!(1))).unwrap(), Some(json!(0.5)));
+        assert!(apply(json!({ "$inc": 1 }), Some(json!("doggo"))).is_err());
+
+        assert_eq!(apply(json!({ "$push": "b" }), None).unwrap(), Some(json!(["b"])));
+        assert_eq!(
+            apply(json!({ "$push": ["b", "c"] }), Some(json!(["a"]))).unwrap(),
+            Some(json!(["a", "b", "c"]))
+        );
+        assert!(apply(json!({ "$push": 1 }), Some(json!("doggo"))).is_err());
+
+        assert_eq!(apply(json!({ "$pull": "b" }), None).unwrap(), None);
+        assert_eq!(
+            apply(json!({ "$pull": ["a", "c"] }), Some(json!(["a", "b", "c", "a"]))).unwrap(),
+            Some(json!(["b"]))
+        );
+        assert!(apply(json!({ "$pull": 1 }), Some(json!(1))).is_err());
+
+        assert_eq!(apply(json!({ "$set": 1 }), None).unwrap(), Some(json!(1)));
+        assert_eq!(apply(json!({ "$set": 1 }), Some(json!(2))).unwrap(), Some(json!(2)));
+    }
+}
@@ -4,11 +4,14 @@ use std::io;
 use std::result::Result as StdResult;
 
 use roaring::RoaringBitmap;
+use serde_json::Value;
 
+use super::FieldOperator;
+use crate::error::InternalError;
 use crate::heed_codec::CboRoaringBitmapCodec;
 use crate::update::del_add::{DelAdd, KvReaderDelAdd, KvWriterDelAdd};
 use crate::update::index_documents::transform::Operation;
-use crate::Result;
+use crate::{DocumentId, Result};
 
 pub type MergeFn = for<'a> fn(&[u8], &[Cow<'a, [u8]>]) -> Result<Cow<'a, [u8]>>;
 
@@ -160,6 +163,218 @@ pub fn obkvs_keep_last_addition_merge_deletions<'a>(
     inner_merge_del_add_obkvs(obkvs, false)
 }
 
+/// Merge all the obkvs from the oldest to the newest, starting from the last complete deletion.
+///
+/// The newest additions replace the oldest ones, unless they are field operators.
+pub fn obkvs_merge_additions_with_operators_and_deletions<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    fold_del_add_obkvs(key, obkvs, update_field_value)
+}
+
+/// Apply all the obkvs as JSON merge patches (RFC 7396) from the oldest to the newest,
+/// starting from the last complete deletion.
+pub fn obkvs_merge_patch_additions_and_deletions<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    fold_del_add_obkvs(key, obkvs, merge_patch_field_value)
+}
+
+/// Apply all the obkvs as JSON merge patches (RFC 7396) from the oldest to the newest,
+/// starting from the last complete deletion, the field operators are applied instead of merged.
+pub fn obkvs_merge_patch_with_operators_additions_and_deletions<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+) -> Result<Cow<'a, [u8]>> {
+    fold_del_add_obkvs(key, obkvs, merge_patch_or_update_field_value)
+}
+
+/// Computes the new value of a field from its previous value, if any, and the update.
+/// Returns `None` when the field must be missing.
+///
+/// The `document_id` is only used to report the errors.
+pub type FieldUpdateFn =
+    for<'v> fn(Option<&'v [u8]>, &'v [u8], &dyn Fn() -> String) -> Result<Option<Cow<'v, [u8]>>>;
+
+/// The update replaces the previous value, unless it is a field operator.
+pub fn update_field_value<'v>(
+    previous: Option<&'v [u8]>,
+    update: &'v [u8],
+    document_id: &dyn Fn() -> String,
+) -> Result<Option<Cow<'v, [u8]>>> {
+    match FieldOperator::from_slice(update, document_id)? {
+        Some(operator) => apply_field_operator(operator, previous, document_id),
+        None => Ok(Some(Cow::Borrowed(update))),
+    }
+}
+
+/// The update is applied as a JSON merge patch on the previous value,
+/// unless it is a field operator.
+pub fn merge_patch_or_update_field_value<'v>(
+    previous: Option<&'v [u8]>,
+    patch: &'v [u8],
+    document_id: &dyn Fn() -> String,
+) -> Result<Option<Cow<'v, [u8]>>> {
+    match FieldOperator::from_slice(patch, document_id)? {
+        Some(operator) => apply_field_operator(operator, previous, document_id),
+        None => merge_patch_field_value(previous, patch, document_id),
+    }
+}
+
+/// The update is applied as a JSON merge patch on the previous value.
+pub fn merge_patch_field_value<'v>(
+    previous: Option<&'v [u8]>,
+    patch: &'v [u8],
+    _document_id: &dyn Fn() -> String,
+) -> Result<Option<Cow<'v, [u8]>>> {
+    let mut value = match previous {
+        Some(previous) => serde_json::from_slice(previous).map_err(InternalError::SerdeJson)?,
+        None => Value::Null,
+    };
+    let patch = serde_json::from_slice(patch).map_err(InternalError::SerdeJson)?;
+    json_merge_patch(&mut value, patch);
+    match value {
+        Value::Null => Ok(None),
+        value => {
+            Ok(Some(Cow::Owned(serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?)))
+        }
+    }
+}
+
+fn apply_field_operator<'v>(
+    operator: FieldOperator,
+    previous: Option<&[u8]>,
+    document_id: &dyn Fn() -> String,
+) -> Result<Option<Cow<'v, [u8]>>> {
+    let previous =
+        previous.map(serde_json::from_slice).transpose().map_err(InternalError::SerdeJson)?;
+    match operator.apply(previous, document_id)? {
+        Some(value) => {
+            Ok(Some(Cow::Owned(serde_json::to_vec(&value).map_err(InternalError::SerdeJson)?)))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Apply all the obkvs from the oldest to the newest, starting from the last complete deletion.
+fn fold_del_add_obkvs<'a>(
+    key: &[u8],
+    obkvs: &[Cow<'a, [u8]>],
+    update_value: FieldUpdateFn,
+) -> Result<Cow<'a, [u8]>> {
+    // keep the operation type of the newest obkv for the returned value.
+    let newest_operation_type = obkvs.last().unwrap()[0];
+
+    // a complete deletion means that the obkvs before it must be ignored.
+    let start = obkvs.iter().rposition(|obkv| obkv[0] == Operation::Deletion as u8).unwrap_or(0);
+    let (oldest, updates) = obkvs[start..].split_first().unwrap();
+
+    let mut acc = oldest[1..].to_vec();
+    let mut buffer = Vec::new();
+    for update in updates {
+        let base = obkv::KvReader::new(&acc);
+        let update = obkv::KvReader::new(&update[1..]);
+        update_two_del_add_obkvs(key, base, update, update_value, &mut buffer)?;
+
+        // we want the result of the merge into our accumulator.
+        std::mem::swap(&mut acc, &mut buffer);
+    }
+
+    acc.insert(0, newest_operation_type);
+    Ok(Cow::from(acc))
+}
+
+/// Apply the additions of the `update` obkv on the additions of the `base` obkv with
+/// `update_value`, keeping the newest deletions.
+fn update_two_del_add_obkvs(
+    key: &[u8],
+    base: obkv::KvReaderU16<'_>,
+    update: obkv::KvReaderU16<'_>,
+    update_value: FieldUpdateFn,
+    buffer: &mut Vec<u8>,
+) -> Result<()> {
+    use itertools::merge_join_by;
+    use itertools::EitherOrBoth::{Both, Left, Right};
+
+    buffer.clear();
+
+    let mut writer = obkv::KvWriter::new(buffer);
+    let mut value_buffer = Vec::new();
+    for eob in merge_join_by(base.iter(), update.iter(), |(b, _), (u, _)| b.cmp(u)) {
+        let (k, base, update) = match eob {
+            Left((k, v)) => {
+                writer.insert(k, v)?;
+                continue;
+            }
+            Right((k, v)) => (k, None, KvReaderDelAdd::new(v)),
+            Both((k, base), (_, update)) => {
+                (k, Some(KvReaderDelAdd::new(base)), KvReaderDelAdd::new(update))
+            }
+        };
+
+        // keep newest deletion.
+        let deletion = update
+            .get(DelAdd::Deletion)
+            .or_else(|| base.and_then(|base| base.get(DelAdd::Deletion)));
+
+        let base_addition = base.and_then(|base| base.get(DelAdd::Addition));
+        let addition = match update.get(DelAdd::Addition) {
+            Some(update) => update_value(base_addition, update, &|| external_document_id(key))?,
+            None => base_addition.map(Cow::Borrowed),
+        };
+
+        // the field was removed by the update before ever being added.
+        if deletion.is_none() && addition.is_none() {
+            continue;
+        }
+
+        value_buffer.clear();
+        let mut value_writer = KvWriterDelAdd::new(&mut value_buffer);
+        if let Some(deletion) = deletion {
+            value_writer.insert(DelAdd::Deletion, deletion)?;
+        }
+        if let Some(addition) = addition {
+            value_writer.insert(DelAdd::Addition, addition)?;
+        }
+        value_writer.finish()?;
+        writer.insert(k, &value_buffer)?;
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// The keys of the original documents are the internal document id followed by the external one.
+fn external_document_id(key: &[u8]) -> String {
+    let external_id = key.get(std::mem::size_of::<DocumentId>()..).unwrap_or_default();
+    String::from_utf8_lossy(external_id).into_owned()
+}
+
+/// Apply a JSON merge patch (RFC 7396) on the target.
+///
+/// The objects are merged recursively, a `null` in the patch removes the key from
+/// the target and any other value replaces the target.
+fn json_merge_patch(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(serde_json::Map::new());
+            }
+            let target = target.as_object_mut().unwrap();
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(&key);
+                } else {
+                    json_merge_patch(target.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+        patch => *target = patch,
+    }
+}
+
 /// Do a union of all the CboRoaringBitmaps in the values.
 pub fn merge_cbo_roaring_bitmaps<'a>(
     _key: &[u8],
@@ -1,4 +1,5 @@
 mod clonable_mmap;
+mod field_operators;
 mod grenad_helpers;
 mod merge_functions;
 
@@ -6,6 +7,7 @@ use std::collections::HashSet;
 use std::convert::{TryFrom, TryInto};
 
 pub use clonable_mmap::{ClonableMmap, CursorClonableMmap};
+pub use field_operators::FieldOperator;
 use fst::{IntoStreamer, Streamer};
 pub use grenad_helpers::{
     as_cloneable_grenad, create_sorter, create_writer, grenad_obkv_into_chunks,
@@ -15,8 +17,11 @@ pub use grenad_helpers::{
 pub use merge_functions::{
     keep_first, keep_latest_obkv, merge_cbo_roaring_bitmaps, merge_deladd_btreeset_string,
     merge_deladd_cbo_roaring_bitmaps, merge_deladd_cbo_roaring_bitmaps_into_cbo_roaring_bitmap,
-    merge_roaring_bitmaps, obkvs_keep_last_addition_merge_deletions,
-    obkvs_merge_additions_and_deletions, MergeFn,
+    merge_patch_field_value, merge_patch_or_update_field_value, merge_roaring_bitmaps,
+    obkvs_keep_last_addition_merge_deletions, obkvs_merge_additions_and_deletions,
+    obkvs_merge_additions_with_operators_and_deletions, obkvs_merge_patch_additions_and_deletions,
+    obkvs_merge_patch_with_operators_additions_and_deletions, update_field_value, FieldUpdateFn,
+    MergeFn,
 };
 
 use crate::MAX_WORD_LENGTH;
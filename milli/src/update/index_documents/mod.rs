@@ -68,6 +68,31 @@ pub enum IndexDocumentsMethod {
     /// Merge the previous version of the document with the new version,
     /// replacing old attributes values with the new ones and add the new attributes.
     UpdateDocuments,
+
+    /// Apply the new version of the document as a JSON merge patch (RFC 7396) on the previous
+    /// version, merging the nested objects and removing the attributes set to `null`.
+    DeepMergeDocuments,
+
+    /// Like `UpdateDocuments`, but the operator objects, like `{ "$inc": 1 }`,
+    /// update the previous value of their attribute instead of replacing it.
+    UpdateDocumentsWithOperators,
+
+    /// Like `DeepMergeDocuments`, but the operator objects, like `{ "$inc": 1 }`,
+    /// update the previous value of their attribute instead of being merged into it.
+    DeepMergeDocumentsWithOperators,
+}
+
+impl IndexDocumentsMethod {
+    /// Whether the operator objects of the documents update the previous values of their
+    /// attributes, they are stored as regular values otherwise.
+    pub fn field_operators(&self) -> bool {
+        matches!(self, Self::UpdateDocumentsWithOperators | Self::DeepMergeDocumentsWithOperators)
+    }
+
+    /// Whether the documents are applied as JSON merge patches on the previous versions.
+    pub fn merge_patch(&self) -> bool {
+        matches!(self, Self::DeepMergeDocuments | Self::DeepMergeDocumentsWithOperators)
+    }
 }
 
 impl Default for IndexDocumentsMethod {
@@ -86,6 +111,7 @@ pub struct IndexDocuments<'t, 'i, 'a, FP, FA> {
     should_abort: FA,
     added_documents: u64,
     deleted_documents: u64,
+    version_conflicts: Vec<String>,
     embedders: EmbeddingConfigs,
 }
 
@@ -130,6 +156,7 @@ where
             index,
             added_documents: 0,
             deleted_documents: 0,
+            version_conflicts: Vec::new(),
             embedders: Default::default(),
         })
     }
@@ -145,6 +172,8 @@ where
         mut self,
         reader: DocumentsBatchReader<R>,
     ) -> Result<(Self, StdResult<u64, UserError>)> {
+        self.version_conflicts.clear();
+
         // Early return when there is no document to add
         if reader.is_empty() {
             return Ok((self, Ok(0)));
@@ -163,13 +192,14 @@ where
             Err(user_error) => return Ok((self, Err(user_error))),
         };
 
-        let indexed_documents =
-            self.transform.as_mut().expect("Invalid document addition state").read_documents(
-                enriched_documents_reader,
-                self.wtxn,
-                &self.progress,
-                &self.should_abort,
-            )? as u64;
+        let transform = self.transform.as_mut().expect("Invalid document addition state");
+        let indexed_documents = transform.read_documents(
+            enriched_documents_reader,
+            self.wtxn,
+            &self.progress,
+            &self.should_abort,
+        )? as u64;
+        self.version_conflicts = transform.take_version_conflicts();
 
         self.added_documents += indexed_documents;
 
@@ -311,6 +341,12 @@ where
         Ok((this, result.map(|added| (removed, added))))
     }
 
+    /// Returns the external ids of the documents of the last added batch that were skipped
+    /// because they weren't at their `_expectedVersion` anymore, at most `MAX_VERSION_CONFLICTS`.
+    pub fn version_conflicts(&self) -> &[String] {
+        &self.version_conflicts
+    }
+
     pub fn with_embedders(mut self, embedders: EmbeddingConfigs) -> Self {
         self.embedders = embedders;
         self
@@ -681,21 +717,13 @@ where
 
         for (embedder_name, dimension) in dimension {
             let wtxn = &mut *self.wtxn;
-            let vector_arroy = self.index.vector_arroy;
 
-            let embedder_index = self.index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
+            let arroy = self.index.arroy_wrapper(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
 
             pool.install(|| {
-                for k in crate::vector::arroy_db_range_for_embedder(embedder_index) {
-                    let writer = arroy::Writer::new(vector_arroy, k, dimension);
-                    if writer.need_build(wtxn)? {
-                        writer.build(wtxn, &mut rng, None)?;
-                    } else if writer.is_empty(wtxn)? {
-                        break;
-                    }
-                }
+                arroy.build(wtxn, &mut rng, dimension)?;
                 Result::Ok(())
             })
             .map_err(InternalError::from)??;
@@ -1039,6 +1067,186 @@ mod tests {
         drop(rtxn);
     }
 
+    #[test]
+    fn simple_document_deep_merge() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::DeepMergeDocuments;
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset!(S("price.usd")));
+            })
+            .unwrap();
+
+        let document = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+            let docs = index.documents(&rtxn, Some(0)).unwrap();
+            assert_eq!(docs.len(), 1);
+            let (_, obkv) = docs[0];
+            serde_json::Value::from(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        let filter = |index: &TempIndex, filter: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let mut search = Search::new(&rtxn, index);
+            search.filter(Filter::from_str(filter).unwrap().unwrap());
+            search.execute().unwrap().documents_ids
+        };
+
+        // The `null` values of a new document are removed.
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": { "eur": 10, "usd": 12 }, "tags": { "a": null, "b": "kevin" } }
+            ]))
+            .unwrap();
+        assert_eq!(
+            document(&index),
+            serde_json::json!({ "id": 1, "price": { "eur": 10, "usd": 12 }, "tags": { "b": "kevin" } })
+        );
+        assert_eq!(filter(&index, "price.usd = 12"), vec![0]);
+
+        // The nested objects are merged.
+        index.add_documents(documents!([ { "id": 1, "price": { "eur": 11 } } ])).unwrap();
+        assert_eq!(
+            document(&index),
+            serde_json::json!({ "id": 1, "price": { "eur": 11, "usd": 12 }, "tags": { "b": "kevin" } })
+        );
+        assert_eq!(filter(&index, "price.usd = 12"), vec![0]);
+
+        // The patches of the same batch are applied in order, a `null` removes the attribute.
+        index
+            .add_documents(documents!([
+                { "id": 1, "price": { "usd": null } },
+                { "id": 1, "price": { "eur": 12 }, "tags": null }
+            ]))
+            .unwrap();
+        assert_eq!(document(&index), serde_json::json!({ "id": 1, "price": { "eur": 12 } }));
+        assert!(filter(&index, "price.usd EXISTS").is_empty());
+
+        let rtxn = index.read_txn().unwrap();
+        let count = index.number_of_documents(&rtxn).unwrap();
+        assert_eq!(count, 1);
+        drop(rtxn);
+    }
+
+    #[test]
+    fn document_update_field_operators() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method =
+            IndexDocumentsMethod::UpdateDocumentsWithOperators;
+        index
+            .update_settings(|settings| {
+                settings.set_filterable_fields(hashset!(S("views"), S("tags")));
+            })
+            .unwrap();
+
+        let document = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+            let docs = index.documents(&rtxn, Some(0)).unwrap();
+            let (_, obkv) = docs[0];
+            serde_json::Value::from(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+        let filter = |index: &TempIndex, filter: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let mut search = Search::new(&rtxn, index);
+            search.filter(Filter::from_str(filter).unwrap().unwrap());
+            search.execute().unwrap().documents_ids
+        };
+
+        // The operators of a new document are applied on missing fields.
+        index
+            .add_documents(documents!([
+                { "id": 1, "views": { "$inc": 1 }, "tags": { "$push": "a" }, "old": { "$pull": "a" } }
+            ]))
+            .unwrap();
+        assert_eq!(document(&index), serde_json::json!({ "id": 1, "views": 1, "tags": ["a"] }));
+        assert_eq!(filter(&index, "views = 1 AND tags = a"), vec![0]);
+
+        // The operators are applied on the fields of the indexed document.
+        index
+            .add_documents(documents!([
+                { "id": 1, "views": { "$inc": 2 }, "tags": { "$push": ["b", "c"] }, "title": { "$set": "kevin" } }
+            ]))
+            .unwrap();
+        assert_eq!(
+            document(&index),
+            serde_json::json!({ "id": 1, "views": 3, "tags": ["a", "b", "c"], "title": "kevin" })
+        );
+        assert_eq!(filter(&index, "views = 3 AND tags = c"), vec![0]);
+
+        // The operators of the same batch are applied in order.
+        index
+            .add_documents(documents!([
+                { "id": 1, "views": { "$inc": 1 }, "tags": { "$pull": "a" } },
+                { "id": 1, "views": { "$inc": 1 }, "title": { "$set": "lol" } },
+                { "id": 1, "tags": { "$push": "d" } }
+            ]))
+            .unwrap();
+        assert_eq!(
+            document(&index),
+            serde_json::json!({ "id": 1, "views": 5, "tags": ["b", "c", "d"], "title": "kevin" })
+        );
+        assert_eq!(filter(&index, "views = 5"), vec![0]);
+        assert!(filter(&index, "tags = a").is_empty());
+
+        // An operator applied on a field of the wrong type fails the update.
+        let err =
+            index.add_documents(documents!([ { "id": 1, "title": { "$inc": 1 } } ])).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UserError(UserError::InvalidDocumentFieldOperator { ref document_id, .. })
+                if document_id == "1"
+        ));
+        assert_eq!(filter(&index, "views = 5"), vec![0]);
+    }
+
+    #[test]
+    fn document_field_operators_are_opt_in() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        let document = |index: &TempIndex| {
+            let rtxn = index.read_txn().unwrap();
+            let fields_ids_map = index.fields_ids_map(&rtxn).unwrap();
+            let docs = index.documents(&rtxn, Some(0)).unwrap();
+            let (_, obkv) = docs[0];
+            serde_json::Value::from(crate::all_obkv_to_json(obkv, &fields_ids_map).unwrap())
+        };
+
+        // The operators are regular values when they are not enabled.
+        index.add_documents(documents!([ { "id": 1, "views": 1 } ])).unwrap();
+        index.add_documents(documents!([ { "id": 1, "views": { "$inc": 1 } } ])).unwrap();
+        assert_eq!(document(&index), serde_json::json!({ "id": 1, "views": { "$inc": 1 } }));
+
+        index.index_documents_config.update_method = IndexDocumentsMethod::DeepMergeDocuments;
+        index.add_documents(documents!([ { "id": 1, "views": { "$inc": 2 } } ])).unwrap();
+        assert_eq!(document(&index), serde_json::json!({ "id": 1, "views": { "$inc": 2 } }));
+
+        // The operators of the replaced documents are rejected.
+        index.index_documents_config.update_method = IndexDocumentsMethod::ReplaceDocuments;
+        let err =
+            index.add_documents(documents!([ { "id": 1, "views": { "$set": 1 } } ])).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UserError(UserError::InvalidDocumentFieldOperator { ref document_id, .. })
+                if document_id == "1"
+        ));
+        assert_eq!(document(&index), serde_json::json!({ "id": 1, "views": { "$inc": 2 } }));
+
+        // The deep merges apply the operators instead of merging them when they are enabled.
+        index.index_documents_config.update_method =
+            IndexDocumentsMethod::DeepMergeDocumentsWithOperators;
+        index
+            .add_documents(documents!([
+                { "id": 1, "views": { "$set": 3 }, "price": { "$inc": 10 } }
+            ]))
+            .unwrap();
+        assert_eq!(
+            document(&index),
+            serde_json::json!({ "id": 1, "views": { "$inc": 2 }, "price": 10 })
+        );
+    }
+
     #[test]
     fn not_auto_generated_documents_ids() {
         let index = TempIndex::new();
@@ -2392,6 +2600,123 @@ mod tests {
         "###);
     }
 
+    #[test]
+    fn documents_versions_in_single_transform() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        let version = |index: &TempIndex, external_id: &str| {
+            let rtxn = index.read_txn().unwrap();
+            let docid = index.external_documents_ids().get(&rtxn, external_id).unwrap().unwrap();
+            index.document_version(&rtxn, docid).unwrap()
+        };
+
+        index
+            .add_documents(documents!([{ "id": 1, "doggo": "kevin" }, { "id": 2, "doggo": "bob" }]))
+            .unwrap();
+        assert_eq!(version(&index, "1"), 1);
+        assert_eq!(version(&index, "2"), 1);
+
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+
+        // the second update of the document expects the version before the first update
+        let documents = documents!([
+            { "id": 1, "doggo": "kevina", "_expectedVersion": 1 },
+            { "id": 1, "doggo": "kevinou", "_expectedVersion": 1 },
+            { "id": 2, "doggo": "bobby", "_expectedVersion": 1 },
+            { "id": 3, "doggo": "jean", "_expectedVersion": 0 },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"3");
+        assert_eq!(builder.version_conflicts(), ["1"]);
+
+        // a deleted document doesn't exist anymore
+        let (builder, removed) = builder.remove_documents(vec![S("2")]).unwrap();
+        insta::assert_snapshot!(removed.unwrap(), @"1");
+        let documents = documents!([{ "id": 2, "doggo": "bob", "_expectedVersion": 0 }]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"1");
+        assert!(builder.version_conflicts().is_empty());
+
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"doggo":"kevina"}
+        {"id":2,"doggo":"bob"}
+        {"id":3,"doggo":"jean"}
+        "###);
+        assert_eq!(version(&index, "1"), 2);
+        assert_eq!(version(&index, "2"), 1);
+        assert_eq!(version(&index, "3"), 1);
+
+        // the versions are removed with the documents
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids().get(&rtxn, "3").unwrap().unwrap();
+        drop(rtxn);
+        index.delete_document("3");
+        let rtxn = index.read_txn().unwrap();
+        assert_eq!(index.document_version(&rtxn, docid).unwrap(), 0);
+    }
+
+    #[test]
+    fn documents_versions_of_documents_indexed_without_versions() {
+        let mut index = TempIndex::new();
+        index.index_documents_config.update_method = IndexDocumentsMethod::UpdateDocuments;
+
+        index
+            .add_documents(documents!([{ "id": 1, "doggo": "kevin" }, { "id": 2, "doggo": "bob" }]))
+            .unwrap();
+
+        // the documents indexed before the versions were introduced have no version
+        let mut wtxn = index.write_txn().unwrap();
+        index.documents_versions.clear(&mut wtxn).unwrap();
+        wtxn.commit().unwrap();
+
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids().get(&rtxn, "1").unwrap().unwrap();
+        assert_eq!(index.document_version(&rtxn, docid).unwrap(), 1);
+        drop(rtxn);
+
+        // they exist, the additions expecting a missing document are conflicts
+        let mut wtxn = index.write_txn().unwrap();
+        let builder = IndexDocuments::new(
+            &mut wtxn,
+            &index,
+            &index.indexer_config,
+            index.index_documents_config.clone(),
+            |_| (),
+            || false,
+        )
+        .unwrap();
+        let documents = documents!([
+            { "id": 1, "doggo": "kevina", "_expectedVersion": 0 },
+            { "id": 2, "doggo": "bobby", "_expectedVersion": 1 },
+        ]);
+        let (builder, added) = builder.add_documents(documents).unwrap();
+        insta::assert_snapshot!(added.unwrap(), @"1");
+        assert_eq!(builder.version_conflicts(), ["1"]);
+        builder.execute().unwrap();
+        wtxn.commit().unwrap();
+
+        db_snap!(index, documents, @r###"
+        {"id":1,"doggo":"kevin"}
+        {"id":2,"doggo":"bobby"}
+        "###);
+        let rtxn = index.read_txn().unwrap();
+        let docid = index.external_documents_ids().get(&rtxn, "2").unwrap().unwrap();
+        assert_eq!(index.document_version(&rtxn, docid).unwrap(), 2);
+    }
+
     #[test]
     fn add_update_and_delete_documents_in_single_transform() {
         let mut index = TempIndex::new();
@@ -2746,6 +3071,13 @@ mod tests {
                         response: Setting::NotSet,
                         distribution: Setting::NotSet,
                         headers: Setting::NotSet,
+                        distance: Setting::NotSet,
+                        quantization: Setting::NotSet,
+                        rescore: Setting::NotSet,
+                        chunking: Setting::NotSet,
+                        tokenizer: Setting::NotSet,
+                        pooling: Setting::NotSet,
+                        normalize: Setting::NotSet,
                     }),
                 );
                 settings.set_embedder_settings(embedders);
@@ -2770,8 +3102,9 @@ mod tests {
             embedding_configs.pop().unwrap();
         insta::assert_snapshot!(embedder_name, @"manual");
         insta::assert_debug_snapshot!(user_provided, @"RoaringBitmap<[0, 1, 2]>");
-        let embedder =
-            std::sync::Arc::new(crate::vector::Embedder::new(embedder.embedder_options).unwrap());
+        let embedder = std::sync::Arc::new(
+            crate::vector::Embedder::new(embedder.embedder_options, &Default::default()).unwrap(),
+        );
         let res = index
             .search(&rtxn)
             .semantic(embedder_name, embedder, Some([0.0, 1.0, 2.0].to_vec()))
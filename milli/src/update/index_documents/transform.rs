@@ -13,26 +13,36 @@ use serde_json::Value;
 use smartstring::SmartString;
 
 use super::helpers::{
-    create_sorter, create_writer, keep_first, obkvs_keep_last_addition_merge_deletions,
-    obkvs_merge_additions_and_deletions, sorter_into_reader, MergeFn,
+    create_sorter, create_writer, keep_first, merge_patch_field_value,
+    merge_patch_or_update_field_value, obkvs_keep_last_addition_merge_deletions,
+    obkvs_merge_additions_and_deletions, obkvs_merge_additions_with_operators_and_deletions,
+    obkvs_merge_patch_additions_and_deletions,
+    obkvs_merge_patch_with_operators_additions_and_deletions, sorter_into_reader,
+    update_field_value, FieldOperator, FieldUpdateFn, MergeFn,
 };
 use super::{IndexDocumentsMethod, IndexerConfig};
 use crate::documents::{DocumentsBatchIndex, EnrichedDocument, EnrichedDocumentsBatchReader};
 use crate::error::{Error, InternalError, UserError};
-use crate::index::{db_name, main_key};
+use crate::index::{db_name, main_key, RESERVED_EXPECTED_VERSION_FIELD_NAME};
 use crate::update::del_add::{
-    into_del_add_obkv, into_del_add_obkv_conditional_operation, DelAdd, DelAddOperation,
-    KvReaderDelAdd,
+    del_add_from_two_obkvs, into_del_add_obkv, into_del_add_obkv_conditional_operation, DelAdd,
+    DelAddOperation, KvReaderDelAdd,
 };
 use crate::update::index_documents::GrenadParameters;
 use crate::update::settings::{InnerIndexSettings, InnerIndexSettingsDiff};
 use crate::update::{AvailableDocumentsIds, UpdateIndexingStep};
 use crate::vector::parsed_vectors::{ExplicitVectors, VectorOrArrayOfVectors};
 use crate::vector::settings::{EmbedderAction, WriteBackToDocuments};
+use crate::vector::ArroyWrapper;
 use crate::{
-    is_faceted_by, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap, Index, Result,
+    is_faceted_by, DocumentId, FieldDistribution, FieldId, FieldIdMapMissingEntry, FieldsIdsMap,
+    Index, Result,
 };
 
+/// The maximum number of conflicting external ids reported for a batch of documents,
+/// they are stored in the details of the task.
+const MAX_VERSION_CONFLICTS: usize = 100;
+
 pub struct TransformOutput {
     pub primary_key: String,
     pub settings_diff: InnerIndexSettingsDiff,
@@ -61,6 +71,15 @@ pub struct Transform<'a, 'i> {
     // u32 | 1 byte for the Operation byte, the rest is the obkv of the document stored
     original_sorter: grenad::Sorter<MergeFn>,
     flattened_sorter: grenad::Sorter<MergeFn>,
+    // The merge patches and the field operators can't be applied on the flattened documents,
+    // when there are some we flatten the merged original documents instead.
+    flatten_merged_documents: bool,
+
+    // The versions of the documents written by this transform, `0` for the deleted ones.
+    documents_versions: BTreeMap<DocumentId, u64>,
+    // The external ids of the documents of the last read batch that were skipped
+    // because they weren't at their expected version anymore, at most `MAX_VERSION_CONFLICTS`.
+    version_conflicts: Vec<String>,
 
     replaced_documents_ids: RoaringBitmap,
     new_documents_ids: RoaringBitmap,
@@ -87,6 +106,8 @@ fn create_fields_mapping(
 ) -> Result<HashMap<FieldId, FieldId>> {
     batch_field_map
         .iter()
+        // the expected version is a condition on the document, not one of its fields.
+        .filter(|(_, name)| name.as_str() != RESERVED_EXPECTED_VERSION_FIELD_NAME)
         // we sort by id here to ensure a deterministic mapping of the fields, that preserves
         // the original ordering.
         .sorted_by_key(|(&id, _)| id)
@@ -113,6 +134,13 @@ impl<'a, 'i> Transform<'a, 'i> {
         let merge_function = match index_documents_method {
             IndexDocumentsMethod::ReplaceDocuments => obkvs_keep_last_addition_merge_deletions,
             IndexDocumentsMethod::UpdateDocuments => obkvs_merge_additions_and_deletions,
+            IndexDocumentsMethod::DeepMergeDocuments => obkvs_merge_patch_additions_and_deletions,
+            IndexDocumentsMethod::UpdateDocumentsWithOperators => {
+                obkvs_merge_additions_with_operators_and_deletions
+            }
+            IndexDocumentsMethod::DeepMergeDocumentsWithOperators => {
+                obkvs_merge_patch_with_operators_additions_and_deletions
+            }
         };
 
         // We initialize the sorter with the user indexing settings.
@@ -143,7 +171,10 @@ impl<'a, 'i> Transform<'a, 'i> {
             available_documents_ids: AvailableDocumentsIds::from_documents_ids(&documents_ids),
             original_sorter,
             flattened_sorter,
+            flatten_merged_documents: index_documents_method.merge_patch(),
             index_documents_method,
+            documents_versions: BTreeMap::new(),
+            version_conflicts: Vec::new(),
             replaced_documents_ids: RoaringBitmap::new(),
             new_documents_ids: RoaringBitmap::new(),
             new_external_documents_ids_builder: FxHashMap::default(),
@@ -167,6 +198,8 @@ impl<'a, 'i> Transform<'a, 'i> {
         let (mut cursor, fields_index) = reader.into_cursor_and_fields_index();
         let external_documents_ids = self.index.external_documents_ids();
         let mapping = create_fields_mapping(&mut self.fields_ids_map, &fields_index)?;
+        let expected_version_id = fields_index.id(RESERVED_EXPECTED_VERSION_FIELD_NAME);
+        self.version_conflicts.clear();
 
         let primary_key = cursor.primary_key().to_string();
         let primary_key_id =
@@ -185,6 +218,18 @@ impl<'a, 'i> Transform<'a, 'i> {
                 return Err(Error::InternalError(InternalError::AbortedIndexation));
             }
 
+            // The documents that aren't at their expected version anymore are skipped.
+            if let Some(expected_version) = expected_version_id.and_then(|id| document.get(id)) {
+                let expected_version: u64 =
+                    serde_json::from_slice(expected_version).map_err(InternalError::SerdeJson)?;
+                if self.current_version(wtxn, document_id.value())? != expected_version {
+                    if self.version_conflicts.len() < MAX_VERSION_CONFLICTS {
+                        self.version_conflicts.push(document_id.value().to_string());
+                    }
+                    continue;
+                }
+            }
+
             // drop_and_reuse is called instead of .clear() to communicate to the compiler that field_buffer
             // does not keep references from the cursor between loop iterations
             let mut field_buffer_cache = drop_and_reuse(field_buffer);
@@ -204,6 +249,9 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
 
             for (k, v) in document.iter() {
+                if Some(k) == expected_version_id {
+                    continue;
+                }
                 let mapped_id =
                     *mapping.get(&k).ok_or(InternalError::FieldIdMappingMissingEntry { key: k })?;
                 field_buffer_cache.push((mapped_id, Cow::from(v)));
@@ -221,6 +269,7 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
 
             let mut original_docid = None;
+            let mut new_document = false;
             let docid = match self.new_external_documents_ids_builder.entry((*external_id).into()) {
                 HEntry::Occupied(entry) => *entry.get() as u32,
                 HEntry::Vacant(entry) => {
@@ -234,16 +283,40 @@ impl<'a, 'i> Transform<'a, 'i> {
 
                             docid
                         }
-                        None => self
-                            .available_documents_ids
-                            .next()
-                            .ok_or(UserError::DocumentLimitReached)?,
+                        None => {
+                            new_document = true;
+                            self.available_documents_ids
+                                .next()
+                                .ok_or(UserError::DocumentLimitReached)?
+                        }
                     };
                     entry.insert(docid as u64);
                     docid
                 }
             };
 
+            if self.index_documents_method == IndexDocumentsMethod::ReplaceDocuments {
+                Self::reject_field_operators(KvReader::new(&obkv_buffer), external_id)?;
+            }
+
+            if let Some(update_value) = self.field_update_function() {
+                let has_operators = self.index_documents_method.field_operators()
+                    && KvReader::<FieldId>::new(&obkv_buffer)
+                        .iter()
+                        .any(|(_, value)| value.starts_with(br#"{"$"#));
+                self.flatten_merged_documents |= has_operators;
+                // The updates of a document that doesn't exist yet are applied on nothing,
+                // the other updates are applied by the merge function of the sorters.
+                let merge_patch = self.index_documents_method.merge_patch();
+                if new_document && (has_operators || merge_patch) {
+                    obkv_buffer = Self::update_missing_document(
+                        KvReader::new(&obkv_buffer),
+                        update_value,
+                        external_id,
+                    )?;
+                }
+            }
+
             let mut skip_insertion = false;
             if let Some(original_docid) = original_docid {
                 let original_key = original_docid;
@@ -267,7 +340,10 @@ impl<'a, 'i> Transform<'a, 'i> {
                 } else {
                     // we associate the base document with the new key, everything will get merged later.
                     let deladd_operation = match self.index_documents_method {
-                        IndexDocumentsMethod::UpdateDocuments => {
+                        IndexDocumentsMethod::UpdateDocuments
+                        | IndexDocumentsMethod::DeepMergeDocuments
+                        | IndexDocumentsMethod::UpdateDocumentsWithOperators
+                        | IndexDocumentsMethod::DeepMergeDocumentsWithOperators => {
                             DelAddOperation::DeletionAndAddition
                         }
                         IndexDocumentsMethod::ReplaceDocuments => DelAddOperation::Deletion,
@@ -284,26 +360,31 @@ impl<'a, 'i> Transform<'a, 'i> {
                     )?;
                     self.original_sorter
                         .insert(&document_sorter_key_buffer, &document_sorter_value_buffer)?;
-                    let base_obkv = KvReader::new(base_obkv);
-                    if let Some(flattened_obkv) =
-                        Self::flatten_from_fields_ids_map(&base_obkv, &mut self.fields_ids_map)?
-                    {
-                        // we recreate our buffer with the flattened documents
-                        document_sorter_value_buffer.clear();
-                        document_sorter_value_buffer.push(Operation::Addition as u8);
-                        into_del_add_obkv(
-                            KvReaderU16::new(&flattened_obkv),
-                            deladd_operation,
-                            &mut document_sorter_value_buffer,
-                        )?;
+                    // the merged documents are flattened in `output_from_sorter`.
+                    if !self.flatten_merged_documents {
+                        let base_obkv = KvReader::new(base_obkv);
+                        if let Some(flattened_obkv) =
+                            Self::flatten_from_fields_ids_map(&base_obkv, &mut self.fields_ids_map)?
+                        {
+                            // we recreate our buffer with the flattened documents
+                            document_sorter_value_buffer.clear();
+                            document_sorter_value_buffer.push(Operation::Addition as u8);
+                            into_del_add_obkv(
+                                KvReaderU16::new(&flattened_obkv),
+                                deladd_operation,
+                                &mut document_sorter_value_buffer,
+                            )?;
+                        }
+                        self.flattened_sorter
+                            .insert(docid.to_be_bytes(), &document_sorter_value_buffer)?;
                     }
-                    self.flattened_sorter
-                        .insert(docid.to_be_bytes(), &document_sorter_value_buffer)?;
                 }
             }
 
             if !skip_insertion {
                 self.new_documents_ids.insert(docid);
+                let version = self.document_version(wtxn, docid)? + 1;
+                self.documents_versions.insert(docid, version);
 
                 document_sorter_key_buffer.clear();
                 document_sorter_key_buffer.extend_from_slice(&docid.to_be_bytes());
@@ -319,19 +400,24 @@ impl<'a, 'i> Transform<'a, 'i> {
                 self.original_sorter
                     .insert(&document_sorter_key_buffer, &document_sorter_value_buffer)?;
 
-                let flattened_obkv = KvReader::new(&obkv_buffer);
-                if let Some(obkv) =
-                    Self::flatten_from_fields_ids_map(&flattened_obkv, &mut self.fields_ids_map)?
-                {
-                    document_sorter_value_buffer.clear();
-                    document_sorter_value_buffer.push(Operation::Addition as u8);
-                    into_del_add_obkv(
-                        KvReaderU16::new(&obkv),
-                        DelAddOperation::Addition,
-                        &mut document_sorter_value_buffer,
-                    )?
+                // the merged documents are flattened in `output_from_sorter`.
+                if !self.flatten_merged_documents {
+                    let flattened_obkv = KvReader::new(&obkv_buffer);
+                    if let Some(obkv) = Self::flatten_from_fields_ids_map(
+                        &flattened_obkv,
+                        &mut self.fields_ids_map,
+                    )? {
+                        document_sorter_value_buffer.clear();
+                        document_sorter_value_buffer.push(Operation::Addition as u8);
+                        into_del_add_obkv(
+                            KvReaderU16::new(&obkv),
+                            DelAddOperation::Addition,
+                            &mut document_sorter_value_buffer,
+                        )?
+                    }
+                    self.flattened_sorter
+                        .insert(docid.to_be_bytes(), &document_sorter_value_buffer)?;
                 }
-                self.flattened_sorter.insert(docid.to_be_bytes(), &document_sorter_value_buffer)?;
             }
             documents_count += 1;
 
@@ -414,6 +500,7 @@ impl<'a, 'i> Transform<'a, 'i> {
                         // 2. It wasn't in it because the document was created by a previous batch and since
                         //    we're removing it there is nothing to do.
                         self.new_documents_ids.remove(docid);
+                        self.documents_versions.insert(docid, 0);
                         entry.remove_entry();
                         true
                     }
@@ -445,6 +532,31 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(documents_deleted)
     }
 
+    /// Returns the external ids of the documents of the last read batch that were skipped
+    /// because they weren't at their expected version anymore, at most `MAX_VERSION_CONFLICTS`.
+    pub fn take_version_conflicts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.version_conflicts)
+    }
+
+    /// Returns the version of a document at this point of the transform, `0` if it doesn't exist.
+    fn current_version(&self, rtxn: &heed::RoTxn<'_>, external_id: &str) -> Result<u64> {
+        let docid = match self.new_external_documents_ids_builder.get(external_id) {
+            Some(docid) => *docid as DocumentId,
+            None => match self.index.external_documents_ids().get(rtxn, external_id)? {
+                Some(docid) => docid,
+                None => return Ok(0),
+            },
+        };
+        self.document_version(rtxn, docid)
+    }
+
+    fn document_version(&self, rtxn: &heed::RoTxn<'_>, docid: DocumentId) -> Result<u64> {
+        match self.documents_versions.get(&docid) {
+            Some(version) => Ok(*version),
+            None => Ok(self.index.document_version(rtxn, docid)?),
+        }
+    }
+
     /// Removes documents from db using their internal document ids.
     ///
     /// # Warning
@@ -498,6 +610,7 @@ impl<'a, 'i> Transform<'a, 'i> {
         document_sorter_value_buffer: &mut Vec<u8>,
     ) -> Result<()> {
         self.replaced_documents_ids.insert(internal_docid);
+        self.documents_versions.insert(internal_docid, 0);
 
         // fetch the obkv document
         let original_key = internal_docid;
@@ -607,6 +720,86 @@ impl<'a, 'i> Transform<'a, 'i> {
         Ok(Some(buffer))
     }
 
+    /// Flatten both sides of a del/add document, see [`Self::flatten_from_fields_ids_map`],
+    /// and write the flattened del/add document in the buffer.
+    fn flatten_del_add_from_fields_ids_map(
+        obkv: &KvReader<'_, FieldId>,
+        fields_ids_map: &mut FieldsIdsMap,
+        output_buffer: &mut Vec<u8>,
+    ) -> Result<()> {
+        let mut deletion = KvWriter::<_, FieldId>::memory();
+        let mut addition = KvWriter::<_, FieldId>::memory();
+        for (key, value) in obkv.iter() {
+            let value = KvReaderDelAdd::new(value);
+            if let Some(value) = value.get(DelAdd::Deletion) {
+                deletion.insert(key, value)?;
+            }
+            if let Some(value) = value.get(DelAdd::Addition) {
+                addition.insert(key, value)?;
+            }
+        }
+        let deletion = deletion.into_inner()?;
+        let addition = addition.into_inner()?;
+
+        let flattened_deletion =
+            Self::flatten_from_fields_ids_map(&KvReader::new(&deletion), fields_ids_map)?;
+        let flattened_addition =
+            Self::flatten_from_fields_ids_map(&KvReader::new(&addition), fields_ids_map)?;
+
+        output_buffer.clear();
+        del_add_from_two_obkvs(
+            &KvReader::<FieldId>::new(flattened_deletion.as_deref().unwrap_or(&deletion)),
+            &KvReader::<FieldId>::new(flattened_addition.as_deref().unwrap_or(&addition)),
+            output_buffer,
+        )?;
+        Ok(())
+    }
+
+    /// The function applying the fields of an update on the fields of a document,
+    /// `None` when the fields of the update replace the ones of the document.
+    fn field_update_function(&self) -> Option<FieldUpdateFn> {
+        match self.index_documents_method {
+            IndexDocumentsMethod::ReplaceDocuments | IndexDocumentsMethod::UpdateDocuments => None,
+            IndexDocumentsMethod::DeepMergeDocuments => Some(merge_patch_field_value),
+            IndexDocumentsMethod::UpdateDocumentsWithOperators => Some(update_field_value),
+            IndexDocumentsMethod::DeepMergeDocumentsWithOperators => {
+                Some(merge_patch_or_update_field_value)
+            }
+        }
+    }
+
+    /// The replaced documents have no previous values to update, their operator objects
+    /// are rejected instead of being stored as regular values.
+    fn reject_field_operators(obkv: KvReader<'_, FieldId>, external_id: &str) -> Result<()> {
+        for (_, value) in obkv.iter() {
+            if FieldOperator::from_slice(value, || external_id.to_string())?.is_some() {
+                return Err(UserError::InvalidDocumentFieldOperator {
+                    document_id: external_id.to_string(),
+                    message: "Field operators can only be used when updating documents."
+                        .to_string(),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the update of a document on nothing, resolving its field operators
+    /// and the `null` values of its merge patches.
+    fn update_missing_document(
+        obkv: KvReader<'_, FieldId>,
+        update_value: FieldUpdateFn,
+        external_id: &str,
+    ) -> Result<Vec<u8>> {
+        let mut writer = KvWriter::<_, FieldId>::memory();
+        for (key, value) in obkv.iter() {
+            if let Some(value) = update_value(None, value, &|| external_id.to_string())? {
+                writer.insert(key, value)?;
+            }
+        }
+        Ok(writer.into_inner()?)
+    }
+
     /// Generate an obkv from a slice of key / value sorted by key.
     fn create_obkv_from_key_value(
         key_value: &mut [(FieldId, Cow<'_, [u8]>)],
@@ -676,7 +869,7 @@ impl<'a, 'i> Transform<'a, 'i> {
     /// id for the user side and the value must be an obkv where keys are valid fields ids.
     #[tracing::instrument(level = "trace", skip_all, target = "indexing::transform")]
     pub(crate) fn output_from_sorter<F>(
-        self,
+        mut self,
         wtxn: &mut heed::RwTxn<'_>,
         progress_callback: F,
     ) -> Result<TransformOutput>
@@ -704,6 +897,17 @@ impl<'a, 'i> Transform<'a, 'i> {
         // 2. Add all the new documents to the field distribution
         let mut field_distribution = self.index.field_distribution(wtxn)?;
 
+        // We create a final writer to write the new flattened documents in order.
+        let mut flattened_writer = create_writer(
+            self.indexer_settings.chunk_compression_type,
+            self.indexer_settings.chunk_compression_level,
+            tempfile::tempfile()?,
+        );
+        // The merge patches and the field operators are only applied on the original documents,
+        // so we flatten the merged documents instead of merging the flattened updates.
+        let flatten_merged_documents = self.flatten_merged_documents;
+        let mut flattened_buffer = Vec::new();
+
         // Here we are going to do the document count + field distribution + `write_into_stream_writer`
         let mut iter = self.original_sorter.into_stream_merger_iter()?;
         // used only for the callback
@@ -767,29 +971,45 @@ impl<'a, 'i> Transform<'a, 'i> {
                 }
             }
             writer.insert(key, val)?;
+
+            if flatten_merged_documents {
+                Self::flatten_del_add_from_fields_ids_map(
+                    &KvReader::new(val),
+                    &mut self.fields_ids_map,
+                    &mut flattened_buffer,
+                )?;
+                // the key of the flattened documents is the internal docid only.
+                let docid = &key[..std::mem::size_of::<DocumentId>()];
+                flattened_writer.insert(docid, &flattened_buffer)?;
+            }
+        }
+
+        // the versions of the deleted documents are removed with the documents.
+        for (docid, version) in &self.documents_versions {
+            if *version != 0 {
+                self.index.documents_versions.put(wtxn, docid, version)?;
+            }
         }
 
         let mut original_documents = writer.into_inner()?;
         // We then extract the file and reset the seek to be able to read it again.
         original_documents.rewind()?;
 
-        // We create a final writer to write the new documents in order from the sorter.
-        let mut writer = create_writer(
-            self.indexer_settings.chunk_compression_type,
-            self.indexer_settings.chunk_compression_level,
-            tempfile::tempfile()?,
-        );
-
-        // Once we have written all the documents into the final sorter, we write the nested documents
-        // into this writer.
-        // We get rids of the `Operation` byte and skip the deleted documents as well.
-        let mut iter = self.flattened_sorter.into_stream_merger_iter()?;
-        while let Some((key, val)) = iter.next()? {
-            // skip first byte corresponding to the operation type (Deletion or Addition).
-            let val = &val[1..];
-            writer.insert(key, val)?;
+        if flatten_merged_documents {
+            // the flattening may have generated new fields.
+            self.index.put_fields_ids_map(wtxn, &self.fields_ids_map)?;
+        } else {
+            // Once we have written all the documents into the final sorter, we write the nested documents
+            // into this writer.
+            // We get rids of the `Operation` byte and skip the deleted documents as well.
+            let mut iter = self.flattened_sorter.into_stream_merger_iter()?;
+            while let Some((key, val)) = iter.next()? {
+                // skip first byte corresponding to the operation type (Deletion or Addition).
+                let val = &val[1..];
+                flattened_writer.insert(key, val)?;
+            }
         }
-        let mut flattened_documents = writer.into_inner()?;
+        let mut flattened_documents = flattened_writer.into_inner()?;
         flattened_documents.rewind()?;
 
         let mut new_external_documents_ids_builder: Vec<_> =
@@ -802,7 +1022,8 @@ impl<'a, 'i> Transform<'a, 'i> {
             fst_new_external_documents_ids_builder.insert(key, value)
         })?;
 
-        let old_inner_settings = InnerIndexSettings::from_index(self.index, wtxn)?;
+        let old_inner_settings =
+            InnerIndexSettings::from_index(self.index, wtxn, &self.indexer_settings.onnx_runtime)?;
         let fields_ids_map = self.fields_ids_map;
         let primary_key_id = self.index.primary_key(wtxn)?.and_then(|name| fields_ids_map.id(name));
         let mut new_inner_settings = old_inner_settings.clone();
@@ -896,7 +1117,11 @@ impl<'a, 'i> Transform<'a, 'i> {
                         Default::default()
                     };
 
-                    existing_vectors.append(&mut injected_vectors);
+                    // the full-precision embeddings kept in the document take precedence
+                    // over the quantized embeddings read back from the trees
+                    for (name, vectors) in std::mem::take(&mut injected_vectors) {
+                        existing_vectors.entry(name).or_insert(vectors);
+                    }
 
                     operations.insert(vectors_fid, DelAddOperation::DeletionAndAddition);
                     obkv_writer
@@ -989,29 +1214,29 @@ impl<'a, 'i> Transform<'a, 'i> {
             None
         };
 
-        let readers: Result<
-            BTreeMap<&str, (Vec<arroy::Reader<'_, arroy::distances::Angular>>, &RoaringBitmap)>,
-        > = settings_diff
+        let readers: BTreeMap<&str, (ArroyWrapper, &RoaringBitmap)> = settings_diff
             .embedding_config_updates
             .iter()
             .filter_map(|(name, action)| {
                 if let EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
                     embedder_id,
+                    distance,
+                    quantization,
                     user_provided,
                 }) = action
                 {
-                    let readers: Result<Vec<_>> =
-                        self.index.arroy_readers(wtxn, *embedder_id).collect();
-                    match readers {
-                        Ok(readers) => Some(Ok((name.as_str(), (readers, user_provided)))),
-                        Err(error) => Some(Err(error)),
-                    }
+                    let arroy = ArroyWrapper::new(
+                        self.index.vector_arroy,
+                        *embedder_id,
+                        *distance,
+                        *quantization,
+                    );
+                    Some((name.as_str(), (arroy, user_provided)))
                 } else {
                     None
                 }
             })
             .collect();
-        let readers = readers?;
 
         let old_vectors_fid = settings_diff
             .old
@@ -1049,21 +1274,14 @@ impl<'a, 'i> Transform<'a, 'i> {
                     arroy::Error,
                 > = readers
                     .iter()
-                    .filter_map(|(name, (readers, user_provided))| {
+                    .filter_map(|(name, (arroy, user_provided))| {
                         if !user_provided.contains(docid) {
                             return None;
                         }
-                        let mut vectors = Vec::new();
-                        for reader in readers {
-                            let Some(vector) = reader.item_vector(wtxn, docid).transpose() else {
-                                break;
-                            };
-
-                            match vector {
-                                Ok(vector) => vectors.push(vector),
-                                Err(error) => return Some(Err(error)),
-                            }
-                        }
+                        let vectors = match arroy.item_vectors(wtxn, docid) {
+                            Ok(vectors) => vectors,
+                            Err(error) => return Some(Err(error)),
+                        };
                         if vectors.is_empty() {
                             return None;
                         }
@@ -1104,21 +1322,9 @@ impl<'a, 'i> Transform<'a, 'i> {
             }
         }
 
-        let mut writers = Vec::new();
-
         // delete all vectors from the embedders that need removal
-        for (_, (readers, _)) in readers {
-            for reader in readers {
-                let dimensions = reader.dimensions();
-                let arroy_index = reader.index();
-                drop(reader);
-                let writer = arroy::Writer::new(self.index.vector_arroy, arroy_index, dimensions);
-                writers.push(writer);
-            }
-        }
-
-        for writer in writers {
-            writer.clear(wtxn)?;
+        for (_, (arroy, _)) in readers {
+            arroy.clear(wtxn)?;
         }
 
         let grenad_params = GrenadParameters {
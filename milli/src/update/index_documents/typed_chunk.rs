@@ -154,9 +154,11 @@ pub(crate) fn write_typed_chunk_into_index(
             let mut docids = index.documents_ids(wtxn)?;
             let mut iter = merger.into_stream_merger_iter()?;
 
+            // the user-provided embeddings of the rescored embedders are kept in the documents
             let embedders: BTreeSet<_> = index
                 .embedding_configs(wtxn)?
                 .into_iter()
+                .filter(|IndexEmbeddingConfig { config, .. }| !config.rescored())
                 .map(|IndexEmbeddingConfig { name, .. }| name)
                 .collect();
             let mut vectors_buffer = Vec::new();
@@ -217,6 +219,7 @@ pub(crate) fn write_typed_chunk_into_index(
                     docids.insert(docid);
                 } else {
                     db.delete(wtxn, &docid)?;
+                    index.documents_versions.delete(wtxn, &docid)?;
                     operations.push(DocumentOperation {
                         external_id: external_id.to_string(),
                         internal_id: docid,
@@ -602,13 +605,15 @@ pub(crate) fn write_typed_chunk_into_index(
 
                 let deladd_obkv = KvReaderDelAdd::new(value);
                 if let Some(value) = deladd_obkv.get(DelAdd::Deletion) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.remove(&geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.remove(&geopoint);
+                    }
                     geo_faceted_docids.remove(docid);
                 }
                 if let Some(value) = deladd_obkv.get(DelAdd::Addition) {
-                    let geopoint = extract_geo_point(value, docid);
-                    rtree.insert(geopoint);
+                    for geopoint in extract_geo_points(value, docid) {
+                        rtree.insert(geopoint);
+                    }
                     geo_faceted_docids.insert(docid);
                 }
             }
@@ -663,26 +668,16 @@ pub(crate) fn write_typed_chunk_into_index(
 
             index.put_embedding_configs(wtxn, embedding_configs)?;
 
-            let embedder_index = index.embedder_category_id.get(wtxn, &embedder_name)?.ok_or(
+            let arroy = index.arroy_wrapper(wtxn, &embedder_name)?.ok_or(
                 InternalError::DatabaseMissingEntry { db_name: "embedder_category_id", key: None },
             )?;
-            // FIXME: allow customizing distance
-            let writers: Vec<_> = crate::vector::arroy_db_range_for_embedder(embedder_index)
-                .map(|k| arroy::Writer::new(index.vector_arroy, k, expected_dimension))
-                .collect();
 
             // remove vectors for docids we want them removed
             let merger = remove_vectors_builder.build();
             let mut iter = merger.into_stream_merger_iter()?;
             while let Some((key, _)) = iter.next()? {
                 let docid = key.try_into().map(DocumentId::from_be_bytes).unwrap();
-
-                for writer in &writers {
-                    // Uses invariant: vectors are packed in the first writers.
-                    if !writer.del_item(wtxn, docid)? {
-                        break;
-                    }
-                }
+                arroy.del_items(wtxn, expected_dimension, docid)?;
             }
 
             // add generated embeddings
@@ -710,9 +705,7 @@ pub(crate) fn write_typed_chunk_into_index(
                         embeddings.embedding_count(),
                     )));
                 }
-                for (embedding, writer) in embeddings.iter().zip(&writers) {
-                    writer.add_item(wtxn, docid, embedding)?;
-                }
+                arroy.add_items(wtxn, expected_dimension, docid, &embeddings)?;
             }
 
             // perform the manual diff
@@ -726,47 +719,14 @@ pub(crate) fn write_typed_chunk_into_index(
                 let vector_deladd_obkv = KvReaderDelAdd::new(value);
                 if let Some(value) = vector_deladd_obkv.get(DelAdd::Deletion) {
                     let vector: Vec<f32> = pod_collect_to_vec(value);
-
-                    let mut deleted_index = None;
-                    for (index, writer) in writers.iter().enumerate() {
-                        let Some(candidate) = writer.item_vector(wtxn, docid)? else {
-                            // uses invariant: vectors are packed in the first writers.
-                            break;
-                        };
-                        if candidate == vector {
-                            writer.del_item(wtxn, docid)?;
-                            deleted_index = Some(index);
-                        }
-                    }
-
-                    // 🥲 enforce invariant: vectors are packed in the first writers.
-                    if let Some(deleted_index) = deleted_index {
-                        let mut last_index_with_a_vector = None;
-                        for (index, writer) in writers.iter().enumerate().skip(deleted_index) {
-                            let Some(candidate) = writer.item_vector(wtxn, docid)? else {
-                                break;
-                            };
-                            last_index_with_a_vector = Some((index, candidate));
-                        }
-                        if let Some((last_index, vector)) = last_index_with_a_vector {
-                            // unwrap: computed the index from the list of writers
-                            let writer = writers.get(last_index).unwrap();
-                            writer.del_item(wtxn, docid)?;
-                            writers.get(deleted_index).unwrap().add_item(wtxn, docid, &vector)?;
-                        }
-                    }
+                    arroy.del_item(wtxn, expected_dimension, docid, &vector)?;
                 }
 
                 if let Some(value) = vector_deladd_obkv.get(DelAdd::Addition) {
-                    let vector = pod_collect_to_vec(value);
+                    let vector: Vec<f32> = pod_collect_to_vec(value);
 
                     // overflow was detected during vector extraction.
-                    for writer in &writers {
-                        if !writer.contains_item(wtxn, docid)? {
-                            writer.add_item(wtxn, docid, &vector)?;
-                            break;
-                        }
-                    }
+                    arroy.add_item(wtxn, expected_dimension, docid, &vector)?;
                 }
             }
 
@@ -777,13 +737,15 @@ pub(crate) fn write_typed_chunk_into_index(
     Ok((RoaringBitmap::new(), is_merged_database))
 }
 
-/// Converts the latitude and longitude back to an xyz GeoPoint.
-fn extract_geo_point(value: &[u8], docid: DocumentId) -> GeoPoint {
-    let (lat, tail) = helpers::try_split_array_at::<u8, 8>(value).unwrap();
-    let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
-    let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
-    let xyz_point = lat_lng_to_xyz(&point);
-    GeoPoint::new(xyz_point, (docid, point))
+/// Converts the latitudes and longitudes of a document back to xyz GeoPoints.
+fn extract_geo_points(value: &[u8], docid: DocumentId) -> impl Iterator<Item = GeoPoint> + '_ {
+    value.chunks_exact(16).map(move |bytes| {
+        let (lat, tail) = helpers::try_split_array_at::<u8, 8>(bytes).unwrap();
+        let (lng, _) = helpers::try_split_array_at::<u8, 8>(tail).unwrap();
+        let point = [f64::from_ne_bytes(lat), f64::from_ne_bytes(lng)];
+        let xyz_point = lat_lng_to_xyz(&point);
+        GeoPoint::new(xyz_point, (docid, point))
+    })
 }
 
 fn merge_word_docids_reader_into_fst(
@@ -1,6 +1,7 @@
 use grenad::CompressionType;
 
 use crate::thread_pool_no_abort::ThreadPoolNoAbort;
+use crate::vector::onnx;
 
 #[derive(Debug)]
 pub struct IndexerConfig {
@@ -13,6 +14,7 @@ pub struct IndexerConfig {
     pub thread_pool: Option<ThreadPoolNoAbort>,
     pub max_positions_per_attributes: Option<u32>,
     pub skip_index_budget: bool,
+    pub onnx_runtime: onnx::RuntimeOptions,
 }
 
 impl Default for IndexerConfig {
@@ -27,6 +29,7 @@ impl Default for IndexerConfig {
             thread_pool: None,
             max_positions_per_attributes: None,
             skip_index_budget: false,
+            onnx_runtime: Default::default(),
         }
     }
 }
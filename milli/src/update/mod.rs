@@ -7,7 +7,9 @@ pub use self::index_documents::{
     IndexDocuments, IndexDocumentsConfig, IndexDocumentsMethod, MergeFn,
 };
 pub use self::indexer_config::IndexerConfig;
-pub use self::settings::{validate_embedding_settings, Setting, Settings};
+pub use self::settings::{
+    validate_embedding_settings, validate_reranker_settings, Setting, Settings,
+};
 pub use self::update_step::UpdateIndexingStep;
 pub use self::word_prefix_docids::WordPrefixDocids;
 pub use self::words_prefix_integer_docids::WordPrefixIntegerDocids;
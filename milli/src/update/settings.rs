@@ -7,6 +7,7 @@ use std::sync::Arc;
 use charabia::{Normalize, Tokenizer, TokenizerBuilder};
 use deserr::{DeserializeError, Deserr};
 use itertools::{EitherOrBoth, Itertools};
+use rand::SeedableRng;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use time::OffsetDateTime;
@@ -26,10 +27,11 @@ use crate::update::index_documents::IndexDocumentsMethod;
 use crate::update::{IndexDocuments, UpdateIndexingStep};
 use crate::vector::parsed_vectors::RESERVED_VECTORS_FIELD_NAME;
 use crate::vector::settings::{
-    check_set, check_unset, EmbedderAction, EmbedderSource, EmbeddingSettings, ReindexAction,
+    check_reranker_set, check_reranker_unset, check_set, check_unset, EmbedderAction,
+    EmbedderSource, EmbeddingSettings, ReindexAction, RerankerSettings, RerankerSource,
     WriteBackToDocuments,
 };
-use crate::vector::{Embedder, EmbeddingConfig, EmbeddingConfigs};
+use crate::vector::{onnx, Embedder, EmbeddingConfig, EmbeddingConfigs, Quantization};
 use crate::{FieldId, FieldsIdsMap, Index, LocalizedAttributesRule, LocalizedFieldIds, Result};
 
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -148,6 +150,8 @@ pub struct Settings<'a, 't, 'i> {
     displayed_fields: Setting<Vec<String>>,
     filterable_fields: Setting<HashSet<String>>,
     sortable_fields: Setting<HashSet<String>>,
+    datetime_fields: Setting<HashSet<String>>,
+    expiry_field: Setting<String>,
     criteria: Setting<Vec<Criterion>>,
     stop_words: Setting<BTreeSet<String>>,
     non_separator_tokens: Setting<BTreeSet<String>>,
@@ -167,6 +171,7 @@ pub struct Settings<'a, 't, 'i> {
     pagination_max_total_hits: Setting<usize>,
     proximity_precision: Setting<ProximityPrecision>,
     embedder_settings: Setting<BTreeMap<String, Setting<EmbeddingSettings>>>,
+    reranker_settings: Setting<BTreeMap<String, Setting<RerankerSettings>>>,
     search_cutoff: Setting<u64>,
     localized_attributes_rules: Setting<Vec<LocalizedAttributesRule>>,
 }
@@ -184,6 +189,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             displayed_fields: Setting::NotSet,
             filterable_fields: Setting::NotSet,
             sortable_fields: Setting::NotSet,
+            datetime_fields: Setting::NotSet,
+            expiry_field: Setting::NotSet,
             criteria: Setting::NotSet,
             stop_words: Setting::NotSet,
             non_separator_tokens: Setting::NotSet,
@@ -202,6 +209,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             pagination_max_total_hits: Setting::NotSet,
             proximity_precision: Setting::NotSet,
             embedder_settings: Setting::NotSet,
+            reranker_settings: Setting::NotSet,
             search_cutoff: Setting::NotSet,
             localized_attributes_rules: Setting::NotSet,
             indexer_config,
@@ -240,6 +248,22 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.sortable_fields = Setting::Reset;
     }
 
+    pub fn set_datetime_fields(&mut self, names: HashSet<String>) {
+        self.datetime_fields = Setting::Set(names);
+    }
+
+    pub fn reset_datetime_fields(&mut self) {
+        self.datetime_fields = Setting::Reset;
+    }
+
+    pub fn set_expiry_field(&mut self, name: String) {
+        self.expiry_field = Setting::Set(name);
+    }
+
+    pub fn reset_expiry_field(&mut self) {
+        self.expiry_field = Setting::Reset;
+    }
+
     pub fn reset_criteria(&mut self) {
         self.criteria = Setting::Reset;
     }
@@ -394,6 +418,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.embedder_settings = Setting::Reset;
     }
 
+    pub fn set_reranker_settings(&mut self, value: BTreeMap<String, Setting<RerankerSettings>>) {
+        self.reranker_settings = Setting::Set(value);
+    }
+
+    pub fn reset_reranker_settings(&mut self) {
+        self.reranker_settings = Setting::Reset;
+    }
+
     pub fn set_search_cutoff(&mut self, value: u64) {
         self.search_cutoff = Setting::Set(value);
     }
@@ -762,6 +794,32 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_datetime_fields(&mut self) -> Result<()> {
+        match self.datetime_fields {
+            Setting::Set(ref fields) => {
+                self.index.put_datetime_fields(self.wtxn, fields)?;
+            }
+            Setting::Reset => {
+                self.index.delete_datetime_fields(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
+    fn update_expiry_field(&mut self) -> Result<()> {
+        match self.expiry_field {
+            Setting::Set(ref field) => {
+                self.index.put_expiry_field(self.wtxn, field)?;
+            }
+            Setting::Reset => {
+                self.index.delete_expiry_field(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+        Ok(())
+    }
+
     fn update_criteria(&mut self) -> Result<()> {
         match &self.criteria {
             Setting::Set(criteria) => {
@@ -954,7 +1012,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 let old_configs = self.index.embedding_configs(self.wtxn)?;
                 let remove_all: Result<BTreeMap<String, EmbedderAction>> = old_configs
                     .into_iter()
-                    .map(|IndexEmbeddingConfig { name, config: _, user_provided }| -> Result<_> {
+                    .map(|IndexEmbeddingConfig { name, config, user_provided }| -> Result<_> {
                         let embedder_id =
                             self.index.embedder_category_id.get(self.wtxn, &name)?.ok_or(
                                 crate::InternalError::DatabaseMissingEntry {
@@ -962,10 +1020,14 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                                     key: None,
                                 },
                             )?;
+                        let distance =
+                            self.index.embedder_distance.get(self.wtxn, &name)?.unwrap_or_default();
                         Ok((
                             name,
                             EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
                                 embedder_id,
+                                distance,
+                                quantization: config.quantization,
                                 user_provided,
                             }),
                         ))
@@ -975,6 +1037,7 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 let remove_all = remove_all?;
 
                 self.index.embedder_category_id.clear(self.wtxn)?;
+                self.index.embedder_distance.clear(self.wtxn)?;
                 self.index.delete_embedding_configs(self.wtxn)?;
                 Ok(remove_all)
             }
@@ -1004,7 +1067,8 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
             match joined {
                 // updated config
                 EitherOrBoth::Both((name, (old, user_provided)), (_, new)) => {
-                    let settings_diff = SettingsDiff::from_settings(old, new);
+                    let quantization = old.quantization.set();
+                    let settings_diff = SettingsDiff::from_settings(&name, old, new)?;
                     match settings_diff {
                         SettingsDiff::Remove => {
                             tracing::debug!(
@@ -1019,12 +1083,20 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                                         key: None,
                                     },
                                 )?;
+                            let distance = self
+                                .index
+                                .embedder_distance
+                                .get(self.wtxn, &name)?
+                                .unwrap_or_default();
                             // free id immediately
                             self.index.embedder_category_id.delete(self.wtxn, &name)?;
+                            self.index.embedder_distance.delete(self.wtxn, &name)?;
                             embedder_actions.insert(
                                 name,
                                 EmbedderAction::WriteBackToDocuments(WriteBackToDocuments {
                                     embedder_id,
+                                    distance,
+                                    quantization,
                                     user_provided,
                                 }),
                             );
@@ -1097,6 +1169,20 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
                 EmbedderAction::WriteBackToDocuments(_) => { /* already removed */ }
             }
         }
+        // convert the trees of the embedders whose distance or quantization changed, keeping their embeddings
+        for (name, (config, _)) in updated_configs.iter() {
+            let Setting::Set(config) = config else { continue };
+            let distance = config.distance.set().unwrap_or_default();
+            let quantization = config.quantization.set();
+            // the embedding configs are not written yet, so the wrapper reflects the current trees
+            let Some(arroy) = self.index.arroy_wrapper(self.wtxn, name)? else { continue };
+            if arroy.distance() != distance || arroy.quantization() != quantization {
+                tracing::debug!(embedder = name, ?distance, ?quantization, "converting embedder");
+                let mut rng = rand::rngs::StdRng::seed_from_u64(42);
+                arroy.convert(self.wtxn, &mut rng, distance, quantization)?;
+                self.index.embedder_distance.put(self.wtxn, name, &distance)?;
+            }
+        }
         let updated_configs: Vec<IndexEmbeddingConfig> = updated_configs
             .into_iter()
             .filter_map(|(name, (config, user_provided))| match config {
@@ -1154,6 +1240,49 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         Ok(())
     }
 
+    fn update_rerankers(&mut self) -> Result<()> {
+        match std::mem::take(&mut self.reranker_settings) {
+            Setting::Set(settings) => {
+                let mut configs = self.index.reranker_configs(self.wtxn)?;
+                for (name, setting) in settings {
+                    match setting {
+                        Setting::Set(new) => {
+                            let mut settings = configs
+                                .remove(&name)
+                                .map(RerankerSettings::from)
+                                .unwrap_or_default();
+                            settings.apply(new);
+                            if settings.source.is_not_set() {
+                                settings.source = Setting::Set(RerankerSource::default());
+                            }
+                            let Setting::Set(settings) =
+                                validate_reranker_settings(Setting::Set(settings), &name)?
+                            else {
+                                unreachable!("the settings of a reranker stay set when validated")
+                            };
+                            configs.insert(name, settings.into());
+                        }
+                        Setting::Reset => {
+                            configs.remove(&name);
+                        }
+                        Setting::NotSet => (),
+                    }
+                }
+                if configs.is_empty() {
+                    self.index.delete_reranker_configs(self.wtxn)?;
+                } else {
+                    self.index.put_reranker_configs(self.wtxn, &configs)?;
+                }
+            }
+            Setting::Reset => {
+                self.index.delete_reranker_configs(self.wtxn)?;
+            }
+            Setting::NotSet => (),
+        }
+
+        Ok(())
+    }
+
     pub fn execute<FP, FA>(mut self, progress_callback: FP, should_abort: FA) -> Result<()>
     where
         FP: Fn(UpdateIndexingStep) + Sync,
@@ -1161,7 +1290,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
     {
         self.index.set_updated_at(self.wtxn, &OffsetDateTime::now_utc())?;
 
-        let old_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
+        let old_inner_settings = InnerIndexSettings::from_index(
+            self.index,
+            self.wtxn,
+            &self.indexer_config.onnx_runtime,
+        )?;
 
         // never trigger re-indexing
         self.update_displayed()?;
@@ -1175,10 +1308,13 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
         self.update_sort_facet_values_by()?;
         self.update_pagination_max_total_hits()?;
         self.update_search_cutoff()?;
+        self.update_rerankers()?;
 
         // could trigger re-indexing
         self.update_filterable()?;
         self.update_sortable()?;
+        self.update_datetime_fields()?;
+        self.update_expiry_field()?;
         self.update_stop_words()?;
         self.update_non_separator_tokens()?;
         self.update_separator_tokens()?;
@@ -1191,7 +1327,11 @@ impl<'a, 't, 'i> Settings<'a, 't, 'i> {
 
         let embedding_config_updates = self.update_embedding_configs()?;
 
-        let mut new_inner_settings = InnerIndexSettings::from_index(self.index, self.wtxn)?;
+        let mut new_inner_settings = InnerIndexSettings::from_index(
+            self.index,
+            self.wtxn,
+            &self.indexer_config.onnx_runtime,
+        )?;
         new_inner_settings.recompute_facets(self.wtxn, self.index)?;
 
         let primary_key_id = self
@@ -1370,6 +1510,7 @@ impl InnerIndexSettingsDiff {
 
         (existing_fields - old_faceted_fields) != (existing_fields - new_faceted_fields)
             || self.old.localized_faceted_fields_ids != self.new.localized_faceted_fields_ids
+            || self.old.datetime_fields != self.new.datetime_fields
     }
 
     pub fn reindex_vectors(&self) -> bool {
@@ -1386,7 +1527,11 @@ impl InnerIndexSettingsDiff {
     }
 
     pub fn modified_faceted_fields(&self) -> HashSet<String> {
-        &self.old.user_defined_faceted_fields ^ &self.new.user_defined_faceted_fields
+        let mut modified =
+            &self.old.user_defined_faceted_fields ^ &self.new.user_defined_faceted_fields;
+        // the values of the fields that became, or stopped being, datetimes must be extracted again.
+        modified.extend(&self.old.datetime_fields ^ &self.new.datetime_fields);
+        modified
     }
 }
 
@@ -1405,6 +1550,7 @@ pub(crate) struct InnerIndexSettings {
     pub embedding_configs: EmbeddingConfigs,
     pub existing_fields: HashSet<String>,
     pub geo_fields_ids: Option<(FieldId, FieldId)>,
+    pub datetime_fields: HashSet<String>,
     pub non_searchable_fields_ids: Vec<FieldId>,
     pub non_faceted_fields_ids: Vec<FieldId>,
     pub localized_searchable_fields_ids: LocalizedFieldIds,
@@ -1412,7 +1558,11 @@ pub(crate) struct InnerIndexSettings {
 }
 
 impl InnerIndexSettings {
-    pub fn from_index(index: &Index, rtxn: &heed::RoTxn<'_>) -> Result<Self> {
+    pub fn from_index(
+        index: &Index,
+        rtxn: &heed::RoTxn<'_>,
+        onnx_runtime: &onnx::RuntimeOptions,
+    ) -> Result<Self> {
         let stop_words = index.stop_words(rtxn)?;
         let stop_words = stop_words.map(|sw| sw.map_data(Vec::from).unwrap());
         let allowed_separators = index.allowed_separators(rtxn)?;
@@ -1426,7 +1576,7 @@ impl InnerIndexSettings {
         let mut faceted_fields_ids = index.faceted_fields_ids(rtxn)?;
         let exact_attributes = index.exact_attributes_ids(rtxn)?;
         let proximity_precision = index.proximity_precision(rtxn)?.unwrap_or_default();
-        let embedding_configs = embedders(index.embedding_configs(rtxn)?)?;
+        let embedding_configs = embedders(index.embedding_configs(rtxn)?, onnx_runtime)?;
         let existing_fields: HashSet<_> = index
             .field_distribution(rtxn)?
             .into_iter()
@@ -1450,6 +1600,7 @@ impl InnerIndexSettings {
             }
             None => None,
         };
+        let datetime_fields = index.indexed_datetime_fields(rtxn)?;
         let localized_attributes_rules = index.localized_attributes_rules(rtxn)?;
         let localized_searchable_fields_ids = LocalizedFieldIds::new(
             &localized_attributes_rules,
@@ -1480,6 +1631,7 @@ impl InnerIndexSettings {
             embedding_configs,
             existing_fields,
             geo_fields_ids,
+            datetime_fields,
             non_searchable_fields_ids: vectors_fids.clone(),
             non_faceted_fields_ids: vectors_fids.clone(),
             localized_searchable_fields_ids,
@@ -1540,19 +1692,22 @@ impl InnerIndexSettings {
     }
 }
 
-fn embedders(embedding_configs: Vec<IndexEmbeddingConfig>) -> Result<EmbeddingConfigs> {
+fn embedders(
+    embedding_configs: Vec<IndexEmbeddingConfig>,
+    onnx_runtime: &onnx::RuntimeOptions,
+) -> Result<EmbeddingConfigs> {
     let res: Result<_> = embedding_configs
         .into_iter()
         .map(
             |IndexEmbeddingConfig {
                  name,
-                 config: EmbeddingConfig { embedder_options, prompt },
+                 config: EmbeddingConfig { embedder_options, prompt, .. },
                  ..
              }| {
                 let prompt = Arc::new(prompt.try_into().map_err(crate::Error::from)?);
 
                 let embedder = Arc::new(
-                    Embedder::new(embedder_options.clone())
+                    Embedder::new(embedder_options.clone(), onnx_runtime)
                         .map_err(crate::vector::Error::from)
                         .map_err(crate::Error::from)?,
                 );
@@ -1581,6 +1736,13 @@ fn validate_prompt(
             response,
             distribution,
             headers,
+            distance,
+            quantization,
+            rescore,
+            chunking,
+            tokenizer,
+            pooling,
+            normalize,
         }) => {
             let max_bytes = match document_template_max_bytes.set() {
                 Some(max_bytes) => NonZeroUsize::new(max_bytes).ok_or_else(|| {
@@ -1613,6 +1775,13 @@ fn validate_prompt(
                 response,
                 distribution,
                 headers,
+                distance,
+                quantization,
+                rescore,
+                chunking,
+                tokenizer,
+                pooling,
+                normalize,
             }))
         }
         new => Ok(new),
@@ -1638,6 +1807,13 @@ pub fn validate_embedding_settings(
         response,
         distribution,
         headers,
+        distance,
+        quantization,
+        rescore,
+        chunking,
+        tokenizer,
+        pooling,
+        normalize,
     } = settings;
 
     if let Some(0) = dimensions.set() {
@@ -1647,6 +1823,34 @@ pub fn validate_embedding_settings(
         .into());
     }
 
+    if let Setting::Set(Quantization::Binary) = quantization {
+        let distance = distance.set().unwrap_or_default();
+        if !distance.supports_binary_quantization() {
+            return Err(crate::error::UserError::InvalidBinaryQuantizationDistance {
+                embedder_name: name.to_owned(),
+                distance: distance.to_string(),
+            }
+            .into());
+        }
+    }
+
+    if let Some(chunking) = chunking.as_ref().set() {
+        if chunking.size == 0 {
+            return Err(crate::error::UserError::InvalidSettingsChunkingSize {
+                embedder_name: name.to_owned(),
+            }
+            .into());
+        }
+        if chunking.overlap >= chunking.size {
+            return Err(crate::error::UserError::InvalidSettingsChunkingOverlap {
+                embedder_name: name.to_owned(),
+                overlap: chunking.overlap,
+                size: chunking.size,
+            }
+            .into());
+        }
+    }
+
     if let Some(url) = url.as_ref().set() {
         url::Url::parse(url).map_err(|error| crate::error::UserError::InvalidUrl {
             embedder_name: name.to_owned(),
@@ -1678,8 +1882,20 @@ pub fn validate_embedding_settings(
             response,
             distribution,
             headers,
+            distance,
+            quantization,
+            rescore,
+            chunking,
+            tokenizer,
+            pooling,
+            normalize,
         }));
     };
+    if inferred_source != EmbedderSource::Onnx {
+        check_unset(&tokenizer, EmbeddingSettings::TOKENIZER, inferred_source, name)?;
+        check_unset(&pooling, EmbeddingSettings::POOLING, inferred_source, name)?;
+        check_unset(&normalize, EmbeddingSettings::NORMALIZE, inferred_source, name)?;
+    }
     match inferred_source {
         EmbedderSource::OpenAi => {
             check_unset(&revision, EmbeddingSettings::REVISION, inferred_source, name)?;
@@ -1751,6 +1967,7 @@ pub fn validate_embedding_settings(
                 inferred_source,
                 name,
             )?;
+            check_unset(&chunking, EmbeddingSettings::CHUNKING, inferred_source, name)?;
             check_set(&dimensions, EmbeddingSettings::DIMENSIONS, inferred_source, name)?;
 
             check_unset(&url, EmbeddingSettings::URL, inferred_source, name)?;
@@ -1765,6 +1982,31 @@ pub fn validate_embedding_settings(
             check_set(&request, EmbeddingSettings::REQUEST, inferred_source, name)?;
             check_set(&response, EmbeddingSettings::RESPONSE, inferred_source, name)?;
         }
+        EmbedderSource::Onnx => {
+            check_set(&model, EmbeddingSettings::MODEL, inferred_source, name)?;
+            for (field, path) in
+                [(EmbeddingSettings::MODEL, &model), (EmbeddingSettings::TOKENIZER, &tokenizer)]
+            {
+                if let Setting::Set(path) = path {
+                    if !crate::vector::onnx::is_valid_model_path(path) {
+                        return Err(crate::error::UserError::InvalidOnnxModelPath {
+                            embedder_name: name.to_owned(),
+                            field,
+                            path: path.clone(),
+                        }
+                        .into());
+                    }
+                }
+            }
+            check_unset(&revision, EmbeddingSettings::REVISION, inferred_source, name)?;
+            check_unset(&api_key, EmbeddingSettings::API_KEY, inferred_source, name)?;
+            check_unset(&dimensions, EmbeddingSettings::DIMENSIONS, inferred_source, name)?;
+
+            check_unset(&url, EmbeddingSettings::URL, inferred_source, name)?;
+            check_unset(&request, EmbeddingSettings::REQUEST, inferred_source, name)?;
+            check_unset(&response, EmbeddingSettings::RESPONSE, inferred_source, name)?;
+            check_unset(&headers, EmbeddingSettings::HEADERS, inferred_source, name)?;
+        }
     }
     Ok(Setting::Set(EmbeddingSettings {
         source,
@@ -1779,9 +2021,78 @@ pub fn validate_embedding_settings(
         response,
         distribution,
         headers,
+        distance,
+        quantization,
+        rescore,
+        chunking,
+        tokenizer,
+        pooling,
+        normalize,
     }))
 }
 
+pub fn validate_reranker_settings(
+    settings: Setting<RerankerSettings>,
+    name: &str,
+) -> Result<Setting<RerankerSettings>> {
+    let Setting::Set(mut settings) = settings else { return Ok(settings) };
+
+    if let Some(template) = settings.document_template.as_ref().set() {
+        let max_bytes = match settings.document_template_max_bytes.set() {
+            Some(max_bytes) => NonZeroUsize::new(max_bytes).ok_or_else(|| {
+                UserError::InvalidSettingsRerankerDocumentTemplateMaxBytes {
+                    reranker_name: name.to_owned(),
+                }
+            })?,
+            None => default_max_bytes(),
+        };
+        let template = crate::prompt::Prompt::new(template.to_owned(), Some(max_bytes))
+            .map(|prompt| crate::prompt::PromptData::from(prompt).template)
+            .map_err(|inner| UserError::InvalidPromptForReranker(name.to_owned(), inner))?;
+        settings.document_template = Setting::Set(template);
+    } else if let Some(0) = settings.document_template_max_bytes.set() {
+        return Err(UserError::InvalidSettingsRerankerDocumentTemplateMaxBytes {
+            reranker_name: name.to_owned(),
+        }
+        .into());
+    }
+
+    if let Some(url) = settings.url.as_ref().set() {
+        url::Url::parse(url).map_err(|error| UserError::InvalidRerankerUrl {
+            reranker_name: name.to_owned(),
+            inner_error: error,
+            url: url.to_owned(),
+        })?;
+    }
+
+    if let (Some(request), Some(response)) =
+        (settings.request.as_ref().set(), settings.response.as_ref().set())
+    {
+        crate::vector::rerank::validate_templates(request.to_owned(), response.to_owned())
+            .map_err(|error| UserError::VectorEmbeddingError(error.into()))?;
+    }
+
+    let Some(source) = settings.source.set() else { return Ok(Setting::Set(settings)) };
+    match source {
+        RerankerSource::HuggingFace => {
+            check_reranker_unset(&settings.api_key, RerankerSettings::API_KEY, source, name)?;
+            check_reranker_unset(&settings.url, RerankerSettings::URL, source, name)?;
+            check_reranker_unset(&settings.request, RerankerSettings::REQUEST, source, name)?;
+            check_reranker_unset(&settings.response, RerankerSettings::RESPONSE, source, name)?;
+            check_reranker_unset(&settings.headers, RerankerSettings::HEADERS, source, name)?;
+        }
+        RerankerSource::Rest => {
+            check_reranker_unset(&settings.model, RerankerSettings::MODEL, source, name)?;
+            check_reranker_unset(&settings.revision, RerankerSettings::REVISION, source, name)?;
+            check_reranker_set(&settings.url, RerankerSettings::URL, source, name)?;
+            check_reranker_set(&settings.request, RerankerSettings::REQUEST, source, name)?;
+            check_reranker_set(&settings.response, RerankerSettings::RESPONSE, source, name)?;
+        }
+    }
+
+    Ok(Setting::Set(settings))
+}
+
 #[cfg(test)]
 mod tests {
     use big_s::S;
@@ -2677,6 +2988,8 @@ mod tests {
                     displayed_fields,
                     filterable_fields,
                     sortable_fields,
+                    datetime_fields,
+                    expiry_field,
                     criteria,
                     stop_words,
                     non_separator_tokens,
@@ -2695,6 +3008,7 @@ mod tests {
                     pagination_max_total_hits,
                     proximity_precision,
                     embedder_settings,
+                    reranker_settings,
                     search_cutoff,
                     localized_attributes_rules,
                 } = settings;
@@ -2702,6 +3016,8 @@ mod tests {
                 assert!(matches!(displayed_fields, Setting::NotSet));
                 assert!(matches!(filterable_fields, Setting::NotSet));
                 assert!(matches!(sortable_fields, Setting::NotSet));
+                assert!(matches!(datetime_fields, Setting::NotSet));
+                assert!(matches!(expiry_field, Setting::NotSet));
                 assert!(matches!(criteria, Setting::NotSet));
                 assert!(matches!(stop_words, Setting::NotSet));
                 assert!(matches!(non_separator_tokens, Setting::NotSet));
@@ -2720,6 +3036,7 @@ mod tests {
                 assert!(matches!(pagination_max_total_hits, Setting::NotSet));
                 assert!(matches!(proximity_precision, Setting::NotSet));
                 assert!(matches!(embedder_settings, Setting::NotSet));
+                assert!(matches!(reranker_settings, Setting::NotSet));
                 assert!(matches!(search_cutoff, Setting::NotSet));
                 assert!(matches!(localized_attributes_rules, Setting::NotSet));
             })
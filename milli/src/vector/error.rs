@@ -54,6 +54,10 @@ pub enum EmbedErrorKind {
     TensorValue(candle_core::Error),
     #[error("could not run model:\n  - {0}")]
     ModelForward(candle_core::Error),
+    #[error("could not run ONNX model:\n  - {0}")]
+    OnnxRun(ort::Error),
+    #[error("unexpected shape `{0:?}` for the output of the ONNX model, expected the embeddings of the texts or of their tokens")]
+    OnnxOutputShape(Vec<usize>),
     #[error("attempt to embed the following text in a configuration where embeddings must be user provided:\n  - `{0}`")]
     ManualEmbed(String),
     #[error("model not found. Meilisearch will not automatically download models from the Ollama library, please pull the model manually{}", option_info(.0.as_deref(), "server replied with "))]
@@ -62,6 +66,8 @@ pub enum EmbedErrorKind {
     RestResponseDeserialization(std::io::Error),
     #[error("expected a response containing {0} embeddings, got only {1}")]
     RestResponseEmbeddingCount(usize, usize),
+    #[error("expected a response containing {0} scores, got {1}")]
+    RerankResponseScoreCount(usize, usize),
     #[error("could not authenticate against {embedding} server{server_reply}{hint}", embedding=match *.1 {
         ConfigurationSource::User => "embedding",
         ConfigurationSource::OpenAi => "OpenAI",
@@ -124,6 +130,14 @@ impl EmbedError {
         Self { kind: EmbedErrorKind::ModelForward(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn onnx_run(inner: ort::Error) -> Self {
+        Self { kind: EmbedErrorKind::OnnxRun(inner), fault: FaultSource::Runtime }
+    }
+
+    pub fn onnx_output_shape(shape: Vec<usize>) -> Self {
+        Self { kind: EmbedErrorKind::OnnxOutputShape(shape), fault: FaultSource::User }
+    }
+
     pub(crate) fn embed_on_manual_embedder(texts: String) -> EmbedError {
         Self { kind: EmbedErrorKind::ManualEmbed(texts), fault: FaultSource::User }
     }
@@ -146,6 +160,13 @@ impl EmbedError {
         }
     }
 
+    pub(crate) fn rerank_response_score_count(expected: usize, got: usize) -> EmbedError {
+        Self {
+            kind: EmbedErrorKind::RerankResponseScoreCount(expected, got),
+            fault: FaultSource::Runtime,
+        }
+    }
+
     pub(crate) fn rest_unauthorized(
         error_response: Option<String>,
         configuration_source: ConfigurationSource,
@@ -291,6 +312,37 @@ impl NewEmbedderError {
         Self { kind: NewEmbedderErrorKind::LoadModel(inner), fault: FaultSource::Runtime }
     }
 
+    pub fn onnx_load_model(model_filename: PathBuf, inner: ort::Error) -> Self {
+        Self {
+            kind: NewEmbedderErrorKind::OnnxLoadModel(OnnxLoadModel {
+                filename: model_filename,
+                inner,
+            }),
+            fault: FaultSource::User,
+        }
+    }
+
+    pub fn onnx_load_runtime(runtime_filename: PathBuf, inner: String) -> Self {
+        Self {
+            kind: NewEmbedderErrorKind::OnnxLoadRuntime(OnnxLoadRuntime {
+                filename: runtime_filename,
+                inner,
+            }),
+            fault: FaultSource::Runtime,
+        }
+    }
+
+    pub fn onnx_models_dir_not_set() -> Self {
+        Self { kind: NewEmbedderErrorKind::OnnxModelsDirNotSet, fault: FaultSource::User }
+    }
+
+    pub fn onnx_path_outside_models_dir(path: PathBuf, models_dir: PathBuf) -> Self {
+        Self {
+            kind: NewEmbedderErrorKind::OnnxPathOutsideModelsDir { path, models_dir },
+            fault: FaultSource::User,
+        }
+    }
+
     pub fn could_not_determine_dimension(inner: EmbedError) -> NewEmbedderError {
         Self {
             kind: NewEmbedderErrorKind::CouldNotDetermineDimension(inner),
@@ -342,6 +394,21 @@ pub struct OpenTokenizer {
     pub inner: Box<dyn std::error::Error + Send + Sync>,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("could not load ONNX model at {filename}:\n  - {inner}")]
+pub struct OnnxLoadModel {
+    pub filename: PathBuf,
+    #[source]
+    pub inner: ort::Error,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("could not load the ONNX Runtime library at {filename}, set the `--onnx-runtime-path` option or the `ORT_DYLIB_PATH` environment variable to the path of the library:\n  - {inner}")]
+pub struct OnnxLoadRuntime {
+    pub filename: PathBuf,
+    pub inner: String,
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum NewEmbedderErrorKind {
     // hf
@@ -367,6 +434,15 @@ pub enum NewEmbedderErrorKind {
     LoadModel(candle_core::Error),
     #[error("{0}")]
     CouldNotParseTemplate(String),
+    // onnx
+    #[error(transparent)]
+    OnnxLoadModel(OnnxLoadModel),
+    #[error(transparent)]
+    OnnxLoadRuntime(OnnxLoadRuntime),
+    #[error("the ONNX models are read from the directory set by the `--onnx-models-dir` option or the `MEILI_ONNX_MODELS_DIR` environment variable, which is not set")]
+    OnnxModelsDirNotSet,
+    #[error("the path {path} is not inside the ONNX models directory {models_dir}")]
+    OnnxPathOutsideModelsDir { path: PathBuf, models_dir: PathBuf },
 }
 
 pub struct PossibleEmbeddingMistakes {
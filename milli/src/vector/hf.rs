@@ -1,4 +1,6 @@
-use candle_core::Tensor;
+use std::path::PathBuf;
+
+use candle_core::{Device, Tensor};
 use candle_nn::VarBuilder;
 use candle_transformers::models::bert::{BertModel, Config, DTYPE};
 // FIXME: currently we'll be using the hub to retrieve model, in the future we might want to embed it into Meilisearch itself
@@ -70,36 +72,69 @@ impl std::fmt::Debug for Embedder {
     }
 }
 
-impl Embedder {
-    pub fn new(options: EmbedderOptions) -> std::result::Result<Self, NewEmbedderError> {
-        let device = match candle_core::Device::cuda_if_available(0) {
-            Ok(device) => device,
-            Err(error) => {
-                tracing::warn!("could not initialize CUDA device for Hugging Face embedder, defaulting to CPU: {}", error);
-                candle_core::Device::Cpu
+/// The files of a model of the Hugging Face Hub, downloaded to the local cache on first use.
+pub(super) struct ModelFiles {
+    pub config: PathBuf,
+    pub tokenizer: PathBuf,
+    weights: PathBuf,
+    weight_source: WeightSource,
+}
+
+impl ModelFiles {
+    pub fn fetch(model: &str, revision: Option<&str>) -> Result<Self, NewEmbedderError> {
+        let repo = match revision {
+            Some(revision) => {
+                Repo::with_revision(model.to_string(), RepoType::Model, revision.to_string())
             }
+            None => Repo::model(model.to_string()),
         };
-        let repo = match options.revision.clone() {
-            Some(revision) => Repo::with_revision(options.model.clone(), RepoType::Model, revision),
-            None => Repo::model(options.model.clone()),
-        };
-        let (config_filename, tokenizer_filename, weights_filename, weight_source) = {
-            let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
-            let api = api.repo(repo);
-            let config = api.get("config.json").map_err(NewEmbedderError::api_get)?;
-            let tokenizer = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
-            let (weights, source) = {
-                api.get("model.safetensors")
-                    .map(|filename| (filename, WeightSource::Safetensors))
-                    .or_else(|_| {
-                        api.get("pytorch_model.bin")
-                            .map(|filename| (filename, WeightSource::Pytorch))
-                    })
-                    .map_err(NewEmbedderError::api_get)?
-            };
-            (config, tokenizer, weights, source)
-        };
+        let api = Api::new().map_err(NewEmbedderError::new_api_fail)?;
+        let api = api.repo(repo);
+        let config = api.get("config.json").map_err(NewEmbedderError::api_get)?;
+        let tokenizer = api.get("tokenizer.json").map_err(NewEmbedderError::api_get)?;
+        let (weights, weight_source) = api
+            .get("model.safetensors")
+            .map(|filename| (filename, WeightSource::Safetensors))
+            .or_else(|_| {
+                api.get("pytorch_model.bin").map(|filename| (filename, WeightSource::Pytorch))
+            })
+            .map_err(NewEmbedderError::api_get)?;
+
+        Ok(Self { config, tokenizer, weights, weight_source })
+    }
 
+    pub fn var_builder(&self, device: &Device) -> Result<VarBuilder<'static>, NewEmbedderError> {
+        Ok(match self.weight_source {
+            WeightSource::Pytorch => VarBuilder::from_pth(&self.weights, DTYPE, device)
+                .map_err(NewEmbedderError::pytorch_weight)?,
+            WeightSource::Safetensors => unsafe {
+                VarBuilder::from_mmaped_safetensors(&[&self.weights], DTYPE, device)
+                    .map_err(NewEmbedderError::safetensor_weight)?
+            },
+        })
+    }
+}
+
+/// The first CUDA device if there is one, the CPU otherwise.
+pub(super) fn device() -> Device {
+    match Device::cuda_if_available(0) {
+        Ok(device) => device,
+        Err(error) => {
+            tracing::warn!(
+                "could not initialize CUDA device for Hugging Face model, defaulting to CPU: {}",
+                error
+            );
+            Device::Cpu
+        }
+    }
+}
+
+impl Embedder {
+    pub fn new(options: EmbedderOptions) -> std::result::Result<Self, NewEmbedderError> {
+        let device = device();
+        let files = ModelFiles::fetch(&options.model, options.revision.as_deref())?;
+
+        let config_filename = files.config.clone();
         let config = std::fs::read_to_string(&config_filename)
             .map_err(|inner| NewEmbedderError::open_config(config_filename.clone(), inner))?;
         let config: Config = serde_json::from_str(&config).map_err(|inner| {
@@ -110,17 +145,10 @@ impl Embedder {
                 inner,
             )
         })?;
-        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
-            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        let mut tokenizer = Tokenizer::from_file(&files.tokenizer)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(files.tokenizer.clone(), inner))?;
 
-        let vb = match weight_source {
-            WeightSource::Pytorch => VarBuilder::from_pth(&weights_filename, DTYPE, &device)
-                .map_err(NewEmbedderError::pytorch_weight)?,
-            WeightSource::Safetensors => unsafe {
-                VarBuilder::from_mmaped_safetensors(&[weights_filename], DTYPE, &device)
-                    .map_err(NewEmbedderError::safetensor_weight)?
-            },
-        };
+        let vb = files.var_builder(&device)?;
 
         let model = BertModel::load(vb, &config).map_err(NewEmbedderError::load_model)?;
 
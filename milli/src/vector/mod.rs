@@ -1,8 +1,16 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use arroy::distances::{
+    BinaryQuantizedCosine, BinaryQuantizedEuclidean, BinaryQuantizedManhattan, Cosine, DotProduct,
+    Euclidean, Manhattan,
+};
+use arroy::ItemId;
 use deserr::{DeserializeError, Deserr};
+use heed::{RoTxn, RwTxn};
 use ordered_float::OrderedFloat;
+use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
 use self::error::{EmbedError, NewEmbedderError};
@@ -15,9 +23,11 @@ pub mod json_template;
 pub mod manual;
 pub mod openai;
 pub mod parsed_vectors;
+pub mod rerank;
 pub mod settings;
 
 pub mod ollama;
+pub mod onnx;
 pub mod rest;
 
 pub use self::error::Error;
@@ -115,6 +125,8 @@ pub enum Embedder {
     Ollama(ollama::Embedder),
     /// An embedder based on making embedding queries against a generic JSON/REST embedding server.
     Rest(rest::Embedder),
+    /// An embedder based on running local ONNX models read from the disk.
+    Onnx(onnx::Embedder),
 }
 
 /// Configuration for an embedder.
@@ -124,7 +136,23 @@ pub struct EmbeddingConfig {
     pub embedder_options: EmbedderOptions,
     /// Document template
     pub prompt: PromptData,
-    // TODO: add metrics and anything needed
+    /// Distance used to compare the embeddings, cosine if unspecified
+    #[serde(default)]
+    pub distance: Option<Distance>,
+    /// How the embeddings are quantized in the arroy trees, full-precision if unspecified
+    #[serde(default)]
+    pub quantization: Option<Quantization>,
+    /// Whether the user-provided embeddings are kept in the documents to rescore the quantized ones
+    #[serde(default)]
+    pub rescore: Option<bool>,
+}
+
+impl EmbeddingConfig {
+    /// Whether the full-precision embeddings are kept in the documents and used to rescore
+    /// the nearest neighbors found in the quantized trees.
+    pub fn rescored(&self) -> bool {
+        self.quantization.is_some() && self.rescore.unwrap_or_default()
+    }
 }
 
 /// Map of embedder configurations.
@@ -194,6 +222,7 @@ pub enum EmbedderOptions {
     Ollama(ollama::EmbedderOptions),
     UserProvided(manual::EmbedderOptions),
     Rest(rest::EmbedderOptions),
+    Onnx(onnx::EmbedderOptions),
 }
 
 impl Default for EmbedderOptions {
@@ -204,7 +233,12 @@ impl Default for EmbedderOptions {
 
 impl Embedder {
     /// Spawns a new embedder built from its options.
-    pub fn new(options: EmbedderOptions) -> std::result::Result<Self, NewEmbedderError> {
+    ///
+    /// The ONNX embedders find their model and runtime with the `onnx_runtime` options.
+    pub fn new(
+        options: EmbedderOptions,
+        onnx_runtime: &onnx::RuntimeOptions,
+    ) -> std::result::Result<Self, NewEmbedderError> {
         Ok(match options {
             EmbedderOptions::HuggingFace(options) => Self::HuggingFace(hf::Embedder::new(options)?),
             EmbedderOptions::OpenAi(options) => Self::OpenAi(openai::Embedder::new(options)?),
@@ -215,6 +249,9 @@ impl Embedder {
             EmbedderOptions::Rest(options) => {
                 Self::Rest(rest::Embedder::new(options, rest::ConfigurationSource::User)?)
             }
+            EmbedderOptions::Onnx(options) => {
+                Self::Onnx(onnx::Embedder::new(options, onnx_runtime)?)
+            }
         })
     }
 
@@ -231,6 +268,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.embed(texts),
             Embedder::UserProvided(embedder) => embedder.embed(texts),
             Embedder::Rest(embedder) => embedder.embed(texts),
+            Embedder::Onnx(embedder) => embedder.embed(texts),
         }
     }
 
@@ -259,6 +297,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.embed_chunks(text_chunks, threads),
             Embedder::UserProvided(embedder) => embedder.embed_chunks(text_chunks),
             Embedder::Rest(embedder) => embedder.embed_chunks(text_chunks, threads),
+            Embedder::Onnx(embedder) => embedder.embed_chunks(text_chunks, threads),
         }
     }
 
@@ -270,6 +309,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.chunk_count_hint(),
             Embedder::UserProvided(_) => 1,
             Embedder::Rest(embedder) => embedder.chunk_count_hint(),
+            Embedder::Onnx(embedder) => embedder.chunk_count_hint(),
         }
     }
 
@@ -281,6 +321,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.prompt_count_in_chunk_hint(),
             Embedder::UserProvided(_) => 1,
             Embedder::Rest(embedder) => embedder.prompt_count_in_chunk_hint(),
+            Embedder::Onnx(embedder) => embedder.prompt_count_in_chunk_hint(),
         }
     }
 
@@ -292,6 +333,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.dimensions(),
             Embedder::UserProvided(embedder) => embedder.dimensions(),
             Embedder::Rest(embedder) => embedder.dimensions(),
+            Embedder::Onnx(embedder) => embedder.dimensions(),
         }
     }
 
@@ -303,6 +345,7 @@ impl Embedder {
             Embedder::Ollama(embedder) => embedder.distribution(),
             Embedder::UserProvided(embedder) => embedder.distribution(),
             Embedder::Rest(embedder) => embedder.distribution(),
+            Embedder::Onnx(embedder) => embedder.distribution(),
         }
     }
 
@@ -311,7 +354,8 @@ impl Embedder {
             Embedder::HuggingFace(_)
             | Embedder::OpenAi(_)
             | Embedder::Ollama(_)
-            | Embedder::Rest(_) => true,
+            | Embedder::Rest(_)
+            | Embedder::Onnx(_) => true,
             Embedder::UserProvided(_) => false,
         }
     }
@@ -449,3 +493,579 @@ pub fn arroy_db_range_for_embedder(embedder_id: u8) -> impl Iterator<Item = u16>
 
     (0..=u8::MAX).map(move |k| embedder_id | (k as u16))
 }
+
+/// The distance used to compare the embeddings of an embedder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum Distance {
+    /// The angle between the embeddings, regardless of their norms.
+    #[default]
+    Cosine,
+    /// The straight-line distance between the embeddings.
+    Euclidean,
+    /// The sum of the absolute differences between the components of the embeddings.
+    Manhattan,
+    /// The dot product of the embeddings, the greater the closer.
+    DotProduct,
+}
+
+impl std::fmt::Display for Distance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Distance::Cosine => "cosine",
+            Distance::Euclidean => "euclidean",
+            Distance::Manhattan => "manhattan",
+            Distance::DotProduct => "dotProduct",
+        };
+        f.write_str(s)
+    }
+}
+
+impl Distance {
+    /// Whether the trees of an embedder using this distance can store binary quantized embeddings.
+    pub fn supports_binary_quantization(&self) -> bool {
+        !matches!(self, Distance::DotProduct)
+    }
+
+    /// Computes the distance between two full-precision embeddings, as arroy would return it.
+    pub fn distance(&self, left: &[f32], right: &[f32]) -> f32 {
+        let pairs = left.iter().zip(right);
+        match self {
+            Distance::Cosine => {
+                let (mut dot, mut left_norm, mut right_norm) = (0.0, 0.0, 0.0);
+                for (l, r) in pairs {
+                    dot += l * r;
+                    left_norm += l * l;
+                    right_norm += r * r;
+                }
+                let norms = (left_norm * right_norm).sqrt();
+                let cosine = if norms > 0.0 { dot / norms } else { 0.0 };
+                (1.0 - cosine) / 2.0
+            }
+            Distance::Euclidean => pairs.map(|(l, r)| (l - r) * (l - r)).sum::<f32>().sqrt(),
+            Distance::Manhattan => pairs.map(|(l, r)| (l - r).abs()).sum(),
+            Distance::DotProduct => -pairs.map(|(l, r)| l * r).sum::<f32>(),
+        }
+    }
+
+    /// Turns a distance returned by arroy into a similarity between 0 and 1, 1 being the closest.
+    pub fn similarity(&self, distance: f32) -> f32 {
+        match self {
+            // arroy returns `(1 - cos) / 2`
+            Distance::Cosine => 1.0 - distance,
+            Distance::Euclidean | Distance::Manhattan => 1.0 / (1.0 + distance),
+            // arroy returns the opposite of the dot product, which we squash with a logistic function
+            Distance::DotProduct => 1.0 / (1.0 + distance.exp()),
+        }
+    }
+}
+
+/// How the embeddings of an embedder are quantized in its arroy trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, Deserr)]
+#[serde(rename_all = "camelCase")]
+#[deserr(rename_all = camelCase)]
+pub enum Quantization {
+    /// One bit per dimension, the sign of the component, compared with the binary quantized
+    /// distances of arroy.
+    Binary,
+    /// A signed byte per dimension, the components being scaled by the largest of them.
+    ///
+    /// The trees store the values of the bytes scaled back, compared with the full-precision
+    /// distances of arroy.
+    Int8,
+}
+
+impl std::fmt::Display for Quantization {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Quantization::Binary => "binary",
+            Quantization::Int8 => "int8",
+        };
+        f.write_str(s)
+    }
+}
+
+impl Quantization {
+    /// Returns the embedding as it is written in the trees.
+    ///
+    /// The binary quantization is done by arroy, the embedding is returned unchanged.
+    pub fn quantize<'a>(&self, embedding: &'a [f32]) -> Cow<'a, [f32]> {
+        match self {
+            Quantization::Binary => Cow::Borrowed(embedding),
+            Quantization::Int8 => {
+                let max = embedding.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+                if max == 0.0 || !max.is_finite() {
+                    return Cow::Borrowed(embedding);
+                }
+                let scale = max / i8::MAX as f32;
+                Cow::Owned(
+                    embedding
+                        .iter()
+                        .map(|x| (x / scale).round().clamp(-127.0, 127.0) * scale)
+                        .collect(),
+                )
+            }
+        }
+    }
+}
+
+/// Binds `$database` to the arroy database of the wrapper, typed with the distance
+/// and quantization of the wrapper, and evaluates `$body` with it.
+///
+/// The int8 quantized embeddings are stored in the full-precision trees.
+macro_rules! with_distance {
+    ($wrapper:expr, |$database:ident| $body:expr) => {
+        match ($wrapper.distance, $wrapper.quantization) {
+            (Distance::Cosine, Some(Quantization::Binary)) => {
+                let $database: arroy::Database<BinaryQuantizedCosine> =
+                    $wrapper.database.remap_data_type();
+                $body
+            }
+            (Distance::Cosine, _) => {
+                let $database: arroy::Database<Cosine> = $wrapper.database.remap_data_type();
+                $body
+            }
+            (Distance::Euclidean, Some(Quantization::Binary)) => {
+                let $database: arroy::Database<BinaryQuantizedEuclidean> =
+                    $wrapper.database.remap_data_type();
+                $body
+            }
+            (Distance::Euclidean, _) => {
+                let $database: arroy::Database<Euclidean> = $wrapper.database.remap_data_type();
+                $body
+            }
+            (Distance::Manhattan, Some(Quantization::Binary)) => {
+                let $database: arroy::Database<BinaryQuantizedManhattan> =
+                    $wrapper.database.remap_data_type();
+                $body
+            }
+            (Distance::Manhattan, _) => {
+                let $database: arroy::Database<Manhattan> = $wrapper.database.remap_data_type();
+                $body
+            }
+            // binary quantization is refused for the dot product when validating the settings
+            (Distance::DotProduct, _) => {
+                let $database: arroy::Database<DotProduct> = $wrapper.database.remap_data_type();
+                $body
+            }
+        }
+    };
+}
+
+/// The arroy trees of an embedder, read and written with the distance of the embedder.
+///
+/// The embeddings of a document are packed in the first trees of the embedder:
+/// a document without an embedding in a tree has no embedding in the next trees either.
+///
+/// When the embedder is quantized, the trees only store one bit (binary) or one byte (int8)
+/// of information per dimension and the embeddings read back from them are approximations.
+#[derive(Debug, Clone, Copy)]
+pub struct ArroyWrapper {
+    database: arroy::Database<Cosine>,
+    embedder_index: u8,
+    distance: Distance,
+    quantization: Option<Quantization>,
+}
+
+impl ArroyWrapper {
+    pub fn new(
+        database: arroy::Database<Cosine>,
+        embedder_index: u8,
+        distance: Distance,
+        quantization: Option<Quantization>,
+    ) -> Self {
+        Self { database, embedder_index, distance, quantization }
+    }
+
+    pub fn embedder_index(&self) -> u8 {
+        self.embedder_index
+    }
+
+    pub fn distance(&self) -> Distance {
+        self.distance
+    }
+
+    pub fn quantization(&self) -> Option<Quantization> {
+        self.quantization
+    }
+
+    /// Returns the embedding as it is written in the trees.
+    fn quantize<'a>(&self, embedding: &'a [f32]) -> Cow<'a, [f32]> {
+        match self.quantization {
+            Some(quantization) => quantization.quantize(embedding),
+            None => Cow::Borrowed(embedding),
+        }
+    }
+
+    /// Returns the nearest neighbors of the vector among the candidates, the closest first,
+    /// with the index of the embedding of the item that is near the vector.
+    ///
+    /// An item appears once for each of its embeddings that is among the nearest neighbors.
+    pub fn nns_by_vector(
+        &self,
+        rtxn: &RoTxn<'_>,
+        vector: &[f32],
+        limit: usize,
+        candidates: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, usize, f32)>, arroy::Error> {
+        with_distance!(self, |database| {
+            let mut results = Vec::new();
+            for (index, reader) in readers(rtxn, database, self.embedder_index).enumerate() {
+                let reader = reader?;
+                let mut query = reader.nns(limit);
+                if let Some(candidates) = candidates {
+                    query.candidates(candidates);
+                }
+                let nns = query.by_vector(rtxn, vector)?;
+                results.extend(nns.into_iter().map(|(item, distance)| (item, index, distance)));
+            }
+            results.sort_unstable_by_key(|(_, _, distance)| OrderedFloat(*distance));
+            Ok(results)
+        })
+    }
+
+    /// Returns the nearest neighbors of the embeddings of the item among the candidates,
+    /// the closest first.
+    pub fn nns_by_item(
+        &self,
+        rtxn: &RoTxn<'_>,
+        item: ItemId,
+        limit: usize,
+        candidates: Option<&RoaringBitmap>,
+    ) -> Result<Vec<(ItemId, f32)>, arroy::Error> {
+        with_distance!(self, |database| {
+            let mut results = Vec::new();
+            for reader in readers(rtxn, database, self.embedder_index) {
+                let reader = reader?;
+                let mut query = reader.nns(limit);
+                if let Some(candidates) = candidates {
+                    query.candidates(candidates);
+                }
+                match query.by_item(rtxn, item)? {
+                    Some(nns_by_item) => results.extend(nns_by_item),
+                    None => break,
+                }
+            }
+            results.sort_unstable_by_key(|(_, distance)| OrderedFloat(*distance));
+            Ok(results)
+        })
+    }
+
+    /// Returns all the embeddings of the item.
+    pub fn item_vectors(
+        &self,
+        rtxn: &RoTxn<'_>,
+        item: ItemId,
+    ) -> Result<Vec<Embedding>, arroy::Error> {
+        with_distance!(self, |database| {
+            let mut vectors = Vec::new();
+            for reader in readers(rtxn, database, self.embedder_index) {
+                match reader?.item_vector(rtxn, item)? {
+                    Some(vector) => vectors.push(vector),
+                    None => break,
+                }
+            }
+            Ok(vectors)
+        })
+    }
+
+    /// Adds the embeddings of an item that has no embedding yet.
+    pub fn add_items(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        dimension: usize,
+        item: ItemId,
+        embeddings: &Embeddings<f32>,
+    ) -> Result<(), arroy::Error> {
+        with_distance!(self, |database| {
+            for (embedding, writer) in
+                embeddings.iter().zip(writers(database, self.embedder_index, dimension))
+            {
+                writer.add_item(wtxn, item, &self.quantize(embedding))?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Adds an embedding to the embeddings of the item.
+    pub fn add_item(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        dimension: usize,
+        item: ItemId,
+        vector: &[f32],
+    ) -> Result<(), arroy::Error> {
+        with_distance!(self, |database| {
+            for writer in writers(database, self.embedder_index, dimension) {
+                if !writer.contains_item(wtxn, item)? {
+                    writer.add_item(wtxn, item, &self.quantize(vector))?;
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes all the embeddings of the item.
+    pub fn del_items(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        dimension: usize,
+        item: ItemId,
+    ) -> Result<(), arroy::Error> {
+        with_distance!(self, |database| {
+            for writer in writers(database, self.embedder_index, dimension) {
+                if !writer.del_item(wtxn, item)? {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes an embedding from the embeddings of the item.
+    pub fn del_item(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        dimension: usize,
+        item: ItemId,
+        vector: &[f32],
+    ) -> Result<(), arroy::Error> {
+        // the embeddings are compared as they were written in the trees
+        let vector = self.quantize(vector);
+        with_distance!(self, |database| {
+            let writers: Vec<_> = writers(database, self.embedder_index, dimension).collect();
+
+            let mut deleted_index = None;
+            for (index, writer) in writers.iter().enumerate() {
+                let Some(candidate) = writer.item_vector(wtxn, item)? else {
+                    break;
+                };
+                if candidate == *vector {
+                    writer.del_item(wtxn, item)?;
+                    deleted_index = Some(index);
+                }
+            }
+
+            // 🥲 enforce invariant: vectors are packed in the first writers.
+            if let Some(deleted_index) = deleted_index {
+                let mut last_index_with_a_vector = None;
+                for (index, writer) in writers.iter().enumerate().skip(deleted_index) {
+                    let Some(candidate) = writer.item_vector(wtxn, item)? else {
+                        break;
+                    };
+                    last_index_with_a_vector = Some((index, candidate));
+                }
+                if let Some((last_index, vector)) = last_index_with_a_vector {
+                    writers[last_index].del_item(wtxn, item)?;
+                    writers[deleted_index].add_item(wtxn, item, &vector)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Builds the trees that changed since they were last built.
+    pub fn build<R: rand::Rng + rand::SeedableRng>(
+        &self,
+        wtxn: &mut RwTxn<'_>,
+        rng: &mut R,
+        dimension: usize,
+    ) -> Result<(), arroy::Error> {
+        with_distance!(self, |database| {
+            for writer in writers(database, self.embedder_index, dimension) {
+                if writer.need_build(wtxn)? {
+                    writer.builder(rng).build(wtxn)?;
+                } else if writer.is_empty(wtxn)? {
+                    break;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Removes all the embeddings of the embedder.
+    pub fn clear(&self, wtxn: &mut RwTxn<'_>) -> Result<(), arroy::Error> {
+        with_distance!(self, |database| {
+            let mut trees = Vec::new();
+            for reader in readers(wtxn, database, self.embedder_index) {
+                let reader = reader?;
+                trees.push((reader.index(), reader.dimensions()));
+            }
+            for (index, dimensions) in trees {
+                arroy::Writer::new(database, index, dimensions).clear(wtxn)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Rebuilds the trees of the embedder with another distance or quantization,
+    /// keeping the embeddings.
+    ///
+    /// The embeddings of a tree are loaded in memory while the tree is rewritten.
+    /// Quantizing the trees loses the precision of the embeddings for good.
+    pub fn convert<R: rand::Rng + rand::SeedableRng>(
+        self,
+        wtxn: &mut RwTxn<'_>,
+        rng: &mut R,
+        distance: Distance,
+        quantization: Option<Quantization>,
+    ) -> Result<Self, arroy::Error> {
+        let new = Self { distance, quantization, ..self };
+        if self.distance == distance && self.quantization == quantization {
+            return Ok(new);
+        }
+
+        let trees = with_distance!(self, |database| {
+            let mut trees = Vec::new();
+            for reader in readers(wtxn, database, self.embedder_index) {
+                let reader = reader?;
+                trees.push((reader.index(), reader.dimensions()));
+            }
+            trees
+        });
+
+        for (index, dimensions) in trees {
+            let embeddings = with_distance!(self, |database| {
+                let writer = arroy::Writer::new(database, index, dimensions);
+                let reader = arroy::Reader::open(wtxn, index, database)?;
+                let mut embeddings = Vec::new();
+                for item in reader.item_ids() {
+                    if let Some(embedding) = reader.item_vector(wtxn, item)? {
+                        embeddings.push((item, embedding));
+                    }
+                }
+                drop(reader);
+                writer.clear(wtxn)?;
+                embeddings
+            });
+
+            with_distance!(new, |database| {
+                let writer = arroy::Writer::new(database, index, dimensions);
+                for (item, embedding) in embeddings {
+                    writer.add_item(wtxn, item, &new.quantize(&embedding))?;
+                }
+                writer.builder(rng).build(wtxn)?;
+            });
+        }
+
+        Ok(new)
+    }
+
+    /// Rewrites the trees of the embedder written by arroy 0.4, which the current version
+    /// of arroy can't read, and rebuilds them in the current format, keeping the embeddings.
+    ///
+    /// The embeddings of a tree are loaded in memory while the tree is rewritten.
+    pub fn upgrade_from_arroy_v0_4<R: rand::Rng + rand::SeedableRng>(
+        self,
+        wtxn: &mut RwTxn<'_>,
+        rng: &mut R,
+    ) -> crate::Result<()> {
+        for index in arroy_db_range_for_embedder(self.embedder_index) {
+            let Some((dimensions, embeddings)) =
+                take_arroy_v0_4_tree(wtxn, self.database, self.distance, index)?
+            else {
+                break;
+            };
+
+            with_distance!(self, |database| {
+                let writer = arroy::Writer::new(database, index, dimensions);
+                for (item, embedding) in embeddings {
+                    writer.add_item(wtxn, item, &embedding)?;
+                }
+                writer.builder(rng).build(wtxn)?;
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Reads all the embeddings of a tree written by arroy 0.4 and clears it,
+/// `None` when the tree is missing.
+///
+/// Arroy 0.4 had no quantization and named the cosine distance `Angular`.
+#[allow(clippy::type_complexity)]
+fn take_arroy_v0_4_tree(
+    wtxn: &mut RwTxn<'_>,
+    database: arroy::Database<Cosine>,
+    distance: Distance,
+    index: u16,
+) -> Result<Option<(usize, Vec<(ItemId, Embedding)>)>, arroy_v04::Error> {
+    use arroy_v04::distances::{Angular, DotProduct, Euclidean, Manhattan};
+
+    fn take<D: arroy_v04::Distance>(
+        wtxn: &mut RwTxn<'_>,
+        database: arroy_v04::Database<D>,
+        index: u16,
+    ) -> Result<Option<(usize, Vec<(ItemId, Embedding)>)>, arroy_v04::Error> {
+        let reader = match arroy_v04::Reader::open(wtxn, index, database) {
+            Ok(reader) => reader,
+            Err(arroy_v04::Error::MissingMetadata(_)) => return Ok(None),
+            Err(error) => return Err(error),
+        };
+        let dimensions = reader.dimensions();
+        let mut embeddings = Vec::new();
+        for item in reader.item_ids() {
+            if let Some(embedding) = reader.item_vector(wtxn, item)? {
+                embeddings.push((item, embedding));
+            }
+        }
+        drop(reader);
+        arroy_v04::Writer::new(database, index, dimensions).clear(wtxn)?;
+        Ok(Some((dimensions, embeddings)))
+    }
+
+    match distance {
+        Distance::Cosine => take::<Angular>(wtxn, database.remap_types(), index),
+        Distance::Euclidean => take::<Euclidean>(wtxn, database.remap_types(), index),
+        Distance::Manhattan => take::<Manhattan>(wtxn, database.remap_types(), index),
+        Distance::DotProduct => take::<DotProduct>(wtxn, database.remap_types(), index),
+    }
+}
+
+/// Opens the trees of the embedder, up to the first missing one.
+fn readers<'t, D: arroy::Distance>(
+    rtxn: &'t RoTxn<'t>,
+    database: arroy::Database<D>,
+    embedder_index: u8,
+) -> impl Iterator<Item = Result<arroy::Reader<'t, D>, arroy::Error>> + 't {
+    arroy_db_range_for_embedder(embedder_index).map_while(move |index| {
+        arroy::Reader::open(rtxn, index, database)
+            .map(Some)
+            .or_else(|error| match error {
+                arroy::Error::MissingMetadata(_) => Ok(None),
+                error => Err(error),
+            })
+            .transpose()
+    })
+}
+
+fn writers<D: arroy::Distance>(
+    database: arroy::Database<D>,
+    embedder_index: u8,
+    dimension: usize,
+) -> impl Iterator<Item = arroy::Writer<D>> {
+    arroy_db_range_for_embedder(embedder_index)
+        .map(move |index| arroy::Writer::new(database, index, dimension))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Quantization;
+
+    #[test]
+    fn int8_quantization() {
+        let embedding = [0.5, -1.0, 0.25, 0.0];
+        let quantized = Quantization::Int8.quantize(&embedding);
+        for (x, q) in embedding.iter().zip(quantized.iter()) {
+            assert!((x - q).abs() <= 0.5 / 127.0, "{x} was quantized to {q}");
+        }
+        assert_eq!(quantized[1], -1.0);
+
+        // the binary quantization is done by arroy
+        assert_eq!(&*Quantization::Binary.quantize(&embedding), &embedding);
+        // null embeddings can't be scaled
+        assert_eq!(&*Quantization::Int8.quantize(&[0.0, 0.0]), &[0.0, 0.0]);
+    }
+}
@@ -0,0 +1,373 @@
+//! Embeds the texts with a local ONNX model.
+//!
+//! The ONNX Runtime library is loaded when the first embedder is created, from the path in the
+//! [`RuntimeOptions`] or else from the library search path of the system.
+//! The models and their tokenizers are read from the models directory of the [`RuntimeOptions`],
+//! their paths in the settings are relative to this directory.
+
+use std::path::{Component, Path, PathBuf};
+
+use ndarray::{Array2, ArrayViewD, Axis, Ix2, Ix3};
+use ort::{GraphOptimizationLevel, Session};
+use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
+use tokenizers::{PaddingParams, Tokenizer, TruncationParams};
+
+use super::error::EmbedErrorKind;
+pub use super::error::{EmbedError, Error, NewEmbedderError};
+use super::{DistributionShift, Embedding, Embeddings};
+use crate::error::FaultSource;
+use crate::ThreadPoolNoAbort;
+
+/// How the embeddings of the tokens of a text are turned into the embedding of the text.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    Hash,
+    PartialEq,
+    Eq,
+    serde::Deserialize,
+    serde::Serialize,
+    deserr::Deserr,
+)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum Pooling {
+    /// The mean of the embeddings of the tokens, the padding excluded.
+    #[default]
+    Mean,
+    /// The embedding of the first token.
+    Cls,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct EmbedderOptions {
+    /// Path to the ONNX model file.
+    pub model: String,
+    /// Path to the `tokenizer.json` file, the one next to the model if unspecified.
+    pub tokenizer: Option<String>,
+    pub pooling: Pooling,
+    /// Whether the embeddings are scaled to a norm of 1.
+    pub normalize: bool,
+    pub distribution: Option<DistributionShift>,
+}
+
+/// Where the models and the ONNX Runtime library are found on the disk, set by the instance
+/// and common to all the ONNX embedders.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RuntimeOptions {
+    /// Directory of the models and their tokenizers, no ONNX embedder can be created without it.
+    pub models_dir: Option<PathBuf>,
+    /// Path to the ONNX Runtime library, searched in the library search path of the system
+    /// if unspecified.
+    pub runtime_path: Option<PathBuf>,
+}
+
+impl EmbedderOptions {
+    fn tokenizer_path(&self) -> PathBuf {
+        match &self.tokenizer {
+            Some(tokenizer) => PathBuf::from(tokenizer),
+            None => Path::new(&self.model).with_file_name("tokenizer.json"),
+        }
+    }
+}
+
+/// Whether the path is relative and never goes up to its parent directory,
+/// the only paths accepted for the model and the tokenizer.
+pub fn is_valid_model_path(path: &str) -> bool {
+    let path = Path::new(path);
+    path.components().next().is_some()
+        && path.components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn models_dir(runtime: &RuntimeOptions) -> std::result::Result<PathBuf, NewEmbedderError> {
+    let models_dir =
+        runtime.models_dir.clone().ok_or_else(NewEmbedderError::onnx_models_dir_not_set)?;
+    // a missing directory is reported when the model is opened
+    Ok(models_dir.canonicalize().unwrap_or(models_dir))
+}
+
+/// Resolves the path of a model or a tokenizer in the models directory.
+///
+/// The symbolic links are followed so that they can't escape the directory.
+fn resolve_model_path(
+    models_dir: &Path,
+    path: &Path,
+) -> std::result::Result<PathBuf, NewEmbedderError> {
+    let outside = || NewEmbedderError::onnx_path_outside_models_dir(path.into(), models_dir.into());
+    if !path.to_str().map_or(false, is_valid_model_path) {
+        return Err(outside());
+    }
+    // a missing file is reported when it is opened
+    let resolved = models_dir.join(path);
+    match resolved.canonicalize() {
+        Ok(canonical) if canonical.starts_with(models_dir) => Ok(canonical),
+        Ok(_) => Err(outside()),
+        Err(_) => Ok(resolved),
+    }
+}
+
+/// Checks that the ONNX Runtime library can be loaded, as the `ort` crate
+/// panics when it can't load it.
+fn check_runtime(runtime: &RuntimeOptions) -> std::result::Result<PathBuf, NewEmbedderError> {
+    let runtime_filename = runtime
+        .runtime_path
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(libloading::library_filename("onnxruntime")));
+    // SAFETY: loading the ONNX Runtime library doesn't run any initialization routine.
+    match unsafe { libloading::Library::new(&runtime_filename) } {
+        Ok(_) => Ok(runtime_filename),
+        Err(error) => Err(NewEmbedderError::onnx_load_runtime(runtime_filename, error.to_string())),
+    }
+}
+
+/// Perform embedding of documents and queries with a local ONNX model.
+///
+/// The model and its tokenizer are read from the disk and never fetched from the network.
+pub struct Embedder {
+    session: Session,
+    tokenizer: Tokenizer,
+    /// Whether the model expects the `token_type_ids` input of the BERT-like models.
+    token_type_ids: bool,
+    options: EmbedderOptions,
+    dimensions: usize,
+}
+
+impl std::fmt::Debug for Embedder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Embedder")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl Embedder {
+    pub fn new(
+        options: EmbedderOptions,
+        runtime: &RuntimeOptions,
+    ) -> std::result::Result<Self, NewEmbedderError> {
+        let models_dir = models_dir(runtime)?;
+        let model_filename = resolve_model_path(&models_dir, Path::new(&options.model))?;
+        let tokenizer_filename = resolve_model_path(&models_dir, &options.tokenizer_path())?;
+
+        let runtime_filename = check_runtime(runtime)?;
+        // texts are embedded in parallel in the indexing thread pool, so that a single thread runs the model
+        let session = std::panic::catch_unwind(|| {
+            // only the path given when the library is loaded for the first time is used
+            ort::init_from(runtime_filename.display().to_string())
+                .commit()
+                .and_then(|_| Session::builder())
+                .and_then(|builder| builder.with_optimization_level(GraphOptimizationLevel::Level3))
+                .and_then(|builder| builder.with_intra_threads(1))
+                .and_then(|builder| builder.commit_from_file(&model_filename))
+        })
+        // the library may still be incompatible with the version expected by `ort`
+        .map_err(|panic| {
+            let message = panic
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| panic.downcast_ref::<&str>().map(|message| message.to_string()))
+                .unwrap_or_default();
+            NewEmbedderError::onnx_load_runtime(runtime_filename, message)
+        })?
+        .map_err(|inner| NewEmbedderError::onnx_load_model(model_filename, inner))?;
+        let token_type_ids = session.inputs.iter().any(|input| input.name == "token_type_ids");
+
+        let mut tokenizer = Tokenizer::from_file(&tokenizer_filename)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename.clone(), inner))?;
+
+        if let Some(pp) = tokenizer.get_padding_mut() {
+            pp.strategy = tokenizers::PaddingStrategy::BatchLongest
+        } else {
+            let pp = PaddingParams {
+                strategy: tokenizers::PaddingStrategy::BatchLongest,
+                ..Default::default()
+            };
+            tokenizer.with_padding(Some(pp));
+        }
+        if tokenizer.get_truncation().is_none() {
+            tokenizer
+                .with_truncation(Some(TruncationParams { max_length: 512, ..Default::default() }))
+                .map_err(|inner| NewEmbedderError::open_tokenizer(tokenizer_filename, inner))?;
+        }
+
+        let mut this = Self { session, tokenizer, token_type_ids, options, dimensions: 0 };
+
+        let embeddings = this
+            .embed(vec!["test".into()])
+            .map_err(NewEmbedderError::could_not_determine_dimension)?;
+        this.dimensions = embeddings.first().unwrap().dimension();
+
+        Ok(this)
+    }
+
+    pub fn embed(
+        &self,
+        texts: Vec<String>,
+    ) -> std::result::Result<Vec<Embeddings<f32>>, EmbedError> {
+        let encodings = self.tokenizer.encode_batch(texts, true).map_err(EmbedError::tokenize)?;
+
+        // the encodings are padded to the longest one
+        let n_tokens = encodings.first().map_or(0, |encoding| encoding.len());
+        let mut input_ids = Array2::<i64>::zeros((encodings.len(), n_tokens));
+        let mut attention_mask = Array2::<i64>::zeros((encodings.len(), n_tokens));
+        for (i, encoding) in encodings.iter().enumerate() {
+            for (j, (&id, &mask)) in
+                encoding.get_ids().iter().zip(encoding.get_attention_mask()).enumerate()
+            {
+                input_ids[[i, j]] = id.into();
+                attention_mask[[i, j]] = mask.into();
+            }
+        }
+
+        let inputs = if self.token_type_ids {
+            let token_type_ids = Array2::<i64>::zeros(input_ids.raw_dim());
+            ort::inputs![
+                "input_ids" => input_ids,
+                "attention_mask" => attention_mask.clone(),
+                "token_type_ids" => token_type_ids,
+            ]
+        } else {
+            ort::inputs!["input_ids" => input_ids, "attention_mask" => attention_mask.clone()]
+        }
+        .map_err(EmbedError::onnx_run)?;
+
+        let outputs = self.session.run(inputs).map_err(EmbedError::onnx_run)?;
+        let output_name = self.session.outputs[0].name.as_str();
+        let output =
+            outputs[output_name].try_extract_tensor::<f32>().map_err(EmbedError::onnx_run)?;
+
+        let embeddings = self.pool(output, &attention_mask)?;
+        Ok(embeddings.into_iter().map(Embeddings::from_single_embedding).collect())
+    }
+
+    /// Turns the output of the model into one embedding per text.
+    ///
+    /// The output is either the embeddings of the tokens of the texts, or the embeddings of the texts
+    /// when the pooling is part of the model.
+    fn pool(
+        &self,
+        output: ArrayViewD<'_, f32>,
+        attention_mask: &Array2<i64>,
+    ) -> std::result::Result<Vec<Embedding>, EmbedError> {
+        let mut embeddings: Vec<Embedding> = match output.ndim() {
+            2 => {
+                let output = output.into_dimensionality::<Ix2>().unwrap();
+                output.outer_iter().map(|embedding| embedding.to_vec()).collect()
+            }
+            3 => {
+                let output = output.into_dimensionality::<Ix3>().unwrap();
+                output
+                    .outer_iter()
+                    .zip(attention_mask.outer_iter())
+                    .map(|(tokens, mask)| match self.options.pooling {
+                        Pooling::Cls => tokens.index_axis(Axis(0), 0).to_vec(),
+                        Pooling::Mean => {
+                            let mut sum = vec![0.0; tokens.len_of(Axis(1))];
+                            let mut count = 0.0;
+                            for (token, _) in
+                                tokens.outer_iter().zip(mask.iter()).filter(|(_, &kept)| kept != 0)
+                            {
+                                sum.iter_mut().zip(token.iter()).for_each(|(sum, x)| *sum += x);
+                                count += 1.0;
+                            }
+                            if count > 0.0 {
+                                sum.iter_mut().for_each(|x| *x /= count);
+                            }
+                            sum
+                        }
+                    })
+                    .collect()
+            }
+            _ => return Err(EmbedError::onnx_output_shape(output.shape().to_vec())),
+        };
+
+        if self.options.normalize {
+            for embedding in &mut embeddings {
+                let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+                if norm > 0.0 {
+                    embedding.iter_mut().for_each(|x| *x /= norm);
+                }
+            }
+        }
+
+        Ok(embeddings)
+    }
+
+    pub fn embed_chunks(
+        &self,
+        text_chunks: Vec<Vec<String>>,
+        threads: &ThreadPoolNoAbort,
+    ) -> std::result::Result<Vec<Vec<Embeddings<f32>>>, EmbedError> {
+        threads
+            .install(move || {
+                text_chunks.into_par_iter().map(move |chunk| self.embed(chunk)).collect()
+            })
+            .map_err(|error| EmbedError {
+                kind: EmbedErrorKind::PanicInThreadPool(error),
+                fault: FaultSource::Bug,
+            })?
+    }
+
+    pub fn chunk_count_hint(&self) -> usize {
+        std::thread::available_parallelism().map(|x| x.get()).unwrap_or(8)
+    }
+
+    pub fn prompt_count_in_chunk_hint(&self) -> usize {
+        8
+    }
+
+    pub fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+
+    pub fn distribution(&self) -> Option<DistributionShift> {
+        self.options.distribution
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::vector::error::NewEmbedderErrorKind;
+
+    #[test]
+    fn model_paths() {
+        assert!(is_valid_model_path("model.onnx"));
+        assert!(is_valid_model_path("e5/model.onnx"));
+        assert!(!is_valid_model_path(""));
+        assert!(!is_valid_model_path("/models/model.onnx"));
+        assert!(!is_valid_model_path("../model.onnx"));
+        assert!(!is_valid_model_path("e5/../../model.onnx"));
+        assert!(!is_valid_model_path("./model.onnx"));
+
+        let models_dir = tempfile::tempdir().unwrap();
+        let models_dir = models_dir.path().canonicalize().unwrap();
+        let path = resolve_model_path(&models_dir, Path::new("e5/model.onnx")).unwrap();
+        assert_eq!(path, models_dir.join("e5/model.onnx"));
+        let error = resolve_model_path(&models_dir, Path::new("../model.onnx")).unwrap_err();
+        assert!(matches!(error.kind, NewEmbedderErrorKind::OnnxPathOutsideModelsDir { .. }));
+    }
+
+    #[test]
+    fn missing_runtime_is_an_error() {
+        let models_dir = tempfile::tempdir().unwrap();
+        let runtime = RuntimeOptions {
+            models_dir: Some(models_dir.path().to_path_buf()),
+            runtime_path: Some(models_dir.path().join("libonnxruntime.so")),
+        };
+
+        let options = EmbedderOptions {
+            model: "model.onnx".to_string(),
+            tokenizer: None,
+            pooling: Pooling::Mean,
+            normalize: false,
+            distribution: None,
+        };
+        let error = Embedder::new(options, &runtime).unwrap_err();
+        assert!(matches!(error.kind, NewEmbedderErrorKind::OnnxLoadRuntime(_)));
+    }
+}
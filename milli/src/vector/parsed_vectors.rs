@@ -160,6 +160,8 @@ impl ParsedVectorsDiff {
             VectorState::Manual => VectorState::Manual,
             // generated is still generated
             VectorState::Generated => VectorState::Generated,
+            // the user-provided embeddings of rescored embedders are kept inline in the documents
+            VectorState::Inline(ref vectors) if !vectors.must_regenerate() => VectorState::Manual,
             // weird case that shouldn't happen were the previous docs version is inline,
             // but it was removed in the new version
             // Since it is not in the new version, we switch to generated
@@ -0,0 +1,415 @@
+//! Rerankers give a relevancy score to the texts of the first hits of a search with respect to its query,
+//! so that the hits can be reordered by a model comparing the query to each document.
+
+use std::collections::BTreeMap;
+
+use candle_core::{Device, Tensor};
+use candle_nn::{Linear, Module as _, VarBuilder};
+use candle_transformers::models::bert::{BertModel, Config};
+use serde::{Deserialize, Serialize};
+use tokenizers::{Tokenizer, TruncationParams};
+
+use super::error::{EmbedError, NewEmbedderError};
+use super::hf::ModelFiles;
+use super::json_template::ValueTemplate;
+use super::rest::{self, ConfigurationSource, REPEAT_PLACEHOLDER};
+use super::REQUEST_PARALLELISM;
+use crate::prompt::PromptData;
+use crate::TimeBudget;
+
+const QUERY_PLACEHOLDER: &str = "{{query}}";
+const SCORE_PLACEHOLDER: &str = "{{score}}";
+const INDEX_PLACEHOLDER: &str = "{{index}}";
+
+/// The configuration of a reranker, as stored in the index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RerankerConfig {
+    pub reranker_options: RerankerOptions,
+    /// The template rendering the text of the documents that is compared to the query.
+    pub prompt: PromptData,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RerankerOptions {
+    HuggingFace(HfRerankerOptions),
+    Rest(RestRerankerOptions),
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HfRerankerOptions {
+    pub model: String,
+    pub revision: Option<String>,
+}
+
+impl HfRerankerOptions {
+    pub fn new() -> Self {
+        Self { model: "cross-encoder/ms-marco-MiniLM-L-6-v2".to_string(), revision: None }
+    }
+}
+
+impl Default for HfRerankerOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestRerankerOptions {
+    pub api_key: Option<String>,
+    pub url: String,
+    pub request: serde_json::Value,
+    pub response: serde_json::Value,
+    pub headers: BTreeMap<String, String>,
+}
+
+impl std::hash::Hash for RestRerankerOptions {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.api_key.hash(state);
+        self.url.hash(state);
+        // skip hashing the request and response, as the rest embedder does
+    }
+}
+
+/// Gives a relevancy score to texts with respect to a query.
+#[derive(Debug)]
+pub enum Reranker {
+    /// A cross-encoder of the Hugging Face Hub, running locally.
+    HuggingFace(HfReranker),
+    /// A remote reranking API.
+    Rest(RestReranker),
+}
+
+impl Reranker {
+    pub fn new(options: RerankerOptions) -> Result<Self, NewEmbedderError> {
+        Ok(match options {
+            RerankerOptions::HuggingFace(options) => Self::HuggingFace(HfReranker::new(options)?),
+            RerankerOptions::Rest(options) => Self::Rest(RestReranker::new(options)?),
+        })
+    }
+
+    /// Returns the score of each of the texts with respect to the query, in the order of the texts.
+    ///
+    /// The higher the score, the more relevant the text. When the time budget is exceeded,
+    /// the texts are no longer scored and only the scores of the first texts are returned.
+    pub fn rerank(
+        &self,
+        query: &str,
+        texts: Vec<String>,
+        time_budget: &TimeBudget,
+    ) -> Result<Vec<f32>, EmbedError> {
+        if texts.is_empty() || time_budget.exceeded() {
+            return Ok(Vec::new());
+        }
+        match self {
+            Reranker::HuggingFace(reranker) => reranker.rerank(query, texts, time_budget),
+            Reranker::Rest(reranker) => reranker.rerank(query, &texts, time_budget),
+        }
+    }
+}
+
+/// A BERT model with a sequence classification head, scoring the pairs of a query and a text.
+pub struct HfReranker {
+    model: BertModel,
+    pooler: Linear,
+    classifier: Linear,
+    tokenizer: Tokenizer,
+    options: HfRerankerOptions,
+}
+
+impl std::fmt::Debug for HfReranker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HfReranker")
+            .field("model", &self.options.model)
+            .field("tokenizer", &self.tokenizer)
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+/// The part of the configuration of the model that the classification head needs.
+#[derive(Deserialize)]
+struct ClassifierConfig {
+    hidden_size: usize,
+}
+
+impl HfReranker {
+    pub fn new(options: HfRerankerOptions) -> Result<Self, NewEmbedderError> {
+        let device = super::hf::device();
+        let files = ModelFiles::fetch(&options.model, options.revision.as_deref())?;
+
+        let config_filename = files.config.clone();
+        let config_text = std::fs::read_to_string(&config_filename)
+            .map_err(|inner| NewEmbedderError::open_config(config_filename.clone(), inner))?;
+        let (config, classifier_config) = serde_json::from_str::<Config>(&config_text)
+            .and_then(|config| {
+                Ok((config, serde_json::from_str::<ClassifierConfig>(&config_text)?))
+            })
+            .map_err(|inner| {
+                NewEmbedderError::deserialize_config(
+                    options.model.clone(),
+                    config_text.clone(),
+                    config_filename,
+                    inner,
+                )
+            })?;
+
+        let mut tokenizer = Tokenizer::from_file(&files.tokenizer)
+            .map_err(|inner| NewEmbedderError::open_tokenizer(files.tokenizer.clone(), inner))?;
+        // the pairs are scored one at a time and must fit in the model
+        tokenizer.with_padding(None);
+        tokenizer
+            .with_truncation(Some(TruncationParams { max_length: 512, ..Default::default() }))
+            .map_err(|inner| NewEmbedderError::open_tokenizer(files.tokenizer.clone(), inner))?;
+
+        let vb = files.var_builder(&device)?;
+        let (pooler, classifier) =
+            load_classification_head(vb.clone(), classifier_config.hidden_size)
+                .map_err(NewEmbedderError::load_model)?;
+        let model = BertModel::load(vb, &config).map_err(NewEmbedderError::load_model)?;
+
+        Ok(Self { model, pooler, classifier, tokenizer, options })
+    }
+
+    fn rerank(
+        &self,
+        query: &str,
+        texts: Vec<String>,
+        time_budget: &TimeBudget,
+    ) -> Result<Vec<f32>, EmbedError> {
+        let mut scores = Vec::with_capacity(texts.len());
+        for text in texts {
+            if !scores.is_empty() && time_budget.exceeded() {
+                break;
+            }
+            scores.push(self.score(query, text)?);
+        }
+        Ok(scores)
+    }
+
+    fn score(&self, query: &str, text: String) -> Result<f32, EmbedError> {
+        let device: &Device = &self.model.device;
+        let encoding =
+            self.tokenizer.encode((query.to_string(), text), true).map_err(EmbedError::tokenize)?;
+        let token_ids = Tensor::new(encoding.get_ids(), device)
+            .and_then(|tensor| tensor.unsqueeze(0))
+            .map_err(EmbedError::tensor_shape)?;
+        let token_type_ids = Tensor::new(encoding.get_type_ids(), device)
+            .and_then(|tensor| tensor.unsqueeze(0))
+            .map_err(EmbedError::tensor_shape)?;
+
+        let hidden_states =
+            self.model.forward(&token_ids, &token_type_ids).map_err(EmbedError::model_forward)?;
+        // the classification head reads the hidden state of the `[CLS]` token
+        let cls = hidden_states
+            .narrow(1, 0, 1)
+            .and_then(|cls| cls.squeeze(1))
+            .map_err(EmbedError::tensor_shape)?;
+        let logits = self
+            .pooler
+            .forward(&cls)
+            .and_then(|pooled| pooled.tanh())
+            .and_then(|pooled| self.classifier.forward(&pooled))
+            .map_err(EmbedError::model_forward)?;
+        let logit = logits
+            .flatten_all()
+            .and_then(|logits| logits.to_vec1::<f32>())
+            .map_err(EmbedError::tensor_value)?;
+
+        Ok(sigmoid(logit.first().copied().unwrap_or_default()))
+    }
+}
+
+/// Loads the pooler and the single logit classifier of a `BertForSequenceClassification` model.
+fn load_classification_head(
+    vb: VarBuilder,
+    hidden_size: usize,
+) -> candle_core::Result<(Linear, Linear)> {
+    let pooler = candle_nn::linear(hidden_size, hidden_size, vb.pp("pooler.dense"))
+        .or_else(|_| candle_nn::linear(hidden_size, hidden_size, vb.pp("bert.pooler.dense")))?;
+    let classifier = candle_nn::linear(hidden_size, 1, vb.pp("classifier"))?;
+    Ok((pooler, classifier))
+}
+
+fn sigmoid(x: f32) -> f32 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// A remote reranker, called with a request in which the query and the texts are injected.
+///
+/// The scores are read in the order of the texts, unless the response also contains the
+/// index of the text of each score.
+#[derive(Debug)]
+pub struct RestReranker {
+    client: ureq::Agent,
+    bearer: Option<String>,
+    headers: BTreeMap<String, String>,
+    url: String,
+    request: rest::Request,
+    scores: ValueTemplate,
+    indices: Option<ValueTemplate>,
+}
+
+impl RestReranker {
+    pub fn new(options: RestRerankerOptions) -> Result<Self, NewEmbedderError> {
+        let bearer = options.api_key.as_deref().map(|api_key| format!("Bearer {api_key}"));
+
+        let client = ureq::AgentBuilder::new()
+            .max_idle_connections(REQUEST_PARALLELISM)
+            .max_idle_connections_per_host(REQUEST_PARALLELISM)
+            .build();
+
+        let (request, scores, indices) = parse_templates(options.request, options.response)?;
+
+        Ok(Self {
+            client,
+            bearer,
+            headers: options.headers,
+            url: options.url,
+            request,
+            scores,
+            indices,
+        })
+    }
+
+    fn rerank(
+        &self,
+        query: &str,
+        texts: &[String],
+        time_budget: &TimeBudget,
+    ) -> Result<Vec<f32>, EmbedError> {
+        if self.request.has_array_value() {
+            self.rerank_batch(query, texts)
+        } else {
+            let mut scores = Vec::with_capacity(texts.len());
+            for text in texts {
+                if !scores.is_empty() && time_budget.exceeded() {
+                    break;
+                }
+                scores.extend(self.rerank_batch(query, std::slice::from_ref(text))?);
+            }
+            Ok(scores)
+        }
+    }
+
+    fn rerank_batch(&self, query: &str, texts: &[String]) -> Result<Vec<f32>, EmbedError> {
+        let mut body = self.request.inject_texts(texts);
+        inject_query(&mut body, query);
+
+        let response = rest::send(
+            &self.client,
+            &self.url,
+            self.bearer.as_deref(),
+            &self.headers,
+            &body,
+            ConfigurationSource::User,
+        )?;
+        let response: serde_json::Value =
+            response.into_json().map_err(EmbedError::rest_response_deserialization)?;
+
+        let scores: Vec<f32> = self.scores.extract(response.clone()).map_err(|error| {
+            EmbedError::rest_extraction_error(error.error_message(
+                "response",
+                SCORE_PLACEHOLDER,
+                "a number",
+            ))
+        })?;
+
+        let scores = match &self.indices {
+            Some(indices) => {
+                let indices: Vec<usize> = indices.extract(response).map_err(|error| {
+                    EmbedError::rest_extraction_error(error.error_message(
+                        "response",
+                        INDEX_PLACEHOLDER,
+                        "an index in the texts of the request",
+                    ))
+                })?;
+                let mut ordered = vec![None; texts.len()];
+                for (index, score) in indices.into_iter().zip(scores) {
+                    if let Some(ordered) = ordered.get_mut(index) {
+                        *ordered = Some(score);
+                    }
+                }
+                let count = ordered.iter().flatten().count();
+                ordered
+                    .into_iter()
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(|| EmbedError::rerank_response_score_count(texts.len(), count))?
+            }
+            None => scores,
+        };
+
+        if scores.len() != texts.len() {
+            return Err(EmbedError::rerank_response_score_count(texts.len(), scores.len()));
+        }
+
+        Ok(scores)
+    }
+}
+
+/// Checks the request and response templates of a remote reranker.
+pub fn validate_templates(
+    request: serde_json::Value,
+    response: serde_json::Value,
+) -> Result<(), NewEmbedderError> {
+    parse_templates(request, response).map(|_| ())
+}
+
+fn parse_templates(
+    request: serde_json::Value,
+    response: serde_json::Value,
+) -> Result<(rest::Request, ValueTemplate, Option<ValueTemplate>), NewEmbedderError> {
+    if !contains_string(&request, QUERY_PLACEHOLDER) {
+        return Err(NewEmbedderError::rest_could_not_parse_template(format!(
+            "in `request`: the request must contain the `{QUERY_PLACEHOLDER}` placeholder"
+        )));
+    }
+    let request = rest::Request::new(request)?;
+
+    let template = |placeholder: &str| {
+        ValueTemplate::new(response.clone(), placeholder, REPEAT_PLACEHOLDER).map_err(|error| {
+            NewEmbedderError::rest_could_not_parse_template(error.error_message(
+                "response",
+                placeholder,
+                REPEAT_PLACEHOLDER,
+            ))
+        })
+    };
+    let scores = template(SCORE_PLACEHOLDER)?;
+    let indices = if contains_string(&response, INDEX_PLACEHOLDER) {
+        Some(template(INDEX_PLACEHOLDER)?)
+    } else {
+        None
+    };
+
+    match (scores.has_array_value(), request.has_array_value()) {
+        (true, true) | (false, false) => Ok((request, scores, indices)),
+        (true, false) => Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has multiple scores, but `request` has only one text to score".to_string())),
+        (false, true) => Err(NewEmbedderError::rest_could_not_parse_template("in `response`: `response` has a single score, but `request` has multiple texts to score".to_string())),
+    }
+}
+
+fn contains_string(value: &serde_json::Value, string: &str) -> bool {
+    match value {
+        serde_json::Value::String(s) => s == string,
+        serde_json::Value::Array(values) => {
+            values.iter().any(|value| contains_string(value, string))
+        }
+        serde_json::Value::Object(map) => map.values().any(|value| contains_string(value, string)),
+        _ => false,
+    }
+}
+
+fn inject_query(value: &mut serde_json::Value, query: &str) {
+    match value {
+        serde_json::Value::String(s) if s == QUERY_PLACEHOLDER => {
+            *value = serde_json::Value::String(query.to_string())
+        }
+        serde_json::Value::Array(values) => {
+            values.iter_mut().for_each(|value| inject_query(value, query))
+        }
+        serde_json::Value::Object(map) => {
+            map.values_mut().for_each(|value| inject_query(value, query))
+        }
+        _ => (),
+    }
+}
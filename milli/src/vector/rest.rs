@@ -222,27 +222,41 @@ fn embed<S>(
 where
     S: Serialize,
 {
-    let request = data.client.post(&data.url);
-    let request = if let Some(bearer) = &data.bearer {
-        request.set("Authorization", bearer)
-    } else {
-        request
-    };
+    let body = data.request.inject_texts(inputs);
+    let response = send(
+        &data.client,
+        &data.url,
+        data.bearer.as_deref(),
+        &data.headers,
+        &body,
+        data.configuration_source,
+    )?;
+    response_to_embedding(response, data, expected_count, expected_dimension)
+}
+
+/// Posts the body to the url, retrying when the failure could be transient.
+pub(super) fn send(
+    client: &ureq::Agent,
+    url: &str,
+    bearer: Option<&str>,
+    headers: &BTreeMap<String, String>,
+    body: &serde_json::Value,
+    configuration_source: ConfigurationSource,
+) -> Result<ureq::Response, EmbedError> {
+    let request = client.post(url);
+    let request =
+        if let Some(bearer) = bearer { request.set("Authorization", bearer) } else { request };
     let mut request = request.set("Content-Type", "application/json");
-    for (header, value) in &data.headers {
+    for (header, value) in headers {
         request = request.set(header.as_str(), value.as_str());
     }
 
-    let body = data.request.inject_texts(inputs);
-
     for attempt in 0..10 {
-        let response = request.clone().send_json(&body);
-        let result = check_response(response, data.configuration_source);
+        let response = request.clone().send_json(body);
+        let result = check_response(response, configuration_source);
 
         let retry_duration = match result {
-            Ok(response) => {
-                return response_to_embedding(response, data, expected_count, expected_dimension)
-            }
+            Ok(response) => return Ok(response),
             Err(retry) => {
                 tracing::warn!("Failed: {}", retry.error);
                 retry.into_duration(attempt)
@@ -259,11 +273,8 @@ where
         std::thread::sleep(retry_duration);
     }
 
-    let response = request.send_json(&body);
-    let result = check_response(response, data.configuration_source);
-    result.map_err(Retry::into_error).and_then(|response| {
-        response_to_embedding(response, data, expected_count, expected_dimension)
-    })
+    let response = request.send_json(body);
+    check_response(response, configuration_source).map_err(Retry::into_error)
 }
 
 fn check_response(
@@ -351,6 +362,11 @@ impl Request {
         Ok(Self { template })
     }
 
+    /// Whether the request sends several texts at once.
+    pub fn has_array_value(&self) -> bool {
+        self.template.has_array_value()
+    }
+
     fn input_type(&self) -> InputType {
         if self.template.has_array_value() {
             InputType::TextArray
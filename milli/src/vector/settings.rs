@@ -5,8 +5,9 @@ use deserr::Deserr;
 use roaring::RoaringBitmap;
 use serde::{Deserialize, Serialize};
 
-use super::{ollama, openai, DistributionShift};
-use crate::prompt::{default_max_bytes, PromptData};
+use super::rerank::{HfRerankerOptions, RerankerConfig, RerankerOptions, RestRerankerOptions};
+use super::{ollama, onnx, openai, Distance, DistributionShift, Quantization};
+use crate::prompt::{default_max_bytes, Chunking, PromptData};
 use crate::update::Setting;
 use crate::vector::EmbeddingConfig;
 use crate::UserError;
@@ -51,6 +52,29 @@ pub struct EmbeddingSettings {
     #[serde(default, skip_serializing_if = "Setting::is_not_set")]
     #[deserr(default)]
     pub distribution: Setting<DistributionShift>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub distance: Setting<Distance>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub quantization: Setting<Quantization>,
+    /// Toggling it does not reindex the documents: the full-precision embeddings of the documents
+    /// indexed beforehand are not kept and must be sent again to be used for rescoring.
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub rescore: Setting<bool>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub chunking: Setting<Chunking>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub tokenizer: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub pooling: Setting<onnx::Pooling>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub normalize: Setting<bool>,
 }
 
 pub fn check_unset<T>(
@@ -96,12 +120,18 @@ pub enum EmbedderAction {
 
 pub struct WriteBackToDocuments {
     pub embedder_id: u8,
+    pub distance: Distance,
+    pub quantization: Option<Quantization>,
     pub user_provided: RoaringBitmap,
 }
 
 impl SettingsDiff {
-    pub fn from_settings(old: EmbeddingSettings, new: Setting<EmbeddingSettings>) -> Self {
-        match new {
+    pub fn from_settings(
+        embedder_name: &str,
+        old: EmbeddingSettings,
+        new: Setting<EmbeddingSettings>,
+    ) -> Result<Self, UserError> {
+        let ret = match new {
             Setting::Set(new) => {
                 let EmbeddingSettings {
                     mut source,
@@ -116,6 +146,13 @@ impl SettingsDiff {
                     mut distribution,
                     mut headers,
                     mut document_template_max_bytes,
+                    mut distance,
+                    mut quantization,
+                    mut rescore,
+                    mut chunking,
+                    mut tokenizer,
+                    mut pooling,
+                    mut normalize,
                 } = old;
 
                 let EmbeddingSettings {
@@ -131,6 +168,13 @@ impl SettingsDiff {
                     distribution: new_distribution,
                     headers: new_headers,
                     document_template_max_bytes: new_document_template_max_bytes,
+                    distance: new_distance,
+                    quantization: new_quantization,
+                    rescore: new_rescore,
+                    chunking: new_chunking,
+                    tokenizer: new_tokenizer,
+                    pooling: new_pooling,
+                    normalize: new_normalize,
                 } = new;
 
                 let mut reindex_action = None;
@@ -150,6 +194,10 @@ impl SettingsDiff {
                         &mut document_template,
                         &mut document_template_max_bytes,
                         &mut headers,
+                        &mut chunking,
+                        &mut tokenizer,
+                        &mut pooling,
+                        &mut normalize,
                     )
                 }
                 if model.apply(new_model) {
@@ -158,6 +206,15 @@ impl SettingsDiff {
                 if revision.apply(new_revision) {
                     ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
                 }
+                if tokenizer.apply(new_tokenizer) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+                if pooling.apply(new_pooling) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+                if normalize.apply(new_normalize) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
                 if dimensions.apply(new_dimensions) {
                     match source {
                         // regenerate on dimensions change in OpenAI since truncation is supported
@@ -197,6 +254,11 @@ impl SettingsDiff {
                     );
                 }
 
+                // the texts to embed change even when the rendered text doesn't
+                if chunking.apply(new_chunking) {
+                    ReindexAction::push_action(&mut reindex_action, ReindexAction::FullReindex);
+                }
+
                 if document_template_max_bytes.apply(new_document_template_max_bytes) {
                     let previous_document_template_max_bytes =
                         document_template_max_bytes.set().unwrap_or(default_max_bytes().get());
@@ -215,6 +277,20 @@ impl SettingsDiff {
                 }
 
                 distribution.apply(new_distribution);
+                // the trees of the embedder are converted to the new distance without reindexing
+                distance.apply(new_distance);
+                // the trees are quantized without reindexing, which cannot be undone
+                let was_quantized = quantization.set().is_some();
+                if quantization.apply(new_quantization) && was_quantized {
+                    return Err(UserError::InvalidChangeQuantization {
+                        embedder_name: embedder_name.to_owned(),
+                    });
+                }
+                // No reindex: the full-precision embeddings of the documents already indexed
+                // were dropped once quantized, so they cannot be recovered. Rescoring only uses
+                // the embeddings of the documents sent after it has been enabled, the others keep
+                // the distance to their quantized embeddings.
+                rescore.apply(new_rescore);
                 api_key.apply(new_api_key);
                 headers.apply(new_headers);
 
@@ -231,6 +307,13 @@ impl SettingsDiff {
                     distribution,
                     headers,
                     document_template_max_bytes,
+                    distance,
+                    quantization,
+                    rescore,
+                    chunking,
+                    tokenizer,
+                    pooling,
+                    normalize,
                 };
 
                 match reindex_action {
@@ -240,7 +323,8 @@ impl SettingsDiff {
             }
             Setting::Reset => Self::Remove,
             Setting::NotSet => Self::UpdateWithoutReindex { updated_settings: old },
-        }
+        };
+        Ok(ret)
     }
 }
 
@@ -266,7 +350,16 @@ fn apply_default_for_source(
     document_template: &mut Setting<String>,
     document_template_max_bytes: &mut Setting<usize>,
     headers: &mut Setting<BTreeMap<String, String>>,
+    chunking: &mut Setting<Chunking>,
+    tokenizer: &mut Setting<String>,
+    pooling: &mut Setting<onnx::Pooling>,
+    normalize: &mut Setting<bool>,
 ) {
+    if !matches!(source, Setting::Set(EmbedderSource::Onnx)) {
+        *tokenizer = Setting::NotSet;
+        *pooling = Setting::NotSet;
+        *normalize = Setting::NotSet;
+    }
     match source {
         Setting::Set(EmbedderSource::HuggingFace) => {
             *model = Setting::Reset;
@@ -314,6 +407,19 @@ fn apply_default_for_source(
             *document_template = Setting::NotSet;
             *document_template_max_bytes = Setting::NotSet;
             *headers = Setting::NotSet;
+            *chunking = Setting::NotSet;
+        }
+        Setting::Set(EmbedderSource::Onnx) => {
+            *model = Setting::Reset;
+            *revision = Setting::NotSet;
+            *dimensions = Setting::NotSet;
+            *url = Setting::NotSet;
+            *request = Setting::NotSet;
+            *response = Setting::NotSet;
+            *headers = Setting::NotSet;
+            *tokenizer = Setting::Reset;
+            *pooling = Setting::Reset;
+            *normalize = Setting::Reset;
         }
         Setting::NotSet => {}
     }
@@ -344,13 +450,21 @@ impl EmbeddingSettings {
     pub const DIMENSIONS: &'static str = "dimensions";
     pub const DOCUMENT_TEMPLATE: &'static str = "documentTemplate";
     pub const DOCUMENT_TEMPLATE_MAX_BYTES: &'static str = "documentTemplateMaxBytes";
+    pub const CHUNKING: &'static str = "chunking";
 
     pub const URL: &'static str = "url";
     pub const REQUEST: &'static str = "request";
     pub const RESPONSE: &'static str = "response";
     pub const HEADERS: &'static str = "headers";
 
+    pub const TOKENIZER: &'static str = "tokenizer";
+    pub const POOLING: &'static str = "pooling";
+    pub const NORMALIZE: &'static str = "normalize";
+
     pub const DISTRIBUTION: &'static str = "distribution";
+    pub const DISTANCE: &'static str = "distance";
+    pub const QUANTIZATION: &'static str = "quantization";
+    pub const RESCORE: &'static str = "rescore";
 
     pub fn allowed_sources_for_field(field: &'static str) -> &'static [EmbedderSource] {
         match field {
@@ -360,10 +474,14 @@ impl EmbeddingSettings {
                 EmbedderSource::UserProvided,
                 EmbedderSource::Rest,
                 EmbedderSource::Ollama,
+                EmbedderSource::Onnx,
+            ],
+            Self::MODEL => &[
+                EmbedderSource::HuggingFace,
+                EmbedderSource::OpenAi,
+                EmbedderSource::Ollama,
+                EmbedderSource::Onnx,
             ],
-            Self::MODEL => {
-                &[EmbedderSource::HuggingFace, EmbedderSource::OpenAi, EmbedderSource::Ollama]
-            }
             Self::REVISION => &[EmbedderSource::HuggingFace],
             Self::API_KEY => {
                 &[EmbedderSource::OpenAi, EmbedderSource::Ollama, EmbedderSource::Rest]
@@ -374,22 +492,25 @@ impl EmbeddingSettings {
                 EmbedderSource::Ollama,
                 EmbedderSource::Rest,
             ],
-            Self::DOCUMENT_TEMPLATE => &[
+            Self::DOCUMENT_TEMPLATE | Self::CHUNKING => &[
                 EmbedderSource::HuggingFace,
                 EmbedderSource::OpenAi,
                 EmbedderSource::Ollama,
                 EmbedderSource::Rest,
+                EmbedderSource::Onnx,
             ],
             Self::URL => &[EmbedderSource::Ollama, EmbedderSource::Rest, EmbedderSource::OpenAi],
             Self::REQUEST => &[EmbedderSource::Rest],
             Self::RESPONSE => &[EmbedderSource::Rest],
             Self::HEADERS => &[EmbedderSource::Rest],
-            Self::DISTRIBUTION => &[
+            Self::TOKENIZER | Self::POOLING | Self::NORMALIZE => &[EmbedderSource::Onnx],
+            Self::DISTRIBUTION | Self::DISTANCE | Self::QUANTIZATION | Self::RESCORE => &[
                 EmbedderSource::HuggingFace,
                 EmbedderSource::Ollama,
                 EmbedderSource::OpenAi,
                 EmbedderSource::Rest,
                 EmbedderSource::UserProvided,
+                EmbedderSource::Onnx,
             ],
             _other => unreachable!("unknown field"),
         }
@@ -402,8 +523,12 @@ impl EmbeddingSettings {
                 Self::MODEL,
                 Self::API_KEY,
                 Self::DOCUMENT_TEMPLATE,
+                Self::CHUNKING,
                 Self::DIMENSIONS,
                 Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
                 Self::URL,
             ],
             EmbedderSource::HuggingFace => &[
@@ -411,28 +536,60 @@ impl EmbeddingSettings {
                 Self::MODEL,
                 Self::REVISION,
                 Self::DOCUMENT_TEMPLATE,
+                Self::CHUNKING,
                 Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
             ],
             EmbedderSource::Ollama => &[
                 Self::SOURCE,
                 Self::MODEL,
                 Self::DOCUMENT_TEMPLATE,
+                Self::CHUNKING,
                 Self::URL,
                 Self::API_KEY,
                 Self::DIMENSIONS,
                 Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
+            ],
+            EmbedderSource::UserProvided => &[
+                Self::SOURCE,
+                Self::DIMENSIONS,
+                Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
             ],
-            EmbedderSource::UserProvided => &[Self::SOURCE, Self::DIMENSIONS, Self::DISTRIBUTION],
             EmbedderSource::Rest => &[
                 Self::SOURCE,
                 Self::API_KEY,
                 Self::DIMENSIONS,
                 Self::DOCUMENT_TEMPLATE,
+                Self::CHUNKING,
                 Self::URL,
                 Self::REQUEST,
                 Self::RESPONSE,
                 Self::HEADERS,
                 Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
+            ],
+            EmbedderSource::Onnx => &[
+                Self::SOURCE,
+                Self::MODEL,
+                Self::TOKENIZER,
+                Self::POOLING,
+                Self::NORMALIZE,
+                Self::DOCUMENT_TEMPLATE,
+                Self::CHUNKING,
+                Self::DISTRIBUTION,
+                Self::DISTANCE,
+                Self::QUANTIZATION,
+                Self::RESCORE,
             ],
         }
     }
@@ -469,6 +626,7 @@ pub enum EmbedderSource {
     Ollama,
     UserProvided,
     Rest,
+    Onnx,
 }
 
 impl std::fmt::Display for EmbedderSource {
@@ -479,6 +637,7 @@ impl std::fmt::Display for EmbedderSource {
             EmbedderSource::UserProvided => "userProvided",
             EmbedderSource::Ollama => "ollama",
             EmbedderSource::Rest => "rest",
+            EmbedderSource::Onnx => "onnx",
         };
         f.write_str(s)
     }
@@ -486,7 +645,8 @@ impl std::fmt::Display for EmbedderSource {
 
 impl From<EmbeddingConfig> for EmbeddingSettings {
     fn from(value: EmbeddingConfig) -> Self {
-        let EmbeddingConfig { embedder_options, prompt } = value;
+        let EmbeddingConfig { embedder_options, prompt, distance, quantization, rescore } = value;
+        let chunking = Setting::some_or_not_set(prompt.chunking);
         let document_template_max_bytes =
             Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
         match embedder_options {
@@ -507,6 +667,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 response: Setting::NotSet,
                 headers: Setting::NotSet,
                 distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking,
+                tokenizer: Setting::NotSet,
+                pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
             },
             super::EmbedderOptions::OpenAi(super::openai::EmbedderOptions {
                 url,
@@ -527,6 +694,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 response: Setting::NotSet,
                 headers: Setting::NotSet,
                 distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking,
+                tokenizer: Setting::NotSet,
+                pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
             },
             super::EmbedderOptions::Ollama(super::ollama::EmbedderOptions {
                 embedding_model,
@@ -547,6 +721,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 response: Setting::NotSet,
                 headers: Setting::NotSet,
                 distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking,
+                tokenizer: Setting::NotSet,
+                pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
             },
             super::EmbedderOptions::UserProvided(super::manual::EmbedderOptions {
                 dimensions,
@@ -564,6 +745,13 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 response: Setting::NotSet,
                 headers: Setting::NotSet,
                 distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking: Setting::NotSet,
+                tokenizer: Setting::NotSet,
+                pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
             },
             super::EmbedderOptions::Rest(super::rest::EmbedderOptions {
                 api_key,
@@ -585,7 +773,41 @@ impl From<EmbeddingConfig> for EmbeddingSettings {
                 request: Setting::Set(request),
                 response: Setting::Set(response),
                 distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking,
                 headers: Setting::Set(headers),
+                tokenizer: Setting::NotSet,
+                pooling: Setting::NotSet,
+                normalize: Setting::NotSet,
+            },
+            super::EmbedderOptions::Onnx(super::onnx::EmbedderOptions {
+                model,
+                tokenizer,
+                pooling,
+                normalize,
+                distribution,
+            }) => Self {
+                source: Setting::Set(EmbedderSource::Onnx),
+                model: Setting::Set(model),
+                revision: Setting::NotSet,
+                api_key: Setting::NotSet,
+                dimensions: Setting::NotSet,
+                document_template: Setting::Set(prompt.template),
+                document_template_max_bytes,
+                url: Setting::NotSet,
+                request: Setting::NotSet,
+                response: Setting::NotSet,
+                headers: Setting::NotSet,
+                distribution: Setting::some_or_not_set(distribution),
+                distance: Setting::some_or_not_set(distance),
+                quantization: Setting::some_or_not_set(quantization),
+                rescore: Setting::some_or_not_set(rescore),
+                chunking,
+                tokenizer: Setting::some_or_not_set(tokenizer),
+                pooling: Setting::Set(pooling),
+                normalize: Setting::Set(normalize),
             },
         }
     }
@@ -607,8 +829,19 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
             response,
             distribution,
             headers,
+            distance,
+            quantization,
+            rescore,
+            chunking,
+            tokenizer,
+            pooling,
+            normalize,
         } = value;
 
+        this.distance = distance.set();
+        this.quantization = quantization.set();
+        this.rescore = rescore.set();
+
         if let Some(source) = source.set() {
             match source {
                 EmbedderSource::OpenAi => {
@@ -680,6 +913,16 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                             headers: headers.set().unwrap_or_default(),
                         })
                 }
+                EmbedderSource::Onnx => {
+                    this.embedder_options =
+                        super::EmbedderOptions::Onnx(super::onnx::EmbedderOptions {
+                            model: model.set().unwrap(),
+                            tokenizer: tokenizer.set(),
+                            pooling: pooling.set().unwrap_or_default(),
+                            normalize: normalize.set().unwrap_or_default(),
+                            distribution: distribution.set(),
+                        })
+                }
             }
         }
 
@@ -689,9 +932,259 @@ impl From<EmbeddingSettings> for EmbeddingConfig {
                 .and_then(NonZeroUsize::new)
                 .unwrap_or(default_max_bytes());
 
-            this.prompt = PromptData { template, max_bytes: Some(max_bytes) }
+            this.prompt = PromptData { template, max_bytes: Some(max_bytes), chunking: None }
         }
+        this.prompt.chunking = chunking.set();
 
         this
     }
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub struct RerankerSettings {
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub source: Setting<RerankerSource>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub model: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub revision: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub api_key: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub url: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub request: Setting<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub response: Setting<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub headers: Setting<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub document_template: Setting<String>,
+    #[serde(default, skip_serializing_if = "Setting::is_not_set")]
+    #[deserr(default)]
+    pub document_template_max_bytes: Setting<usize>,
+}
+
+pub fn check_reranker_unset<T>(
+    key: &Setting<T>,
+    field: &'static str,
+    source: RerankerSource,
+    reranker_name: &str,
+) -> Result<(), UserError> {
+    if matches!(key, Setting::NotSet) {
+        Ok(())
+    } else {
+        Err(UserError::InvalidFieldForRerankerSource {
+            reranker_name: reranker_name.to_owned(),
+            source_: source,
+            field,
+            allowed_fields_for_source: RerankerSettings::allowed_fields_for_source(source),
+        })
+    }
+}
+
+pub fn check_reranker_set<T>(
+    key: &Setting<T>,
+    field: &'static str,
+    source: RerankerSource,
+    reranker_name: &str,
+) -> Result<(), UserError> {
+    if matches!(key, Setting::Set(_)) {
+        Ok(())
+    } else {
+        Err(UserError::MissingFieldForRerankerSource {
+            field,
+            source_: source,
+            reranker_name: reranker_name.to_owned(),
+        })
+    }
+}
+
+impl RerankerSettings {
+    pub const SOURCE: &'static str = "source";
+    pub const MODEL: &'static str = "model";
+    pub const REVISION: &'static str = "revision";
+    pub const API_KEY: &'static str = "apiKey";
+    pub const URL: &'static str = "url";
+    pub const REQUEST: &'static str = "request";
+    pub const RESPONSE: &'static str = "response";
+    pub const HEADERS: &'static str = "headers";
+    pub const DOCUMENT_TEMPLATE: &'static str = "documentTemplate";
+    pub const DOCUMENT_TEMPLATE_MAX_BYTES: &'static str = "documentTemplateMaxBytes";
+
+    pub fn allowed_fields_for_source(source: RerankerSource) -> &'static [&'static str] {
+        match source {
+            RerankerSource::HuggingFace => &[
+                Self::SOURCE,
+                Self::MODEL,
+                Self::REVISION,
+                Self::DOCUMENT_TEMPLATE,
+                Self::DOCUMENT_TEMPLATE_MAX_BYTES,
+            ],
+            RerankerSource::Rest => &[
+                Self::SOURCE,
+                Self::API_KEY,
+                Self::URL,
+                Self::REQUEST,
+                Self::RESPONSE,
+                Self::HEADERS,
+                Self::DOCUMENT_TEMPLATE,
+                Self::DOCUMENT_TEMPLATE_MAX_BYTES,
+            ],
+        }
+    }
+
+    /// Applies the fields set in `new` over these settings.
+    ///
+    /// Changing the source starts over from the new settings, as the fields of the old source don't apply to it.
+    pub fn apply(&mut self, new: RerankerSettings) {
+        if matches!(new.source, Setting::Set(_)) && new.source != self.source {
+            *self = new;
+            return;
+        }
+        let RerankerSettings {
+            source,
+            model,
+            revision,
+            api_key,
+            url,
+            request,
+            response,
+            headers,
+            document_template,
+            document_template_max_bytes,
+        } = new;
+        self.source.apply(source);
+        if matches!(model, Setting::Set(_)) {
+            // a new model doesn't keep the revision of the previous one
+            self.revision = Setting::NotSet;
+        }
+        self.model.apply(model);
+        self.revision.apply(revision);
+        self.api_key.apply(api_key);
+        self.url.apply(url);
+        self.request.apply(request);
+        self.response.apply(response);
+        self.headers.apply(headers);
+        self.document_template.apply(document_template);
+        self.document_template_max_bytes.apply(document_template_max_bytes);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Deserr)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[deserr(rename_all = camelCase, deny_unknown_fields)]
+pub enum RerankerSource {
+    #[default]
+    HuggingFace,
+    Rest,
+}
+
+impl std::fmt::Display for RerankerSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            RerankerSource::HuggingFace => "huggingFace",
+            RerankerSource::Rest => "rest",
+        };
+        f.write_str(s)
+    }
+}
+
+impl From<RerankerConfig> for RerankerSettings {
+    fn from(value: RerankerConfig) -> Self {
+        let RerankerConfig { reranker_options, prompt } = value;
+        let document_template = Setting::Set(prompt.template);
+        let document_template_max_bytes =
+            Setting::Set(prompt.max_bytes.unwrap_or(default_max_bytes()).get());
+        match reranker_options {
+            RerankerOptions::HuggingFace(HfRerankerOptions { model, revision }) => Self {
+                source: Setting::Set(RerankerSource::HuggingFace),
+                model: Setting::Set(model),
+                revision: Setting::some_or_not_set(revision),
+                api_key: Setting::NotSet,
+                url: Setting::NotSet,
+                request: Setting::NotSet,
+                response: Setting::NotSet,
+                headers: Setting::NotSet,
+                document_template,
+                document_template_max_bytes,
+            },
+            RerankerOptions::Rest(RestRerankerOptions {
+                api_key,
+                url,
+                request,
+                response,
+                headers,
+            }) => Self {
+                source: Setting::Set(RerankerSource::Rest),
+                model: Setting::NotSet,
+                revision: Setting::NotSet,
+                api_key: Setting::some_or_not_set(api_key),
+                url: Setting::Set(url),
+                request: Setting::Set(request),
+                response: Setting::Set(response),
+                headers: Setting::Set(headers),
+                document_template,
+                document_template_max_bytes,
+            },
+        }
+    }
+}
+
+/// Builds the configuration of the settings of a reranker, validated by
+/// [`crate::update::validate_reranker_settings`] with its source set.
+impl From<RerankerSettings> for RerankerConfig {
+    fn from(value: RerankerSettings) -> Self {
+        let RerankerSettings {
+            source,
+            model,
+            revision,
+            api_key,
+            url,
+            request,
+            response,
+            headers,
+            document_template,
+            document_template_max_bytes,
+        } = value;
+
+        let reranker_options = match source.set().unwrap_or_default() {
+            RerankerSource::HuggingFace => {
+                let mut options = HfRerankerOptions::default();
+                if let Some(model) = model.set() {
+                    options.model = model;
+                }
+                options.revision = revision.set();
+                RerankerOptions::HuggingFace(options)
+            }
+            RerankerSource::Rest => RerankerOptions::Rest(RestRerankerOptions {
+                api_key: api_key.set(),
+                url: url.set().unwrap(),
+                request: request.set().unwrap(),
+                response: response.set().unwrap(),
+                headers: headers.set().unwrap_or_default(),
+            }),
+        };
+
+        let mut prompt = PromptData::default();
+        if let Some(template) = document_template.set() {
+            prompt.template = template;
+        }
+        if let Some(max_bytes) = document_template_max_bytes.set().and_then(NonZeroUsize::new) {
+            prompt.max_bytes = Some(max_bytes);
+        }
+
+        Self { reranker_options, prompt }
+    }
+}